@@ -0,0 +1,188 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    fft::EvaluationDomain,
+    snark::varuna::{AHPForR1CS, CircuitInfo, SNARKMode},
+};
+use snarkvm_fields::PrimeField;
+use snarkvm_utilities::ExecutionPool;
+
+use anyhow::{anyhow, Result};
+
+/// The memory mode of the AHP prover.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProverMemoryMode {
+    /// Computes the oracles of all circuits and instances in the batch concurrently.
+    Parallel,
+    /// Computes the oracles of the circuits and instances in the batch one at a time,
+    /// and frees intermediate polynomials as soon as they are no longer needed.
+    /// This bounds the peak memory of the prover, at the cost of proving time.
+    LowMemory,
+}
+
+impl ProverMemoryMode {
+    /// Returns `true` if this is the low-memory mode.
+    pub const fn is_low_memory(&self) -> bool {
+        matches!(self, Self::LowMemory)
+    }
+
+    /// Executes the jobs in the given pool, and returns their results in order.
+    /// In the low-memory mode, each job only runs once the result of the previous job is consumed.
+    pub(crate) fn execute<'a, T: 'a + Send + Sync>(
+        &self,
+        pool: ExecutionPool<'a, T>,
+    ) -> Box<dyn Iterator<Item = T> + 'a> {
+        match self {
+            Self::Parallel => Box::new(pool.execute_all().into_iter()),
+            Self::LowMemory => Box::new(pool.execute_serially()),
+        }
+    }
+
+    /// Returns the number of jobs that may hold working memory at the same time.
+    fn concurrency(&self, num_jobs: usize) -> usize {
+        #[cfg(not(feature = "serial"))]
+        let num_threads = snarkvm_utilities::parallel::max_available_threads();
+        #[cfg(feature = "serial")]
+        let num_threads = 1;
+
+        match self {
+            Self::Parallel => num_jobs.min(num_threads).max(1),
+            Self::LowMemory => 1,
+        }
+    }
+}
+
+impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
+    /// Returns an estimate of the peak memory (in bytes) that the prover uses to prove a batch,
+    /// where `circuits` contains the circuit info and the number of instances of each circuit.
+    ///
+    /// The estimate accounts for the witnesses, the oracles, and the working memory of each round
+    /// of the AHP prover. It excludes the proving keys, which are already in memory before proving.
+    pub fn estimate_prover_memory<'a>(
+        circuits: impl IntoIterator<Item = (&'a CircuitInfo, usize)>,
+        mode: ProverMemoryMode,
+    ) -> Result<usize> {
+        let domain_size = |num_elements: usize| {
+            EvaluationDomain::<F>::compute_size_of_domain(num_elements)
+                .ok_or_else(|| anyhow!("Cannot create an evaluation domain of size {num_elements}"))
+        };
+
+        // The number of field elements held by the prover across rounds.
+        let (mut witnesses, mut w_polys, mut x_polys, mut assignments, mut transposes, mut index_oracles) =
+            (0usize, 0usize, 0usize, 0usize, 0usize, 0usize);
+        // The number of jobs in each round, and the largest working set of a job in each round.
+        let mut num_jobs = [0usize; 4];
+        let mut max_job = [0usize; 4];
+        let (mut max_constraint_domain, mut max_variable_domain) = (0usize, 0usize);
+
+        for (info, batch_size) in circuits {
+            let constraint_domain = domain_size(info.num_constraints)?;
+            let variable_domain = domain_size(info.num_variables)?;
+            let input_domain = domain_size(info.num_public_inputs)?;
+            let non_zero_domains = [
+                domain_size(info.num_non_zero_a)?,
+                domain_size(info.num_non_zero_b)?,
+                domain_size(info.num_non_zero_c)?,
+            ];
+            let num_non_zero = info.num_non_zero_a + info.num_non_zero_b + info.num_non_zero_c;
+            max_constraint_domain = max_constraint_domain.max(constraint_domain);
+            max_variable_domain = max_variable_domain.max(variable_domain);
+
+            // The `Az`, `Bz`, and `Cz` vectors of each instance, which are held until the second round.
+            // In the parallel mode, the assignment of each instance is also held until the first round,
+            // while in the low-memory mode it is released once the witness polynomial of the instance is computed.
+            witnesses += batch_size * 3 * info.num_constraints;
+            if !mode.is_low_memory() {
+                witnesses += batch_size * info.num_variables;
+            }
+            w_polys += batch_size * variable_domain;
+            x_polys += batch_size * input_domain;
+            // The assignment polynomials and the sparse matrix transposes of the third round.
+            assignments += batch_size * 2 * variable_domain;
+            transposes += 2 * num_non_zero;
+            // The `g`, `a`, `b`, and `lhs` polynomials of each matrix of the fourth round.
+            index_oracles += 4 * non_zero_domains.iter().sum::<usize>();
+
+            // The first round computes `w` for each instance.
+            num_jobs[0] += batch_size;
+            max_job[0] = max_job[0].max(4 * variable_domain);
+            // The second round computes the rowcheck for each instance.
+            num_jobs[1] += batch_size;
+            max_job[1] = max_job[1].max(9 * constraint_domain);
+            // The third round computes the lineval sumcheck for each matrix of each instance.
+            num_jobs[2] += 3 * batch_size;
+            max_job[2] = max_job[2].max(constraint_domain + 8 * variable_domain);
+            // The fourth round computes the matrix sumcheck for each matrix.
+            num_jobs[3] += 3;
+            max_job[3] = max_job[3].max(10 * non_zero_domains.iter().max().copied().unwrap_or(0));
+        }
+
+        let mask_poly = if SM::ZK { 2 * max_variable_domain } else { 0 };
+        let h_0 = 2 * max_constraint_domain;
+        let (g_1, h_1) = (max_variable_domain, 2 * max_variable_domain);
+
+        // The results of a round that are held before they are summed.
+        let held_results = |num_jobs: usize, result_size: usize| match mode {
+            ProverMemoryMode::Parallel => num_jobs * result_size,
+            ProverMemoryMode::LowMemory => 3 * result_size,
+        };
+        let working = |round: usize| mode.concurrency(num_jobs[round]) * max_job[round];
+
+        let first_round = witnesses + x_polys + w_polys + mask_poly + working(0);
+        let second_round = witnesses + x_polys + w_polys + mask_poly + working(1) + held_results(num_jobs[1], h_0);
+        let third_round = x_polys
+            + w_polys
+            + mask_poly
+            + h_0
+            + assignments
+            + transposes
+            + working(2)
+            + held_results(num_jobs[2], 2 * max_variable_domain);
+        let fourth_round = w_polys + mask_poly + h_0 + g_1 + h_1 + index_oracles + working(3);
+
+        let peak = first_round.max(second_round).max(third_round).max(fourth_round);
+        peak.checked_mul(core::mem::size_of::<F>()).ok_or_else(|| anyhow!("The memory estimate overflowed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snark::varuna::VarunaHidingMode;
+    use snarkvm_curves::bls12_377::Fr;
+
+    type AHP = AHPForR1CS<Fr, VarunaHidingMode>;
+
+    #[test]
+    fn test_estimate_prover_memory() {
+        let info = CircuitInfo {
+            num_public_inputs: 4,
+            num_variables: 1 << 12,
+            num_constraints: 1 << 12,
+            num_non_zero_a: 1 << 13,
+            num_non_zero_b: 1 << 13,
+            num_non_zero_c: 1 << 13,
+        };
+
+        let single = AHP::estimate_prover_memory([(&info, 1)], ProverMemoryMode::LowMemory).unwrap();
+        let batch = AHP::estimate_prover_memory([(&info, 16)], ProverMemoryMode::LowMemory).unwrap();
+        assert!(single > 0);
+        assert!(batch > single);
+
+        // Ensure the low-memory mode never estimates more memory than the parallel mode.
+        let parallel = AHP::estimate_prover_memory([(&info, 16)], ProverMemoryMode::Parallel).unwrap();
+        assert!(batch <= parallel);
+    }
+}
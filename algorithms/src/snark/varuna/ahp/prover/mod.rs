@@ -17,6 +17,9 @@
 mod constraint_system;
 pub(crate) use constraint_system::*;
 
+mod memory;
+pub use memory::*;
+
 mod message;
 pub(crate) use message::*;
 
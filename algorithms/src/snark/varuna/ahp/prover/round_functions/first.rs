@@ -74,13 +74,21 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
             let v_domain = circuit_state.variable_domain;
             let i_domain = circuit_state.input_domain;
 
-            for (j, (private_vars, x_poly)) in itertools::izip!(private_variables, x_polys).enumerate() {
-                let w_label = witness_label(circuit.id, "w", j);
-                job_pool.add_job(move || Self::calculate_w(w_label, private_vars, x_poly, v_domain, i_domain, circuit));
+            for (j, (private_inputs, x_poly)) in itertools::izip!(private_variables, x_polys).enumerate() {
+                match private_inputs {
+                    prover::PrivateInputs::Variables(private_vars) => {
+                        let w_label = witness_label(circuit.id, "w", j);
+                        job_pool.add_job(move || {
+                            Self::calculate_w(w_label, private_vars, x_poly, v_domain, i_domain, circuit)
+                        });
+                    }
+                    // The witness polynomial was already computed when the instance was assigned.
+                    prover::PrivateInputs::Witness(w_poly) => job_pool.add_job(move || w_poly),
+                }
             }
         }
         let mut batches =
-            job_pool.execute_all().into_iter().map(|w_poly| prover::WitnessPoly(w_poly)).collect::<Vec<_>>();
+            state.memory_mode.execute(job_pool).map(|w_poly| prover::WitnessPoly(w_poly)).collect::<Vec<_>>();
         assert_eq!(batches.len(), state.total_instances);
 
         let mut circuit_specific_batches = BTreeMap::new();
@@ -123,7 +131,7 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
         LabeledPolynomial::new("mask_poly".to_string(), mask_poly, None, None)
     }
 
-    fn calculate_w(
+    pub(super) fn calculate_w(
         label: String,
         private_variables: Vec<F>,
         x_poly: DensePolynomial<F>,
@@ -121,8 +121,11 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
 
         let mut sums = Vec::with_capacity(state.circuit_specific_states.len());
         let mut gs = BTreeMap::new();
+        // The results are consumed as they are computed, so that in the low-memory mode
+        // only the working memory of one matrix is held at a time.
+        let mut polynomials = Vec::with_capacity(state.circuit_specific_states.len());
         for ((circuit_a, results_a), (circuit_b, results_b), (circuit_c, results_c)) in
-            pool.execute_all().into_iter().tuples()
+            state.memory_mode.execute(pool).tuples()
         {
             assert_eq!(circuit_a, circuit_b);
             assert_eq!(circuit_a, circuit_c);
@@ -131,12 +134,18 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
             let (sum_c, lhs_c, g_c, a_poly_c, b_poly_c) = results_c?;
             let matrix_sum = prover::message::MatrixSums { sum_a, sum_b, sum_c };
             sums.push(matrix_sum);
-            state.circuit_specific_states.get_mut(circuit_a).unwrap().lhs_polynomials = Some([lhs_a, lhs_b, lhs_c]);
-            state.circuit_specific_states.get_mut(circuit_a).unwrap().a_polys = Some([a_poly_a, a_poly_b, a_poly_c]);
-            state.circuit_specific_states.get_mut(circuit_a).unwrap().b_polys = Some([b_poly_a, b_poly_b, b_poly_c]);
+            polynomials.push((circuit_a, [lhs_a, lhs_b, lhs_c], [a_poly_a, a_poly_b, a_poly_c], [
+                b_poly_a, b_poly_b, b_poly_c,
+            ]));
             let matrix_gs = prover::MatrixGs { g_a, g_b, g_c };
             gs.insert(circuit_a.id, matrix_gs);
         }
+        for (circuit, lhs_polynomials, a_polys, b_polys) in polynomials {
+            let state_i = state.circuit_specific_states.get_mut(circuit).unwrap();
+            state_i.lhs_polynomials = Some(lhs_polynomials);
+            state_i.a_polys = Some(a_polys);
+            state_i.b_polys = Some(b_polys);
+        }
 
         let msg = prover::FourthMessage { sums };
         let oracles = prover::FourthOracles { gs };
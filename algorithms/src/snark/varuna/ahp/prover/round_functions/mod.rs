@@ -13,10 +13,12 @@
 // limitations under the License.
 
 use crate::{
-    r1cs::ConstraintSynthesizer,
+    fft::{EvaluationDomain, Evaluations as EvaluationsOnDomain},
+    r1cs::{ConstraintSynthesizer, SynthesisError},
     snark::varuna::{
         ahp::{indexer::Circuit, AHPError, AHPForR1CS},
-        prover,
+        prover::{self, ProverMemoryMode},
+        witness_label,
         SNARKMode,
     },
 };
@@ -42,9 +44,12 @@ mod second;
 mod third;
 
 impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
-    /// Initialize the AHP prover.
+    /// Initialize the AHP prover, in the given memory mode.
+    /// In the low-memory mode, the witnesses of the instances are generated one at a time,
+    /// and the assignment of each instance is released as soon as its witness polynomial is computed.
     pub fn init_prover<'a, C: ConstraintSynthesizer<F>, R: Rng + CryptoRng>(
         circuits_to_constraints: &BTreeMap<&'a Circuit<F, SM>, &[C]>,
+        memory_mode: ProverMemoryMode,
         rng: &mut R,
     ) -> Result<prover::State<'a, F, SM>, AHPError> {
         let init_time = start_timer!(|| "AHP::Prover::Init");
@@ -73,10 +78,8 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
                 let num_non_zero_b = circuit.index_info.num_non_zero_b;
                 let num_non_zero_c = circuit.index_info.num_non_zero_c;
 
-                let assignments = cfg_iter!(constraints)
-                    .zip(circuit_rand_assignments)
-                    .enumerate()
-                    .map(|(_i, (instance, rand_assignments))| {
+                let assign =
+                    |(_i, (instance, rand_assignments)): (usize, (&C, Option<[F; 3]>))| -> Result<_, AHPError> {
                         let constraint_time = start_timer!(|| format!(
                             "Generating constraints and witnesses for {:?} and index {_i}",
                             circuit.id
@@ -152,14 +155,51 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
                             .collect();
                         end_timer!(eval_z_c_time);
 
-                        Ok(prover::Assignments::<F>(padded_public_variables, private_variables, z_a, z_b, z_c))
-                    })
-                    .collect::<Result<Vec<prover::Assignments<F>>, AHPError>>()?;
+                        let private_inputs = match memory_mode {
+                            ProverMemoryMode::Parallel => prover::PrivateInputs::Variables(private_variables),
+                            // Compute the witness polynomial right away, so the private variables are released.
+                            ProverMemoryMode::LowMemory => {
+                                let input_domain =
+                                    EvaluationDomain::new(num_public_variables).ok_or(SynthesisError::PolyTooLarge)?;
+                                let variable_domain = EvaluationDomain::new(circuit.index_info.num_variables)
+                                    .ok_or(SynthesisError::PolyTooLarge)?;
+                                let x_poly = EvaluationsOnDomain::from_vec_and_domain(
+                                    padded_public_variables.clone(),
+                                    input_domain,
+                                )
+                                .interpolate();
+                                prover::PrivateInputs::Witness(Self::calculate_w(
+                                    witness_label(circuit.id, "w", _i),
+                                    private_variables,
+                                    x_poly,
+                                    variable_domain,
+                                    input_domain,
+                                    circuit,
+                                ))
+                            }
+                        };
+
+                        Ok(prover::Assignments::<F>(padded_public_variables, private_inputs, z_a, z_b, z_c))
+                    };
+
+                let assignments = match memory_mode {
+                    ProverMemoryMode::Parallel => cfg_iter!(constraints)
+                        .zip(circuit_rand_assignments)
+                        .enumerate()
+                        .map(assign)
+                        .collect::<Result<Vec<prover::Assignments<F>>, AHPError>>()?,
+                    ProverMemoryMode::LowMemory => constraints
+                        .iter()
+                        .zip(circuit_rand_assignments)
+                        .enumerate()
+                        .map(assign)
+                        .collect::<Result<Vec<prover::Assignments<F>>, AHPError>>()?,
+                };
                 Ok((*circuit, assignments))
             })
             .collect::<Result<BTreeMap<&'a Circuit<F, SM>, Vec<prover::Assignments<F>>>, AHPError>>()?;
 
-        let state = prover::State::initialize(indices_and_assignments)?;
+        let state = prover::State::initialize(indices_and_assignments, memory_mode)?;
         end_timer!(init_time);
 
         Ok(state)
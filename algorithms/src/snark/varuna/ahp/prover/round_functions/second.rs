@@ -19,7 +19,7 @@ use crate::{
     polycommit::sonic_pc::{LabeledPolynomial, PolynomialInfo, PolynomialLabel},
     snark::varuna::{
         ahp::{verifier, AHPForR1CS},
-        prover,
+        prover::{self, ProverMemoryMode},
         selectors::apply_randomized_selector,
         witness_label,
         Circuit,
@@ -126,15 +126,25 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
         }
 
         let h_sum_time = start_timer!(|| "AHP::Prover::SecondRound h_sum");
-        let h_sum: DensePolynomial<F> =
-            cfg_reduce!(cfg_into_iter!(job_pool.execute_all()), || Ok(DensePolynomial::zero()), |a, b| {
-                a.and_then(|a| {
-                    b.map(|mut b| {
-                        b += &a;
-                        b
+        let h_sum: DensePolynomial<F> = match state.memory_mode {
+            ProverMemoryMode::Parallel => {
+                cfg_reduce!(cfg_into_iter!(job_pool.execute_all()), || Ok(DensePolynomial::zero()), |a, b| {
+                    a.and_then(|a| {
+                        b.map(|mut b| {
+                            b += &a;
+                            b
+                        })
                     })
-                })
-            })?;
+                })?
+            }
+            // Sum each `h_0_i` as soon as it is computed, so that only one is held at a time.
+            ProverMemoryMode::LowMemory => {
+                job_pool.execute_serially().try_fold(DensePolynomial::zero(), |mut h_sum, h_0_i| {
+                    h_sum += &h_0_i?;
+                    Ok::<_, anyhow::Error>(h_sum)
+                })?
+            }
+        };
         end_timer!(h_sum_time);
 
         Ok(h_sum)
@@ -24,7 +24,7 @@ use crate::{
     snark::varuna::{
         ahp::{indexer::CircuitId, verifier, AHPForR1CS},
         matrices::transpose,
-        prover::{self, MatrixSums, ThirdMessage},
+        prover::{self, MatrixSums, ProverMemoryMode, ThirdMessage},
         selectors::apply_randomized_selector,
         AHPError,
         Matrix,
@@ -82,6 +82,10 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
         let verifier::SecondMessage { alpha, eta_b, eta_c } = verifier_second_message;
 
         let assignments = Self::calculate_assignments(&mut state)?;
+        // In the low-memory mode, free the public input polynomials, as they are no longer needed.
+        if state.memory_mode.is_low_memory() {
+            state.circuit_specific_states.values_mut().for_each(|state_i| state_i.x_polys = Vec::new());
+        }
         let matrix_transposes = Self::calculate_matrix_transpose(&mut state)?;
 
         let (h_1, x_g_1_sum, msg) = Self::calculate_lineval_sumcheck_witness(
@@ -182,11 +186,8 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
         let mut xg_1_sum = DensePolynomial::zero();
         let mut circuit_index = 0;
         let mut instances_seen = 0;
-        for (i, linevals) in job_pool.execute_all().chunks_exact_mut(3).enumerate() {
-            if linevals[0].is_ok() && linevals[1].is_ok() && linevals[2].is_ok() {
-                let lineval_a = linevals[0].as_ref().unwrap();
-                let lineval_b = linevals[1].as_ref().unwrap();
-                let lineval_c = linevals[2].as_ref().unwrap();
+        for (i, linevals) in state.memory_mode.execute(job_pool).tuples().enumerate() {
+            if let (Ok(lineval_a), Ok(lineval_b), Ok(lineval_c)) = linevals {
                 h_1_sum += &lineval_a.h_1_i;
                 h_1_sum += &lineval_b.h_1_i;
                 h_1_sum += &lineval_c.h_1_i;
@@ -222,6 +223,7 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
         state: &mut prover::State<F, SM>,
     ) -> Result<BTreeMap<CircuitId, Vec<DensePolynomial<F>>>> {
         let assignments_time = start_timer!(|| "Calculate assignments");
+        let memory_mode = state.memory_mode;
         let assignments: BTreeMap<_, _> = state
             .circuit_specific_states
             .iter()
@@ -229,19 +231,19 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
             .map(|((circuit, circuit_specific_state), w_polys)| {
                 let x_polys = &circuit_specific_state.x_polys;
                 let input_domain = &circuit_specific_state.input_domain;
-                let assignments_i: Vec<_> = cfg_iter!(w_polys)
-                    .zip_eq(x_polys)
-                    .enumerate()
-                    .map(|(_j, (w_poly, x_poly))| {
-                        let z_time = start_timer!(move || format!("Compute z poly for circuit {} {}", circuit.id, _j));
-                        let mut assignment =
-                            w_poly.0.polynomial().as_dense().unwrap().mul_by_vanishing_poly(*input_domain);
-                        // Zip safety: `x_poly` is smaller than `z_poly`.
-                        assignment.coeffs.iter_mut().zip(&x_poly.coeffs).for_each(|(z, x)| *z += x);
-                        end_timer!(z_time);
-                        assignment
-                    })
-                    .collect();
+                let assign = |(_j, (w_poly, x_poly)): (usize, (&prover::WitnessPoly<F>, &DensePolynomial<F>))| {
+                    let z_time = start_timer!(move || format!("Compute z poly for circuit {} {}", circuit.id, _j));
+                    let mut assignment = w_poly.0.polynomial().as_dense().unwrap().mul_by_vanishing_poly(*input_domain);
+                    // Zip safety: `x_poly` is smaller than `z_poly`.
+                    assignment.coeffs.iter_mut().zip(&x_poly.coeffs).for_each(|(z, x)| *z += x);
+                    end_timer!(z_time);
+                    assignment
+                };
+                // In the low-memory mode, compute the assignments one at a time.
+                let assignments_i: Vec<_> = match memory_mode {
+                    ProverMemoryMode::Parallel => cfg_iter!(w_polys).zip_eq(x_polys).enumerate().map(assign).collect(),
+                    ProverMemoryMode::LowMemory => w_polys.iter().zip_eq(x_polys).enumerate().map(assign).collect(),
+                };
                 (circuit.id, assignments_i)
             })
             .collect();
@@ -264,7 +266,7 @@ impl<F: PrimeField, SM: SNARKMode> AHPForR1CS<F, SM> {
             }
         });
         let mut matrix_transposes = BTreeMap::new();
-        for ((id_a, matrix_a), (id_b, matrix_b), (id_c, matrix_c)) in job_pool.execute_all().into_iter().tuples() {
+        for ((id_a, matrix_a), (id_b, matrix_b), (id_c, matrix_c)) in state.memory_mode.execute(job_pool).tuples() {
             ensure!(id_a == id_b);
             ensure!(id_a == id_c);
             let mut matrix_transposes_i = BTreeMap::new();
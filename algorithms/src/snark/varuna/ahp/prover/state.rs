@@ -18,7 +18,7 @@ use crate::{
     fft::{DensePolynomial, EvaluationDomain, Evaluations as EvaluationsOnDomain},
     polycommit::sonic_pc::LabeledPolynomial,
     r1cs::{SynthesisError, SynthesisResult},
-    snark::varuna::{AHPError, AHPForR1CS, Circuit, SNARKMode},
+    snark::varuna::{prover::ProverMemoryMode, AHPError, AHPForR1CS, Circuit, SNARKMode},
 };
 use anyhow::anyhow;
 use snarkvm_fields::PrimeField;
@@ -39,9 +39,9 @@ pub struct CircuitSpecificState<F: PrimeField> {
     /// The length of this list must be equal to the batch size.
     pub(super) padded_public_variables: Vec<Vec<F>>,
 
-    /// The list of private variables for each instance in the batch.
+    /// The list of private inputs for each instance in the batch.
     /// The length of this list must be equal to the batch size.
-    pub(super) private_variables: Vec<Vec<F>>,
+    pub(super) private_variables: Vec<PrivateInputs<F>>,
 
     /// The list of Az vectors for each instance in the batch.
     /// The length of this list must be equal to the batch size.
@@ -84,12 +84,19 @@ pub struct State<'a, F: PrimeField, SM: SNARKMode> {
     pub(in crate::snark) max_variable_domain: EvaluationDomain<F>,
     /// The total number of instances we're proving in the batch.
     pub(in crate::snark) total_instances: usize,
+    /// The memory mode of the prover.
+    pub(in crate::snark) memory_mode: ProverMemoryMode,
 }
 
 /// The public inputs for a single instance.
 type PaddedPubInputs<F> = Vec<F>;
 /// The private inputs for a single instance.
-type PrivateInputs<F> = Vec<F>;
+pub(super) enum PrivateInputs<F: PrimeField> {
+    /// The private variables, from which the witness polynomial is computed in the first round.
+    Variables(Vec<F>),
+    /// The witness polynomial, which the low-memory mode computes as soon as the instance is assigned.
+    Witness(LabeledPolynomial<F>),
+}
 /// The z_i_j*A_i vector for a single instance.
 type Za<F> = Vec<F>;
 /// The z_i_j*B_i vector for a single instance.
@@ -97,7 +104,7 @@ type Zb<F> = Vec<F>;
 /// The z_i_j*C_i vector for a single instance.
 type Zc<F> = Vec<F>;
 /// Assignments for a single instance.
-pub(super) struct Assignments<F>(
+pub(super) struct Assignments<F: PrimeField>(
     pub(super) PaddedPubInputs<F>,
     pub(super) PrivateInputs<F>,
     pub(super) Za<F>,
@@ -108,6 +115,7 @@ pub(super) struct Assignments<F>(
 impl<'a, F: PrimeField, SM: SNARKMode> State<'a, F, SM> {
     pub(super) fn initialize(
         indices_and_assignments: BTreeMap<&'a Circuit<F, SM>, Vec<Assignments<F>>>,
+        memory_mode: ProverMemoryMode,
     ) -> Result<Self, AHPError> {
         let mut max_non_zero_domain: Option<EvaluationDomain<F>> = None;
         let mut max_num_constraints = 0;
@@ -186,6 +194,7 @@ impl<'a, F: PrimeField, SM: SNARKMode> State<'a, F, SM> {
             circuit_specific_states,
             total_instances,
             first_round_oracles: None,
+            memory_mode,
         })
     }
 
@@ -259,6 +259,31 @@ impl<E: PairingEngine> Proof<E> {
         &self.batch_sizes
     }
 
+    /// Returns an upper bound on the compressed size in bytes of a proof for the given batch sizes.
+    /// The bound assumes a hiding proof, i.e. one with a masking polynomial and hiding opening proofs.
+    pub fn max_compressed_size(batch_sizes: &[usize]) -> usize {
+        let num_circuits = batch_sizes.len();
+        let num_instances = batch_sizes.iter().sum::<usize>();
+
+        let commitment_size = E::G1Affine::default().compressed_size();
+        let field_size = E::Fr::default().compressed_size();
+        let tag_size = false.compressed_size();
+
+        // The batch sizes are written as a length-prefixed vector of `u64`s.
+        let batch_sizes_size = 8 + 8 * num_circuits;
+        // One witness commitment per instance; `h_0`, `g_1`, `h_1`, `h_2`; and `g_a`, `g_b`, `g_c` per circuit.
+        // The masking polynomial commitment is an optional commitment.
+        let commitments_size = (num_instances + 4 + 3 * num_circuits) * commitment_size + (tag_size + commitment_size);
+        // The evaluation of `g_1`, and the evaluations of `g_a`, `g_b`, `g_c` per circuit.
+        let evaluations_size = (1 + 3 * num_circuits) * field_size;
+        // The matrix sums per instance (third message) and per circuit (fourth message).
+        let messages_size = 3 * (num_instances + num_circuits) * field_size;
+        // One opening proof for each query point (`alpha`, `beta`, `gamma`), as a length-prefixed vector.
+        let pc_proof_size = 8 + 3 * (commitment_size + tag_size + field_size);
+
+        batch_sizes_size + commitments_size + evaluations_size + messages_size + pc_proof_size
+    }
+
     /// Check that the number of messages is consistent with our batch size
     pub fn check_batch_sizes(&self) -> Result<(), SNARKError> {
         let total_instances = self.batch_sizes.iter().sum::<usize>();
@@ -506,4 +531,31 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_max_compressed_size() {
+        let rng = &mut snarkvm_utilities::rand::TestRng::default();
+
+        for i in 1..11 {
+            for j in 1..11 {
+                let batch_sizes = vec![j; i];
+                let commitments = rand_commitments(j, i, false);
+                let evaluations: Evaluations<Fr> = rand_evaluations(rng, i);
+                let third_msg = ThirdMessage::<Fr> { sums: vec![vec![rand_sums(rng); j]; i] };
+                let fourth_msg = FourthMessage::<Fr> { sums: vec![rand_sums(rng); i] };
+                let pc_proof = sonic_pc::BatchLCProof { proof: BatchProof(vec![rand_kzg_proof(rng, false); 3]) };
+                let proof = Proof {
+                    batch_sizes: batch_sizes.clone(),
+                    commitments,
+                    evaluations,
+                    third_msg,
+                    fourth_msg,
+                    pc_proof,
+                };
+                // A hiding proof meets the bound exactly.
+                let expected = Proof::<Bls12_377>::max_compressed_size(&batch_sizes);
+                assert_eq!(proof.to_bytes_le().unwrap().len(), expected);
+            }
+        }
+    }
 }
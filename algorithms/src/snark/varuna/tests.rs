@@ -17,6 +17,7 @@ mod varuna {
     use crate::{
         snark::varuna::{
             mode::SNARKMode,
+            prover::ProverMemoryMode,
             test_circuit::TestCircuit,
             AHPForR1CS,
             CircuitVerifyingKey,
@@ -293,6 +294,64 @@ mod varuna {
         SonicPCTest::test_bincode(num_constraints, num_variables);
         SonicPCPoswTest::test_bincode(num_constraints, num_variables);
     }
+
+    #[test]
+    fn prove_batch_in_low_memory_mode() {
+        let rng = &mut TestRng::default();
+
+        let max_degree = AHPForR1CS::<Fr, VarunaHidingMode>::max_degree(300, 25, 900).unwrap();
+        let universal_srs = VarunaSonicInst::universal_setup(max_degree).unwrap();
+        let universal_prover = &universal_srs.to_universal_prover().unwrap();
+        let universal_verifier = &universal_srs.to_universal_verifier().unwrap();
+        let fs_parameters = FS::sample_parameters();
+
+        // Sample a batch of two circuits with three instances each.
+        let mut constraints = BTreeMap::new();
+        let mut inputs = BTreeMap::new();
+        for i in 0..2 {
+            let (circuits, circuit_inputs): (Vec<_>, Vec<_>) =
+                (0..3).map(|_| TestCircuit::gen_rand(2 + i, 25 + 100 * i, 25, rng)).unzip();
+            let circuit_id = AHPForR1CS::<Fr, VarunaHidingMode>::index(&circuits[0]).unwrap().id;
+            constraints.insert(circuit_id, circuits);
+            inputs.insert(circuit_id, circuit_inputs);
+        }
+        let unique_instances = constraints.values().map(|instances| &instances[0]).collect::<Vec<_>>();
+        let index_keys = VarunaSonicInst::batch_circuit_setup(&universal_srs, unique_instances.as_slice()).unwrap();
+
+        let mut pks_to_constraints = BTreeMap::new();
+        let mut pks_to_batch_sizes = BTreeMap::new();
+        let mut vks_to_inputs = BTreeMap::new();
+        for (index_pk, index_vk) in index_keys.iter() {
+            pks_to_constraints.insert(index_pk, constraints[&index_pk.circuit.id].as_slice());
+            pks_to_batch_sizes.insert(index_pk, constraints[&index_pk.circuit.id].len());
+            vks_to_inputs.insert(index_vk, inputs[&index_pk.circuit.id].as_slice());
+        }
+
+        // Ensure the low-memory mode does not estimate more memory than the parallel mode.
+        let parallel_estimate =
+            VarunaSonicInst::estimate_prover_memory(&pks_to_batch_sizes, ProverMemoryMode::Parallel).unwrap();
+        let low_memory_estimate =
+            VarunaSonicInst::estimate_prover_memory(&pks_to_batch_sizes, ProverMemoryMode::LowMemory).unwrap();
+        assert!(low_memory_estimate > 0);
+        assert!(low_memory_estimate <= parallel_estimate);
+
+        // Ensure both modes produce the same valid proof from the same randomness.
+        let seed = rand::RngCore::next_u64(rng);
+        let mut proofs = Vec::new();
+        for memory_mode in [ProverMemoryMode::Parallel, ProverMemoryMode::LowMemory] {
+            let proof = VarunaSonicInst::prove_batch_with_memory_mode(
+                universal_prover,
+                &fs_parameters,
+                &pks_to_constraints,
+                memory_mode,
+                &mut TestRng::fixed(seed),
+            )
+            .unwrap();
+            assert!(VarunaSonicInst::verify_batch(universal_verifier, &fs_parameters, &vks_to_inputs, &proof).unwrap());
+            proofs.push(proof);
+        }
+        assert_eq!(proofs[0], proofs[1]);
+    }
 }
 
 #[cfg(any(test, feature = "test"))]
@@ -535,7 +594,14 @@ mod varuna_hiding {
 mod varuna_test_vectors {
     use crate::{
         fft::EvaluationDomain,
-        snark::varuna::{ahp::verifier, AHPForR1CS, TestCircuit, VarunaNonHidingMode, VarunaSNARK},
+        snark::varuna::{
+            ahp::verifier,
+            prover::ProverMemoryMode,
+            AHPForR1CS,
+            TestCircuit,
+            VarunaNonHidingMode,
+            VarunaSNARK,
+        },
         traits::snark::SNARK,
     };
     use snarkvm_curves::bls12_377::{Bls12_377, Fq, Fr};
@@ -669,7 +735,8 @@ mod varuna_test_vectors {
         keys_to_constraints.insert(index_pk.circuit.deref(), std::slice::from_ref(&circ));
 
         // Begin the Varuna protocol execution.
-        let prover_state = AHPForR1CS::<_, MM>::init_prover(&keys_to_constraints, rng).unwrap();
+        let prover_state =
+            AHPForR1CS::<_, MM>::init_prover(&keys_to_constraints, ProverMemoryMode::Parallel, rng).unwrap();
         let mut prover_state = AHPForR1CS::<_, MM>::prover_first_round(prover_state, rng).unwrap();
         let first_round_oracles = Arc::new(prover_state.first_round_oracles.as_ref().unwrap());
 
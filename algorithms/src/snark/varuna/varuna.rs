@@ -28,7 +28,7 @@ use crate::{
     snark::varuna::{
         ahp::{AHPError, AHPForR1CS, CircuitId, EvaluationsProvider},
         proof,
-        prover,
+        prover::{self, ProverMemoryMode},
         witness_label,
         CircuitProvingKey,
         CircuitVerifyingKey,
@@ -345,284 +345,13 @@ where
         keys_to_constraints: &BTreeMap<&CircuitProvingKey<E, SM>, &[C]>,
         zk_rng: &mut R,
     ) -> Result<Self::Proof> {
-        let prover_time = start_timer!(|| "Varuna::Prover");
-        if keys_to_constraints.is_empty() {
-            bail!(SNARKError::EmptyBatch);
-        }
-
-        let mut circuits_to_constraints = BTreeMap::new();
-        for (pk, constraints) in keys_to_constraints {
-            circuits_to_constraints.insert(pk.circuit.deref(), *constraints);
-        }
-        let prover_state = AHPForR1CS::<_, SM>::init_prover(&circuits_to_constraints, zk_rng)?;
-
-        // extract information from the prover key and state to consume in further calculations
-        let mut batch_sizes = BTreeMap::new();
-        let mut circuit_infos = BTreeMap::new();
-        let mut inputs_and_batch_sizes = BTreeMap::new();
-        let mut total_instances = 0usize;
-        let mut public_inputs = BTreeMap::new(); // inputs need to live longer than the rest of prover_state
-        let num_unique_circuits = keys_to_constraints.len();
-        let mut circuit_ids = Vec::with_capacity(num_unique_circuits);
-        for pk in keys_to_constraints.keys() {
-            let batch_size = prover_state.batch_size(&pk.circuit).ok_or(SNARKError::CircuitNotFound)?;
-            let public_input = prover_state.public_inputs(&pk.circuit).ok_or(SNARKError::CircuitNotFound)?;
-            let padded_public_input =
-                prover_state.padded_public_inputs(&pk.circuit).ok_or(SNARKError::CircuitNotFound)?;
-            let circuit_id = pk.circuit.id;
-            batch_sizes.insert(circuit_id, batch_size);
-            circuit_infos.insert(circuit_id, &pk.circuit_verifying_key.circuit_info);
-            inputs_and_batch_sizes.insert(circuit_id, (batch_size, padded_public_input));
-            public_inputs.insert(circuit_id, public_input);
-            total_instances = total_instances.saturating_add(batch_size);
-
-            circuit_ids.push(circuit_id);
-        }
-        ensure!(prover_state.total_instances == total_instances);
-
-        let committer_key = CommitterUnionKey::union(keys_to_constraints.keys().map(|pk| pk.committer_key.deref()));
-
-        let circuit_commitments =
-            keys_to_constraints.keys().map(|pk| pk.circuit_verifying_key.circuit_commitments.as_slice());
-
-        let mut sponge = Self::init_sponge(fs_parameters, &inputs_and_batch_sizes, circuit_commitments.clone());
-
-        // --------------------------------------------------------------------
-        // First round
-
-        let prover_state = AHPForR1CS::<_, SM>::prover_first_round(prover_state, zk_rng)?;
-
-        let first_round_comm_time = start_timer!(|| "Committing to first round polys");
-        let (first_commitments, first_commitment_randomnesses) = {
-            let first_round_oracles = prover_state.first_round_oracles.as_ref().unwrap();
-            SonicKZG10::<E, FS>::commit(
-                universal_prover,
-                &committer_key,
-                first_round_oracles.iter().map(Into::into),
-                SM::ZK.then_some(zk_rng),
-            )?
-        };
-        end_timer!(first_round_comm_time);
-
-        Self::absorb_labeled(&first_commitments, &mut sponge);
-
-        let (verifier_first_message, verifier_state) = AHPForR1CS::<_, SM>::verifier_first_round(
-            &batch_sizes,
-            &circuit_infos,
-            prover_state.max_constraint_domain,
-            prover_state.max_variable_domain,
-            prover_state.max_non_zero_domain,
-            &mut sponge,
-        )?;
-        // --------------------------------------------------------------------
-
-        // --------------------------------------------------------------------
-        // Second round
-
-        let (second_oracles, prover_state) =
-            AHPForR1CS::<_, SM>::prover_second_round(&verifier_first_message, prover_state, zk_rng)?;
-
-        let second_round_comm_time = start_timer!(|| "Committing to second round polys");
-        let (second_commitments, second_commitment_randomnesses) = SonicKZG10::<E, FS>::commit(
+        Self::prove_batch_with_memory_mode(
             universal_prover,
-            &committer_key,
-            second_oracles.iter().map(Into::into),
-            SM::ZK.then_some(zk_rng),
-        )?;
-        end_timer!(second_round_comm_time);
-
-        Self::absorb_labeled(&second_commitments, &mut sponge);
-
-        let (verifier_second_msg, verifier_state) =
-            AHPForR1CS::<_, SM>::verifier_second_round(verifier_state, &mut sponge)?;
-        // --------------------------------------------------------------------
-
-        // --------------------------------------------------------------------
-        // Third round
-
-        let (prover_third_message, third_oracles, prover_state) = AHPForR1CS::<_, SM>::prover_third_round(
-            &verifier_first_message,
-            &verifier_second_msg,
-            prover_state,
+            fs_parameters,
+            keys_to_constraints,
+            ProverMemoryMode::Parallel,
             zk_rng,
-        )?;
-
-        let third_round_comm_time = start_timer!(|| "Committing to third round polys");
-        let (third_commitments, third_commitment_randomnesses) = SonicKZG10::<E, FS>::commit(
-            universal_prover,
-            &committer_key,
-            third_oracles.iter().map(Into::into),
-            SM::ZK.then_some(zk_rng),
-        )?;
-        end_timer!(third_round_comm_time);
-
-        Self::absorb_labeled_with_sums(
-            &third_commitments,
-            &prover_third_message.sums.clone().into_iter().flatten().collect_vec(),
-            &mut sponge,
-        );
-
-        let (verifier_third_msg, verifier_state) =
-            AHPForR1CS::<_, SM>::verifier_third_round(verifier_state, &mut sponge)?;
-        // --------------------------------------------------------------------
-
-        // --------------------------------------------------------------------
-        // Fourth round
-
-        let (prover_fourth_message, fourth_oracles, mut prover_state) =
-            AHPForR1CS::<_, SM>::prover_fourth_round(&verifier_second_msg, &verifier_third_msg, prover_state, zk_rng)?;
-
-        let fourth_round_comm_time = start_timer!(|| "Committing to fourth round polys");
-        let (fourth_commitments, fourth_commitment_randomnesses) = SonicKZG10::<E, FS>::commit(
-            universal_prover,
-            &committer_key,
-            fourth_oracles.iter().map(Into::into),
-            SM::ZK.then_some(zk_rng),
-        )?;
-        end_timer!(fourth_round_comm_time);
-
-        Self::absorb_labeled_with_sums(&fourth_commitments, &prover_fourth_message.sums, &mut sponge);
-
-        let (verifier_fourth_msg, verifier_state) =
-            AHPForR1CS::<_, SM>::verifier_fourth_round(verifier_state, &mut sponge)?;
-        // --------------------------------------------------------------------
-
-        // We take out values from state before they are consumed.
-        let first_round_oracles = prover_state.first_round_oracles.take().unwrap();
-        let index_a_polys =
-            prover_state.circuit_specific_states.values_mut().flat_map(|s| s.a_polys.take().unwrap()).collect_vec();
-        let index_b_polys =
-            prover_state.circuit_specific_states.values_mut().flat_map(|s| s.b_polys.take().unwrap()).collect_vec();
-
-        // --------------------------------------------------------------------
-        // Fifth round
-        let fifth_oracles = AHPForR1CS::<_, SM>::prover_fifth_round(verifier_fourth_msg, prover_state, zk_rng)?;
-
-        let fifth_round_comm_time = start_timer!(|| "Committing to fifth round polys");
-        let (fifth_commitments, fifth_commitment_randomnesses) = SonicKZG10::<E, FS>::commit(
-            universal_prover,
-            &committer_key,
-            fifth_oracles.iter().map(Into::into),
-            SM::ZK.then_some(zk_rng),
-        )?;
-        end_timer!(fifth_round_comm_time);
-
-        Self::absorb_labeled(&fifth_commitments, &mut sponge);
-
-        let verifier_state = AHPForR1CS::<_, SM>::verifier_fifth_round(verifier_state, &mut sponge)?;
-        // --------------------------------------------------------------------
-
-        // Gather prover polynomials in one vector.
-        let polynomials: Vec<_> = index_a_polys
-            .into_iter()
-            .chain(index_b_polys)
-            .chain(first_round_oracles.into_iter())
-            .chain(second_oracles.into_iter())
-            .chain(third_oracles.into_iter())
-            .chain(fourth_oracles.into_iter())
-            .chain(fifth_oracles.into_iter())
-            .collect();
-        ensure!(
-            polynomials.len()
-                == num_unique_circuits * 6 + // numerator and denominator for each matrix sumcheck
-            AHPForR1CS::<E::Fr, SM>::num_first_round_oracles(total_instances) +
-            AHPForR1CS::<E::Fr, SM>::num_second_round_oracles() +
-            AHPForR1CS::<E::Fr, SM>::num_third_round_oracles() +
-            AHPForR1CS::<E::Fr, SM>::num_fourth_round_oracles(num_unique_circuits) +
-            AHPForR1CS::<E::Fr, SM>::num_fifth_round_oracles()
-        );
-
-        // Gather commitments in one vector.
-        let witness_comm_len = if SM::ZK { first_commitments.len() - 1 } else { first_commitments.len() };
-        let mask_poly = SM::ZK.then(|| *first_commitments[witness_comm_len].commitment());
-        let witness_commitments = first_commitments[..witness_comm_len]
-            .iter()
-            .map(|c| proof::WitnessCommitments { w: *c.commitment() })
-            .collect_vec();
-        let fourth_commitments_chunked = fourth_commitments.chunks_exact(3);
-        let (g_a_commitments, g_b_commitments, g_c_commitments) = fourth_commitments_chunked
-            .map(|c| (*c[0].commitment(), *c[1].commitment(), *c[2].commitment()))
-            .multiunzip();
-
-        #[rustfmt::skip]
-        let commitments = proof::Commitments {
-            witness_commitments,
-            mask_poly,
-            h_0: *second_commitments[0].commitment(),
-            g_1: *third_commitments[0].commitment(),
-            h_1: *third_commitments[1].commitment(),
-            g_a_commitments,
-            g_b_commitments,
-            g_c_commitments,
-            h_2: *fifth_commitments[0].commitment(),
-        };
-
-        // Gather commitment randomness together.
-        let indexer_randomness = vec![Randomness::<E>::empty(); 6 * num_unique_circuits];
-        let commitment_randomnesses: Vec<Randomness<E>> = indexer_randomness
-            .into_iter()
-            .chain(first_commitment_randomnesses)
-            .chain(second_commitment_randomnesses)
-            .chain(third_commitment_randomnesses)
-            .chain(fourth_commitment_randomnesses)
-            .chain(fifth_commitment_randomnesses)
-            .collect();
-
-        let empty_randomness = Randomness::<E>::empty();
-        if SM::ZK {
-            ensure!(commitment_randomnesses.iter().any(|r| r != &empty_randomness));
-        } else {
-            ensure!(commitment_randomnesses.iter().all(|r| r == &empty_randomness));
-        }
-
-        // Compute the AHP verifier's query set.
-        let (query_set, verifier_state) = AHPForR1CS::<_, SM>::verifier_query_set(verifier_state);
-        let lc_s = AHPForR1CS::<_, SM>::construct_linear_combinations(
-            &public_inputs,
-            &polynomials,
-            &prover_third_message,
-            &prover_fourth_message,
-            &verifier_state,
-        )?;
-
-        let eval_time = start_timer!(|| "Evaluating linear combinations over query set");
-        let mut evaluations = std::collections::BTreeMap::new();
-        for (label, (_, point)) in query_set.to_set() {
-            if !AHPForR1CS::<E::Fr, SM>::LC_WITH_ZERO_EVAL.contains(&label.as_str()) {
-                let lc = lc_s.get(&label).ok_or_else(|| AHPError::MissingEval(label.to_string()))?;
-                let evaluation = polynomials.get_lc_eval(lc, point)?;
-                evaluations.insert(label, evaluation);
-            }
-        }
-
-        let evaluations = proof::Evaluations::from_map(&evaluations, batch_sizes.clone());
-        end_timer!(eval_time);
-
-        sponge.absorb_nonnative_field_elements(evaluations.to_field_elements());
-
-        let pc_proof = SonicKZG10::<E, FS>::open_combinations(
-            universal_prover,
-            &committer_key,
-            lc_s.values(),
-            polynomials,
-            &commitment_randomnesses,
-            &query_set.to_set(),
-            &mut sponge,
-        )?;
-
-        let proof = Proof::<E>::new(
-            batch_sizes,
-            commitments,
-            evaluations,
-            prover_third_message,
-            prover_fourth_message,
-            pc_proof,
-        )?;
-        proof.check_batch_sizes()?;
-        ensure!(proof.pc_proof.is_hiding() == SM::ZK);
-
-        end_timer!(prover_time);
-        Ok(proof)
+        )
     }
 
     /// This is the main entrypoint for verifying proofs.
@@ -785,137 +514,441 @@ where
         let circuit_commitments = keys_to_inputs.keys().map(|vk| vk.circuit_commitments.as_slice());
         let mut sponge = Self::init_sponge(fs_parameters, &inputs_and_batch_sizes, circuit_commitments.clone());
 
-        // --------------------------------------------------------------------
-        // First round
-        let first_round_time = start_timer!(|| "First round");
+        // --------------------------------------------------------------------
+        // First round
+        let first_round_time = start_timer!(|| "First round");
+        Self::absorb_labeled(&first_commitments, &mut sponge);
+        let (_, verifier_state) = AHPForR1CS::<_, SM>::verifier_first_round(
+            &batch_sizes,
+            &circuit_infos,
+            max_constraint_domain,
+            max_variable_domain,
+            max_non_zero_domain,
+            &mut sponge,
+        )?;
+        end_timer!(first_round_time);
+        // --------------------------------------------------------------------
+
+        // --------------------------------------------------------------------
+        // Second round
+        let second_round_time = start_timer!(|| "Second round");
+        Self::absorb_labeled(&second_commitments, &mut sponge);
+        let (_, verifier_state) = AHPForR1CS::<_, SM>::verifier_second_round(verifier_state, &mut sponge)?;
+        end_timer!(second_round_time);
+        // --------------------------------------------------------------------
+
+        // --------------------------------------------------------------------
+        // Third round
+        let third_round_time = start_timer!(|| "Third round");
+        Self::absorb_labeled_with_sums(
+            &third_commitments,
+            &proof.third_msg.sums.clone().into_iter().flatten().collect_vec(),
+            &mut sponge,
+        );
+        let (_, verifier_state) = AHPForR1CS::<_, SM>::verifier_third_round(verifier_state, &mut sponge)?;
+        end_timer!(third_round_time);
+        // --------------------------------------------------------------------
+
+        // --------------------------------------------------------------------
+        // Fourth round
+        let fourth_round_time = start_timer!(|| "Fourth round");
+
+        Self::absorb_labeled_with_sums(&fourth_commitments, &proof.fourth_msg.sums, &mut sponge);
+        let (_, verifier_state) = AHPForR1CS::<_, SM>::verifier_fourth_round(verifier_state, &mut sponge)?;
+        end_timer!(fourth_round_time);
+        // --------------------------------------------------------------------
+
+        // --------------------------------------------------------------------
+        // Fifth round
+        let fifth_round_time = start_timer!(|| "Fifth round");
+
+        Self::absorb_labeled(&fifth_commitments, &mut sponge);
+        let verifier_state = AHPForR1CS::<_, SM>::verifier_fifth_round(verifier_state, &mut sponge)?;
+        end_timer!(fifth_round_time);
+        // --------------------------------------------------------------------
+
+        // Collect degree bounds for commitments. Indexed polynomials have *no*
+        // degree bounds because we know the committed index polynomial has the
+        // correct degree.
+
+        // Gather commitments in one vector.
+        let commitments: Vec<_> = circuit_commitments
+            .into_iter()
+            .flatten()
+            .zip_eq(AHPForR1CS::<E::Fr, SM>::index_polynomial_info(circuit_ids.iter()).values())
+            .map(|(c, info)| LabeledCommitment::new_with_info(info, *c))
+            .chain(first_commitments)
+            .chain(second_commitments)
+            .chain(third_commitments)
+            .chain(fourth_commitments)
+            .chain(fifth_commitments)
+            .collect();
+
+        let query_set_time = start_timer!(|| "Constructing query set");
+        let (query_set, verifier_state) = AHPForR1CS::<_, SM>::verifier_query_set(verifier_state);
+        end_timer!(query_set_time);
+
+        sponge.absorb_nonnative_field_elements(proof.evaluations.to_field_elements());
+
+        let mut evaluations = Evaluations::new();
+
+        let mut current_circuit_id = "".to_string();
+        let mut circuit_index: i64 = -1;
+
+        for (label, (_point_name, q)) in query_set.to_set() {
+            if AHPForR1CS::<E::Fr, SM>::LC_WITH_ZERO_EVAL.contains(&label.as_ref()) {
+                evaluations.insert((label, q), E::Fr::zero());
+            } else {
+                if label != "g_1" {
+                    let circuit_id = CircuitId::from_witness_label(&label).to_string();
+                    if circuit_id != current_circuit_id {
+                        circuit_index += 1;
+                        current_circuit_id = circuit_id;
+                    }
+                }
+                let eval = proof
+                    .evaluations
+                    .get(circuit_index as usize, &label)
+                    .ok_or_else(|| AHPError::MissingEval(label.clone()))?;
+                evaluations.insert((label, q), eval);
+            }
+        }
+
+        let lc_time = start_timer!(|| "Constructing linear combinations");
+        let lc_s = AHPForR1CS::<_, SM>::construct_linear_combinations(
+            &public_inputs,
+            &evaluations,
+            &proof.third_msg,
+            &proof.fourth_msg,
+            &verifier_state,
+        )?;
+        end_timer!(lc_time);
+
+        let pc_time = start_timer!(|| "Checking linear combinations with PC");
+        let evaluations_are_correct = SonicKZG10::<E, FS>::check_combinations(
+            universal_verifier,
+            lc_s.values(),
+            &commitments,
+            &query_set.to_set(),
+            &evaluations,
+            &proof.pc_proof,
+            &mut sponge,
+        )?;
+        end_timer!(pc_time);
+
+        if !evaluations_are_correct {
+            #[cfg(debug_assertions)]
+            eprintln!("SonicKZG10::Check failed using final challenge: {:?}", verifier_state.gamma);
+        }
+
+        end_timer!(verifier_time, || format!(
+            " SonicKZG10::Check for AHP Verifier linear equations: {}",
+            evaluations_are_correct & proof_has_correct_zk_mode
+        ));
+        Ok(evaluations_are_correct & proof_has_correct_zk_mode)
+    }
+}
+
+impl<E: PairingEngine, FS: AlgebraicSponge<E::Fq, 2>, SM: SNARKMode> VarunaSNARK<E, FS, SM> {
+    /// Returns an estimate of the peak memory (in bytes) that the prover uses to prove a batch in the given
+    /// memory mode, where `keys_to_batch_sizes` contains the number of instances to prove for each proving key.
+    pub fn estimate_prover_memory(
+        keys_to_batch_sizes: &BTreeMap<&CircuitProvingKey<E, SM>, usize>,
+        memory_mode: ProverMemoryMode,
+    ) -> Result<usize> {
+        AHPForR1CS::<E::Fr, SM>::estimate_prover_memory(
+            keys_to_batch_sizes.iter().map(|(pk, batch_size)| (&pk.circuit.index_info, *batch_size)),
+            memory_mode,
+        )
+    }
+
+    /// Creates a proof for the given batch, in the given memory mode of the prover.
+    /// In the low-memory mode, the circuits and instances in the batch are processed one at a time,
+    /// which bounds the peak memory of the prover (see `estimate_prover_memory`), at the cost of proving time.
+    pub fn prove_batch_with_memory_mode<C: ConstraintSynthesizer<E::Fr>, R: Rng + CryptoRng>(
+        universal_prover: &UniversalProver<E>,
+        fs_parameters: &FS::Parameters,
+        keys_to_constraints: &BTreeMap<&CircuitProvingKey<E, SM>, &[C]>,
+        memory_mode: ProverMemoryMode,
+        zk_rng: &mut R,
+    ) -> Result<Proof<E>> {
+        let prover_time = start_timer!(|| "Varuna::Prover");
+        if keys_to_constraints.is_empty() {
+            bail!(SNARKError::EmptyBatch);
+        }
+
+        let mut circuits_to_constraints = BTreeMap::new();
+        for (pk, constraints) in keys_to_constraints {
+            circuits_to_constraints.insert(pk.circuit.deref(), *constraints);
+        }
+        let prover_state = AHPForR1CS::<_, SM>::init_prover(&circuits_to_constraints, memory_mode, zk_rng)?;
+
+        // extract information from the prover key and state to consume in further calculations
+        let mut batch_sizes = BTreeMap::new();
+        let mut circuit_infos = BTreeMap::new();
+        let mut inputs_and_batch_sizes = BTreeMap::new();
+        let mut total_instances = 0usize;
+        let mut public_inputs = BTreeMap::new(); // inputs need to live longer than the rest of prover_state
+        let num_unique_circuits = keys_to_constraints.len();
+        let mut circuit_ids = Vec::with_capacity(num_unique_circuits);
+        for pk in keys_to_constraints.keys() {
+            let batch_size = prover_state.batch_size(&pk.circuit).ok_or(SNARKError::CircuitNotFound)?;
+            let public_input = prover_state.public_inputs(&pk.circuit).ok_or(SNARKError::CircuitNotFound)?;
+            let padded_public_input =
+                prover_state.padded_public_inputs(&pk.circuit).ok_or(SNARKError::CircuitNotFound)?;
+            let circuit_id = pk.circuit.id;
+            batch_sizes.insert(circuit_id, batch_size);
+            circuit_infos.insert(circuit_id, &pk.circuit_verifying_key.circuit_info);
+            inputs_and_batch_sizes.insert(circuit_id, (batch_size, padded_public_input));
+            public_inputs.insert(circuit_id, public_input);
+            total_instances = total_instances.saturating_add(batch_size);
+
+            circuit_ids.push(circuit_id);
+        }
+        ensure!(prover_state.total_instances == total_instances);
+
+        let committer_key = CommitterUnionKey::union(keys_to_constraints.keys().map(|pk| pk.committer_key.deref()));
+
+        let circuit_commitments =
+            keys_to_constraints.keys().map(|pk| pk.circuit_verifying_key.circuit_commitments.as_slice());
+
+        let mut sponge = Self::init_sponge(fs_parameters, &inputs_and_batch_sizes, circuit_commitments.clone());
+
+        // --------------------------------------------------------------------
+        // First round
+
+        let prover_state = AHPForR1CS::<_, SM>::prover_first_round(prover_state, zk_rng)?;
+
+        let first_round_comm_time = start_timer!(|| "Committing to first round polys");
+        let (first_commitments, first_commitment_randomnesses) = {
+            let first_round_oracles = prover_state.first_round_oracles.as_ref().unwrap();
+            SonicKZG10::<E, FS>::commit(
+                universal_prover,
+                &committer_key,
+                first_round_oracles.iter().map(Into::into),
+                SM::ZK.then_some(zk_rng),
+            )?
+        };
+        end_timer!(first_round_comm_time);
+
         Self::absorb_labeled(&first_commitments, &mut sponge);
-        let (_, verifier_state) = AHPForR1CS::<_, SM>::verifier_first_round(
+
+        let (verifier_first_message, verifier_state) = AHPForR1CS::<_, SM>::verifier_first_round(
             &batch_sizes,
             &circuit_infos,
-            max_constraint_domain,
-            max_variable_domain,
-            max_non_zero_domain,
+            prover_state.max_constraint_domain,
+            prover_state.max_variable_domain,
+            prover_state.max_non_zero_domain,
             &mut sponge,
         )?;
-        end_timer!(first_round_time);
         // --------------------------------------------------------------------
 
         // --------------------------------------------------------------------
         // Second round
-        let second_round_time = start_timer!(|| "Second round");
+
+        let (second_oracles, prover_state) =
+            AHPForR1CS::<_, SM>::prover_second_round(&verifier_first_message, prover_state, zk_rng)?;
+
+        let second_round_comm_time = start_timer!(|| "Committing to second round polys");
+        let (second_commitments, second_commitment_randomnesses) = SonicKZG10::<E, FS>::commit(
+            universal_prover,
+            &committer_key,
+            second_oracles.iter().map(Into::into),
+            SM::ZK.then_some(zk_rng),
+        )?;
+        end_timer!(second_round_comm_time);
+
         Self::absorb_labeled(&second_commitments, &mut sponge);
-        let (_, verifier_state) = AHPForR1CS::<_, SM>::verifier_second_round(verifier_state, &mut sponge)?;
-        end_timer!(second_round_time);
+
+        let (verifier_second_msg, verifier_state) =
+            AHPForR1CS::<_, SM>::verifier_second_round(verifier_state, &mut sponge)?;
         // --------------------------------------------------------------------
 
         // --------------------------------------------------------------------
         // Third round
-        let third_round_time = start_timer!(|| "Third round");
+
+        let (prover_third_message, third_oracles, prover_state) = AHPForR1CS::<_, SM>::prover_third_round(
+            &verifier_first_message,
+            &verifier_second_msg,
+            prover_state,
+            zk_rng,
+        )?;
+
+        let third_round_comm_time = start_timer!(|| "Committing to third round polys");
+        let (third_commitments, third_commitment_randomnesses) = SonicKZG10::<E, FS>::commit(
+            universal_prover,
+            &committer_key,
+            third_oracles.iter().map(Into::into),
+            SM::ZK.then_some(zk_rng),
+        )?;
+        end_timer!(third_round_comm_time);
+
         Self::absorb_labeled_with_sums(
             &third_commitments,
-            &proof.third_msg.sums.clone().into_iter().flatten().collect_vec(),
+            &prover_third_message.sums.clone().into_iter().flatten().collect_vec(),
             &mut sponge,
         );
-        let (_, verifier_state) = AHPForR1CS::<_, SM>::verifier_third_round(verifier_state, &mut sponge)?;
-        end_timer!(third_round_time);
+
+        let (verifier_third_msg, verifier_state) =
+            AHPForR1CS::<_, SM>::verifier_third_round(verifier_state, &mut sponge)?;
         // --------------------------------------------------------------------
 
         // --------------------------------------------------------------------
         // Fourth round
-        let fourth_round_time = start_timer!(|| "Fourth round");
 
-        Self::absorb_labeled_with_sums(&fourth_commitments, &proof.fourth_msg.sums, &mut sponge);
-        let (_, verifier_state) = AHPForR1CS::<_, SM>::verifier_fourth_round(verifier_state, &mut sponge)?;
-        end_timer!(fourth_round_time);
+        let (prover_fourth_message, fourth_oracles, mut prover_state) =
+            AHPForR1CS::<_, SM>::prover_fourth_round(&verifier_second_msg, &verifier_third_msg, prover_state, zk_rng)?;
+
+        let fourth_round_comm_time = start_timer!(|| "Committing to fourth round polys");
+        let (fourth_commitments, fourth_commitment_randomnesses) = SonicKZG10::<E, FS>::commit(
+            universal_prover,
+            &committer_key,
+            fourth_oracles.iter().map(Into::into),
+            SM::ZK.then_some(zk_rng),
+        )?;
+        end_timer!(fourth_round_comm_time);
+
+        Self::absorb_labeled_with_sums(&fourth_commitments, &prover_fourth_message.sums, &mut sponge);
+
+        let (verifier_fourth_msg, verifier_state) =
+            AHPForR1CS::<_, SM>::verifier_fourth_round(verifier_state, &mut sponge)?;
         // --------------------------------------------------------------------
 
+        // We take out values from state before they are consumed.
+        let first_round_oracles = prover_state.first_round_oracles.take().unwrap();
+        let index_a_polys =
+            prover_state.circuit_specific_states.values_mut().flat_map(|s| s.a_polys.take().unwrap()).collect_vec();
+        let index_b_polys =
+            prover_state.circuit_specific_states.values_mut().flat_map(|s| s.b_polys.take().unwrap()).collect_vec();
+
         // --------------------------------------------------------------------
         // Fifth round
-        let fifth_round_time = start_timer!(|| "Fifth round");
+        let fifth_oracles = AHPForR1CS::<_, SM>::prover_fifth_round(verifier_fourth_msg, prover_state, zk_rng)?;
+
+        let fifth_round_comm_time = start_timer!(|| "Committing to fifth round polys");
+        let (fifth_commitments, fifth_commitment_randomnesses) = SonicKZG10::<E, FS>::commit(
+            universal_prover,
+            &committer_key,
+            fifth_oracles.iter().map(Into::into),
+            SM::ZK.then_some(zk_rng),
+        )?;
+        end_timer!(fifth_round_comm_time);
 
         Self::absorb_labeled(&fifth_commitments, &mut sponge);
+
         let verifier_state = AHPForR1CS::<_, SM>::verifier_fifth_round(verifier_state, &mut sponge)?;
-        end_timer!(fifth_round_time);
         // --------------------------------------------------------------------
 
-        // Collect degree bounds for commitments. Indexed polynomials have *no*
-        // degree bounds because we know the committed index polynomial has the
-        // correct degree.
-
-        // Gather commitments in one vector.
-        let commitments: Vec<_> = circuit_commitments
+        // Gather prover polynomials in one vector.
+        let polynomials: Vec<_> = index_a_polys
             .into_iter()
-            .flatten()
-            .zip_eq(AHPForR1CS::<E::Fr, SM>::index_polynomial_info(circuit_ids.iter()).values())
-            .map(|(c, info)| LabeledCommitment::new_with_info(info, *c))
-            .chain(first_commitments)
-            .chain(second_commitments)
-            .chain(third_commitments)
-            .chain(fourth_commitments)
-            .chain(fifth_commitments)
+            .chain(index_b_polys)
+            .chain(first_round_oracles.into_iter())
+            .chain(second_oracles.into_iter())
+            .chain(third_oracles.into_iter())
+            .chain(fourth_oracles.into_iter())
+            .chain(fifth_oracles.into_iter())
             .collect();
+        ensure!(
+            polynomials.len()
+                == num_unique_circuits * 6 + // numerator and denominator for each matrix sumcheck
+            AHPForR1CS::<E::Fr, SM>::num_first_round_oracles(total_instances) +
+            AHPForR1CS::<E::Fr, SM>::num_second_round_oracles() +
+            AHPForR1CS::<E::Fr, SM>::num_third_round_oracles() +
+            AHPForR1CS::<E::Fr, SM>::num_fourth_round_oracles(num_unique_circuits) +
+            AHPForR1CS::<E::Fr, SM>::num_fifth_round_oracles()
+        );
 
-        let query_set_time = start_timer!(|| "Constructing query set");
-        let (query_set, verifier_state) = AHPForR1CS::<_, SM>::verifier_query_set(verifier_state);
-        end_timer!(query_set_time);
-
-        sponge.absorb_nonnative_field_elements(proof.evaluations.to_field_elements());
+        // Gather commitments in one vector.
+        let witness_comm_len = if SM::ZK { first_commitments.len() - 1 } else { first_commitments.len() };
+        let mask_poly = SM::ZK.then(|| *first_commitments[witness_comm_len].commitment());
+        let witness_commitments = first_commitments[..witness_comm_len]
+            .iter()
+            .map(|c| proof::WitnessCommitments { w: *c.commitment() })
+            .collect_vec();
+        let fourth_commitments_chunked = fourth_commitments.chunks_exact(3);
+        let (g_a_commitments, g_b_commitments, g_c_commitments) = fourth_commitments_chunked
+            .map(|c| (*c[0].commitment(), *c[1].commitment(), *c[2].commitment()))
+            .multiunzip();
 
-        let mut evaluations = Evaluations::new();
+        #[rustfmt::skip]
+        let commitments = proof::Commitments {
+            witness_commitments,
+            mask_poly,
+            h_0: *second_commitments[0].commitment(),
+            g_1: *third_commitments[0].commitment(),
+            h_1: *third_commitments[1].commitment(),
+            g_a_commitments,
+            g_b_commitments,
+            g_c_commitments,
+            h_2: *fifth_commitments[0].commitment(),
+        };
 
-        let mut current_circuit_id = "".to_string();
-        let mut circuit_index: i64 = -1;
+        // Gather commitment randomness together.
+        let indexer_randomness = vec![Randomness::<E>::empty(); 6 * num_unique_circuits];
+        let commitment_randomnesses: Vec<Randomness<E>> = indexer_randomness
+            .into_iter()
+            .chain(first_commitment_randomnesses)
+            .chain(second_commitment_randomnesses)
+            .chain(third_commitment_randomnesses)
+            .chain(fourth_commitment_randomnesses)
+            .chain(fifth_commitment_randomnesses)
+            .collect();
 
-        for (label, (_point_name, q)) in query_set.to_set() {
-            if AHPForR1CS::<E::Fr, SM>::LC_WITH_ZERO_EVAL.contains(&label.as_ref()) {
-                evaluations.insert((label, q), E::Fr::zero());
-            } else {
-                if label != "g_1" {
-                    let circuit_id = CircuitId::from_witness_label(&label).to_string();
-                    if circuit_id != current_circuit_id {
-                        circuit_index += 1;
-                        current_circuit_id = circuit_id;
-                    }
-                }
-                let eval = proof
-                    .evaluations
-                    .get(circuit_index as usize, &label)
-                    .ok_or_else(|| AHPError::MissingEval(label.clone()))?;
-                evaluations.insert((label, q), eval);
-            }
+        let empty_randomness = Randomness::<E>::empty();
+        if SM::ZK {
+            ensure!(commitment_randomnesses.iter().any(|r| r != &empty_randomness));
+        } else {
+            ensure!(commitment_randomnesses.iter().all(|r| r == &empty_randomness));
         }
 
-        let lc_time = start_timer!(|| "Constructing linear combinations");
+        // Compute the AHP verifier's query set.
+        let (query_set, verifier_state) = AHPForR1CS::<_, SM>::verifier_query_set(verifier_state);
         let lc_s = AHPForR1CS::<_, SM>::construct_linear_combinations(
             &public_inputs,
-            &evaluations,
-            &proof.third_msg,
-            &proof.fourth_msg,
+            &polynomials,
+            &prover_third_message,
+            &prover_fourth_message,
             &verifier_state,
         )?;
-        end_timer!(lc_time);
 
-        let pc_time = start_timer!(|| "Checking linear combinations with PC");
-        let evaluations_are_correct = SonicKZG10::<E, FS>::check_combinations(
-            universal_verifier,
+        let eval_time = start_timer!(|| "Evaluating linear combinations over query set");
+        let mut evaluations = std::collections::BTreeMap::new();
+        for (label, (_, point)) in query_set.to_set() {
+            if !AHPForR1CS::<E::Fr, SM>::LC_WITH_ZERO_EVAL.contains(&label.as_str()) {
+                let lc = lc_s.get(&label).ok_or_else(|| AHPError::MissingEval(label.to_string()))?;
+                let evaluation = polynomials.get_lc_eval(lc, point)?;
+                evaluations.insert(label, evaluation);
+            }
+        }
+
+        let evaluations = proof::Evaluations::from_map(&evaluations, batch_sizes.clone());
+        end_timer!(eval_time);
+
+        sponge.absorb_nonnative_field_elements(evaluations.to_field_elements());
+
+        let pc_proof = SonicKZG10::<E, FS>::open_combinations(
+            universal_prover,
+            &committer_key,
             lc_s.values(),
-            &commitments,
+            polynomials,
+            &commitment_randomnesses,
             &query_set.to_set(),
-            &evaluations,
-            &proof.pc_proof,
             &mut sponge,
         )?;
-        end_timer!(pc_time);
 
-        if !evaluations_are_correct {
-            #[cfg(debug_assertions)]
-            eprintln!("SonicKZG10::Check failed using final challenge: {:?}", verifier_state.gamma);
-        }
+        let proof = Proof::<E>::new(
+            batch_sizes,
+            commitments,
+            evaluations,
+            prover_third_message,
+            prover_fourth_message,
+            pc_proof,
+        )?;
+        proof.check_batch_sizes()?;
+        ensure!(proof.pc_proof.is_hiding() == SM::ZK);
 
-        end_timer!(verifier_time, || format!(
-            " SonicKZG10::Check for AHP Verifier linear equations: {}",
-            evaluations_are_correct & proof_has_correct_zk_mode
-        ));
-        Ok(evaluations_are_correct & proof_has_correct_zk_mode)
+        end_timer!(prover_time);
+        Ok(proof)
     }
 }
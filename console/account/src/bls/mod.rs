@@ -0,0 +1,178 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod private_key;
+pub use private_key::BLSPrivateKey;
+
+mod public_key;
+pub use public_key::BLSPublicKey;
+
+mod signature;
+pub use signature::BLSSignature;
+
+use crate::PrivateKey;
+use snarkvm_console_algorithms::Blake2Xs;
+use snarkvm_console_network::prelude::*;
+use snarkvm_console_types::Field;
+use snarkvm_curves::{PairingCurve, PairingEngine};
+use snarkvm_utilities::{CanonicalDeserialize, CanonicalSerialize};
+
+/// The BLS12-377 `G1` affine type of the network, which hosts signatures.
+type G1Affine<N> = <<N as Environment>::PairingCurve as PairingEngine>::G1Affine;
+/// The BLS12-377 `G2` affine type of the network, which hosts public keys.
+type G2Affine<N> = <<N as Environment>::PairingCurve as PairingEngine>::G2Affine;
+
+/// The domain separator for signed messages.
+static BLS_MESSAGE_DOMAIN: &str = "AleoBLSMessage0";
+/// The domain separator for proofs of possession.
+static BLS_POSSESSION_DOMAIN: &str = "AleoBLSProofOfPossession0";
+
+/// Returns the `G1` element for the given message and domain, computed as
+/// `HashToCurve(domain || HashPSD8(message))`.
+fn hash_to_g1<N: Network>(domain: &str, message: &[Field<N>]) -> Result<G1Affine<N>> {
+    // Compress the message into a single field element.
+    let digest = N::hash_psd8(message)?;
+    // Hash the domain-separated digest onto the curve.
+    let (point, _, _) = Blake2Xs::hash_to_curve::<G1Affine<N>>(&format!("{domain}.{digest}"));
+    Ok(point)
+}
+
+/// Returns `true` if `e(signature, G2) == e(point, public_key)`.
+fn check_pairing<N: Network>(signature: &G1Affine<N>, point: &G1Affine<N>, public_key: &G2Affine<N>) -> bool {
+    // Retrieve the generator of `G2`.
+    let generator = G2Affine::<N>::prime_subgroup_generator();
+    // Check `e(signature, -G2) * e(point, public_key) == 1`.
+    <N::PairingCurve as PairingEngine>::product_of_pairings(
+        [(&signature.prepare(), &(-generator).prepare()), (&point.prepare(), &public_key.prepare())].iter().copied(),
+    )
+    .is_one()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    const ITERATIONS: u64 = 10;
+
+    /// Samples `num_keys` random BLS private keys.
+    fn sample_private_keys(num_keys: usize, rng: &mut TestRng) -> Vec<BLSPrivateKey<CurrentNetwork>> {
+        (0..num_keys).map(|_| BLSPrivateKey::new(rng).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_sign_and_verify() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        for i in 0..ITERATIONS {
+            // Sample a private key.
+            let private_key = BLSPrivateKey::<CurrentNetwork>::new(rng)?;
+            let public_key = private_key.to_public_key();
+
+            // Check that the signature is valid for the message.
+            let message: Vec<_> = (0..i).map(|_| Uniform::rand(rng)).collect();
+            let signature = private_key.sign(&message)?;
+            assert!(signature.verify(&public_key, &message));
+
+            // Check that the signature is invalid for an incorrect message.
+            let failure_message: Vec<_> = (0..=i).map(|_| Uniform::rand(rng)).collect();
+            assert!(!signature.verify(&public_key, &failure_message));
+
+            // Check that the signature is invalid for an incorrect public key.
+            let failure_public_key = BLSPrivateKey::<CurrentNetwork>::new(rng)?.to_public_key();
+            assert!(!signature.verify(&failure_public_key, &message));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_verify() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        for i in 1..ITERATIONS {
+            // Sample the signers.
+            let private_keys = sample_private_keys(i as usize, rng);
+            let public_keys = private_keys.iter().map(|private_key| private_key.to_public_key()).collect::<Vec<_>>();
+
+            // Sign the message with every signer.
+            let message = [Field::rand(rng)];
+            let signatures =
+                private_keys.iter().map(|private_key| private_key.sign(&message)).collect::<Result<Vec<_>>>()?;
+
+            // Check that the aggregate signature is valid for all signers.
+            let aggregate = BLSSignature::aggregate(&signatures)?;
+            assert!(aggregate.verify_aggregate(&public_keys, &message));
+
+            // Check that the aggregate signature is invalid for a subset of the signers.
+            assert!(!aggregate.verify_aggregate(&public_keys[1..], &message));
+            // Check that the aggregate signature is invalid for an incorrect message.
+            assert!(!aggregate.verify_aggregate(&public_keys, &[Field::rand(rng)]));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_empty() {
+        // Ensure there is no aggregate for an empty list.
+        assert!(BLSSignature::<CurrentNetwork>::aggregate(&[]).is_err());
+        assert!(BLSPublicKey::<CurrentNetwork>::aggregate(&[]).is_err());
+    }
+
+    #[test]
+    fn test_proof_of_possession() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a private key.
+            let private_key = BLSPrivateKey::<CurrentNetwork>::new(rng)?;
+            let public_key = private_key.to_public_key();
+
+            // Check that the proof of possession is valid for the public key.
+            let proof = private_key.prove_possession()?;
+            assert!(public_key.verify_possession(&proof));
+
+            // Check that the proof of possession is invalid for another public key.
+            let failure_public_key = BLSPrivateKey::<CurrentNetwork>::new(rng)?.to_public_key();
+            assert!(!failure_public_key.verify_possession(&proof));
+
+            // Check that a proof of possession is not a valid signature on the public key bytes as a message.
+            let message = public_key.to_fields()?;
+            assert!(!proof.verify(&public_key, &message));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_account_private_key() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample an account private key.
+            let private_key = PrivateKey::<CurrentNetwork>::new(rng)?;
+
+            // Check that the BLS private key is derived deterministically.
+            let expected = BLSPrivateKey::try_from(&private_key)?;
+            let candidate = BLSPrivateKey::try_from(&private_key)?;
+            assert_eq!(expected, candidate);
+            assert_eq!(expected.to_public_key(), candidate.to_public_key());
+
+            // Check that a different account derives a different BLS private key.
+            let other = BLSPrivateKey::try_from(&PrivateKey::<CurrentNetwork>::new(rng)?)?;
+            assert_ne!(expected, other);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use zeroize::Zeroize;
+
+static BLS_SECRET_KEY_DOMAIN: &str = "AleoBLSSecretKey0";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Zeroize)]
+pub struct BLSPrivateKey<N: Network> {
+    /// The BLS secret key `sk`.
+    sk: Field<N>,
+}
+
+impl<N: Network> BLSPrivateKey<N> {
+    /// Samples a new random BLS private key.
+    pub fn new<R: Rng + CryptoRng>(rng: &mut R) -> Result<Self> {
+        Self::from_secret(Uniform::rand(rng))
+    }
+
+    /// Initializes a BLS private key from a secret key.
+    pub fn from_secret(sk: Field<N>) -> Result<Self> {
+        // Ensure the secret key is nonzero.
+        ensure!(!sk.is_zero(), "The BLS secret key must be nonzero");
+        Ok(Self { sk })
+    }
+
+    /// Returns the BLS public key `pk := sk * G2`.
+    pub fn to_public_key(&self) -> BLSPublicKey<N> {
+        BLSPublicKey::from_point_unchecked((G2Affine::<N>::prime_subgroup_generator() * *self.sk).to_affine())
+    }
+
+    /// Returns a signature `sig := sk * HashToG1(message)` for the given message.
+    pub fn sign(&self, message: &[Field<N>]) -> Result<BLSSignature<N>> {
+        // Hash the message to a point in `G1`.
+        let point = hash_to_g1::<N>(BLS_MESSAGE_DOMAIN, message)?;
+        // Output the signature.
+        Ok(BLSSignature::from_point_unchecked((point * *self.sk).to_affine()))
+    }
+
+    /// Returns a proof of possession of the secret key for the public key, defined as
+    /// a signature on the public key under a separate domain.
+    pub fn prove_possession(&self) -> Result<BLSSignature<N>> {
+        // Hash the public key to a point in `G1`.
+        let point = hash_to_g1::<N>(BLS_POSSESSION_DOMAIN, &self.to_public_key().to_fields()?)?;
+        // Output the proof of possession.
+        Ok(BLSSignature::from_point_unchecked((point * *self.sk).to_affine()))
+    }
+}
+
+impl<N: Network> TryFrom<&PrivateKey<N>> for BLSPrivateKey<N> {
+    type Error = Error;
+
+    /// Derives the BLS private key deterministically from the account seed, as
+    /// `sk := HashPSD2(domain || seed)`.
+    fn try_from(private_key: &PrivateKey<N>) -> Result<Self, Self::Error> {
+        // Construct the domain separator.
+        let domain = Field::<N>::new_domain_separator(BLS_SECRET_KEY_DOMAIN);
+        // Derive the secret key.
+        Self::from_secret(N::hash_psd2(&[domain, private_key.seed()])?)
+    }
+}
+
+impl<N: Network> TryFrom<PrivateKey<N>> for BLSPrivateKey<N> {
+    type Error = Error;
+
+    /// Derives the BLS private key deterministically from the account seed.
+    fn try_from(private_key: PrivateKey<N>) -> Result<Self, Self::Error> {
+        Self::try_from(&private_key)
+    }
+}
+
+impl<N: Network> FromBytes for BLSPrivateKey<N> {
+    /// Reads a BLS private key from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        Self::from_secret(Field::read_le(&mut reader)?).map_err(|e| error(format!("{e}")))
+    }
+}
+
+impl<N: Network> ToBytes for BLSPrivateKey<N> {
+    /// Writes a BLS private key to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.sk.write_le(&mut writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    const ITERATIONS: u64 = 100;
+
+    #[test]
+    fn test_bytes() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a new BLS private key.
+            let expected = BLSPrivateKey::<CurrentNetwork>::new(&mut rng)?;
+
+            // Check the byte representation.
+            let expected_bytes = expected.to_bytes_le()?;
+            assert_eq!(expected, BLSPrivateKey::read_le(&expected_bytes[..])?);
+            assert!(BLSPrivateKey::<CurrentNetwork>::read_le(&expected_bytes[1..]).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_secret_key() {
+        assert!(BLSPrivateKey::<CurrentNetwork>::from_secret(Field::zero()).is_err());
+    }
+}
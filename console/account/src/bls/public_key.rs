@@ -0,0 +1,255 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+static BLS_PUBLIC_KEY_PREFIX: &str = "blspk";
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BLSPublicKey<N: Network> {
+    /// The BLS public key `pk := sk * G2`.
+    pk: G2Affine<N>,
+}
+
+impl<N: Network> BLSPublicKey<N> {
+    /// Initializes a BLS public key from a point in `G2`.
+    pub fn from_point(pk: G2Affine<N>) -> Result<Self> {
+        // Ensure the point is in the prime-order subgroup.
+        ensure!(pk.is_on_curve() && pk.is_in_correct_subgroup_assuming_on_curve(), "Invalid BLS public key");
+        // Ensure the point is not the identity.
+        ensure!(!pk.is_zero(), "The BLS public key must not be the identity");
+        Ok(Self { pk })
+    }
+
+    /// Initializes a BLS public key from a point in `G2`, without checking the point.
+    pub(super) const fn from_point_unchecked(pk: G2Affine<N>) -> Self {
+        Self { pk }
+    }
+
+    /// Returns the public key as a point in `G2`.
+    pub const fn to_point(&self) -> G2Affine<N> {
+        self.pk
+    }
+
+    /// Returns the sum of the given public keys.
+    /// Note: The caller must verify a proof of possession for each public key beforehand,
+    /// to rule out rogue-key attacks on the aggregate.
+    pub fn aggregate(public_keys: &[Self]) -> Result<Self> {
+        // Ensure there is at least one public key.
+        ensure!(!public_keys.is_empty(), "Cannot aggregate an empty list of BLS public keys");
+        // Sum the public keys.
+        let aggregate = public_keys
+            .iter()
+            .fold(<G2Affine<N> as AffineCurve>::Projective::zero(), |sum, public_key| sum.add_mixed(&public_key.pk));
+        Ok(Self { pk: aggregate.to_affine() })
+    }
+
+    /// Returns `true` if the given proof of possession is valid for this public key.
+    pub fn verify_possession(&self, proof: &BLSSignature<N>) -> bool {
+        // Pack the public key into field elements.
+        let message = match self.to_fields() {
+            Ok(message) => message,
+            Err(error) => {
+                eprintln!("Failed to verify the proof of possession: {error}");
+                return false;
+            }
+        };
+        // Hash the public key to a point in `G1`.
+        match hash_to_g1::<N>(BLS_POSSESSION_DOMAIN, &message) {
+            Ok(point) => check_pairing::<N>(&proof.to_point(), &point, &self.pk),
+            Err(error) => {
+                eprintln!("Failed to verify the proof of possession: {error}");
+                false
+            }
+        }
+    }
+}
+
+impl<N: Network> From<&BLSPrivateKey<N>> for BLSPublicKey<N> {
+    /// Derives the BLS public key from the BLS private key.
+    fn from(private_key: &BLSPrivateKey<N>) -> Self {
+        private_key.to_public_key()
+    }
+}
+
+impl<N: Network> ToFields for BLSPublicKey<N> {
+    type Field = Field<N>;
+
+    /// Packs the compressed public key into a list of base fields.
+    fn to_fields(&self) -> Result<Vec<Self::Field>> {
+        self.to_bytes_le()?.to_bits_le().chunks(Field::<N>::size_in_data_bits()).map(Field::from_bits_le).collect()
+    }
+}
+
+impl<N: Network> FromBytes for BLSPublicKey<N> {
+    /// Reads a BLS public key from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the compressed point, checking that it is in the prime-order subgroup.
+        let pk = G2Affine::<N>::deserialize_compressed(&mut reader)?;
+        Self::from_point(pk).map_err(|e| error(format!("{e}")))
+    }
+}
+
+impl<N: Network> ToBytes for BLSPublicKey<N> {
+    /// Writes a BLS public key to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        Ok(self.pk.serialize_compressed(&mut writer)?)
+    }
+}
+
+impl<N: Network> FromStr for BLSPublicKey<N> {
+    type Err = Error;
+
+    /// Reads in the BLS public key string.
+    fn from_str(public_key: &str) -> Result<Self, Self::Err> {
+        // Decode the public key string from bech32m.
+        let (hrp, data, variant) = bech32::decode(public_key)?;
+        if hrp != BLS_PUBLIC_KEY_PREFIX {
+            bail!("Failed to decode BLS public key: '{hrp}' is an invalid prefix")
+        } else if data.is_empty() {
+            bail!("Failed to decode BLS public key: data field is empty")
+        } else if variant != bech32::Variant::Bech32m {
+            bail!("Found a BLS public key that is not bech32m encoded: {public_key}");
+        }
+        // Decode the public key data from u5 to u8, and into the public key.
+        Ok(Self::read_le(&Vec::from_base32(&data)?[..])?)
+    }
+}
+
+impl<N: Network> Debug for BLSPublicKey<N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<N: Network> Display for BLSPublicKey<N> {
+    /// Writes the BLS public key as a bech32m string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // Convert the public key to bytes.
+        let bytes = self.to_bytes_le().map_err(|_| fmt::Error)?;
+        // Encode the bytes into bech32m.
+        let string = bech32::encode(BLS_PUBLIC_KEY_PREFIX, bytes.to_base32(), bech32::Variant::Bech32m)
+            .map_err(|_| fmt::Error)?;
+        // Output the string.
+        Display::fmt(&string, f)
+    }
+}
+
+impl<N: Network> Serialize for BLSPublicKey<N> {
+    /// Serializes the BLS public key into string or bytes.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => serializer.collect_str(self),
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for BLSPublicKey<N> {
+    /// Deserializes the BLS public key from a string or bytes.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => FromStr::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom),
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "BLS public key"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    const ITERATIONS: u64 = 100;
+
+    #[test]
+    fn test_bytes() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a new BLS public key.
+            let expected = BLSPrivateKey::<CurrentNetwork>::new(&mut rng)?.to_public_key();
+
+            // Check the byte representation.
+            let expected_bytes = expected.to_bytes_le()?;
+            assert_eq!(96, expected_bytes.len(), "Update me if serialization has changed");
+            assert_eq!(expected, BLSPublicKey::read_le(&expected_bytes[..])?);
+            assert!(BLSPublicKey::<CurrentNetwork>::read_le(&expected_bytes[1..]).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_string() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a new BLS public key.
+            let expected = BLSPrivateKey::<CurrentNetwork>::new(&mut rng)?.to_public_key();
+
+            // Check the string representation.
+            let candidate = expected.to_string();
+            assert_eq!(BLS_PUBLIC_KEY_PREFIX, &candidate[0..5], "Update me if the prefix has changed");
+            assert_eq!(expected, BLSPublicKey::from_str(&candidate)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_json() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a new BLS public key.
+            let expected = BLSPrivateKey::<CurrentNetwork>::new(&mut rng)?.to_public_key();
+
+            // Serialize
+            let expected_string = &expected.to_string();
+            let candidate_string = serde_json::to_string(&expected)?;
+            assert_eq!(expected_string, serde_json::Value::from_str(&candidate_string)?.as_str().unwrap());
+
+            // Deserialize
+            assert_eq!(expected, BLSPublicKey::from_str(expected_string)?);
+            assert_eq!(expected, serde_json::from_str(&candidate_string)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_bincode() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a new BLS public key.
+            let expected = BLSPrivateKey::<CurrentNetwork>::new(&mut rng)?.to_public_key();
+
+            // Serialize
+            let expected_bytes = expected.to_bytes_le()?;
+            let expected_bytes_with_size_encoding = bincode::serialize(&expected)?;
+            assert_eq!(&expected_bytes[..], &expected_bytes_with_size_encoding[8..]);
+
+            // Deserialize
+            assert_eq!(expected, BLSPublicKey::read_le(&expected_bytes[..])?);
+            assert_eq!(expected, bincode::deserialize(&expected_bytes_with_size_encoding[..])?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_identity_is_rejected() {
+        assert!(BLSPublicKey::<CurrentNetwork>::from_point(G2Affine::<CurrentNetwork>::zero()).is_err());
+    }
+}
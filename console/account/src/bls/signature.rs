@@ -0,0 +1,230 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+static BLS_SIGNATURE_PREFIX: &str = "blssig";
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BLSSignature<N: Network> {
+    /// The BLS signature `sig := sk * HashToG1(message)`.
+    sig: G1Affine<N>,
+}
+
+impl<N: Network> BLSSignature<N> {
+    /// Initializes a BLS signature from a point in `G1`.
+    pub fn from_point(sig: G1Affine<N>) -> Result<Self> {
+        // Ensure the point is in the prime-order subgroup.
+        ensure!(sig.is_on_curve() && sig.is_in_correct_subgroup_assuming_on_curve(), "Invalid BLS signature");
+        Ok(Self { sig })
+    }
+
+    /// Initializes a BLS signature from a point in `G1`, without checking the point.
+    pub(super) const fn from_point_unchecked(sig: G1Affine<N>) -> Self {
+        Self { sig }
+    }
+
+    /// Returns the signature as a point in `G1`.
+    pub const fn to_point(&self) -> G1Affine<N> {
+        self.sig
+    }
+
+    /// Returns the aggregate of the given signatures.
+    pub fn aggregate(signatures: &[Self]) -> Result<Self> {
+        // Ensure there is at least one signature.
+        ensure!(!signatures.is_empty(), "Cannot aggregate an empty list of BLS signatures");
+        // Sum the signatures.
+        let aggregate = signatures
+            .iter()
+            .fold(<G1Affine<N> as AffineCurve>::Projective::zero(), |sum, signature| sum.add_mixed(&signature.sig));
+        Ok(Self { sig: aggregate.to_affine() })
+    }
+
+    /// Verifies `e(sig, G2) == e(HashToG1(message), pk)`.
+    pub fn verify(&self, public_key: &BLSPublicKey<N>, message: &[Field<N>]) -> bool {
+        // Ensure the number of field elements does not exceed the maximum allowed size.
+        if message.len() > N::MAX_DATA_SIZE_IN_FIELDS as usize {
+            eprintln!("Cannot verify the BLS signature: the signed message exceeds maximum allowed size");
+            return false;
+        }
+        // Hash the message to a point in `G1`, and return `false` if this operation fails.
+        match hash_to_g1::<N>(BLS_MESSAGE_DOMAIN, message) {
+            Ok(point) => check_pairing::<N>(&self.sig, &point, &public_key.to_point()),
+            Err(error) => {
+                eprintln!("Failed to verify the BLS signature: {error}");
+                false
+            }
+        }
+    }
+
+    /// Verifies an aggregate signature, where every public key signed the same message.
+    /// Note: The caller must verify a proof of possession for each public key beforehand,
+    /// to rule out rogue-key attacks on the aggregate.
+    pub fn verify_aggregate(&self, public_keys: &[BLSPublicKey<N>], message: &[Field<N>]) -> bool {
+        match BLSPublicKey::aggregate(public_keys) {
+            Ok(public_key) => self.verify(&public_key, message),
+            Err(error) => {
+                eprintln!("Failed to verify the aggregate BLS signature: {error}");
+                false
+            }
+        }
+    }
+}
+
+impl<N: Network> FromBytes for BLSSignature<N> {
+    /// Reads a BLS signature from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the compressed point, checking that it is in the prime-order subgroup.
+        Ok(Self { sig: G1Affine::<N>::deserialize_compressed(&mut reader)? })
+    }
+}
+
+impl<N: Network> ToBytes for BLSSignature<N> {
+    /// Writes a BLS signature to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        Ok(self.sig.serialize_compressed(&mut writer)?)
+    }
+}
+
+impl<N: Network> FromStr for BLSSignature<N> {
+    type Err = Error;
+
+    /// Reads in the BLS signature string.
+    fn from_str(signature: &str) -> Result<Self, Self::Err> {
+        // Decode the signature string from bech32m.
+        let (hrp, data, variant) = bech32::decode(signature)?;
+        if hrp != BLS_SIGNATURE_PREFIX {
+            bail!("Failed to decode BLS signature: '{hrp}' is an invalid prefix")
+        } else if data.is_empty() {
+            bail!("Failed to decode BLS signature: data field is empty")
+        } else if variant != bech32::Variant::Bech32m {
+            bail!("Found a BLS signature that is not bech32m encoded: {signature}");
+        }
+        // Decode the signature data from u5 to u8, and into the signature.
+        Ok(Self::read_le(&Vec::from_base32(&data)?[..])?)
+    }
+}
+
+impl<N: Network> Debug for BLSSignature<N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<N: Network> Display for BLSSignature<N> {
+    /// Writes the BLS signature as a bech32m string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // Convert the signature to bytes.
+        let bytes = self.to_bytes_le().map_err(|_| fmt::Error)?;
+        // Encode the bytes into bech32m.
+        let string = bech32::encode(BLS_SIGNATURE_PREFIX, bytes.to_base32(), bech32::Variant::Bech32m)
+            .map_err(|_| fmt::Error)?;
+        // Output the string.
+        Display::fmt(&string, f)
+    }
+}
+
+impl<N: Network> Serialize for BLSSignature<N> {
+    /// Serializes the BLS signature into string or bytes.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => serializer.collect_str(self),
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for BLSSignature<N> {
+    /// Deserializes the BLS signature from a string or bytes.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => FromStr::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom),
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "BLS signature"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    const ITERATIONS: u64 = 100;
+
+    /// Samples a random BLS signature.
+    fn sample_signature(rng: &mut TestRng) -> BLSSignature<CurrentNetwork> {
+        let private_key = BLSPrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        private_key.sign(&[Uniform::rand(rng)]).unwrap()
+    }
+
+    #[test]
+    fn test_bytes() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a new BLS signature.
+            let expected = sample_signature(&mut rng);
+
+            // Check the byte representation.
+            let expected_bytes = expected.to_bytes_le()?;
+            assert_eq!(48, expected_bytes.len(), "Update me if serialization has changed");
+            assert_eq!(expected, BLSSignature::read_le(&expected_bytes[..])?);
+            assert!(BLSSignature::<CurrentNetwork>::read_le(&expected_bytes[1..]).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_json() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a new BLS signature.
+            let expected = sample_signature(&mut rng);
+
+            // Serialize
+            let expected_string = &expected.to_string();
+            let candidate_string = serde_json::to_string(&expected)?;
+            assert_eq!(BLS_SIGNATURE_PREFIX, &expected_string[0..6], "Update me if the prefix has changed");
+            assert_eq!(expected_string, serde_json::Value::from_str(&candidate_string)?.as_str().unwrap());
+
+            // Deserialize
+            assert_eq!(expected, BLSSignature::from_str(expected_string)?);
+            assert_eq!(expected, serde_json::from_str(&candidate_string)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_bincode() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a new BLS signature.
+            let expected = sample_signature(&mut rng);
+
+            // Serialize
+            let expected_bytes = expected.to_bytes_le()?;
+            let expected_bytes_with_size_encoding = bincode::serialize(&expected)?;
+            assert_eq!(&expected_bytes[..], &expected_bytes_with_size_encoding[8..]);
+
+            // Deserialize
+            assert_eq!(expected, BLSSignature::read_le(&expected_bytes[..])?);
+            assert_eq!(expected, bincode::deserialize(&expected_bytes_with_size_encoding[..])?);
+        }
+        Ok(())
+    }
+}
@@ -21,6 +21,11 @@ pub use snarkvm_console_types::{environment::prelude::*, Address, Field, Group,
 
 mod address;
 
+#[cfg(feature = "bls")]
+pub mod bls;
+#[cfg(feature = "bls")]
+pub use bls::*;
+
 #[cfg(feature = "compute_key")]
 pub mod compute_key;
 #[cfg(feature = "compute_key")]
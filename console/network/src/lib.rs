@@ -113,6 +113,8 @@ pub trait Network:
     const MAX_SOLUTIONS: usize = 1 << 8; // 256 solutions
     /// The number of blocks per epoch.
     const NUM_BLOCKS_PER_EPOCH: u32 = 3600 / Self::BLOCK_TIME as u32; // 360 blocks == ~1 hour
+    /// The block height from which the leader of a quorum block is elected with the leader reputation.
+    const REPUTATION_ELECTION_HEIGHT: u32 = 0;
 
     /// The maximum number of entries in data.
     const MAX_DATA_ENTRIES: usize = 32;
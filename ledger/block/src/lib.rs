@@ -52,7 +52,7 @@ use console::{
 };
 use ledger_authority::Authority;
 use ledger_coinbase::{CoinbaseSolution, ProverSolution, PuzzleCommitment};
-use ledger_committee::Committee;
+use ledger_committee::{Committee, LeaderReputation};
 use ledger_narwhal_subdag::Subdag;
 use ledger_narwhal_transmission_id::TransmissionID;
 
@@ -56,7 +56,13 @@ impl<N: Network> FromBytes for Ratify<N> {
                 // Return the ratify object.
                 Self::PuzzleReward(amount)
             }
-            3.. => return Err(error(format!("Failed to decode ratify object variant {variant}"))),
+            3 => {
+                // Read the equivocation evidence.
+                let evidence: EquivocationEvidence<N> = FromBytes::read_le(&mut reader)?;
+                // Return the ratify object.
+                Self::Slash(Box::new(evidence))
+            }
+            4.. => return Err(error(format!("Failed to decode ratify object variant {variant}"))),
         };
         Ok(ratify)
     }
@@ -87,6 +93,10 @@ impl<N: Network> ToBytes for Ratify<N> {
                 (2 as Variant).write_le(&mut writer)?;
                 amount.write_le(&mut writer)
             }
+            Self::Slash(evidence) => {
+                (3 as Variant).write_le(&mut writer)?;
+                evidence.write_le(&mut writer)
+            }
         }
     }
 }
@@ -18,6 +18,7 @@ mod string;
 
 use console::{network::prelude::*, types::Address};
 use ledger_committee::Committee;
+use ledger_narwhal_evidence::EquivocationEvidence;
 
 use indexmap::IndexMap;
 
@@ -33,6 +34,8 @@ pub enum Ratify<N: Network> {
     BlockReward(u64),
     /// The puzzle reward.
     PuzzleReward(u64),
+    /// The slashing of a validator, for the given equivocation evidence.
+    Slash(Box<EquivocationEvidence<N>>),
 }
 
 impl<N: Network> Ratify<N> {
@@ -60,6 +63,7 @@ pub(crate) mod test_helpers {
             Ratify::Genesis(committee, public_balances),
             Ratify::BlockReward(rng.gen()),
             Ratify::PuzzleReward(rng.gen()),
+            Ratify::Slash(Box::new(ledger_narwhal_evidence::test_helpers::sample_equivocation_evidence(rng))),
         ]
     }
 }
@@ -38,6 +38,12 @@ impl<N: Network> Serialize for Ratify<N> {
                     input.serialize_field("amount", &amount)?;
                     input.end()
                 }
+                Self::Slash(evidence) => {
+                    let mut input = serializer.serialize_struct("Ratify", 2)?;
+                    input.serialize_field("type", "slash")?;
+                    input.serialize_field("evidence", &evidence)?;
+                    input.end()
+                }
             },
             false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
         }
@@ -75,6 +81,13 @@ impl<'de, N: Network> Deserialize<'de> for Ratify<N> {
                         // Construct the ratify object.
                         Ratify::PuzzleReward(amount)
                     }
+                    Some("slash") => {
+                        // Retrieve the equivocation evidence.
+                        let evidence: EquivocationEvidence<N> =
+                            DeserializeExt::take_from_value::<D>(&mut object, "evidence")?;
+                        // Construct the ratify object.
+                        Ratify::Slash(Box::new(evidence))
+                    }
                     _ => return Err(de::Error::custom("Invalid ratify object type")),
                 };
                 // Return the ratify object.
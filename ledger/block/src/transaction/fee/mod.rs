@@ -93,8 +93,10 @@ impl<N: Network> Fee<N> {
         // Retrieve the priority fee amount.
         let priority_fee_amount = self.priority_amount()?;
         // Return the amount.
-        // Note: Use of saturating add is safe, as the sum cannot exceed a u64.
-        Ok(U64::new(base_fee_amount.saturating_add(*priority_fee_amount)))
+        match (*base_fee_amount).checked_add(*priority_fee_amount) {
+            Some(amount) => Ok(U64::new(amount)),
+            None => bail!("The sum of the base fee and the priority fee overflowed"),
+        }
     }
 
     /// Returns the base amount (in microcredits).
@@ -116,7 +118,7 @@ impl<N: Network> Fee<N> {
         }
     }
 
-    /// Returns the base amount (in microcredits).
+    /// Returns the priority amount (in microcredits).
     pub fn priority_amount(&self) -> Result<U64<N>> {
         // Determine the indexes for the priority fee.
         // Note: Checking whether the `output` is a `Record` or `Future` is a faster way to determine if the fee is private or public respectively.
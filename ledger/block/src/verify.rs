@@ -31,6 +31,7 @@ impl<N: Network> Block<N> {
         previous_block: &Block<N>,
         current_state_root: N::StateRoot,
         current_committee: &Committee<N>,
+        current_leader_reputation: Option<&LeaderReputation<N>>,
         current_puzzle: &CoinbasePuzzle<N>,
         current_epoch_challenge: &EpochChallenge<N>,
         current_timestamp: i64,
@@ -40,8 +41,12 @@ impl<N: Network> Block<N> {
         self.verify_hash(previous_block.height(), previous_block.hash())?;
 
         // Ensure the block authority is correct.
-        let (expected_round, expected_height, expected_timestamp) =
-            self.verify_authority(previous_block.round(), previous_block.height(), current_committee)?;
+        let (expected_round, expected_height, expected_timestamp) = self.verify_authority(
+            previous_block.round(),
+            previous_block.height(),
+            current_committee,
+            current_leader_reputation,
+        )?;
 
         // Ensure the block solutions are correct.
         let (
@@ -136,6 +141,7 @@ impl<N: Network> Block<N> {
         previous_round: u64,
         previous_height: u32,
         current_committee: &Committee<N>,
+        current_leader_reputation: Option<&LeaderReputation<N>>,
     ) -> Result<(u64, u32, i64)> {
         #[cfg(not(any(test, feature = "test")))]
         ensure!(self.authority.is_quorum(), "The next block must be a quorum block");
@@ -186,8 +192,11 @@ impl<N: Network> Block<N> {
                 );
             }
             Authority::Quorum(subdag) => {
-                // Compute the expected leader.
-                let expected_leader = current_committee.get_leader(expected_round)?;
+                // Compute the expected leader, excluding the demoted members if the leader reputation is given.
+                let expected_leader = match current_leader_reputation {
+                    Some(reputation) => current_committee.get_leader_with_reputation(expected_round, reputation)?,
+                    None => current_committee.get_leader(expected_round)?,
+                };
                 // Ensure the block is authored by the expected leader.
                 ensure!(
                     subdag.leader_address() == expected_leader,
@@ -247,6 +256,20 @@ impl<N: Network> Block<N> {
             puzzle_reward == expected_puzzle_reward,
             "Block {height} has an invalid puzzle reward (found '{puzzle_reward}', expected '{expected_puzzle_reward}')",
         );
+
+        // Ensure the remaining ratifications are slashes, for distinct equivocation offenses.
+        let mut evidence_ids = HashSet::new();
+        for ratify in ratifications_iter {
+            match ratify {
+                Ratify::Slash(evidence) => ensure!(
+                    evidence_ids.insert(evidence.to_id()?),
+                    "Block {height} is invalid - found a duplicate slash for '{}' in round {}",
+                    evidence.author(),
+                    evidence.round()
+                ),
+                _ => bail!("Block {height} is invalid - found an unexpected ratification after the rewards"),
+            }
+        }
         Ok(())
     }
 
@@ -0,0 +1,139 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use console::prelude::{cfg_iter, Environment};
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// The mode of a batched proof search.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BatchMode {
+    /// Returns the solution for the first nonce (in the given order) that meets the minimum proof target.
+    First,
+    /// Returns the solutions for all nonces that meet the minimum proof target.
+    All,
+}
+
+/// The throughput statistics of a batched proof search.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProverStats {
+    /// The number of nonces for which a commitment was computed.
+    num_attempts: u64,
+    /// The number of nonces that met the minimum proof target, and were opened.
+    num_solutions: u64,
+    /// The time spent on the batch.
+    elapsed: Duration,
+}
+
+impl ProverStats {
+    /// Returns the number of nonces for which a commitment was computed.
+    /// In `BatchMode::First`, this may include nonces that were attempted in parallel after the first solution.
+    pub const fn num_attempts(&self) -> u64 {
+        self.num_attempts
+    }
+
+    /// Returns the number of nonces that met the minimum proof target, and were opened.
+    pub const fn num_solutions(&self) -> u64 {
+        self.num_solutions
+    }
+
+    /// Returns the time spent on the batch.
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the number of nonces attempted per second.
+    pub fn attempts_per_second(&self) -> f64 {
+        match self.elapsed.is_zero() {
+            true => 0.0,
+            false => self.num_attempts as f64 / self.elapsed.as_secs_f64(),
+        }
+    }
+}
+
+/// A commitment to the product polynomial for a nonce that met the minimum proof target.
+type Candidate<N> = (
+    PartialSolution<N>,
+    DensePolynomial<<<N as Environment>::PairingCurve as PairingEngine>::Fr>,
+    Vec<<<N as Environment>::PairingCurve as PairingEngine>::Fr>,
+);
+
+impl<N: Network> CoinbasePuzzle<N> {
+    /// Returns the prover solutions for the given nonces that meet the minimum proof target,
+    /// along with the throughput statistics of the batch.
+    ///
+    /// The nonces are committed to in parallel, and only the nonces that meet the minimum proof target are opened.
+    /// In `BatchMode::First`, at most one solution is returned.
+    /// In `BatchMode::All`, each candidate is opened as soon as it is committed to, so that the evaluations
+    /// of at most one product polynomial per thread are held in memory.
+    pub fn prove_batch(
+        &self,
+        epoch_challenge: &EpochChallenge<N>,
+        address: Address<N>,
+        nonces: &[u64],
+        minimum_proof_target: Option<u64>,
+        mode: BatchMode,
+    ) -> Result<(Vec<ProverSolution<N>>, ProverStats)> {
+        let start = Instant::now();
+
+        // Retrieve the coinbase proving key.
+        let pk = match self {
+            Self::Prover(coinbase_proving_key) => coinbase_proving_key,
+            Self::Verifier(_) => bail!("Cannot prove the coinbase puzzle with a verifier"),
+        };
+
+        // Initialize the counter for the attempted nonces.
+        let num_attempts = AtomicU64::new(0);
+
+        // Commits to the given nonce, and returns it as a candidate if it meets the minimum proof target.
+        let commit = |nonce: u64| -> Result<Option<Candidate<N>>> {
+            num_attempts.fetch_add(1, Ordering::Relaxed);
+            let (polynomial, product_evaluations, commitment) = Self::commit(pk, epoch_challenge, address, nonce)?;
+            let partial_solution = PartialSolution::new(address, nonce, commitment);
+            // Check the proof target before the opening, which is the expensive step.
+            match minimum_proof_target {
+                Some(minimum_target) if partial_solution.to_target()? < minimum_target => Ok(None),
+                _ => Ok(Some((partial_solution, polynomial, product_evaluations))),
+            }
+        };
+        // Opens the given candidate.
+        let open = |(partial_solution, polynomial, product_evaluations): Candidate<N>| {
+            Self::open(pk, epoch_challenge, partial_solution, &polynomial, &product_evaluations)
+        };
+
+        // Compute the solutions.
+        let solutions = match mode {
+            BatchMode::First => {
+                #[cfg(not(feature = "serial"))]
+                let candidate = nonces.par_iter().map(|nonce| commit(*nonce)).find_map_first(Result::transpose);
+                #[cfg(feature = "serial")]
+                let candidate = nonces.iter().find_map(|nonce| commit(*nonce).transpose());
+                candidate.map(|candidate| open(candidate?)).into_iter().collect::<Result<Vec<_>>>()?
+            }
+            BatchMode::All => cfg_iter!(nonces)
+                .filter_map(|nonce| commit(*nonce).transpose())
+                .map(|candidate| open(candidate?))
+                .collect::<Result<Vec<_>>>()?,
+        };
+
+        let stats = ProverStats {
+            num_attempts: num_attempts.into_inner(),
+            num_solutions: solutions.len() as u64,
+            elapsed: start.elapsed(),
+        };
+        Ok((solutions, stats))
+    }
+}
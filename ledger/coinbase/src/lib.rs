@@ -19,9 +19,15 @@
 mod helpers;
 pub use helpers::*;
 
+mod batch;
+pub use batch::*;
+
 mod hash;
 use hash::*;
 
+mod pool;
+pub use pool::*;
+
 #[cfg(test)]
 mod tests;
 
@@ -31,7 +37,7 @@ use console::{
 };
 use snarkvm_algorithms::{
     fft::{DensePolynomial, EvaluationDomain},
-    polycommit::kzg10::{UniversalParams as SRS, KZG10},
+    polycommit::kzg10::{KZGCommitment, UniversalParams as SRS, KZG10},
 };
 use snarkvm_curves::PairingEngine;
 use snarkvm_fields::Zero;
@@ -115,31 +121,14 @@ impl<N: Network> CoinbasePuzzle<N> {
         minimum_proof_target: Option<u64>,
     ) -> Result<ProverSolution<N>> {
         // Retrieve the coinbase proving key.
-        //let msmtime = start_timer("");
         let pk = match self {
             Self::Prover(coinbase_proving_key) => coinbase_proving_key,
             Self::Verifier(_) => bail!("Cannot prove the coinbase puzzle with a verifier"),
         };
 
-        let polynomial = Self::prover_polynomial(epoch_challenge, address, nonce)?;
-        // println!("prove polynomial: {}", polynomial.coeffs().len());
-
-        //end_timer(&msmtime, "polynomial");
-        let product_evaluations = {
-            let polynomial_evaluations = pk.product_domain.in_order_fft_with_pc(&polynomial, &pk.fft_precomputation);
-
-            // println!("polynomial_evaluations: {}", polynomial_evaluations.len());
-            let product_evaluations = pk.product_domain.mul_polynomials_in_evaluation_domain(
-                polynomial_evaluations,
-                &epoch_challenge.epoch_polynomial_evaluations().evaluations,
-            );
-            product_evaluations
-        };
-        //end_timer(&msmtime, "fft");
+        // Compute the commitment to the product polynomial.
+        let (polynomial, product_evaluations, commitment) = Self::commit(pk, epoch_challenge, address, nonce)?;
 
-        let (commitment, _rand) = KZG10::commit_lagrange(&pk.lagrange_basis(), &product_evaluations, None, None)?;
-        //end_timer(&msmtime, "commitment");
- 
         let partial_solution = PartialSolution::new(address, nonce, commitment);
 
         // Check that the minimum target is met.
@@ -151,25 +140,8 @@ impl<N: Network> CoinbasePuzzle<N> {
             );
         }
 
-        let point = hash_commitment(&commitment)?;
-        let product_eval_at_point = polynomial.evaluate(point) * epoch_challenge.epoch_polynomial().evaluate(point);
-        //end_timer(&msmtime, "product_eval_at_point");
-
-        let proof = KZG10::open_lagrange(
-            &pk.lagrange_basis(),
-            pk.product_domain_elements(),
-            &product_evaluations,
-            point,
-            product_eval_at_point,
-        )?;
-
-        //end_timer(&msmtime, "open_lagrange");
-        ensure!(!proof.is_hiding(), "The prover solution must contain a non-hiding proof");
-
-        debug_assert!(KZG10::check(&pk.verifying_key, &commitment, point, product_eval_at_point, &proof)?);
-
-        //end_timer(&msmtime, "KZG10::check");
-        Ok(ProverSolution::new(partial_solution, proof))
+        // Open the commitment.
+        Self::open(pk, epoch_challenge, partial_solution, &polynomial, &product_evaluations)
     }
 
     /// Returns `true` if the solutions are valid.
@@ -244,6 +216,58 @@ impl<N: Network> CoinbasePuzzle<N> {
         Ok(product_domain)
     }
 
+    /// Returns the prover polynomial, the evaluations of the product polynomial,
+    /// and the commitment to the product polynomial for the given nonce.
+    #[allow(clippy::type_complexity)]
+    fn commit(
+        pk: &CoinbaseProvingKey<N>,
+        epoch_challenge: &EpochChallenge<N>,
+        address: Address<N>,
+        nonce: u64,
+    ) -> Result<(
+        DensePolynomial<<N::PairingCurve as PairingEngine>::Fr>,
+        Vec<<N::PairingCurve as PairingEngine>::Fr>,
+        KZGCommitment<N::PairingCurve>,
+    )> {
+        let polynomial = Self::prover_polynomial(epoch_challenge, address, nonce)?;
+
+        // Evaluate the prover polynomial over the product domain, and multiply by the epoch polynomial.
+        let polynomial_evaluations = pk.product_domain.in_order_fft_with_pc(&polynomial, &pk.fft_precomputation);
+        let product_evaluations = pk.product_domain.mul_polynomials_in_evaluation_domain(
+            polynomial_evaluations,
+            &epoch_challenge.epoch_polynomial_evaluations().evaluations,
+        );
+
+        let (commitment, _rand) = KZG10::commit_lagrange(&pk.lagrange_basis(), &product_evaluations, None, None)?;
+        Ok((polynomial, product_evaluations, commitment))
+    }
+
+    /// Returns the prover solution for the given partial solution, by opening its commitment.
+    fn open(
+        pk: &CoinbaseProvingKey<N>,
+        epoch_challenge: &EpochChallenge<N>,
+        partial_solution: PartialSolution<N>,
+        polynomial: &DensePolynomial<<N::PairingCurve as PairingEngine>::Fr>,
+        product_evaluations: &[<N::PairingCurve as PairingEngine>::Fr],
+    ) -> Result<ProverSolution<N>> {
+        let commitment = partial_solution.commitment();
+        let point = hash_commitment(&commitment)?;
+        let product_eval_at_point = polynomial.evaluate(point) * epoch_challenge.epoch_polynomial().evaluate(point);
+
+        let proof = KZG10::open_lagrange(
+            &pk.lagrange_basis(),
+            pk.product_domain_elements(),
+            product_evaluations,
+            point,
+            product_eval_at_point,
+        )?;
+        ensure!(!proof.is_hiding(), "The prover solution must contain a non-hiding proof");
+
+        debug_assert!(KZG10::check(&pk.verifying_key, &commitment, point, product_eval_at_point, &proof)?);
+
+        Ok(ProverSolution::new(partial_solution, proof))
+    }
+
     /// Returns the prover polynomial for the coinbase puzzle.
     fn prover_polynomial(
         epoch_challenge: &EpochChallenge<N>,
@@ -0,0 +1,293 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use core::ops::Range;
+use indexmap::IndexMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The status of an accepted share.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ShareStatus {
+    /// The share met the share target, but not the proof target.
+    Share,
+    /// The share met the proof target, and is queued to be forwarded as a solution.
+    Solution,
+}
+
+/// The accounting of the work submitted by a worker.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct WorkerStats {
+    /// The number of accepted shares, including solutions.
+    num_shares: u64,
+    /// The number of accepted shares that met the proof target.
+    num_solutions: u64,
+    /// The number of rejected shares.
+    num_rejected: u64,
+    /// The sum of the proof targets of the accepted shares.
+    total_work: u128,
+}
+
+impl WorkerStats {
+    /// Returns the number of accepted shares, including solutions.
+    pub const fn num_shares(&self) -> u64 {
+        self.num_shares
+    }
+
+    /// Returns the number of accepted shares that met the proof target.
+    pub const fn num_solutions(&self) -> u64 {
+        self.num_solutions
+    }
+
+    /// Returns the number of rejected shares.
+    pub const fn num_rejected(&self) -> u64 {
+        self.num_rejected
+    }
+
+    /// Returns the sum of the proof targets of the accepted shares.
+    pub const fn total_work(&self) -> u128 {
+        self.total_work
+    }
+}
+
+/// A pool-side coordinator, which hands out nonce ranges to workers, accepts their shares at the share target,
+/// and queues the shares that meet the proof target, to be forwarded as solutions.
+///
+/// Every solution is computed for the pool address, so workers are identified by the address they are paid to,
+/// and a share is only credited to the worker that was assigned its nonce, in the current epoch.
+pub struct SolutionPool<N: Network> {
+    /// The coinbase puzzle, used to verify the shares.
+    puzzle: CoinbasePuzzle<N>,
+    /// The address of the pool, for which every share is computed.
+    pool_address: Address<N>,
+    /// The current epoch challenge.
+    epoch_challenge: EpochChallenge<N>,
+    /// The proof target of the network.
+    proof_target: u64,
+    /// The minimum proof target of a share.
+    share_target: u64,
+    /// The number of nonces in each assigned range.
+    range_size: u64,
+    /// The first nonce of the next assigned range.
+    next_nonce: u64,
+    /// The assigned nonce ranges in the current epoch, as a map of `start` to `(end, worker)`.
+    assignments: BTreeMap<u64, (u64, Address<N>)>,
+    /// The starts of the open nonce ranges of each worker in the current epoch, from oldest to newest.
+    worker_ranges: HashMap<Address<N>, Vec<u64>>,
+    /// The commitments of the accepted shares in the current epoch.
+    commitments: HashSet<PuzzleCommitment<N>>,
+    /// The accepted solutions that have not been forwarded yet.
+    solutions: Vec<ProverSolution<N>>,
+    /// The accounting of each worker.
+    workers: IndexMap<Address<N>, WorkerStats>,
+}
+
+impl<N: Network> SolutionPool<N> {
+    /// The maximum number of open nonce ranges per worker in an epoch.
+    pub const MAX_OPEN_RANGES: usize = 16;
+
+    /// Initializes a new solution pool for the given epoch challenge and proof target.
+    pub fn new(
+        puzzle: CoinbasePuzzle<N>,
+        pool_address: Address<N>,
+        epoch_challenge: EpochChallenge<N>,
+        proof_target: u64,
+        share_target: u64,
+        range_size: u64,
+    ) -> Result<Self> {
+        ensure!(share_target > 0, "The share target must be greater than zero");
+        ensure!(range_size > 0, "The nonce range size must be greater than zero");
+        Ok(Self {
+            puzzle,
+            pool_address,
+            epoch_challenge,
+            proof_target,
+            share_target,
+            range_size,
+            next_nonce: 0,
+            assignments: Default::default(),
+            worker_ranges: Default::default(),
+            commitments: Default::default(),
+            solutions: Default::default(),
+            workers: Default::default(),
+        })
+    }
+
+    /// Returns the address of the pool.
+    pub const fn pool_address(&self) -> Address<N> {
+        self.pool_address
+    }
+
+    /// Returns the current epoch challenge.
+    pub const fn epoch_challenge(&self) -> &EpochChallenge<N> {
+        &self.epoch_challenge
+    }
+
+    /// Returns the proof target of the network.
+    pub const fn proof_target(&self) -> u64 {
+        self.proof_target
+    }
+
+    /// Returns the minimum proof target of a share.
+    /// If the proof target of the network is lower, the share target is the proof target.
+    pub fn share_target(&self) -> u64 {
+        self.share_target.min(self.proof_target)
+    }
+
+    /// Returns the accounting of the given worker, if it has submitted a share.
+    pub fn get_worker_stats(&self, worker: &Address<N>) -> Option<&WorkerStats> {
+        self.workers.get(worker)
+    }
+
+    /// Returns the accounting of each worker, in order of their first submitted share.
+    pub const fn workers(&self) -> &IndexMap<Address<N>, WorkerStats> {
+        &self.workers
+    }
+
+    /// Returns the accounting of each worker, and resets it (e.g. after a payout).
+    pub fn take_workers(&mut self) -> IndexMap<Address<N>, WorkerStats> {
+        core::mem::take(&mut self.workers)
+    }
+
+    /// Returns the accepted solutions that have not been forwarded yet, and clears them.
+    pub fn take_solutions(&mut self) -> Vec<ProverSolution<N>> {
+        core::mem::take(&mut self.solutions)
+    }
+
+    /// Updates the proof target of the network.
+    ///
+    /// The queued solutions that no longer meet the proof target are discarded, as they would be rejected.
+    /// Returns the number of discarded solutions.
+    pub fn set_proof_target(&mut self, proof_target: u64) -> usize {
+        self.proof_target = proof_target;
+        let num_solutions = self.solutions.len();
+        self.solutions.retain(|solution| solution.to_target().map_or(false, |target| target >= proof_target));
+        num_solutions - self.solutions.len()
+    }
+
+    /// Updates the epoch challenge and the proof target of the network.
+    ///
+    /// If the epoch changes, the nonce ranges, share commitments, and queued solutions of the previous epoch are
+    /// discarded, as they are no longer valid. The accounting of the workers is kept.
+    /// Otherwise, the queued solutions that no longer meet the proof target are discarded.
+    /// Returns the number of discarded solutions.
+    pub fn set_epoch_challenge(&mut self, epoch_challenge: EpochChallenge<N>, proof_target: u64) -> usize {
+        // If the epoch is unchanged, keep the current state, except for the solutions below the proof target.
+        if epoch_challenge == self.epoch_challenge {
+            return self.set_proof_target(proof_target);
+        }
+        self.proof_target = proof_target;
+        self.epoch_challenge = epoch_challenge;
+        self.next_nonce = 0;
+        self.assignments.clear();
+        self.worker_ranges.clear();
+        self.commitments.clear();
+        core::mem::take(&mut self.solutions).len()
+    }
+
+    /// Assigns the next range of nonces in the current epoch to the given worker.
+    ///
+    /// If the worker already has `MAX_OPEN_RANGES` open ranges, its oldest range is closed,
+    /// and the shares with a nonce from the closed range are no longer accepted.
+    pub fn assign_nonces(&mut self, worker: Address<N>) -> Result<Range<u64>> {
+        let start = self.next_nonce;
+        let end = start.checked_add(self.range_size).ok_or_else(|| anyhow!("The nonces of the epoch are exhausted"))?;
+        // Close the oldest range of the worker, if it has reached the maximum number of open ranges.
+        let ranges = self.worker_ranges.entry(worker).or_default();
+        if ranges.len() >= Self::MAX_OPEN_RANGES {
+            self.assignments.remove(&ranges.remove(0));
+        }
+        ranges.push(start);
+        self.assignments.insert(start, (end, worker));
+        self.next_nonce = end;
+        Ok(start..end)
+    }
+
+    /// Returns `true` if the given worker was assigned a range of nonces in the current epoch.
+    pub fn is_assigned(&self, worker: &Address<N>) -> bool {
+        self.worker_ranges.contains_key(worker)
+    }
+
+    /// Returns the worker that was assigned the given nonce in the current epoch, if one exists.
+    pub fn get_assignee(&self, nonce: u64) -> Option<Address<N>> {
+        match self.assignments.range(..=nonce).next_back() {
+            Some((_, (end, worker))) if nonce < *end => Some(*worker),
+            _ => None,
+        }
+    }
+
+    /// Submits a share from the given worker, and returns its status if it is accepted.
+    ///
+    /// The share must be computed for the pool address, with a nonce assigned to the worker in the current epoch.
+    /// It must not duplicate an accepted share, and must be a valid solution at the share target, as in
+    /// `CoinbasePuzzle::check_solutions`. A rejected share is counted in the accounting of the worker,
+    /// only if the worker was assigned a range of nonces in the current epoch.
+    pub fn submit_share(&mut self, worker: Address<N>, share: ProverSolution<N>) -> Result<ShareStatus> {
+        let target = match self.check_share(worker, &share) {
+            Ok(target) => target,
+            Err(error) => {
+                // Count the rejected share, only for a known worker, so unknown addresses cannot grow the accounting.
+                if self.is_assigned(&worker) {
+                    let stats = self.workers.entry(worker).or_default();
+                    stats.num_rejected = stats.num_rejected.saturating_add(1);
+                }
+                return Err(error);
+            }
+        };
+        // Update the accounting of the worker.
+        let stats = self.workers.entry(worker).or_default();
+        stats.num_shares = stats.num_shares.saturating_add(1);
+        stats.total_work = stats.total_work.saturating_add(target as u128);
+        self.commitments.insert(share.commitment());
+
+        // If the share meets the proof target, queue it to be forwarded.
+        match target >= self.proof_target {
+            true => {
+                stats.num_solutions = stats.num_solutions.saturating_add(1);
+                self.solutions.push(share);
+                Ok(ShareStatus::Solution)
+            }
+            false => Ok(ShareStatus::Share),
+        }
+    }
+
+    /// Checks the given share from the given worker, and returns its proof target.
+    fn check_share(&self, worker: Address<N>, share: &ProverSolution<N>) -> Result<u64> {
+        // Ensure the share is for the pool address.
+        ensure!(share.address() == self.pool_address, "The share is not for the pool address '{}'", self.pool_address);
+        // Ensure the nonce was assigned to the worker.
+        ensure!(
+            self.get_assignee(share.nonce()) == Some(worker),
+            "The nonce '{}' is not assigned to '{worker}' in epoch {}",
+            share.nonce(),
+            self.epoch_challenge.epoch_number()
+        );
+        // Ensure the share is not a duplicate.
+        ensure!(!self.commitments.contains(&share.commitment()), "The share '{}' is a duplicate", share.commitment());
+        // Ensure the share is valid at the share target.
+        let target = share.to_target()?;
+        ensure!(
+            target >= self.share_target(),
+            "The share does not meet the share target ({target} < {})",
+            self.share_target()
+        );
+        ensure!(
+            share.verify(self.puzzle.coinbase_verifying_key(), &self.epoch_challenge, self.share_target())?,
+            "The share '{}' is invalid",
+            share.commitment()
+        );
+        Ok(target)
+    }
+}
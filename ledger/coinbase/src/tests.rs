@@ -83,6 +83,182 @@ fn test_prover_solution_minimum_target() {
     }
 }
 
+#[test]
+fn test_prove_batch() {
+    let mut rng = TestRng::default();
+
+    let max_degree = 1 << 15;
+    let max_config = PuzzleConfig { degree: max_degree };
+    let srs = CoinbasePuzzle::<Testnet3>::setup(max_config).unwrap();
+
+    let degree = (1 << 8) - 1;
+    let puzzle = CoinbasePuzzle::<Testnet3>::trim(&srs, PuzzleConfig { degree }).unwrap();
+    let epoch_challenge = EpochChallenge::new(rng.next_u32(), Default::default(), degree).unwrap();
+
+    let private_key = PrivateKey::<Testnet3>::new(&mut rng).unwrap();
+    let address = Address::try_from(private_key).unwrap();
+    let nonces = (0..16).map(|_| u64::rand(&mut rng)).collect::<Vec<_>>();
+
+    // Ensure the batch matches the individual proofs, without a minimum target.
+    let expected =
+        nonces.iter().map(|nonce| puzzle.prove(&epoch_challenge, address, *nonce, None).unwrap()).collect::<Vec<_>>();
+    let (solutions, stats) = puzzle.prove_batch(&epoch_challenge, address, &nonces, None, BatchMode::All).unwrap();
+    assert_eq!(solutions, expected);
+    assert_eq!(stats.num_attempts(), nonces.len() as u64);
+    assert_eq!(stats.num_solutions(), nonces.len() as u64);
+
+    // Ensure only the solutions that meet the minimum target are returned.
+    let mut targets = expected.iter().map(|solution| solution.to_target().unwrap()).collect::<Vec<_>>();
+    targets.sort_unstable();
+    let minimum_target = targets[targets.len() / 2];
+    let expected =
+        expected.into_iter().filter(|solution| solution.to_target().unwrap() >= minimum_target).collect::<Vec<_>>();
+    let (solutions, stats) =
+        puzzle.prove_batch(&epoch_challenge, address, &nonces, Some(minimum_target), BatchMode::All).unwrap();
+    assert_eq!(solutions, expected);
+    assert_eq!(stats.num_attempts(), nonces.len() as u64);
+    assert_eq!(stats.num_solutions(), expected.len() as u64);
+    let coinbase_solution = CoinbaseSolution::new(solutions).unwrap();
+    assert!(puzzle.check_solutions(&coinbase_solution, &epoch_challenge, minimum_target).is_ok());
+
+    // Ensure only the first solution is returned.
+    let (solutions, stats) =
+        puzzle.prove_batch(&epoch_challenge, address, &nonces, Some(minimum_target), BatchMode::First).unwrap();
+    assert_eq!(solutions, expected[..1]);
+    assert_eq!(stats.num_solutions(), 1);
+
+    // Ensure no solution is returned if the minimum target is not met.
+    let (solutions, stats) =
+        puzzle.prove_batch(&epoch_challenge, address, &nonces, Some(u64::MAX), BatchMode::First).unwrap();
+    assert!(solutions.is_empty());
+    assert_eq!(stats.num_attempts(), nonces.len() as u64);
+
+    // Ensure a verifier cannot prove.
+    let verifier = CoinbasePuzzle::<Testnet3>::Verifier(Arc::new(puzzle.coinbase_verifying_key().clone()));
+    assert!(verifier.prove_batch(&epoch_challenge, address, &nonces, None, BatchMode::All).is_err());
+}
+
+#[test]
+fn test_solution_pool() {
+    let mut rng = TestRng::default();
+
+    let max_degree = 1 << 15;
+    let max_config = PuzzleConfig { degree: max_degree };
+    let srs = CoinbasePuzzle::<Testnet3>::setup(max_config).unwrap();
+
+    let degree = (1 << 8) - 1;
+    let puzzle = CoinbasePuzzle::<Testnet3>::trim(&srs, PuzzleConfig { degree }).unwrap();
+    let epoch_challenge = EpochChallenge::new(rng.next_u32(), Default::default(), degree).unwrap();
+
+    let mut sample_address = || Address::try_from(PrivateKey::<Testnet3>::new(&mut rng).unwrap()).unwrap();
+    let (pool_address, worker_1, worker_2, worker_3) =
+        (sample_address(), sample_address(), sample_address(), sample_address());
+
+    // Compute the shares for the first range of nonces, and set the targets to accept half of them as shares.
+    let nonces = (0..16).collect::<Vec<_>>();
+    let (shares, _) = puzzle.prove_batch(&epoch_challenge, pool_address, &nonces, None, BatchMode::All).unwrap();
+    let mut targets = shares.iter().map(|share| share.to_target().unwrap()).collect::<Vec<_>>();
+    targets.sort_unstable();
+    let (share_target, proof_target) = (targets[targets.len() / 2], targets[targets.len() - 1]);
+
+    // Ensure the pool requires a non-zero share target and range size.
+    assert!(SolutionPool::new(puzzle.clone(), pool_address, epoch_challenge.clone(), proof_target, 0, 16).is_err());
+    assert!(SolutionPool::new(puzzle.clone(), pool_address, epoch_challenge.clone(), proof_target, 1, 0).is_err());
+
+    // Assign the nonce ranges.
+    let mut pool =
+        SolutionPool::new(puzzle.clone(), pool_address, epoch_challenge.clone(), proof_target, share_target, 16)
+            .unwrap();
+    assert_eq!(pool.assign_nonces(worker_1).unwrap(), 0..16);
+    assert_eq!(pool.assign_nonces(worker_2).unwrap(), 16..32);
+    assert_eq!(pool.get_assignee(15), Some(worker_1));
+    assert_eq!(pool.get_assignee(16), Some(worker_2));
+    assert_eq!(pool.get_assignee(32), None);
+    assert!(pool.is_assigned(&worker_1) && pool.is_assigned(&worker_2) && !pool.is_assigned(&worker_3));
+
+    // Ensure the oldest range of a worker is closed, once it reaches the maximum number of open ranges.
+    let mut capped_pool =
+        SolutionPool::new(puzzle.clone(), pool_address, epoch_challenge.clone(), proof_target, share_target, 16)
+            .unwrap();
+    for _ in 0..SolutionPool::<Testnet3>::MAX_OPEN_RANGES {
+        capped_pool.assign_nonces(worker_1).unwrap();
+    }
+    assert_eq!(capped_pool.get_assignee(0), Some(worker_1));
+    capped_pool.assign_nonces(worker_1).unwrap();
+    assert_eq!(capped_pool.get_assignee(0), None);
+    assert_eq!(capped_pool.get_assignee(16), Some(worker_1));
+    assert!(capped_pool.is_assigned(&worker_1));
+
+    // Submit the shares of the first worker.
+    let (mut num_shares, mut num_solutions, mut num_rejected, mut total_work) = (0, 0, 0, 0);
+    for share in &shares {
+        let target = share.to_target().unwrap();
+        match pool.submit_share(worker_1, *share) {
+            Ok(status) => {
+                assert!(target >= share_target);
+                assert_eq!(status == ShareStatus::Solution, target >= proof_target);
+                num_shares += 1;
+                num_solutions += u64::from(status == ShareStatus::Solution);
+                total_work += target as u128;
+            }
+            Err(_) => {
+                assert!(target < share_target);
+                num_rejected += 1;
+            }
+        }
+    }
+    let stats = *pool.get_worker_stats(&worker_1).unwrap();
+    assert_eq!(stats.num_shares(), num_shares);
+    assert_eq!(stats.num_solutions(), num_solutions);
+    assert_eq!(stats.num_rejected(), num_rejected);
+    assert_eq!(stats.total_work(), total_work);
+    assert!(num_solutions >= 1);
+
+    // Ensure duplicate shares, shares from unassigned nonces, and shares for another address are rejected.
+    let accepted = *shares.iter().find(|share| share.to_target().unwrap() >= share_target).unwrap();
+    assert!(pool.submit_share(worker_1, accepted).is_err());
+    assert!(pool.submit_share(worker_2, accepted).is_err());
+    let share = puzzle.prove(&epoch_challenge, worker_2, 16, None).unwrap();
+    assert!(pool.submit_share(worker_2, share).is_err());
+    assert_eq!(pool.get_worker_stats(&worker_1).unwrap().num_rejected(), num_rejected + 1);
+    assert_eq!(pool.get_worker_stats(&worker_2).unwrap().num_rejected(), 2);
+    // Ensure the shares of a worker without a nonce range are rejected, without adding the worker.
+    assert!(pool.submit_share(worker_3, accepted).is_err());
+    assert!(pool.get_worker_stats(&worker_3).is_none());
+
+    // Ensure the queued solutions are valid at the proof target.
+    let solutions = pool.take_solutions();
+    assert_eq!(solutions.len() as u64, num_solutions);
+    assert!(pool.take_solutions().is_empty());
+    let coinbase_solution = CoinbaseSolution::new(solutions).unwrap();
+    assert!(puzzle.check_solutions(&coinbase_solution, &epoch_challenge, proof_target).is_ok());
+
+    // Ensure a new epoch discards the nonce ranges and shares, but keeps the accounting.
+    let next_epoch_challenge = EpochChallenge::new(rng.next_u32(), Default::default(), degree).unwrap();
+    assert_eq!(pool.set_epoch_challenge(next_epoch_challenge.clone(), proof_target), 0);
+    assert_eq!(pool.get_assignee(0), None);
+    assert_eq!(pool.assign_nonces(worker_1).unwrap(), 0..16);
+    assert!(pool.submit_share(worker_1, accepted).is_err());
+
+    // Accept every share of the new epoch as a solution, then raise the proof target.
+    assert_eq!(pool.set_epoch_challenge(next_epoch_challenge.clone(), share_target), 0);
+    let (shares, _) = puzzle.prove_batch(&next_epoch_challenge, pool_address, &nonces, None, BatchMode::All).unwrap();
+    let mut targets = Vec::new();
+    for share in shares {
+        if let Ok(ShareStatus::Solution) = pool.submit_share(worker_1, share) {
+            targets.push(share.to_target().unwrap());
+        }
+    }
+    let next_proof_target = *targets.iter().max().unwrap();
+    let num_discarded = targets.iter().filter(|target| **target < next_proof_target).count();
+    // Ensure the queued solutions below the raised proof target are discarded.
+    assert_eq!(pool.set_proof_target(next_proof_target), num_discarded);
+    assert_eq!(pool.take_solutions().len(), targets.len() - num_discarded);
+
+    assert_eq!(pool.take_workers().len(), 2);
+    assert!(pool.workers().is_empty());
+}
+
 #[test]
 fn test_edge_case_for_degree() {
     let mut rng = rand::thread_rng();
@@ -20,7 +20,7 @@ impl<N: Network> FromBytes for Committee<N> {
         // Read the version.
         let version = u8::read_le(&mut reader)?;
         // Ensure the version is valid.
-        if version != 1 {
+        if version != 1 && version != 2 {
             return Err(error("Invalid committee version"));
         }
 
@@ -44,8 +44,14 @@ impl<N: Network> FromBytes for Committee<N> {
             let stake = u64::read_le(&mut reader)?;
             // Read the is_open flag.
             let is_open = bool::read_le(&mut reader)?;
-            // Insert the member and (stake, is_open).
-            members.insert(member, (stake, is_open));
+            // Read the commission.
+            // Note: Version 1 committees predate commissions, and are read with a commission of 0.
+            let commission = match version {
+                1 => 0u8,
+                _ => u8::read_le(&mut reader)?,
+            };
+            // Insert the member and (stake, is_open, commission).
+            members.insert(member, (stake, is_open, commission));
         }
         // Read the total stake.
         let total_stake = u64::read_le(&mut reader)?;
@@ -62,20 +68,31 @@ impl<N: Network> FromBytes for Committee<N> {
 impl<N: Network> ToBytes for Committee<N> {
     /// Writes the committee to the buffer.
     fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Determine the version.
+        // Note: Version 2 is only written if a member charges a commission, so that committees without
+        // commissions keep their version 1 byte representation.
+        let version = match self.members.values().any(|(_, _, commission)| *commission > 0) {
+            true => 2u8,
+            false => 1u8,
+        };
         // Write the version.
-        1u8.write_le(&mut writer)?;
+        version.write_le(&mut writer)?;
         // Write the starting round.
         self.starting_round.write_le(&mut writer)?;
         // Write the number of members.
         u16::try_from(self.members.len()).map_err(|e| error(e.to_string()))?.write_le(&mut writer)?;
         // Write the members.
-        for (address, (stake, is_open)) in &self.members {
+        for (address, (stake, is_open, commission)) in &self.members {
             // Write the address.
             address.write_le(&mut writer)?;
             // Write the stake.
             stake.write_le(&mut writer)?;
             // Write the is_open flag.
             is_open.write_le(&mut writer)?;
+            // Write the commission.
+            if version == 2 {
+                commission.write_le(&mut writer)?;
+            }
         }
         // Write the total stake.
         self.total_stake.write_le(&mut writer)
@@ -96,4 +113,47 @@ mod tests {
             assert_eq!(expected, Committee::read_le(&expected_bytes[..]).unwrap());
         }
     }
+
+    #[test]
+    fn test_bytes_version_1() {
+        let rng = &mut TestRng::default();
+
+        for committee in crate::test_helpers::sample_committees(rng) {
+            // Construct the committee without commissions.
+            let members = committee
+                .members()
+                .iter()
+                .map(|(address, (stake, is_open, _))| (*address, (*stake, *is_open, 0)))
+                .collect::<IndexMap<_, _>>();
+            let expected = Committee::new(committee.starting_round(), members).unwrap();
+
+            // Construct the version 1 byte representation, which omits the commission.
+            let mut expected_bytes = vec![1u8];
+            expected.starting_round.write_le(&mut expected_bytes).unwrap();
+            u16::try_from(expected.members.len()).unwrap().write_le(&mut expected_bytes).unwrap();
+            for (address, (stake, is_open, _)) in &expected.members {
+                address.write_le(&mut expected_bytes).unwrap();
+                stake.write_le(&mut expected_bytes).unwrap();
+                is_open.write_le(&mut expected_bytes).unwrap();
+            }
+            expected.total_stake.write_le(&mut expected_bytes).unwrap();
+
+            // Ensure the committee is written as version 1, and round-trips.
+            assert_eq!(expected.to_bytes_le().unwrap(), expected_bytes);
+            assert_eq!(expected, Committee::read_le(&expected_bytes[..]).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_bytes_version_2() {
+        let rng = &mut TestRng::default();
+
+        for expected in crate::test_helpers::sample_committees(rng) {
+            // Ensure the committee is written as version 2 if, and only if, a member charges a commission.
+            let expected_bytes = expected.to_bytes_le().unwrap();
+            let has_commission = expected.members().values().any(|(_, _, commission)| *commission > 0);
+            assert_eq!(expected_bytes[0], if has_commission { 2 } else { 1 });
+            assert_eq!(expected, Committee::read_le(&expected_bytes[..]).unwrap());
+        }
+    }
 }
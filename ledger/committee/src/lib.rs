@@ -19,6 +19,9 @@ mod bytes;
 mod serialize;
 mod string;
 
+mod reputation;
+pub use reputation::*;
+
 #[cfg(any(test, feature = "prop-tests"))]
 pub mod prop_tests;
 
@@ -35,13 +38,15 @@ use std::collections::HashSet;
 pub const MIN_VALIDATOR_STAKE: u64 = 1_000_000_000_000u64; // microcredits
 /// The minimum amount of stake required for a delegator to bond.
 pub const MIN_DELEGATOR_STAKE: u64 = 10_000_000u64; // microcredits
+/// The maximum commission rate a validator may charge on delegated staking rewards.
+pub const MAX_COMMISSION: u8 = 100; // percent
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct Committee<N: Network> {
     /// The starting round number for this committee.
     starting_round: u64,
-    /// A map of `address` to `(stake, is_open)` state.
-    members: IndexMap<Address<N>, (u64, bool)>,
+    /// A map of `address` to `(stake, is_open, commission)` state.
+    members: IndexMap<Address<N>, (u64, bool, u8)>,
     /// The total stake of all `members`.
     total_stake: u64,
 }
@@ -51,13 +56,13 @@ impl<N: Network> Committee<N> {
     pub const MAX_COMMITTEE_SIZE: u16 = 200;
 
     /// Initializes a new `Committee` instance.
-    pub fn new_genesis(members: IndexMap<Address<N>, (u64, bool)>) -> Result<Self> {
+    pub fn new_genesis(members: IndexMap<Address<N>, (u64, bool, u8)>) -> Result<Self> {
         // Return the new committee.
         Self::new(0u64, members)
     }
 
     /// Initializes a new `Committee` instance.
-    pub fn new(starting_round: u64, members: IndexMap<Address<N>, (u64, bool)>) -> Result<Self> {
+    pub fn new(starting_round: u64, members: IndexMap<Address<N>, (u64, bool, u8)>) -> Result<Self> {
         // Ensure there are at least 3 members.
         ensure!(members.len() >= 3, "Committee must have at least 3 members");
         // Ensure there are no more than the maximum number of members.
@@ -68,9 +73,14 @@ impl<N: Network> Committee<N> {
         );
         // Ensure all members have the minimum required stake.
         ensure!(
-            members.values().all(|(stake, _)| *stake >= MIN_VALIDATOR_STAKE),
+            members.values().all(|(stake, _, _)| *stake >= MIN_VALIDATOR_STAKE),
             "All members must have at least {MIN_VALIDATOR_STAKE} microcredits in stake"
         );
+        // Ensure all members have a valid commission rate.
+        ensure!(
+            members.values().all(|(_, _, commission)| *commission <= MAX_COMMISSION),
+            "All members must have a commission of at most {MAX_COMMISSION}%"
+        );
         // Compute the total stake of the committee for this round.
         let total_stake = Self::compute_total_stake(&members)?;
         #[cfg(feature = "metrics")]
@@ -86,8 +96,8 @@ impl<N: Network> Committee<N> {
         self.starting_round
     }
 
-    /// Returns the committee members alongside their stake.
-    pub const fn members(&self) -> &IndexMap<Address<N>, (u64, bool)> {
+    /// Returns the committee members alongside their `(stake, is_open, commission)` state.
+    pub const fn members(&self) -> &IndexMap<Address<N>, (u64, bool, u8)> {
         &self.members
     }
 
@@ -111,6 +121,11 @@ impl<N: Network> Committee<N> {
         self.members.get(&address).copied().unwrap_or_default().0
     }
 
+    /// Returns the commission rate (in percent) for the given address.
+    pub fn get_commission(&self, address: Address<N>) -> u8 {
+        self.members.get(&address).copied().unwrap_or_default().2
+    }
+
     /// Returns `true` if the combined stake for the given addresses reaches the availability threshold.
     /// This method takes in a `HashSet` to guarantee that the given addresses are unique.
     pub fn is_availability_threshold_reached(&self, addresses: &HashSet<Address<N>>) -> bool {
@@ -161,17 +176,48 @@ impl<N: Network> Committee<N> {
     /// Returns the leader address for the current round.
     /// Note: This method returns a deterministic result that is SNARK-friendly.
     pub fn get_leader(&self, current_round: u64) -> Result<Address<N>> {
+        self.get_leader_among(current_round, |_| true)
+    }
+
+    /// Returns the leader address for the current round, excluding the members demoted by the given reputation.
+    /// If every member is demoted, this method returns the leader from `Committee::get_leader`.
+    /// Note: If no member is demoted, this method returns the same leader as `Committee::get_leader`.
+    pub fn get_leader_with_reputation(
+        &self,
+        current_round: u64,
+        reputation: &LeaderReputation<N>,
+    ) -> Result<Address<N>> {
+        // Retrieve the demoted members.
+        let demoted = reputation.demoted();
+        // If every member is demoted, fall back to the stake-weighted election.
+        if self.members.keys().all(|address| demoted.contains(address)) {
+            return self.get_leader(current_round);
+        }
+        self.get_leader_among(current_round, |address| !demoted.contains(address))
+    }
+
+    /// Returns the leader address for the current round, among the members that are eligible.
+    fn get_leader_among(&self, current_round: u64, is_eligible: impl Fn(&Address<N>) -> bool) -> Result<Address<N>> {
         // Ensure the current round is at least the starting round.
         ensure!(current_round >= self.starting_round, "Current round must be at least the starting round");
         // Retrieve the total stake of the committee.
         let total_stake = self.total_stake();
+        // Compute the total stake of the eligible members.
+        let eligible_stake = self
+            .members
+            .iter()
+            .filter(|(address, _)| is_eligible(address))
+            .map(|(_, (stake, _, _))| *stake)
+            .sum::<u64>();
+        // Ensure there is eligible stake.
+        ensure!(eligible_stake > 0, "There must be at least one eligible member with stake");
         // Construct the round seed.
         let seed = [self.starting_round, current_round, total_stake].map(Field::from_u64);
         // Hash the round seed.
         let hash = Literal::Field(N::hash_to_group_psd4(&seed)?.to_x_coordinate());
         // Compute the stake index from the hash output.
         let stake_index = match hash.cast_lossy(LiteralType::U64)? {
-            Literal::U64(output) => (*output) % total_stake,
+            Literal::U64(output) => (*output) % eligible_stake,
             _ => bail!("BFT failed to downcast the hash output to a U64 literal"),
         };
 
@@ -179,10 +225,10 @@ impl<N: Network> Committee<N> {
         let mut leader = None;
         // Initialize a tracker for the current stake index.
         let mut current_stake_index = 0u64;
-        // Sort the committee members.
-        let candidates = self.sorted_members();
+        // Sort the eligible committee members.
+        let candidates = self.sorted_members().filter(|(address, _)| is_eligible(address));
         // Determine the leader of the previous round.
-        for (candidate, (stake, _)) in candidates {
+        for (candidate, (stake, _, _)) in candidates {
             // Increment the current stake index by the candidate's stake.
             current_stake_index = current_stake_index.saturating_add(stake);
             // If the current stake index is greater than or equal to the stake index,
@@ -199,11 +245,12 @@ impl<N: Network> Committee<N> {
     /// Returns the committee members sorted by stake in decreasing order.
     /// For members with matching stakes, we further sort by their address' x-coordinate in decreasing order.
     /// Note: This ensures the method returns a deterministic result that is SNARK-friendly.
-    fn sorted_members(&self) -> indexmap::map::IntoIter<Address<N>, (u64, bool)> {
+    fn sorted_members(&self) -> indexmap::map::IntoIter<Address<N>, (u64, bool, u8)> {
         let members = self.members.clone();
-        members.sorted_unstable_by(|address1, stake1, address2, stake2| {
+        members.sorted_unstable_by(|address1, (stake1, is_open1, _), address2, (stake2, is_open2, _)| {
             // Sort by stake in decreasing order.
-            let cmp = stake2.cmp(stake1);
+            // Note: The commission rate is intentionally excluded, so it does not influence leader selection.
+            let cmp = (stake2, is_open2).cmp(&(stake1, is_open1));
             // If the stakes are equal, sort by x-coordinate in decreasing order.
             if cmp == Ordering::Equal { address2.to_x_coordinate().cmp(&address1.to_x_coordinate()) } else { cmp }
         })
@@ -212,9 +259,9 @@ impl<N: Network> Committee<N> {
 
 impl<N: Network> Committee<N> {
     /// Compute the total stake of the given members.
-    fn compute_total_stake(members: &IndexMap<Address<N>, (u64, bool)>) -> Result<u64> {
+    fn compute_total_stake(members: &IndexMap<Address<N>, (u64, bool, u8)>) -> Result<u64> {
         let mut power = 0u64;
-        for (stake, _) in members.values() {
+        for (stake, _, _) in members.values() {
             // Accumulate the stake, checking for overflow.
             power = match power.checked_add(*stake) {
                 Some(power) => power,
@@ -263,7 +310,10 @@ pub mod test_helpers {
         let mut members = IndexMap::new();
         for _ in 0..num_members {
             let is_open = rng.gen();
-            members.insert(Address::<CurrentNetwork>::new(rng.gen()), (2 * MIN_VALIDATOR_STAKE, is_open));
+            members.insert(
+                Address::<CurrentNetwork>::new(rng.gen()),
+                (2 * MIN_VALIDATOR_STAKE, is_open, rng.gen_range(0..=MAX_COMMISSION)),
+            );
         }
         // Return the committee.
         Committee::<CurrentNetwork>::new(round, members).unwrap()
@@ -279,7 +329,7 @@ pub mod test_helpers {
         let mut committee_members = IndexMap::new();
         for member in members {
             let is_open = rng.gen();
-            committee_members.insert(member, (2 * MIN_VALIDATOR_STAKE, is_open));
+            committee_members.insert(member, (2 * MIN_VALIDATOR_STAKE, is_open, rng.gen_range(0..=MAX_COMMISSION)));
         }
         // Return the committee.
         Committee::<CurrentNetwork>::new(round, committee_members).unwrap()
@@ -298,18 +348,21 @@ pub mod test_helpers {
         // Sample the members.
         let mut members = IndexMap::new();
         // Add in the minimum and maximum staked nodes.
-        members.insert(Address::<CurrentNetwork>::new(rng.gen()), (MIN_VALIDATOR_STAKE, false));
+        members.insert(Address::<CurrentNetwork>::new(rng.gen()), (MIN_VALIDATOR_STAKE, false, 0));
         while members.len() < num_members as usize - 1 {
             loop {
                 let stake = MIN_VALIDATOR_STAKE as f64 + range * distribution.sample(rng);
                 if stake >= MIN_VALIDATOR_STAKE as f64 && stake <= MAX_STAKE as f64 {
                     let is_open = rng.gen();
-                    members.insert(Address::<CurrentNetwork>::new(rng.gen()), (stake as u64, is_open));
+                    members.insert(
+                        Address::<CurrentNetwork>::new(rng.gen()),
+                        (stake as u64, is_open, rng.gen_range(0..=MAX_COMMISSION)),
+                    );
                     break;
                 }
             }
         }
-        members.insert(Address::<CurrentNetwork>::new(rng.gen()), (MAX_STAKE, false));
+        members.insert(Address::<CurrentNetwork>::new(rng.gen()), (MAX_STAKE, false, 0));
         // Return the committee.
         Committee::<CurrentNetwork>::new(1, members).unwrap()
     }
@@ -339,7 +392,7 @@ mod tests {
         });
         let leaders = leaders.read();
         // Ensure the leader distribution is uniform.
-        for (i, (address, (stake, _))) in committee.members.iter().enumerate() {
+        for (i, (address, (stake, _, _))) in committee.members.iter().enumerate() {
             // Get the leader count for the validator.
             let Some(leader_count) = leaders.get(address) else {
                 println!("{i}: 0 rounds");
@@ -401,8 +454,8 @@ mod tests {
         println!("sorted_members: {}ms", timer.elapsed().as_millis());
         // Check that the members are sorted based on our sorting criteria.
         for i in 0..sorted_members.len() - 1 {
-            let (address1, (stake1, _)) = sorted_members[i];
-            let (address2, (stake2, _)) = sorted_members[i + 1];
+            let (address1, (stake1, _, _)) = sorted_members[i];
+            let (address2, (stake2, _, _)) = sorted_members[i + 1];
             assert!(stake1 >= stake2);
             if stake1 == stake2 {
                 assert!(address1.to_x_coordinate() > address2.to_x_coordinate());
@@ -410,6 +463,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_commission() {
+        // Initialize the RNG.
+        let rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = crate::test_helpers::sample_committee(rng);
+        // Ensure the commission matches the member state.
+        for (address, (_, _, commission)) in committee.members() {
+            assert_eq!(committee.get_commission(*address), *commission);
+        }
+        // Ensure a non-member has no commission.
+        assert_eq!(committee.get_commission(Address::new(rng.gen())), 0);
+    }
+
+    #[test]
+    fn test_invalid_commission() {
+        // Initialize the RNG.
+        let rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = crate::test_helpers::sample_committee(rng);
+        // Set an invalid commission for one of the members.
+        let mut members = committee.members().clone();
+        members[0].2 = MAX_COMMISSION + 1;
+        // Ensure the committee is rejected.
+        assert!(Committee::<CurrentNetwork>::new(committee.starting_round(), members).is_err());
+    }
+
     #[test]
     fn test_maximum_committee_size() {
         assert_eq!(
@@ -63,7 +63,7 @@ impl Hash for Validator {
 }
 
 fn to_committee((round, ValidatorSet(validators)): (u64, ValidatorSet)) -> Result<Committee<CurrentNetwork>> {
-    Committee::new(round, validators.iter().map(|v| (v.address, (v.stake, v.is_open))).collect())
+    Committee::new(round, validators.iter().map(|v| (v.address, (v.stake, v.is_open, 0))).collect())
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,287 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Committee;
+use console::{prelude::*, types::Address};
+
+use std::collections::{BTreeMap, HashSet};
+
+/// The method used to elect the leader of a round.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ElectionMode {
+    /// The leader is elected in proportion to stake.
+    Stake,
+    /// The leader is elected in proportion to stake, excluding the members demoted by their leader reputation.
+    Reputation,
+}
+
+impl ElectionMode {
+    /// Returns the method used to elect the leader of the block at the given height.
+    pub const fn at_height<N: Network>(height: u32) -> Self {
+        match height >= N::REPUTATION_ELECTION_HEIGHT {
+            true => Self::Reputation,
+            false => Self::Stake,
+        }
+    }
+}
+
+/// The reputation of the committee members as leaders, derived from the committed leader certificates.
+///
+/// A member is demoted if it was elected leader within the reputation window, and its most recent
+/// leader certificate in the window was not committed. A demoted member is excluded from the
+/// reputation-aware leader election, until the failure falls outside of the window.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeaderReputation<N: Network> {
+    /// The anchor round of the latest committed leader certificate.
+    last_anchor_round: Option<u64>,
+    /// The elected leader of each leader round in the window, and whether its certificate was committed.
+    outcomes: BTreeMap<u64, (Address<N>, bool)>,
+}
+
+impl<N: Network> Default for LeaderReputation<N> {
+    /// Initializes a new leader reputation, with no committed leader certificates.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Network> LeaderReputation<N> {
+    /// The number of rounds over which the leader outcomes are remembered.
+    pub const WINDOW_IN_ROUNDS: u64 = 200;
+
+    /// Initializes a new leader reputation, with no committed leader certificates.
+    pub const fn new() -> Self {
+        Self { last_anchor_round: None, outcomes: BTreeMap::new() }
+    }
+
+    /// Returns the anchor round of the latest committed leader certificate.
+    pub const fn last_anchor_round(&self) -> Option<u64> {
+        self.last_anchor_round
+    }
+
+    /// Returns the elected leader of each leader round in the window, and whether its certificate was committed.
+    pub const fn outcomes(&self) -> &BTreeMap<u64, (Address<N>, bool)> {
+        &self.outcomes
+    }
+
+    /// Returns `true` if the given address is demoted.
+    pub fn is_demoted(&self, address: &Address<N>) -> bool {
+        matches!(self.outcomes.values().rev().find(|(leader, _)| leader == address), Some((_, false)))
+    }
+
+    /// Returns the demoted addresses.
+    pub fn demoted(&self) -> HashSet<Address<N>> {
+        let mut demoted = HashSet::new();
+        // Iterate over the outcomes in increasing round order, so the most recent outcome of each leader prevails.
+        for (leader, is_committed) in self.outcomes.values() {
+            match is_committed {
+                true => demoted.remove(leader),
+                false => demoted.insert(*leader),
+            };
+        }
+        demoted
+    }
+
+    /// Records the committed leader certificate for the given anchor round, given the committee for the round.
+    /// The leader rounds since the previous anchor round were not committed, and are recorded as failures
+    /// of the leaders elected by `Committee::get_leader_with_reputation`, as of the previous anchor round.
+    pub fn record_commit(&mut self, committee: &Committee<N>, anchor_round: u64, leader: Address<N>) -> Result<()> {
+        // Ensure the anchor round is even.
+        ensure!(anchor_round % 2 == 0, "The anchor round ({anchor_round}) must be even");
+        // Ensure the anchor round is after the previous anchor round.
+        if let Some(last_anchor_round) = self.last_anchor_round {
+            ensure!(
+                anchor_round > last_anchor_round,
+                "The anchor round ({anchor_round}) must be after the previous anchor round ({last_anchor_round})"
+            );
+        }
+
+        // Compute the first round within the window.
+        let window_start = anchor_round.saturating_sub(Self::WINDOW_IN_ROUNDS);
+
+        // Determine the elected leaders of the uncommitted leader rounds within the window.
+        let failures = match self.last_anchor_round {
+            Some(last_anchor_round) => (last_anchor_round.saturating_add(2).max(window_start)..anchor_round)
+                .step_by(2)
+                .filter(|round| *round >= committee.starting_round())
+                .map(|round| Ok((round, committee.get_leader_with_reputation(round, self)?)))
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![],
+        };
+
+        // Record the uncommitted leader rounds.
+        for (round, elected) in failures {
+            self.outcomes.insert(round, (elected, false));
+        }
+        // Record the committed leader round.
+        self.outcomes.insert(anchor_round, (leader, true));
+        self.last_anchor_round = Some(anchor_round);
+        // Remove the outcomes that fell outside of the window.
+        self.outcomes = self.outcomes.split_off(&window_start);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::prelude::TestRng;
+
+    type CurrentNetwork = console::network::Testnet3;
+
+    /// Simulates the given number of leader rounds, in which the given validators are offline,
+    /// and returns the number of leader rounds that failed.
+    fn simulate_offline_leaders(
+        committee: &Committee<CurrentNetwork>,
+        offline: &HashSet<Address<CurrentNetwork>>,
+        num_leader_rounds: u64,
+        use_reputation: bool,
+    ) -> u64 {
+        let mut reputation = LeaderReputation::new();
+        let mut num_failures = 0;
+        for round in (committee.starting_round() + 1..).filter(|round| round % 2 == 0).take(num_leader_rounds as usize)
+        {
+            // Elect the leader.
+            let leader = match use_reputation {
+                true => committee.get_leader_with_reputation(round, &reputation).unwrap(),
+                false => committee.get_leader(round).unwrap(),
+            };
+            // If the leader is offline, the leader round fails.
+            match offline.contains(&leader) {
+                true => num_failures += 1,
+                false => reputation.record_commit(committee, round, leader).unwrap(),
+            }
+        }
+        num_failures
+    }
+
+    #[test]
+    fn test_reputation_demotes_offline_leaders() {
+        let rng = &mut TestRng::default();
+
+        // Set the number of leader rounds.
+        const NUM_LEADER_ROUNDS: u64 = 500;
+
+        for _ in 0..5 {
+            // Sample a committee.
+            let committee = crate::test_helpers::sample_committee_custom(rng.gen_range(4..20), rng);
+            // Take the validator with the most stake offline.
+            let offline = HashSet::from([committee.sorted_members().next().unwrap().0]);
+
+            // Simulate the leader rounds, with and without reputation.
+            let failures_without_reputation = simulate_offline_leaders(&committee, &offline, NUM_LEADER_ROUNDS, false);
+            let failures_with_reputation = simulate_offline_leaders(&committee, &offline, NUM_LEADER_ROUNDS, true);
+
+            // Ensure the offline validator is elected at most once per window, with reputation.
+            let num_windows = 2 * NUM_LEADER_ROUNDS / LeaderReputation::<CurrentNetwork>::WINDOW_IN_ROUNDS + 1;
+            assert!(failures_with_reputation <= num_windows);
+            assert!(failures_with_reputation < failures_without_reputation);
+        }
+    }
+
+    #[test]
+    fn test_reputation_without_demotions_matches_stake_election() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = crate::test_helpers::sample_committee(rng);
+
+        // Record every leader round as committed.
+        let mut reputation = LeaderReputation::new();
+        for round in (2..200).step_by(2) {
+            let leader = committee.get_leader_with_reputation(round, &reputation).unwrap();
+            // Ensure the election matches the stake-weighted election, as no member is demoted.
+            assert_eq!(leader, committee.get_leader(round).unwrap());
+            reputation.record_commit(&committee, round, leader).unwrap();
+        }
+        assert!(reputation.demoted().is_empty());
+        // Ensure only the outcomes within the window are remembered.
+        assert_eq!(reputation.outcomes().len() as u64, LeaderReputation::<CurrentNetwork>::WINDOW_IN_ROUNDS / 2);
+    }
+
+    #[test]
+    fn test_reputation_is_deterministic() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = crate::test_helpers::sample_committee_custom(10, rng);
+
+        // Sample a sequence of commits, in which random leader rounds are skipped.
+        let mut reputation = LeaderReputation::new();
+        let mut commits = Vec::new();
+        for round in (2..1000).step_by(2) {
+            let leader = committee.get_leader_with_reputation(round, &reputation).unwrap();
+            if rng.gen_bool(0.7) {
+                reputation.record_commit(&committee, round, leader).unwrap();
+                commits.push((round, leader));
+            } else {
+                // Ensure a leader that is demoted is not elected.
+                assert!(!reputation.is_demoted(&leader));
+            }
+        }
+        // Ensure the demoted set matches the demoted check.
+        let demoted = reputation.demoted();
+        for address in committee.members().keys() {
+            assert_eq!(demoted.contains(address), reputation.is_demoted(address));
+        }
+
+        // Ensure replaying the commits produces the same reputation.
+        let mut replayed = LeaderReputation::new();
+        for (round, leader) in commits {
+            replayed.record_commit(&committee, round, leader).unwrap();
+        }
+        assert_eq!(reputation, replayed);
+    }
+
+    #[test]
+    fn test_reputation_with_all_members_demoted() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = crate::test_helpers::sample_committee_custom(4, rng);
+        let members = committee.members().keys().copied().collect::<Vec<_>>();
+
+        // Demote every member.
+        let mut reputation = LeaderReputation {
+            last_anchor_round: Some(10),
+            outcomes: members.iter().enumerate().map(|(i, address)| (2 * i as u64 + 2, (*address, false))).collect(),
+        };
+        assert_eq!(reputation.demoted().len(), members.len());
+        // Ensure the election falls back to the stake-weighted election.
+        assert_eq!(committee.get_leader_with_reputation(12, &reputation).unwrap(), committee.get_leader(12).unwrap());
+
+        // Ensure a committed leader round restores the member.
+        reputation.record_commit(&committee, 12, members[0]).unwrap();
+        assert!(!reputation.is_demoted(&members[0]));
+        assert!(members[1..].iter().all(|address| reputation.is_demoted(address)));
+    }
+
+    #[test]
+    fn test_record_commit_with_invalid_round() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = crate::test_helpers::sample_committee(rng);
+        let leader = *committee.members().keys().next().unwrap();
+
+        let mut reputation = LeaderReputation::new();
+        // Ensure the anchor round must be even.
+        assert!(reputation.record_commit(&committee, 3, leader).is_err());
+        // Ensure the anchor round must be after the previous anchor round.
+        reputation.record_commit(&committee, 4, leader).unwrap();
+        assert!(reputation.record_commit(&committee, 4, leader).is_err());
+        assert!(reputation.record_commit(&committee, 2, leader).is_err());
+        assert_eq!(reputation.last_anchor_round(), Some(4));
+    }
+}
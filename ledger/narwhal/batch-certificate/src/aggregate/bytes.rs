@@ -0,0 +1,87 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> FromBytes for AggregateBatchCertificate<N> {
+    /// Reads the aggregate batch certificate from the buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid aggregate batch certificate version"));
+        }
+
+        // Read the batch header.
+        let batch_header = BatchHeader::read_le(&mut reader)?;
+        // Read the number of entries in the bitmap.
+        let num_signers = u16::read_le(&mut reader)?;
+        // Ensure the number of entries does not exceed the maximum committee size.
+        if num_signers > Committee::<N>::MAX_COMMITTEE_SIZE {
+            return Err(error("Aggregate batch certificate bitmap exceeds the maximum committee size"));
+        }
+        // Read the bitmap.
+        let mut bytes = vec![0u8; (num_signers as usize).saturating_add(7) / 8];
+        reader.read_exact(&mut bytes)?;
+        let mut signers = bytes.to_bits_le();
+        // Ensure the padding bits are zero.
+        if signers.iter().skip(num_signers as usize).any(|bit| *bit) {
+            return Err(error("Invalid padding in the aggregate batch certificate bitmap"));
+        }
+        signers.truncate(num_signers as usize);
+        // Read the signature.
+        let signature = BLSSignature::read_le(&mut reader)?;
+        // Return the aggregate batch certificate.
+        Self::from(batch_header, signers, signature).map_err(error)
+    }
+}
+
+impl<N: Network> ToBytes for AggregateBatchCertificate<N> {
+    /// Writes the aggregate batch certificate to the buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+        // Write the batch header.
+        self.batch_header.write_le(&mut writer)?;
+        // Write the number of entries in the bitmap.
+        u16::try_from(self.signers.len()).map_err(error)?.write_le(&mut writer)?;
+        // Write the bitmap.
+        for chunk in self.signers.chunks(8) {
+            chunk.iter().rev().fold(0u8, |byte, bit| (byte << 1) | (*bit as u8)).write_le(&mut writer)?;
+        }
+        // Write the signature.
+        self.signature.write_le(&mut writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes() {
+        let rng = &mut TestRng::default();
+
+        for _ in 0..5 {
+            let (expected, committee, public_keys) = test_helpers::sample_aggregate_batch_certificate(rng);
+            // Check the byte representation.
+            let expected_bytes = expected.to_bytes_le().unwrap();
+            let candidate = AggregateBatchCertificate::read_le(&expected_bytes[..]).unwrap();
+            assert_eq!(expected, candidate);
+            assert_eq!(expected.signer_bitmap(), candidate.signer_bitmap());
+            assert!(candidate.verify(&committee, &public_keys).is_ok());
+        }
+    }
+}
@@ -0,0 +1,360 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod bytes;
+mod serialize;
+mod string;
+
+use super::*;
+use console::account::{BLSPrivateKey, BLSPublicKey, BLSSignature};
+use ledger_committee::Committee;
+
+use std::collections::HashSet;
+
+/// A batch certificate that carries one aggregate BLS signature over the batch ID,
+/// alongside a bitmap of the committee members that contributed to it.
+#[derive(Clone)]
+pub struct AggregateBatchCertificate<N: Network> {
+    /// The batch header.
+    batch_header: BatchHeader<N>,
+    /// The bitmap of signers, indexed in the order of the committee members.
+    signers: Vec<bool>,
+    /// The aggregate signature for the batch ID from the signers.
+    signature: BLSSignature<N>,
+}
+
+impl<N: Network> AggregateBatchCertificate<N> {
+    /// Initializes a new aggregate batch certificate.
+    pub fn from(batch_header: BatchHeader<N>, signers: Vec<bool>, signature: BLSSignature<N>) -> Result<Self> {
+        // Ensure the signers are not empty.
+        ensure!(signers.iter().any(|is_signer| *is_signer), "Aggregate batch certificate must contain signers");
+        // Ensure the bitmap does not exceed the maximum committee size.
+        ensure!(
+            signers.len() <= Committee::<N>::MAX_COMMITTEE_SIZE as usize,
+            "Aggregate batch certificate bitmap exceeds the maximum committee size"
+        );
+        // Return the aggregate batch certificate.
+        Ok(Self { batch_header, signers, signature })
+    }
+
+    /// Initializes a new aggregate batch certificate from the BLS signatures of the given committee members.
+    pub fn aggregate(
+        batch_header: BatchHeader<N>,
+        committee: &Committee<N>,
+        signatures: &IndexMap<Address<N>, BLSSignature<N>>,
+    ) -> Result<Self> {
+        // Ensure every signature belongs to a committee member.
+        for address in signatures.keys() {
+            ensure!(committee.is_committee_member(*address), "Signer '{address}' is not in the committee");
+        }
+        // Construct the bitmap of signers, in the order of the committee members.
+        let signers = committee.members().keys().map(|address| signatures.contains_key(address)).collect();
+        // Aggregate the signatures.
+        let signature = BLSSignature::aggregate(&signatures.values().copied().collect::<Vec<_>>())?;
+        // Return the aggregate batch certificate.
+        Self::from(batch_header, signers, signature)
+    }
+
+    /// Returns the BLS signature of the given committee member for the batch ID.
+    pub fn sign(private_key: &BLSPrivateKey<N>, batch_header: &BatchHeader<N>) -> Result<BLSSignature<N>> {
+        private_key.sign(&[batch_header.batch_id()])
+    }
+}
+
+impl<N: Network> AggregateBatchCertificate<N> {
+    /// Returns the certificate ID.
+    pub const fn id(&self) -> Field<N> {
+        self.batch_header.batch_id()
+    }
+
+    /// Returns the batch header.
+    pub const fn batch_header(&self) -> &BatchHeader<N> {
+        &self.batch_header
+    }
+
+    /// Returns the batch ID.
+    pub const fn batch_id(&self) -> Field<N> {
+        self.batch_header.batch_id()
+    }
+
+    /// Returns the author.
+    pub const fn author(&self) -> Address<N> {
+        self.batch_header.author()
+    }
+
+    /// Returns the round.
+    pub const fn round(&self) -> u64 {
+        self.batch_header.round()
+    }
+
+    /// Returns the timestamp of the batch header.
+    pub const fn timestamp(&self) -> i64 {
+        self.batch_header.timestamp()
+    }
+
+    /// Returns the bitmap of signers, indexed in the order of the committee members.
+    pub fn signer_bitmap(&self) -> &[bool] {
+        &self.signers
+    }
+
+    /// Returns the aggregate signature for the batch ID.
+    pub const fn signature(&self) -> &BLSSignature<N> {
+        &self.signature
+    }
+
+    /// Returns the addresses of the signers in the given committee.
+    pub fn signers(&self, committee: &Committee<N>) -> Result<Vec<Address<N>>> {
+        // Ensure the bitmap matches the committee size.
+        ensure!(
+            self.signers.len() == committee.num_members(),
+            "Aggregate batch certificate bitmap has {} entries, but the committee has {} members",
+            self.signers.len(),
+            committee.num_members()
+        );
+        // Return the addresses of the signers.
+        Ok(committee
+            .members()
+            .keys()
+            .zip_eq(self.signers.iter())
+            .filter_map(|(address, is_signer)| is_signer.then_some(*address))
+            .collect())
+    }
+
+    /// Checks the aggregate batch certificate against the given committee, where `public_keys`
+    /// maps each committee member to a BLS public key and its proof of possession.
+    ///
+    /// The proof of possession of every signer is checked before the aggregate signature,
+    /// as aggregate verification is otherwise vulnerable to rogue public keys.
+    pub fn verify(
+        &self,
+        committee: &Committee<N>,
+        public_keys: &IndexMap<Address<N>, (BLSPublicKey<N>, BLSSignature<N>)>,
+    ) -> Result<()> {
+        // Ensure the author is in the committee.
+        ensure!(committee.is_committee_member(self.author()), "The author '{}' is not in the committee", self.author());
+        // Retrieve the signers.
+        let signers = self.signers(committee)?;
+        // Retrieve the public keys of the signers.
+        let signer_public_keys = signers
+            .iter()
+            .map(|address| match public_keys.get(address) {
+                // Ensure the proof of possession of the signer is valid.
+                Some((public_key, proof)) => match public_key.verify_possession(proof) {
+                    true => Ok(*public_key),
+                    false => bail!("Invalid BLS proof of possession for signer '{address}'"),
+                },
+                None => bail!("Missing the BLS public key for signer '{address}'"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // Ensure the signers and the author reach the quorum threshold.
+        let addresses = signers.into_iter().chain([self.author()]).collect::<HashSet<_>>();
+        ensure!(committee.is_quorum_threshold_reached(&addresses), "Aggregate batch certificate did not reach quorum");
+        // Verify the aggregate signature.
+        ensure!(
+            self.signature.verify_aggregate(&signer_public_keys, &[self.batch_id()]),
+            "Invalid aggregate batch certificate signature"
+        );
+        Ok(())
+    }
+}
+
+impl<N: Network> PartialEq for AggregateBatchCertificate<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.batch_header == other.batch_header && self.signers == other.signers && self.signature == other.signature
+    }
+}
+
+impl<N: Network> Eq for AggregateBatchCertificate<N> {}
+
+impl<N: Network> Hash for AggregateBatchCertificate<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.batch_id().hash(state);
+        self.signers.hash(state);
+        self.signature.hash(state);
+    }
+}
+
+#[cfg(any(test, feature = "test-helpers"))]
+pub mod test_helpers {
+    use super::*;
+    use console::{account::PrivateKey, network::Testnet3, prelude::TestRng};
+
+    type CurrentNetwork = Testnet3;
+
+    /// Returns a sample committee, the BLS public keys and proofs of possession of its members,
+    /// and an aggregate batch certificate signed by all members except the author, sampled at random.
+    pub fn sample_aggregate_batch_certificate(
+        rng: &mut TestRng,
+    ) -> (
+        AggregateBatchCertificate<CurrentNetwork>,
+        Committee<CurrentNetwork>,
+        IndexMap<Address<CurrentNetwork>, (BLSPublicKey<CurrentNetwork>, BLSSignature<CurrentNetwork>)>,
+    ) {
+        // Sample the committee members.
+        let private_keys = (0..4).map(|_| PrivateKey::<CurrentNetwork>::new(rng).unwrap()).collect::<Vec<_>>();
+        let addresses = private_keys.iter().map(|private_key| Address::try_from(private_key).unwrap()).collect();
+        let committee = ledger_committee::test_helpers::sample_committee_for_round_and_members(1, addresses, rng);
+        // Derive the BLS private keys.
+        let bls_private_keys = private_keys
+            .iter()
+            .map(|private_key| {
+                let address = Address::try_from(private_key).unwrap();
+                (address, BLSPrivateKey::try_from(private_key).unwrap())
+            })
+            .collect::<IndexMap<_, _>>();
+        let public_keys = bls_private_keys
+            .iter()
+            .map(|(address, private_key)| {
+                (*address, (private_key.to_public_key(), private_key.prove_possession().unwrap()))
+            })
+            .collect::<IndexMap<_, _>>();
+
+        // Sample a batch header from the first member.
+        let batch_header =
+            BatchHeader::new(&private_keys[0], 1, rng.gen::<u32>() as i64, Default::default(), Default::default(), rng)
+                .unwrap();
+        // Sign the batch ID with the remaining members.
+        let signatures = bls_private_keys
+            .iter()
+            .skip(1)
+            .map(|(address, private_key)| {
+                (*address, AggregateBatchCertificate::sign(private_key, &batch_header).unwrap())
+            })
+            .collect::<IndexMap<_, _>>();
+        // Return the aggregate batch certificate.
+        let certificate = AggregateBatchCertificate::aggregate(batch_header, &committee, &signatures).unwrap();
+        (certificate, committee, public_keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_verify() {
+        let rng = &mut TestRng::default();
+
+        for _ in 0..5 {
+            // Sample an aggregate batch certificate.
+            let (certificate, committee, public_keys) = test_helpers::sample_aggregate_batch_certificate(rng);
+            // Check that the certificate is valid.
+            assert!(certificate.verify(&committee, &public_keys).is_ok());
+            // Check that the signers exclude the author.
+            let signers = certificate.signers(&committee).unwrap();
+            assert_eq!(signers.len(), 3);
+            assert!(!signers.contains(&certificate.author()));
+        }
+    }
+
+    #[test]
+    fn test_verify_fails_on_incorrect_signers() {
+        let rng = &mut TestRng::default();
+
+        // Sample an aggregate batch certificate.
+        let (certificate, committee, public_keys) = test_helpers::sample_aggregate_batch_certificate(rng);
+
+        // Check that the certificate fails if a signer is missing from the bitmap.
+        let mut signers = certificate.signer_bitmap().to_vec();
+        signers[3] = false;
+        let candidate =
+            AggregateBatchCertificate::from(certificate.batch_header().clone(), signers, *certificate.signature())
+                .unwrap();
+        assert!(candidate.verify(&committee, &public_keys).is_err());
+
+        // Check that the certificate fails if the author is claimed as a signer.
+        let candidate = AggregateBatchCertificate::<CurrentNetwork>::from(
+            certificate.batch_header().clone(),
+            vec![true; 4],
+            *certificate.signature(),
+        )
+        .unwrap();
+        assert!(candidate.verify(&committee, &public_keys).is_err());
+
+        // Check that the certificate fails if the bitmap does not match the committee size.
+        let candidate = AggregateBatchCertificate::<CurrentNetwork>::from(
+            certificate.batch_header().clone(),
+            vec![false, true, true, true, false],
+            *certificate.signature(),
+        )
+        .unwrap();
+        assert!(candidate.verify(&committee, &public_keys).is_err());
+    }
+
+    #[test]
+    fn test_verify_fails_without_quorum() {
+        let rng = &mut TestRng::default();
+
+        // Sample an aggregate batch certificate.
+        let (certificate, committee, public_keys) = test_helpers::sample_aggregate_batch_certificate(rng);
+
+        // Aggregate a signature from only one member besides the author.
+        let (address, _) = committee.members().get_index(1).unwrap();
+        let private_key = BLSPrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let mut public_keys = public_keys;
+        public_keys.insert(*address, (private_key.to_public_key(), private_key.prove_possession().unwrap()));
+        let signatures = IndexMap::from([(
+            *address,
+            AggregateBatchCertificate::sign(&private_key, certificate.batch_header()).unwrap(),
+        )]);
+        let candidate =
+            AggregateBatchCertificate::aggregate(certificate.batch_header().clone(), &committee, &signatures).unwrap();
+        // Check that the certificate fails to reach quorum.
+        assert!(candidate.verify(&committee, &public_keys).is_err());
+    }
+
+    #[test]
+    fn test_verify_fails_on_missing_public_key() {
+        let rng = &mut TestRng::default();
+
+        // Sample an aggregate batch certificate.
+        let (certificate, committee, mut public_keys) = test_helpers::sample_aggregate_batch_certificate(rng);
+        // Remove the public key of a signer.
+        public_keys.swap_remove_index(2);
+        // Check that the certificate fails.
+        assert!(certificate.verify(&committee, &public_keys).is_err());
+    }
+
+    #[test]
+    fn test_verify_fails_on_invalid_proof_of_possession() {
+        let rng = &mut TestRng::default();
+
+        // Sample an aggregate batch certificate.
+        let (certificate, committee, mut public_keys) = test_helpers::sample_aggregate_batch_certificate(rng);
+        // Replace the proof of possession of a signer with one for another key.
+        let other = BLSPrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        public_keys[2].1 = other.prove_possession().unwrap();
+        // Check that the certificate fails.
+        assert!(certificate.verify(&committee, &public_keys).is_err());
+    }
+
+    #[test]
+    fn test_eq_compares_signatures() {
+        let rng = &mut TestRng::default();
+
+        // Sample an aggregate batch certificate.
+        let (certificate, ..) = test_helpers::sample_aggregate_batch_certificate(rng);
+        // Check that a certificate with the same batch but different signers is not equal.
+        let mut signers = certificate.signer_bitmap().to_vec();
+        signers[3] = false;
+        let candidate =
+            AggregateBatchCertificate::from(certificate.batch_header().clone(), signers, *certificate.signature())
+                .unwrap();
+        assert_eq!(certificate.batch_id(), candidate.batch_id());
+        assert_ne!(certificate, candidate);
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> Serialize for AggregateBatchCertificate<N> {
+    /// Serializes the aggregate batch certificate to a JSON-string or buffer.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => {
+                let mut state = serializer.serialize_struct("AggregateBatchCertificate", 3)?;
+                state.serialize_field("batch_header", &self.batch_header)?;
+                state.serialize_field("signers", &self.signers)?;
+                state.serialize_field("signature", &self.signature)?;
+                state.end()
+            }
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for AggregateBatchCertificate<N> {
+    /// Deserializes the aggregate batch certificate from a JSON-string or buffer.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => {
+                let mut value = serde_json::Value::deserialize(deserializer)?;
+                Self::from(
+                    DeserializeExt::take_from_value::<D>(&mut value, "batch_header")?,
+                    DeserializeExt::take_from_value::<D>(&mut value, "signers")?,
+                    DeserializeExt::take_from_value::<D>(&mut value, "signature")?,
+                )
+                .map_err(de::Error::custom)
+            }
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(
+                deserializer,
+                "aggregate batch certificate",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde_json() {
+        let rng = &mut TestRng::default();
+
+        for _ in 0..5 {
+            let (expected, ..) = test_helpers::sample_aggregate_batch_certificate(rng);
+
+            // Serialize
+            let expected_string = expected.to_string();
+            let candidate_string = serde_json::to_string(&expected).unwrap();
+            assert_eq!(expected_string, candidate_string);
+
+            // Deserialize
+            let candidate = serde_json::from_str::<AggregateBatchCertificate<_>>(&candidate_string).unwrap();
+            assert_eq!(expected, candidate);
+            assert_eq!(expected.signer_bitmap(), candidate.signer_bitmap());
+            assert_eq!(expected, AggregateBatchCertificate::from_str(&expected_string).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_bincode() {
+        let rng = &mut TestRng::default();
+
+        for _ in 0..5 {
+            let (expected, ..) = test_helpers::sample_aggregate_batch_certificate(rng);
+
+            // Serialize
+            let expected_bytes = expected.to_bytes_le().unwrap();
+            let expected_bytes_with_size_encoding = bincode::serialize(&expected).unwrap();
+            assert_eq!(&expected_bytes[..], &expected_bytes_with_size_encoding[8..]);
+
+            // Deserialize
+            assert_eq!(expected, AggregateBatchCertificate::read_le(&expected_bytes[..]).unwrap());
+            assert_eq!(expected, bincode::deserialize(&expected_bytes_with_size_encoding[..]).unwrap());
+        }
+    }
+}
@@ -19,6 +19,9 @@ mod bytes;
 mod serialize;
 mod string;
 
+pub mod aggregate;
+pub use aggregate::AggregateBatchCertificate;
+
 use console::{
     account::{Address, Signature},
     prelude::*,
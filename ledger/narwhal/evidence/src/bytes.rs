@@ -0,0 +1,62 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> FromBytes for EquivocationEvidence<N> {
+    /// Reads the equivocation evidence from the buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid equivocation evidence version"));
+        }
+
+        // Read the first batch header.
+        let first = BatchHeader::read_le(&mut reader)?;
+        // Read the second batch header.
+        let second = BatchHeader::read_le(&mut reader)?;
+        // Return the equivocation evidence.
+        Self::new(first, second).map_err(error)
+    }
+}
+
+impl<N: Network> ToBytes for EquivocationEvidence<N> {
+    /// Writes the equivocation evidence to the buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+        // Write the first batch header.
+        self.first.write_le(&mut writer)?;
+        // Write the second batch header.
+        self.second.write_le(&mut writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes() {
+        let rng = &mut TestRng::default();
+
+        for expected in crate::test_helpers::sample_equivocation_evidences(rng) {
+            // Check the byte representation.
+            let expected_bytes = expected.to_bytes_le().unwrap();
+            assert_eq!(expected, EquivocationEvidence::read_le(&expected_bytes[..]).unwrap());
+        }
+    }
+}
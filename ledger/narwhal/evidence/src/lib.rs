@@ -0,0 +1,199 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![forbid(unsafe_code)]
+#![warn(clippy::cast_possible_truncation)]
+
+mod bytes;
+mod serialize;
+mod string;
+
+use console::{account::Address, prelude::*, types::Field};
+use ledger_committee::Committee;
+use narwhal_batch_header::BatchHeader;
+
+/// Evidence that a validator signed two conflicting batch headers in the same round.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EquivocationEvidence<N: Network> {
+    /// The conflicting batch header with the smaller batch ID.
+    first: BatchHeader<N>,
+    /// The conflicting batch header with the larger batch ID.
+    second: BatchHeader<N>,
+}
+
+impl<N: Network> EquivocationEvidence<N> {
+    /// Initializes new equivocation evidence from two conflicting batch headers.
+    /// Note: The batch headers are ordered by batch ID, so the evidence is independent of the order they are given in.
+    pub fn new(first: BatchHeader<N>, second: BatchHeader<N>) -> Result<Self> {
+        // Ensure the batch headers are from the same author.
+        ensure!(first.author() == second.author(), "Equivocation evidence must be from a single author");
+        // Ensure the batch headers are for the same round.
+        ensure!(first.round() == second.round(), "Equivocation evidence must be for a single round");
+        // Ensure the batch headers are conflicting.
+        ensure!(first.batch_id() != second.batch_id(), "Equivocation evidence must contain distinct batch headers");
+        // Order the batch headers by batch ID.
+        match first.batch_id() < second.batch_id() {
+            true => Ok(Self { first, second }),
+            false => Ok(Self { first: second, second: first }),
+        }
+    }
+}
+
+impl<N: Network> EquivocationEvidence<N> {
+    /// Returns the conflicting batch header with the smaller batch ID.
+    pub const fn first(&self) -> &BatchHeader<N> {
+        &self.first
+    }
+
+    /// Returns the conflicting batch header with the larger batch ID.
+    pub const fn second(&self) -> &BatchHeader<N> {
+        &self.second
+    }
+
+    /// Returns the address of the validator that equivocated.
+    pub const fn author(&self) -> Address<N> {
+        self.first.author()
+    }
+
+    /// Returns the round in which the validator equivocated.
+    pub const fn round(&self) -> u64 {
+        self.first.round()
+    }
+
+    /// Returns the evidence ID, defined as the hash of the author and round.
+    /// Note: The ID identifies the offense rather than the batch headers, so that a validator
+    /// may only be held accountable once per round, regardless of how many headers they signed.
+    pub fn to_id(&self) -> Result<Field<N>> {
+        N::hash_psd2(&[self.author().to_field()?, Field::from_u64(self.round())])
+    }
+}
+
+impl<N: Network> EquivocationEvidence<N> {
+    /// Checks that the evidence is valid for the given committee.
+    pub fn verify(&self, committee: &Committee<N>) -> Result<()> {
+        let author = self.author();
+        // Ensure the author is a member of the committee.
+        ensure!(
+            committee.is_committee_member(author),
+            "Equivocation evidence author '{author}' is not in the committee"
+        );
+        // Ensure both batch headers are signed by the author.
+        for header in [&self.first, &self.second] {
+            ensure!(
+                header.signature().verify(&author, &[header.batch_id()]),
+                "Equivocation evidence contains an invalid signature for batch {}",
+                header.batch_id()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(any(test, feature = "test-helpers"))]
+pub mod test_helpers {
+    use super::*;
+    use console::{account::PrivateKey, network::Testnet3, prelude::TestRng};
+
+    use indexmap::IndexSet;
+
+    type CurrentNetwork = Testnet3;
+
+    /// Returns sample equivocation evidence for the given private key and round.
+    pub fn sample_equivocation_evidence_for_round(
+        private_key: &PrivateKey<CurrentNetwork>,
+        round: u64,
+        rng: &mut TestRng,
+    ) -> EquivocationEvidence<CurrentNetwork> {
+        // Sample the previous certificate IDs.
+        let previous_certificate_ids = match round {
+            0 | 1 => IndexSet::new(),
+            _ => (0..10).map(|_| Field::<CurrentNetwork>::rand(rng)).collect::<IndexSet<_>>(),
+        };
+        // Sample two batch headers, that differ only in their timestamp.
+        let timestamp = rng.gen_range(0..i64::MAX - 1);
+        let first =
+            BatchHeader::new(private_key, round, timestamp, Default::default(), previous_certificate_ids.clone(), rng)
+                .unwrap();
+        let second =
+            BatchHeader::new(private_key, round, timestamp + 1, Default::default(), previous_certificate_ids, rng)
+                .unwrap();
+        // Return the equivocation evidence.
+        EquivocationEvidence::new(first, second).unwrap()
+    }
+
+    /// Returns sample equivocation evidence, sampled at random.
+    pub fn sample_equivocation_evidence(rng: &mut TestRng) -> EquivocationEvidence<CurrentNetwork> {
+        // Sample a private key.
+        let private_key = PrivateKey::new(rng).unwrap();
+        // Return the equivocation evidence.
+        sample_equivocation_evidence_for_round(&private_key, rng.gen_range(0..u64::MAX), rng)
+    }
+
+    /// Returns a list of sample equivocation evidence, sampled at random.
+    pub fn sample_equivocation_evidences(rng: &mut TestRng) -> Vec<EquivocationEvidence<CurrentNetwork>> {
+        (0..10).map(|_| sample_equivocation_evidence(rng)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::{account::PrivateKey, network::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_new() {
+        let rng = &mut TestRng::default();
+
+        // Sample equivocation evidence.
+        let evidence = crate::test_helpers::sample_equivocation_evidence(rng);
+        // Ensure the evidence is independent of the order of the batch headers.
+        let candidate = EquivocationEvidence::new(evidence.second().clone(), evidence.first().clone()).unwrap();
+        assert_eq!(evidence, candidate);
+        assert_eq!(evidence.to_id().unwrap(), candidate.to_id().unwrap());
+        // Ensure the same batch header twice is not evidence.
+        assert!(EquivocationEvidence::new(evidence.first().clone(), evidence.first().clone()).is_err());
+
+        // Ensure batch headers from different rounds are not evidence.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let first = crate::test_helpers::sample_equivocation_evidence_for_round(&private_key, 5, rng);
+        let second = crate::test_helpers::sample_equivocation_evidence_for_round(&private_key, 6, rng);
+        assert!(EquivocationEvidence::new(first.first().clone(), second.first().clone()).is_err());
+
+        // Ensure batch headers from different authors are not evidence.
+        let other = crate::test_helpers::sample_equivocation_evidence_for_round(&PrivateKey::new(rng).unwrap(), 5, rng);
+        assert!(EquivocationEvidence::new(first.first().clone(), other.first().clone()).is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let rng = &mut TestRng::default();
+
+        // Sample the committee members.
+        let private_keys = (0..4).map(|_| PrivateKey::<CurrentNetwork>::new(rng).unwrap()).collect::<Vec<_>>();
+        let members = private_keys.iter().map(|private_key| Address::try_from(private_key).unwrap()).collect();
+        // Sample the committee.
+        let committee = ledger_committee::test_helpers::sample_committee_for_round_and_members(5, members, rng);
+
+        // Ensure evidence from a committee member is valid.
+        let evidence = crate::test_helpers::sample_equivocation_evidence_for_round(&private_keys[0], 5, rng);
+        assert!(evidence.verify(&committee).is_ok());
+
+        // Ensure evidence from a non-member is invalid.
+        let evidence =
+            crate::test_helpers::sample_equivocation_evidence_for_round(&PrivateKey::new(rng).unwrap(), 5, rng);
+        assert!(evidence.verify(&committee).is_err());
+    }
+}
@@ -18,7 +18,7 @@
 #[cfg(feature = "batch-certificate")]
 pub use narwhal_batch_certificate as batch_certificate;
 #[cfg(feature = "batch-certificate")]
-pub use narwhal_batch_certificate::BatchCertificate;
+pub use narwhal_batch_certificate::{AggregateBatchCertificate, BatchCertificate};
 
 #[cfg(feature = "batch-header")]
 pub use narwhal_batch_header as batch_header;
@@ -30,6 +30,11 @@ pub use narwhal_data as data;
 #[cfg(feature = "data")]
 pub use narwhal_data::Data;
 
+#[cfg(feature = "evidence")]
+pub use narwhal_evidence as evidence;
+#[cfg(feature = "evidence")]
+pub use narwhal_evidence::EquivocationEvidence;
+
 #[cfg(feature = "subdag")]
 pub use narwhal_subdag as subdag;
 #[cfg(feature = "subdag")]
@@ -0,0 +1,61 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Enables the address index, which records the transitions where each address appears
+    /// in a public input or output, or as the payer of a public fee.
+    /// The blocks already in storage are indexed immediately, and subsequent blocks are indexed as they are added.
+    pub fn enable_address_index(&self) -> Result<()> {
+        // Ensure the ledger is writable.
+        self.ensure_writable()?;
+        self.vm.block_store().enable_address_index()
+    }
+
+    /// Returns `true` if the address index is enabled.
+    pub fn is_address_index_enabled(&self) -> Result<bool> {
+        self.vm.block_store().is_address_index_enabled()
+    }
+
+    /// Returns up to `limit` of the `(transition position, transition ID)` pairs for the public activity of the given address,
+    /// in the order the transitions were added to the ledger. If a cursor is given, only the transitions after it are returned.
+    /// The position of the last transition can be used as the cursor to retrieve the next page.
+    pub fn get_address_activity(
+        &self,
+        address: &Address<N>,
+        after: Option<TransitionPosition>,
+        limit: usize,
+    ) -> Result<Vec<(TransitionPosition, N::TransitionID)>> {
+        // Ensure the address index is enabled.
+        ensure!(self.is_address_index_enabled()?, "The address index is not enabled");
+        // Determine the position to start at, which directly follows the cursor.
+        let start = match after {
+            Some(after) => match after.successor() {
+                Some(start) => Some(start),
+                None => return Ok(Vec::new()),
+            },
+            None => None,
+        };
+        // Scan the requested page of activity from the index.
+        self.vm.block_store().get_address_activity(address, start, limit)
+    }
+
+    /// Returns the number of transitions in the public activity of the given address.
+    pub fn get_address_activity_count(&self, address: &Address<N>) -> Result<usize> {
+        // Ensure the address index is enabled.
+        ensure!(self.is_address_index_enabled()?, "The address index is not enabled");
+        self.vm.block_store().get_address_activity_count(address)
+    }
+}
@@ -14,20 +14,28 @@
 
 use super::*;
 
+/// The number of blocks between the publications of the storage metrics.
+#[cfg(feature = "metrics")]
+const STORAGE_METRICS_INTERVAL: u32 = 10;
+
 impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
     /// Returns a candidate for the next block in the ledger, using a committed subdag and its transmissions.
+    /// The given equivocation evidence is included in the block as slash ratifications.
     pub fn prepare_advance_to_next_quorum_block(
         &self,
         subdag: Subdag<N>,
         transmissions: IndexMap<TransmissionID<N>, Transmission<N>>,
+        equivocation_evidence: Vec<EquivocationEvidence<N>>,
     ) -> Result<Block<N>> {
         // Retrieve the latest block as the previous block (for the next block).
         let previous_block = self.latest_block();
 
         // Decouple the transmissions into ratifications, solutions, and transactions.
-        let (ratifications, solutions, transactions) = decouple_transmissions(transmissions.into_iter())?;
+        let (mut ratifications, solutions, transactions) = decouple_transmissions(transmissions.into_iter())?;
         // Currently, we do not support ratifications from the memory pool.
         ensure!(ratifications.is_empty(), "Ratifications are currently unsupported from the memory pool");
+        // Add a slash ratification for each equivocation evidence.
+        ratifications.extend(equivocation_evidence.into_iter().map(|evidence| Ratify::Slash(Box::new(evidence))));
         // Construct the block template.
         let (header, ratifications, solutions, transactions, aborted_transaction_ids) =
             self.construct_block_template(&previous_block, Some(&subdag), ratifications, solutions, transactions)?;
@@ -45,6 +53,7 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
     }
 
     /// Returns a candidate for the next block in the ledger.
+    /// The candidate ratifications may only be slashes, as the rewards are computed by the ledger.
     pub fn prepare_advance_to_next_beacon_block<R: Rng + CryptoRng>(
         &self,
         private_key: &PrivateKey<N>,
@@ -53,8 +62,11 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
         candidate_transactions: Vec<Transaction<N>>,
         rng: &mut R,
     ) -> Result<Block<N>> {
-        // Currently, we do not support ratifications from the memory pool.
-        ensure!(candidate_ratifications.is_empty(), "Ratifications are currently unsupported from the memory pool");
+        // Ensure the candidate ratifications are only slashes.
+        ensure!(
+            candidate_ratifications.iter().all(|ratify| matches!(ratify, Ratify::Slash(..))),
+            "Only slash ratifications are currently supported as candidate ratifications"
+        );
 
         // Retrieve the latest block as the previous block (for the next block).
         let previous_block = self.latest_block();
@@ -82,7 +94,13 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
     }
 
     /// Adds the given block as the next block in the ledger.
+    ///
+    /// An error means that the block was not added. Once the block is committed, a failure to update
+    /// the staking index, the reputation window, or to prune the older blocks is logged,
+    /// and the pruning is retried with the next block.
     pub fn advance_to_next_block(&self, block: &Block<N>) -> Result<()> {
+        // Ensure the ledger is writable.
+        self.ensure_writable()?;
         // Acquire the write lock on the current block.
         let mut current_block = self.current_block.write();
         // Update the VM.
@@ -97,12 +115,50 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
             *self.current_committee.write() = Some(current_committee);
         }
 
+        // Update the staking index with the bond states that may have changed.
+        // If the update fails, rebuild the staking index from storage, so that it does not diverge from it.
+        if let Err(error) = self.update_staking_index(block) {
+            warn!("Failed to update the staking index for block {}, rebuilding it - {error}", block.height());
+            if let Err(error) = self.rebuild_staking_index() {
+                error!("Failed to rebuild the staking index for block {} - {error}", block.height());
+            }
+        }
+
+        // Update the reputation window with the leader certificate of the block.
+        // If the update fails, rebuild the reputation window from storage, so that it does not diverge from it.
+        if let Err(error) = self.update_leader_reputation(block) {
+            warn!("Failed to update the reputation window for block {}, rebuilding it - {error}", block.height());
+            if let Err(error) = self.rebuild_leader_reputation() {
+                error!("Failed to rebuild the reputation window for block {} - {error}", block.height());
+            }
+        }
+
         // If the block is the start of a new epoch, or the epoch challenge has not been set, update the current epoch challenge.
         if block.height() % N::NUM_BLOCKS_PER_EPOCH == 0 || self.current_epoch_challenge.read().is_none() {
             // Update the current epoch challenge.
             self.current_epoch_challenge.write().clone_from(&self.get_epoch_challenge(block.height()).ok());
         }
 
+        // Publish the storage estimates on a regular schedule, as they are cheap to retrieve.
+        #[cfg(feature = "metrics")]
+        if block.height() % STORAGE_METRICS_INTERVAL == 0 {
+            match self.vm.backend_stats() {
+                Ok(Some(stats)) => stats.publish_metrics(),
+                Ok(None) => (),
+                Err(error) => warn!("Failed to retrieve the storage statistics at block {} - {error}", block.height()),
+            }
+        }
+
+        // If pruning is enabled, prune the blocks that are now older than the pruning depth.
+        // Note: The blocks that fail to be pruned are pruned along with the blocks that follow them.
+        if let Some(depth) = *self.pruning_depth.read() {
+            if let Some(height) = block.height().checked_sub(depth) {
+                if let Err(error) = self.vm.block_store().prune(height) {
+                    warn!("Added block {}, but failed to prune up to block {height} - {error}", block.height());
+                }
+            }
+        }
+
         Ok(())
     }
 }
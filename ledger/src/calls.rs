@@ -0,0 +1,173 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// A cursor into the calls of a program, which points at a transition by its block height and transition index.
+pub type CallCursor = TransitionPosition;
+
+/// A call to a program function that was included in the ledger.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramCall<N: Network> {
+    /// The ID of the transaction that contains the call.
+    transaction_id: N::TransactionID,
+    /// The ID of the transition of the call.
+    transition_id: N::TransitionID,
+    /// The name of the function that was called.
+    function_name: Identifier<N>,
+    /// The position of the call in the ledger.
+    cursor: CallCursor,
+}
+
+impl<N: Network> ProgramCall<N> {
+    /// Returns the ID of the transaction that contains the call.
+    pub const fn transaction_id(&self) -> &N::TransactionID {
+        &self.transaction_id
+    }
+
+    /// Returns the ID of the transition of the call.
+    pub const fn transition_id(&self) -> &N::TransitionID {
+        &self.transition_id
+    }
+
+    /// Returns the name of the function that was called.
+    pub const fn function_name(&self) -> &Identifier<N> {
+        &self.function_name
+    }
+
+    /// Returns the block height of the call.
+    pub const fn height(&self) -> u32 {
+        self.cursor.height()
+    }
+
+    /// Returns the cursor of the call, from which the subsequent calls can be retrieved.
+    pub const fn cursor(&self) -> CallCursor {
+        self.cursor
+    }
+}
+
+/// The number of calls that are scanned from storage at a time, when iterating over the calls of a program.
+const CALLS_PAGE_SIZE: usize = 128;
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Returns an iterator over the calls to the given program, optionally restricted to the given function,
+    /// in the order they were added to the ledger. If a cursor is given, only the calls after it are returned.
+    pub fn program_calls(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: Option<&Identifier<N>>,
+        after: Option<CallCursor>,
+    ) -> Result<impl '_ + Iterator<Item = Result<ProgramCall<N>>>> {
+        // Retrieve the names of the functions to scan.
+        let program_id = *program_id;
+        let function_names = self.get_call_function_names(&program_id, function_name)?;
+        // Scan the calls one page at a time, continuing from the cursor of the last call of the previous page.
+        let mut cursor = after;
+        let mut page = Vec::new().into_iter();
+        let mut is_done = false;
+        Ok(std::iter::from_fn(move || {
+            loop {
+                if let Some(call) = page.next() {
+                    return Some(Ok(call));
+                }
+                if is_done {
+                    return None;
+                }
+                match self.scan_program_calls(&program_id, &function_names, cursor, CALLS_PAGE_SIZE) {
+                    Ok(calls) => {
+                        is_done = calls.len() < CALLS_PAGE_SIZE;
+                        cursor = calls.last().map(ProgramCall::cursor).or(cursor);
+                        page = calls.into_iter();
+                    }
+                    Err(error) => {
+                        is_done = true;
+                        return Some(Err(error));
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Returns up to `limit` of the calls to the given program, optionally restricted to the given function,
+    /// in the order they were added to the ledger. If a cursor is given, only the calls after it are returned.
+    /// The cursor of the last call can be used to retrieve the next page.
+    pub fn get_program_calls(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: Option<&Identifier<N>>,
+        after: Option<CallCursor>,
+        limit: usize,
+    ) -> Result<Vec<ProgramCall<N>>> {
+        let function_names = self.get_call_function_names(program_id, function_name)?;
+        self.scan_program_calls(program_id, &function_names, after, limit)
+    }
+
+    /// Returns the names of the functions whose calls are scanned: the given function, if there is one,
+    /// and otherwise the functions of the given program.
+    fn get_call_function_names(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: Option<&Identifier<N>>,
+    ) -> Result<Vec<Identifier<N>>> {
+        match function_name {
+            Some(function_name) => Ok(vec![*function_name]),
+            None => match self.vm.contains_program(program_id) {
+                true => Ok(self.vm.process().read().get_program(*program_id)?.functions().keys().copied().collect()),
+                false => Ok(Vec::new()),
+            },
+        }
+    }
+
+    /// Returns up to `limit` of the calls to the given functions of the given program, after the given cursor,
+    /// by scanning the call index of each function from the cursor, and merging the calls in ledger order.
+    fn scan_program_calls(
+        &self,
+        program_id: &ProgramID<N>,
+        function_names: &[Identifier<N>],
+        after: Option<CallCursor>,
+        limit: usize,
+    ) -> Result<Vec<ProgramCall<N>>> {
+        // Determine the position to start at, which directly follows the cursor.
+        let start = match after {
+            Some(after) => match after.successor() {
+                Some(start) => Some(start),
+                None => return Ok(Vec::new()),
+            },
+            None => None,
+        };
+        // Scan the calls to each function, starting at the position.
+        let transition_store = self.vm.transition_store();
+        let mut calls = Vec::new();
+        for function_name in function_names {
+            for (cursor, transition_id) in transition_store.get_calls(program_id, function_name, start, limit)? {
+                calls.push((cursor, *function_name, transition_id));
+            }
+        }
+        // Merge the calls in ledger order, and keep the first `limit` of them.
+        calls.sort_unstable_by_key(|(cursor, ..)| *cursor);
+        calls.truncate(limit);
+
+        calls
+            .into_iter()
+            .map(|(cursor, function_name, transition_id)| {
+                // Retrieve the ID of the transaction that contains the transition.
+                let transaction_id = match self.find_transaction_id_from_transition_id(&transition_id)? {
+                    Some(transaction_id) => transaction_id,
+                    None => bail!("Missing the transaction ID for transition '{transition_id}'"),
+                };
+                Ok(ProgramCall { transaction_id, transition_id, function_name, cursor })
+            })
+            .collect()
+    }
+}
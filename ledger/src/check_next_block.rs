@@ -85,11 +85,18 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
         let ratified_finalize_operations =
             self.vm.check_speculate(state, block.ratifications(), block.solutions(), block.transactions())?;
 
+        // Retrieve the leader reputation, if the leader of the block is elected with reputation.
+        let leader_reputation = match ElectionMode::at_height::<N>(height) {
+            ElectionMode::Stake => None,
+            ElectionMode::Reputation => Some(self.get_leader_reputation()),
+        };
+
         // Ensure the block is correct.
         block.verify(
             &self.latest_block(),
             self.latest_state_root(),
             &self.latest_committee()?,
+            leader_reputation.as_ref(),
             self.coinbase_puzzle(),
             &self.latest_epoch_challenge()?,
             OffsetDateTime::now_utc().unix_timestamp(),
@@ -53,6 +53,8 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
         if height == 0 {
             return Ok(self.genesis_block.clone());
         }
+        // Ensure the block has not been pruned.
+        self.vm.block_store().check_pruned(height)?;
         // Retrieve the block hash.
         let block_hash = match self.vm.block_store().get_block_hash(height)? {
             Some(block_hash) => block_hash,
@@ -73,6 +75,14 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
 
     /// Returns the block for the given block hash.
     pub fn get_block_by_hash(&self, block_hash: &N::BlockHash) -> Result<Block<N>> {
+        // If the block hash is the genesis block hash, return the genesis block.
+        if *block_hash == self.genesis_block.hash() {
+            return Ok(self.genesis_block.clone());
+        }
+        // Ensure the block has not been pruned.
+        if let Some(height) = self.vm.block_store().get_block_height(block_hash)? {
+            self.vm.block_store().check_pruned(height)?;
+        }
         // Retrieve the block.
         match self.vm.block_store().get_block(block_hash)? {
             Some(block) => Ok(block),
@@ -136,6 +146,8 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
         if height == 0 {
             return Ok(self.genesis_block.transactions().clone());
         }
+        // Ensure the block has not been pruned.
+        self.vm.block_store().check_pruned(height)?;
         // Retrieve the block hash.
         let Some(block_hash) = self.vm.block_store().get_block_hash(height)? else {
             bail!("Block {height} does not exist in storage");
@@ -166,6 +178,8 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
 
     /// Returns the transaction for the given transaction ID.
     pub fn get_transaction(&self, transaction_id: N::TransactionID) -> Result<Transaction<N>> {
+        // Ensure the transaction has not been pruned.
+        self.check_transaction_pruned(&transaction_id)?;
         // Retrieve the transaction.
         match self.vm.block_store().get_transaction(&transaction_id)? {
             Some(transaction) => Ok(transaction),
@@ -175,6 +189,8 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
 
     /// Returns the confirmed transaction for the given transaction ID.
     pub fn get_confirmed_transaction(&self, transaction_id: N::TransactionID) -> Result<ConfirmedTransaction<N>> {
+        // Ensure the transaction has not been pruned.
+        self.check_transaction_pruned(&transaction_id)?;
         // Retrieve the confirmed transaction.
         match self.vm.block_store().get_confirmed_transaction(&transaction_id)? {
             Some(confirmed_transaction) => Ok(confirmed_transaction),
@@ -184,6 +200,8 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
 
     /// Returns the unconfirmed transaction for the given `transaction ID`.
     pub fn get_unconfirmed_transaction(&self, transaction_id: &N::TransactionID) -> Result<Transaction<N>> {
+        // Ensure the transaction has not been pruned.
+        self.check_transaction_pruned(transaction_id)?;
         // Retrieve the unconfirmed transaction.
         match self.vm.block_store().get_unconfirmed_transaction(transaction_id)? {
             Some(unconfirmed_transaction) => Ok(unconfirmed_transaction),
@@ -34,6 +34,7 @@ pub use ledger_test_helpers;
 mod helpers;
 pub use helpers::*;
 
+mod activity;
 mod advance;
 mod check_next_block;
 mod check_transaction_basic;
@@ -41,6 +42,20 @@ mod contains;
 mod find;
 mod get;
 mod iterators;
+mod prune;
+mod replay;
+
+mod calls;
+pub use calls::*;
+
+mod snapshot;
+pub use snapshot::*;
+
+mod reputation;
+pub use reputation::*;
+
+mod staking;
+pub use staking::*;
 
 #[cfg(test)]
 mod tests;
@@ -54,10 +69,14 @@ use console::{
 use ledger_authority::Authority;
 use ledger_block::{Block, ConfirmedTransaction, Header, Metadata, Ratify, Transaction, Transactions};
 use ledger_coinbase::{CoinbasePuzzle, CoinbaseSolution, EpochChallenge, ProverSolution, PuzzleCommitment};
-use ledger_committee::Committee;
-use ledger_narwhal::{BatchCertificate, Subdag, Transmission, TransmissionID};
+use ledger_committee::{Committee, ElectionMode, LeaderReputation};
+use ledger_narwhal::{BatchCertificate, EquivocationEvidence, Subdag, Transmission, TransmissionID};
 use ledger_query::Query;
-use ledger_store::{ConsensusStorage, ConsensusStore};
+use ledger_store::{
+    helpers::{StorageMode, TransitionPosition},
+    ConsensusStorage,
+    ConsensusStore,
+};
 use synthesizer::{
     program::{FinalizeGlobalState, Program},
     vm::VM,
@@ -105,17 +124,23 @@ pub struct Ledger<N: Network, C: ConsensusStorage<N>> {
     current_committee: Arc<RwLock<Option<Committee<N>>>>,
     /// The current block.
     current_block: Arc<RwLock<Block<N>>>,
+    /// The number of recent blocks to keep unpruned, if pruning is enabled.
+    pruning_depth: Arc<RwLock<Option<u32>>>,
+    /// The index of the stakers bonded to each validator.
+    staking_index: Arc<RwLock<StakingIndex<N>>>,
+    /// The committed leader certificates within the reputation window, and the leader reputation.
+    reputation_window: Arc<RwLock<ReputationWindow<N>>>,
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
     /// Loads the ledger from storage.
-    pub fn load(genesis_block: Block<N>, dev: Option<u16>) -> Result<Self> {
+    pub fn load<S: Clone + Into<StorageMode>>(genesis_block: Block<N>, storage: S) -> Result<Self> {
         let timer = timer!("Ledger::load");
 
         // Retrieve the genesis hash.
         let genesis_hash = genesis_block.hash();
         // Initialize the ledger.
-        let ledger = Self::load_unchecked(genesis_block, dev)?;
+        let ledger = Self::load_unchecked(genesis_block, storage)?;
 
         // Ensure the ledger contains the correct genesis block.
         if !ledger.contains_block_hash(&genesis_hash)? {
@@ -127,9 +152,14 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
         // Retrieve the latest height.
         let latest_height = ledger.current_block.read().height();
         debug_assert_eq!(latest_height, *ledger.vm.block_store().heights().max().unwrap(), "Mismatch in latest height");
+        // Determine the first unpruned block height, as pruned blocks are no longer retrievable.
+        let start_height = match ledger.pruned_height()? {
+            Some(pruned_height) => pruned_height.saturating_add(1),
+            None => 0,
+        };
         // Sample random block heights.
-        let block_heights: Vec<u32> =
-            (0..=latest_height).choose_multiple(&mut OsRng, (latest_height as usize).min(NUM_BLOCKS));
+        let block_heights: Vec<u32> = (start_height..=latest_height)
+            .choose_multiple(&mut OsRng, (latest_height.saturating_sub(start_height) as usize).min(NUM_BLOCKS));
         cfg_into_iter!(block_heights).try_for_each(|height| {
             ledger.get_block(height)?;
             Ok::<_, Error>(())
@@ -141,57 +171,109 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
     }
 
     /// Loads the ledger from storage, without performing integrity checks.
-    pub fn load_unchecked(genesis_block: Block<N>, dev: Option<u16>) -> Result<Self> {
+    pub fn load_unchecked<S: Clone + Into<StorageMode>>(genesis_block: Block<N>, storage: S) -> Result<Self> {
         let timer = timer!("Ledger::load_unchecked");
 
         // Initialize the consensus store.
-        let store = match ConsensusStore::<N, C>::open(dev) {
+        let store = match ConsensusStore::<N, C>::open(storage) {
             Ok(store) => store,
             Err(e) => bail!("Failed to load ledger (run 'snarkos clean' and try again)\n\n{e}\n"),
         };
         lap!(timer, "Load consensus store");
 
+        // Initialize the ledger.
+        let ledger = Self::from_store(genesis_block, store)?;
+
+        finish!(timer, "Initialize ledger");
+        Ok(ledger)
+    }
+
+    /// Initializes the ledger from the given consensus store, without performing integrity checks.
+    fn from_store(genesis_block: Block<N>, store: ConsensusStore<N, C>) -> Result<Self> {
+        let timer = timer!("Ledger::from_store");
+
         // Initialize a new VM.
         let vm = VM::from(store)?;
         lap!(timer, "Initialize a new VM");
 
         // Retrieve the current committee.
         let current_committee = vm.finalize_store().committee_store().current_committee().ok();
+        // Retrieve the pruning depth.
+        let pruning_depth = vm.block_store().pruning_depth()?;
 
         // Initialize the ledger.
-        let mut ledger = Self {
+        let ledger = Self {
             vm,
             genesis_block: genesis_block.clone(),
             coinbase_puzzle: CoinbasePuzzle::<N>::load()?,
             current_epoch_challenge: Default::default(),
             current_committee: Arc::new(RwLock::new(current_committee)),
             current_block: Arc::new(RwLock::new(genesis_block.clone())),
+            pruning_depth: Arc::new(RwLock::new(pruning_depth)),
+            staking_index: Default::default(),
+            reputation_window: Default::default(),
         };
 
         // If the block store is empty, initialize the genesis block.
         if ledger.vm.block_store().heights().max().is_none() {
+            // Ensure the ledger is writable, as a read-only ledger must wait for its primary to add the genesis block.
+            ensure!(!ledger.is_read_only(), "The primary ledger does not contain the genesis block yet");
             // Add the genesis block.
             ledger.advance_to_next_block(&genesis_block)?;
         }
         lap!(timer, "Initialize genesis");
 
+        // Load the latest block, committee, and epoch challenge.
+        ledger.load_latest_state()?;
+
+        finish!(timer);
+        Ok(ledger)
+    }
+
+    /// Loads the latest block, committee, epoch challenge, staking index, and reputation window from storage.
+    fn load_latest_state(&self) -> Result<()> {
         // Retrieve the latest height.
         let latest_height =
-            *ledger.vm.block_store().heights().max().ok_or_else(|| anyhow!("Failed to load blocks from the ledger"))?;
+            *self.vm.block_store().heights().max().ok_or_else(|| anyhow!("Failed to load blocks from the ledger"))?;
         // Fetch the latest block.
-        let block = ledger
+        let block = self
             .get_block(latest_height)
             .map_err(|_| anyhow!("Failed to load block {latest_height} from the ledger"))?;
 
         // Set the current block.
-        ledger.current_block = Arc::new(RwLock::new(block));
+        *self.current_block.write() = block;
         // Set the current committee (and ensures the latest committee exists).
-        ledger.current_committee = Arc::new(RwLock::new(Some(ledger.latest_committee()?)));
+        let committee = self.vm.finalize_store().committee_store().current_committee()?;
+        *self.current_committee.write() = Some(committee);
         // Set the current epoch challenge.
-        ledger.current_epoch_challenge = Arc::new(RwLock::new(Some(ledger.get_epoch_challenge(latest_height)?)));
+        *self.current_epoch_challenge.write() = Some(self.get_epoch_challenge(latest_height)?);
+        // Rebuild the staking index.
+        self.rebuild_staking_index()?;
+        // Rebuild the reputation window.
+        self.rebuild_leader_reputation()
+    }
 
-        finish!(timer, "Initialize ledger");
-        Ok(ledger)
+    /// Returns `true` if the ledger is backed by a read-only secondary store.
+    pub fn is_read_only(&self) -> bool {
+        self.vm.is_read_only()
+    }
+
+    /// Catches up with the blocks that the primary ledger has written to storage since the last call,
+    /// and updates the latest block, committee, epoch challenge, staking index, and reputation window.
+    /// This method is only supported by a ledger that is backed by a read-only secondary store.
+    pub fn catch_up_with_primary(&self) -> Result<()> {
+        // Catch up with the primary store.
+        self.vm.catch_up_with_primary()?;
+        // Reload the latest state.
+        self.load_latest_state()
+    }
+
+    /// Ensures the ledger is writable.
+    pub(crate) fn ensure_writable(&self) -> Result<()> {
+        match self.is_read_only() {
+            true => bail!("Cannot modify a read-only ledger"),
+            false => Ok(()),
+        }
     }
 
     /// Returns the VM.
@@ -204,6 +286,11 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
         &self.coinbase_puzzle
     }
 
+    /// Returns the method used to elect the leader of the next quorum block.
+    pub fn election_mode(&self) -> ElectionMode {
+        ElectionMode::at_height::<N>(self.latest_height().saturating_add(1))
+    }
+
     /// Returns the latest committee.
     pub fn latest_committee(&self) -> Result<Committee<N>> {
         match self.current_committee.read().as_ref() {
@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Enables pruning, which keeps the transaction bodies of only the latest `depth` blocks in storage.
+    /// The blocks older than the pruning depth are pruned immediately, and subsequently as the ledger advances.
+    /// The pruning depth is stored, so that pruning remains enabled when the ledger is reloaded.
+    pub fn enable_pruning(&self, depth: u32) -> Result<()> {
+        // Ensure the ledger is writable.
+        self.ensure_writable()?;
+        // Ensure the latest block is never pruned, as it is required to reload the ledger.
+        ensure!(depth > 0, "The pruning depth must be at least 1");
+        // Set the pruning depth.
+        self.vm.block_store().set_pruning_depth(Some(depth))?;
+        *self.pruning_depth.write() = Some(depth);
+        // Prune the blocks that are older than the pruning depth.
+        match self.latest_height().checked_sub(depth) {
+            Some(height) => self.prune(height),
+            None => Ok(()),
+        }
+    }
+
+    /// Disables pruning. The blocks that have already been pruned remain pruned.
+    pub fn disable_pruning(&self) -> Result<()> {
+        // Ensure the ledger is writable.
+        self.ensure_writable()?;
+        // Clear the pruning depth.
+        self.vm.block_store().set_pruning_depth(None)?;
+        *self.pruning_depth.write() = None;
+        Ok(())
+    }
+
+    /// Returns the pruning depth, if pruning is enabled.
+    pub fn pruning_depth(&self) -> Option<u32> {
+        *self.pruning_depth.read()
+    }
+
+    /// Returns the height of the latest pruned block, if any blocks have been pruned.
+    pub fn pruned_height(&self) -> Result<Option<u32>> {
+        self.vm.block_store().pruned_height()
+    }
+
+    /// Prunes the proofs and transition payloads of all blocks up to and including the given block height.
+    /// The pruned blocks remain in the block tree, so their hashes, headers, and state paths are still available,
+    /// but retrieving their blocks or transactions returns a `PrunedError`.
+    pub fn prune(&self, height: u32) -> Result<()> {
+        // Ensure the ledger is writable.
+        self.ensure_writable()?;
+        // Ensure the latest block is not pruned.
+        let latest_height = self.latest_height();
+        ensure!(height < latest_height, "Cannot prune block {height}: the latest block height is {latest_height}");
+        // Prune the blocks.
+        self.vm.block_store().prune(height)
+    }
+
+    /// Returns a `PrunedError` if the block containing the given transaction ID has been pruned.
+    pub(crate) fn check_transaction_pruned(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        // Retrieve the block hash of the transaction.
+        let Some(block_hash) = self.vm.block_store().find_block_hash(transaction_id)? else { return Ok(()) };
+        // Retrieve the block height.
+        match self.vm.block_store().get_block_height(&block_hash)? {
+            Some(height) => self.vm.block_store().check_pruned(height),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use ledger_store::helpers::{memory::ConsensusMemory, PrunedError};
+use synthesizer::vm::ReplayReport;
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Replays the given block on top of the latest block, as a **dry-run**, and returns a report with the
+    /// trace of every finalize command, and a comparison of the replayed finalize operations against the block.
+    /// The ledger is left unchanged.
+    pub fn replay_next_block(&self, block: &Block<N>) -> Result<ReplayReport<N>> {
+        self.vm.replay_block(block)
+    }
+
+    /// Replays the block at the given height, and returns a report with the trace of every finalize command,
+    /// and a comparison of the replayed finalize operations against the block.
+    ///
+    /// As the finalize store only holds the latest state, the state at the previous height is reconstructed
+    /// by re-executing every block from genesis in an in-memory ledger. This method is therefore linear in
+    /// the height, and is rejected with a `PrunedError` if the ledger has pruned any block. To replay a block
+    /// on top of the latest state instead, use `Ledger::replay_next_block`.
+    pub fn replay_block(&self, height: u32) -> Result<ReplayReport<N>> {
+        let timer = timer!("Ledger::replay_block");
+
+        // Ensure the block height is valid.
+        ensure!(height > 0, "Cannot replay the genesis block");
+        ensure!(
+            height <= self.latest_height(),
+            "Cannot replay block {height}: the latest block height is {}",
+            self.latest_height()
+        );
+        // Ensure no block has been pruned, as every block from genesis is re-executed.
+        // Note: Blocks are pruned from the first block onwards.
+        if self.pruned_height()?.is_some() {
+            return Err(Error::new(PrunedError::new(1))
+                .context(format!("Cannot replay block {height}, as the blocks it is replayed from are pruned")));
+        }
+
+        // Initialize an in-memory VM.
+        let vm = VM::from(ConsensusStore::<N, ConsensusMemory<N>>::open(None)?)?;
+        // Advance the VM up to the previous block.
+        for previous_height in 0..height {
+            vm.add_next_block(&self.get_block(previous_height)?)?;
+        }
+        lap!(timer, "Load the state at block {}", height - 1);
+
+        // Replay the block.
+        let report = vm.replay_block(&self.get_block(height)?);
+        finish!(timer, "Replay block {height}");
+        report
+    }
+}
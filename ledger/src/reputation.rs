@@ -0,0 +1,113 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::collections::VecDeque;
+
+/// The committed leader certificates within the reputation window, and the leader reputation replayed from them.
+///
+/// The reputation is replayed from the start of the window on every block, rather than updated in place,
+/// so that it only depends on the blocks within the window. This ensures a node that rebuilds the window
+/// from storage derives the same reputation as a node that has maintained it since genesis.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ReputationWindow<N: Network> {
+    /// The anchor round, leader, and electing committee of each committed leader certificate, by increasing round.
+    commits: VecDeque<(u64, Address<N>, Committee<N>)>,
+    /// The leader reputation, replayed from the committed leader certificates.
+    reputation: LeaderReputation<N>,
+}
+
+impl<N: Network> Default for ReputationWindow<N> {
+    /// Initializes an empty reputation window.
+    fn default() -> Self {
+        Self { commits: Default::default(), reputation: LeaderReputation::new() }
+    }
+}
+
+impl<N: Network> ReputationWindow<N> {
+    /// Removes the committed leader certificates that fell outside of the window as of the given round,
+    /// and replays the leader reputation from the remaining ones.
+    fn replay(&mut self, latest_round: u64) -> Result<()> {
+        // Compute the first round within the window.
+        let window_start = latest_round.saturating_sub(LeaderReputation::<N>::WINDOW_IN_ROUNDS);
+        // Remove the committed leader certificates before the window.
+        while matches!(self.commits.front(), Some((anchor_round, ..)) if *anchor_round < window_start) {
+            self.commits.pop_front();
+        }
+        // Replay the committed leader certificates, in increasing round order.
+        let mut reputation = LeaderReputation::new();
+        for (anchor_round, leader, committee) in &self.commits {
+            reputation.record_commit(committee, *anchor_round, *leader)?;
+        }
+        self.reputation = reputation;
+        Ok(())
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Returns the leader reputation, derived from the subdags of the quorum blocks within the reputation window.
+    pub fn get_leader_reputation(&self) -> LeaderReputation<N> {
+        self.reputation_window.read().reputation.clone()
+    }
+
+    /// Rebuilds the reputation window from the quorum blocks in storage.
+    pub(crate) fn rebuild_leader_reputation(&self) -> Result<()> {
+        // Compute the first round within the window.
+        let window_start = self.latest_round().saturating_sub(LeaderReputation::<N>::WINDOW_IN_ROUNDS);
+
+        // Collect the committed leader certificates within the window, from the latest block backwards.
+        let mut window = ReputationWindow::default();
+        for height in (0..=self.latest_height()).rev() {
+            match self.get_authority(height)? {
+                Authority::Quorum(subdag) if subdag.anchor_round() >= window_start => {
+                    // Retrieve the committee that elected the leader, which is the committee as of the previous block.
+                    let previous_height = height.saturating_sub(1);
+                    let Some(committee) = self.get_committee(previous_height)? else {
+                        bail!("Missing the committee for block {previous_height}")
+                    };
+                    window.commits.push_front((subdag.anchor_round(), subdag.leader_address(), committee));
+                }
+                _ => break,
+            }
+        }
+
+        // Replay the leader reputation, and replace the reputation window.
+        window.replay(self.latest_round())?;
+        *self.reputation_window.write() = window;
+        Ok(())
+    }
+
+    /// Updates the reputation window with the given block, which must be the latest block in the ledger.
+    pub(crate) fn update_leader_reputation(&self, block: &Block<N>) -> Result<()> {
+        let mut window = self.reputation_window.read().clone();
+        match block.authority() {
+            Authority::Quorum(subdag) => {
+                // Retrieve the committee that elected the leader, which is the committee as of the previous block.
+                let previous_height = block.height().saturating_sub(1);
+                let Some(committee) = self.get_committee(previous_height)? else {
+                    bail!("Missing the committee for block {previous_height}")
+                };
+                window.commits.push_back((subdag.anchor_round(), subdag.leader_address(), committee));
+            }
+            // The reputation is only derived from the quorum blocks since the latest beacon block.
+            Authority::Beacon(_) => window.commits.clear(),
+        }
+
+        // Replay the leader reputation, and replace the reputation window.
+        window.replay(block.round())?;
+        *self.reputation_window.write() = window;
+        Ok(())
+    }
+}
@@ -0,0 +1,451 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use console::program::BlockTree;
+use synthesizer::program::FinalizeOperation;
+
+use std::path::Path;
+
+/// The mapping entries in a snapshot, as `(program ID, mapping name, entries)` tuples.
+type SnapshotMappings<N> = Vec<(ProgramID<N>, Identifier<N>, Vec<(Plaintext<N>, Value<N>)>)>;
+
+/// A snapshot of the ledger at a given block height, from which a new node can start serving queries
+/// without replaying every block from genesis.
+///
+/// The block hashes are recomputed from the block headers and recent blocks, and are checked against a
+/// trusted block hash and state root when the snapshot is loaded. The finalize operations of the snapshot block
+/// are checked against its finalize root, and the mappings are checked to hold the key-values written by them.
+/// The remaining mapping entries are checked against a trusted finalize checksum.
+/// The committees and deployments are not committed to by the block hashes or the finalize checksum,
+/// and are trusted as given, so a snapshot must only be loaded from a trusted source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot<N: Network> {
+    /// The block headers, from genesis up to (but excluding) the first recent block.
+    headers: Vec<Header<N>>,
+    /// The recent blocks, up to and including the snapshot height.
+    recent_blocks: Vec<Block<N>>,
+    /// The committees, for each block height up to and including the snapshot height.
+    committees: Vec<Committee<N>>,
+    /// The deployment transactions of the programs in the ledger.
+    deployments: Vec<Transaction<N>>,
+    /// The mappings of 'credits.aleo' and the deployed programs.
+    mappings: SnapshotMappings<N>,
+    /// The finalize checksum of the mappings.
+    checksum: Field<N>,
+    /// The finalize operations from the ratifications of the snapshot block.
+    ratified_operations: Vec<FinalizeOperation<N>>,
+}
+
+impl<N: Network> Snapshot<N> {
+    /// The magic bytes at the start of a snapshot file.
+    pub const MAGIC: [u8; 8] = *b"ALEOSNAP";
+    /// The number of most recent blocks that are included in full in a snapshot.
+    pub const NUM_RECENT_BLOCKS: u32 = 100;
+
+    /// Returns the block height of the snapshot.
+    pub fn height(&self) -> u32 {
+        self.recent_blocks.last().map_or(0, |block| block.height())
+    }
+
+    /// Returns the block hash of the snapshot.
+    pub fn hash(&self) -> N::BlockHash {
+        self.recent_blocks.last().map_or_else(Default::default, |block| block.hash())
+    }
+
+    /// Returns the recent blocks, up to and including the snapshot height.
+    pub fn recent_blocks(&self) -> &[Block<N>] {
+        &self.recent_blocks
+    }
+
+    /// Returns the finalize checksum of the mappings.
+    pub const fn checksum(&self) -> Field<N> {
+        self.checksum
+    }
+
+    /// Checks the snapshot against the given genesis block, trusted block hash, trusted state root,
+    /// and trusted finalize checksum, and returns the `(block hash, block header, state root)` triples for the block headers.
+    fn verify(
+        &self,
+        genesis_block: &Block<N>,
+        trusted_block_hash: N::BlockHash,
+        trusted_state_root: N::StateRoot,
+        trusted_checksum: Field<N>,
+    ) -> Result<Vec<(N::BlockHash, Header<N>, N::StateRoot)>> {
+        // Ensure the snapshot contains at least one recent block.
+        let Some(first_block) = self.recent_blocks.first() else { bail!("The snapshot does not contain any blocks") };
+        // Ensure the recent blocks immediately follow the block headers.
+        ensure!(first_block.height() as usize == self.headers.len(), "The snapshot blocks do not follow its headers");
+        // Ensure there is a committee for every block height.
+        ensure!(
+            self.committees.len() == self.height() as usize + 1,
+            "The snapshot contains {} committees, but expected {}",
+            self.committees.len(),
+            self.height() as usize + 1
+        );
+        // Ensure the deployments are all deployment transactions.
+        ensure!(self.deployments.iter().all(|tx| tx.is_deploy()), "The snapshot contains a non-deployment transaction");
+
+        // Recompute the block hashes from the block headers.
+        let mut block_hashes = Vec::with_capacity(self.height() as usize + 1);
+        let mut previous_hash = N::BlockHash::default();
+        for (height, header) in self.headers.iter().enumerate() {
+            // Ensure the block header is for the expected network and height.
+            ensure!(header.network() == N::ID, "The snapshot header {height} is for the wrong network");
+            ensure!(header.height() as usize == height, "The snapshot header {height} is at the wrong height");
+            // Compute the block hash.
+            previous_hash = N::hash_bhp1024(&to_bits_le![previous_hash, header.to_root()?])?.into();
+            block_hashes.push(previous_hash);
+        }
+        // Ensure the recent blocks form a chain from the block headers.
+        for (block, height) in self.recent_blocks.iter().zip(first_block.height()..) {
+            ensure!(block.network() == N::ID, "The snapshot block {height} is for the wrong network");
+            ensure!(block.height() == height, "The snapshot block {height} is at the wrong height");
+            ensure!(block.previous_hash() == previous_hash, "The snapshot block {height} does not follow its parent");
+            previous_hash = block.hash();
+            block_hashes.push(previous_hash);
+        }
+
+        // Ensure the snapshot starts from the given genesis block.
+        ensure!(block_hashes[0] == genesis_block.hash(), "The snapshot does not start from the given genesis block");
+        // Ensure the snapshot ends at the trusted block hash.
+        ensure!(
+            previous_hash == trusted_block_hash,
+            "The snapshot block hash '{previous_hash}' does not match the trusted block hash '{trusted_block_hash}'"
+        );
+
+        // Ensure the block headers produce the state root expected by the first recent block.
+        let num_headers = self.headers.len();
+        if num_headers > 0 {
+            let leaves = block_hashes[..num_headers].iter().map(|hash| hash.to_bits_le()).collect::<Vec<_>>();
+            let tree: BlockTree<N> = N::merkle_tree_bhp(&leaves)?;
+            ensure!(
+                N::StateRoot::from(*tree.root()) == first_block.previous_state_root(),
+                "The snapshot headers do not match the state root of block {}",
+                first_block.height()
+            );
+        }
+        // Ensure the block hashes produce the trusted state root.
+        let leaves = block_hashes.iter().map(|hash| hash.to_bits_le()).collect::<Vec<_>>();
+        let tree: BlockTree<N> = N::merkle_tree_bhp(&leaves)?;
+        let state_root = N::StateRoot::from(*tree.root());
+        ensure!(
+            state_root == trusted_state_root,
+            "The snapshot state root '{state_root}' does not match the trusted state root '{trusted_state_root}'"
+        );
+
+        // Ensure the finalize checksum is the trusted finalize checksum.
+        ensure!(
+            self.checksum == trusted_checksum,
+            "The snapshot finalize checksum '{}' does not match the trusted finalize checksum '{trusted_checksum}'",
+            self.checksum
+        );
+
+        // Ensure the finalize operations of the snapshot block produce its finalize root.
+        let Some(block) = self.recent_blocks.last() else { bail!("The snapshot does not contain any blocks") };
+        ensure!(
+            block.transactions().to_finalize_root(self.ratified_operations.clone())? == block.finalize_root(),
+            "The snapshot finalize operations do not match the finalize root of block {}",
+            block.height()
+        );
+
+        // Return the block headers, along with their block hashes and the state roots committed to by their children.
+        let next_state_roots = self.headers.iter().skip(1).map(|header| header.previous_state_root());
+        Ok(self
+            .headers
+            .iter()
+            .zip(block_hashes)
+            .zip(next_state_roots.chain([first_block.previous_state_root()]))
+            .map(|((header, block_hash), state_root)| (block_hash, *header, state_root))
+            .collect())
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Writes a snapshot of the ledger at the given block height to the given path.
+    /// The finalize state is only available for the latest block, so the height must be the latest height.
+    pub fn export_snapshot(&self, height: u32, path: impl AsRef<Path>) -> Result<()> {
+        // Prepare the snapshot.
+        let snapshot = self.prepare_snapshot(height, Snapshot::<N>::NUM_RECENT_BLOCKS)?;
+        // Write the snapshot.
+        std::fs::write(path, snapshot.to_bytes_le()?)?;
+        Ok(())
+    }
+
+    /// Returns a snapshot of the ledger at the given block height, with the given number of recent blocks in full.
+    pub(crate) fn prepare_snapshot(&self, height: u32, num_recent_blocks: u32) -> Result<Snapshot<N>> {
+        let timer = timer!("Ledger::prepare_snapshot");
+
+        // Ensure the height is the latest height.
+        let latest_height = self.latest_height();
+        ensure!(
+            height == latest_height,
+            "Cannot export a snapshot at block {height}, as only the latest block ({latest_height}) has its finalize state"
+        );
+        // Ensure the snapshot contains at least one recent block.
+        ensure!(num_recent_blocks > 0, "A snapshot must contain at least one recent block");
+
+        // Retrieve the block headers and the recent blocks.
+        let num_recent_blocks = (height + 1).min(num_recent_blocks);
+        let first_recent_height = height + 1 - num_recent_blocks;
+        let headers = (0..first_recent_height).map(|height| self.get_header(height)).collect::<Result<Vec<_>>>()?;
+        let recent_blocks =
+            (first_recent_height..=height).map(|height| self.get_block(height)).collect::<Result<_>>()?;
+        lap!(timer, "Retrieve {} headers and {num_recent_blocks} blocks", headers.len());
+
+        // Retrieve the committees.
+        let committees = (0..=height).map(|height| self.get_committee(height)).collect::<Result<Vec<_>>>()?;
+        let committees = committees
+            .into_iter()
+            .zip(0..)
+            .map(|(committee, height)| committee.ok_or_else(|| anyhow!("Missing committee for block {height}")))
+            .collect::<Result<_>>()?;
+        lap!(timer, "Retrieve the committees");
+
+        // Retrieve the deployments.
+        let transaction_store = self.vm.transaction_store();
+        let program_ids = transaction_store.program_ids().map(|program_id| *program_id).collect::<Vec<_>>();
+        let deployments = program_ids
+            .iter()
+            .map(|program_id| {
+                let Some(transaction_id) = transaction_store.find_transaction_id_from_program_id(program_id)? else {
+                    bail!("Missing deployment transaction for program '{program_id}'")
+                };
+                match transaction_store.get_transaction(&transaction_id)? {
+                    Some(transaction) => Ok(transaction),
+                    None => bail!("Missing deployment transaction '{transaction_id}' for program '{program_id}'"),
+                }
+            })
+            .collect::<Result<_>>()?;
+        lap!(timer, "Retrieve {} deployments", program_ids.len());
+
+        // Retrieve the mappings of 'credits.aleo' and the deployed programs.
+        let finalize_store = self.vm.finalize_store();
+        let mut mappings = Vec::new();
+        for program_id in [ProgramID::from_str("credits.aleo")?].into_iter().chain(program_ids) {
+            for mapping_name in finalize_store.get_mapping_names_confirmed(&program_id)?.unwrap_or_default() {
+                let entries = finalize_store.get_mapping_confirmed(program_id, mapping_name)?;
+                mappings.push((program_id, mapping_name, entries));
+            }
+        }
+        let checksum = finalize_store.get_checksum_confirmed()?;
+        // Retrieve the finalize operations from the ratifications of the latest block.
+        let Some(ratified_operations) = finalize_store.get_ratified_operations(height)? else {
+            bail!("The finalize operations of block {height} are not available (try again after the next block)")
+        };
+        lap!(timer, "Retrieve {} mappings", mappings.len());
+
+        finish!(timer, "Prepared a snapshot at block {height}");
+        Ok(Snapshot { headers, recent_blocks, committees, deployments, mappings, checksum, ratified_operations })
+    }
+
+    /// Loads the ledger from the snapshot at the given path into the given (empty) storage.
+    /// The snapshot is checked against the genesis block, and the trusted block hash, state root, and finalize checksum,
+    /// before it is written to a staging storage, which is swapped in for the given storage
+    /// once its mappings are checked against the finalize root and checksum of the snapshot block.
+    ///
+    /// The committees and deployments in the snapshot are trusted as given, so the snapshot must come from a trusted source.
+    ///
+    /// Only the recent blocks in the snapshot are available in full. The older blocks are available
+    /// by their block hash, header, and state root, and are marked as pruned.
+    pub fn load_from_snapshot<S: Clone + Into<StorageMode>>(
+        genesis_block: Block<N>,
+        path: impl AsRef<Path>,
+        trusted_block_hash: N::BlockHash,
+        trusted_state_root: N::StateRoot,
+        trusted_checksum: Field<N>,
+        storage: S,
+    ) -> Result<Self> {
+        let timer = timer!("Ledger::load_from_snapshot");
+
+        // Read the snapshot.
+        let snapshot = Snapshot::<N>::from_bytes_le(&std::fs::read(path)?)?;
+        lap!(timer, "Read the snapshot at block {}", snapshot.height());
+        // Check the snapshot.
+        let headers = snapshot.verify(&genesis_block, trusted_block_hash, trusted_state_root, trusted_checksum)?;
+        lap!(timer, "Verify the snapshot");
+
+        // Initialize the staging consensus store.
+        let store = ConsensusStore::<N, C>::open_staging(storage.clone())?;
+        // Ensure the storage is empty.
+        ensure!(store.block_store().heights().max().is_none(), "Cannot load a snapshot into a non-empty ledger");
+
+        // Store the block headers.
+        store.block_store().insert_headers(&headers)?;
+        // Store the recent blocks.
+        for block in &snapshot.recent_blocks {
+            ensure!(
+                block.previous_state_root() == store.block_store().current_state_root(),
+                "The snapshot block {} does not follow the state root of its parent",
+                block.height()
+            );
+            store.block_store().insert(block)?;
+        }
+        lap!(timer, "Store the blocks");
+
+        // Store the committees.
+        for (committee, height) in snapshot.committees.into_iter().zip(0..) {
+            store.finalize_store().committee_store().insert(height, committee)?;
+        }
+        // Store the deployments that are not in the recent blocks.
+        for transaction in &snapshot.deployments {
+            if !store.transaction_store().contains_transaction_id(&transaction.id())? {
+                store.transaction_store().insert(transaction)?;
+            }
+        }
+        // Store the mappings.
+        for (program_id, mapping_name, entries) in snapshot.mappings {
+            if !store.finalize_store().contains_mapping_confirmed(&program_id, &mapping_name)? {
+                store.finalize_store().initialize_mapping(program_id, mapping_name)?;
+            }
+            store.finalize_store().replace_mapping(program_id, mapping_name, entries)?;
+        }
+        // Store the finalize operations from the ratifications of the snapshot block.
+        store.finalize_store().set_ratified_operations(snapshot.height(), &snapshot.ratified_operations)?;
+        lap!(timer, "Store the committees, deployments, and mappings");
+
+        // Ensure the mappings match the finalize root of the snapshot block.
+        let block = snapshot.recent_blocks.last().ok_or_else(|| anyhow!("The snapshot does not contain any blocks"))?;
+        store.finalize_store().check_finalize_root(block)?;
+        // Ensure the mappings match the finalize checksum.
+        ensure!(
+            store.finalize_store().get_checksum_confirmed()? == snapshot.checksum,
+            "The snapshot mappings do not match its finalize checksum"
+        );
+        lap!(timer, "Check the mappings against block {}", block.height());
+
+        // Swap in the staging store.
+        let store = store.swap_in(storage)?;
+        // Initialize the ledger.
+        let ledger = Self::from_store(genesis_block, store)?;
+        // Ensure the ledger is at the trusted block hash and state root.
+        ensure!(ledger.latest_hash() == trusted_block_hash, "The ledger is not at the trusted block hash");
+        ensure!(ledger.latest_state_root() == trusted_state_root, "The ledger is not at the trusted state root");
+
+        finish!(timer);
+        Ok(ledger)
+    }
+}
+
+impl<N: Network> FromBytes for Snapshot<N> {
+    /// Reads the snapshot from the buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the magic bytes.
+        let magic = <[u8; 8]>::read_le(&mut reader)?;
+        if magic != Self::MAGIC {
+            return Err(error("Invalid snapshot magic bytes"));
+        }
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        if version != 1 {
+            return Err(error("Invalid snapshot version"));
+        }
+        // Read the network ID.
+        let network = u16::read_le(&mut reader)?;
+        if network != N::ID {
+            return Err(error(format!("Invalid snapshot network ID (expected {}, found {network})", N::ID)));
+        }
+        // Read the block height.
+        let height = u32::read_le(&mut reader)?;
+
+        // Read the block headers.
+        let num_headers = u32::read_le(&mut reader)?;
+        let headers = (0..num_headers).map(|_| FromBytes::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        // Read the recent blocks.
+        let num_blocks = u32::read_le(&mut reader)?;
+        let recent_blocks = (0..num_blocks).map(|_| FromBytes::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        // Read the committees.
+        let num_committees = u32::read_le(&mut reader)?;
+        let committees = (0..num_committees).map(|_| FromBytes::read_le(&mut reader)).collect::<IoResult<_>>()?;
+        // Read the deployments.
+        let num_deployments = u32::read_le(&mut reader)?;
+        let deployments = (0..num_deployments).map(|_| FromBytes::read_le(&mut reader)).collect::<IoResult<_>>()?;
+
+        // Read the mappings.
+        let num_mappings = u32::read_le(&mut reader)?;
+        let mut mappings = Vec::new();
+        for _ in 0..num_mappings {
+            // Read the program ID and mapping name.
+            let program_id = FromBytes::read_le(&mut reader)?;
+            let mapping_name = FromBytes::read_le(&mut reader)?;
+            // Read the entries.
+            let num_entries = u32::read_le(&mut reader)?;
+            let entries = (0..num_entries)
+                .map(|_| Ok((FromBytes::read_le(&mut reader)?, FromBytes::read_le(&mut reader)?)))
+                .collect::<IoResult<_>>()?;
+            mappings.push((program_id, mapping_name, entries));
+        }
+        // Read the finalize checksum.
+        let checksum = FromBytes::read_le(&mut reader)?;
+        // Read the finalize operations from the ratifications of the snapshot block.
+        let num_operations = u32::read_le(&mut reader)?;
+        let ratified_operations =
+            (0..num_operations).map(|_| FromBytes::read_le(&mut reader)).collect::<IoResult<_>>()?;
+
+        // Construct the snapshot.
+        let snapshot =
+            Self { headers, recent_blocks, committees, deployments, mappings, checksum, ratified_operations };
+        // Ensure the block height matches the blocks.
+        if snapshot.height() != height || snapshot.recent_blocks.is_empty() {
+            return Err(error(format!("Invalid snapshot blocks (expected up to block {height})")));
+        }
+        Ok(snapshot)
+    }
+}
+
+impl<N: Network> ToBytes for Snapshot<N> {
+    /// Writes the snapshot to the buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the magic bytes.
+        Self::MAGIC.write_le(&mut writer)?;
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+        // Write the network ID.
+        N::ID.write_le(&mut writer)?;
+        // Write the block height.
+        self.height().write_le(&mut writer)?;
+
+        // Write the block headers.
+        u32::try_from(self.headers.len()).map_err(error)?.write_le(&mut writer)?;
+        self.headers.iter().try_for_each(|header| header.write_le(&mut writer))?;
+        // Write the recent blocks.
+        u32::try_from(self.recent_blocks.len()).map_err(error)?.write_le(&mut writer)?;
+        self.recent_blocks.iter().try_for_each(|block| block.write_le(&mut writer))?;
+        // Write the committees.
+        u32::try_from(self.committees.len()).map_err(error)?.write_le(&mut writer)?;
+        self.committees.iter().try_for_each(|committee| committee.write_le(&mut writer))?;
+        // Write the deployments.
+        u32::try_from(self.deployments.len()).map_err(error)?.write_le(&mut writer)?;
+        self.deployments.iter().try_for_each(|transaction| transaction.write_le(&mut writer))?;
+
+        // Write the mappings.
+        u32::try_from(self.mappings.len()).map_err(error)?.write_le(&mut writer)?;
+        for (program_id, mapping_name, entries) in &self.mappings {
+            // Write the program ID and mapping name.
+            program_id.write_le(&mut writer)?;
+            mapping_name.write_le(&mut writer)?;
+            // Write the entries.
+            u32::try_from(entries.len()).map_err(error)?.write_le(&mut writer)?;
+            for (key, value) in entries {
+                key.write_le(&mut writer)?;
+                value.write_le(&mut writer)?;
+            }
+        }
+        // Write the finalize checksum.
+        self.checksum.write_le(&mut writer)?;
+        // Write the finalize operations from the ratifications of the snapshot block.
+        u32::try_from(self.ratified_operations.len()).map_err(error)?.write_le(&mut writer)?;
+        self.ratified_operations.iter().try_for_each(|operation| operation.write_le(&mut writer))
+    }
+}
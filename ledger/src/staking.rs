@@ -0,0 +1,309 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use console::program::Argument;
+use ledger_block::Output;
+
+use indexmap::IndexSet;
+
+/// The bond state of a staker, from the `bonded` mapping of `credits.aleo`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BondState<N: Network> {
+    /// The validator the staker is bonded to.
+    validator: Address<N>,
+    /// The amount of microcredits bonded.
+    microcredits: u64,
+}
+
+impl<N: Network> BondState<N> {
+    /// Returns the validator the staker is bonded to.
+    pub const fn validator(&self) -> Address<N> {
+        self.validator
+    }
+
+    /// Returns the amount of microcredits bonded.
+    pub const fn microcredits(&self) -> u64 {
+        self.microcredits
+    }
+}
+
+/// The unbond state of a staker, from the `unbonding` mapping of `credits.aleo`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UnbondState {
+    /// The amount of microcredits unbonding.
+    microcredits: u64,
+    /// The block height at which the unbonding is complete, and can be claimed.
+    claimable_height: u32,
+}
+
+impl UnbondState {
+    /// Returns the amount of microcredits unbonding.
+    pub const fn microcredits(&self) -> u64 {
+        self.microcredits
+    }
+
+    /// Returns the block height at which the unbonding is complete, and can be claimed.
+    pub const fn claimable_height(&self) -> u32 {
+        self.claimable_height
+    }
+
+    /// Returns `true` if the unbonded microcredits can be claimed at the given block height.
+    pub const fn is_claimable(&self, block_height: u32) -> bool {
+        block_height >= self.claimable_height
+    }
+}
+
+/// The state of a validator, from the `committee` mapping of `credits.aleo`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ValidatorState {
+    /// The total microcredits bonded to the validator, including its own bond.
+    microcredits: u64,
+    /// Whether the validator is open to stakers.
+    is_open: bool,
+    /// The commission of the validator, in percent.
+    commission: u8,
+}
+
+impl ValidatorState {
+    /// Returns the total microcredits bonded to the validator, including its own bond.
+    pub const fn microcredits(&self) -> u64 {
+        self.microcredits
+    }
+
+    /// Returns `true` if the validator is open to stakers.
+    pub const fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Returns the commission of the validator, in percent.
+    pub const fn commission(&self) -> u8 {
+        self.commission
+    }
+}
+
+/// An index of the stakers bonded to each validator, which mirrors the `bonded` mapping of `credits.aleo`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct StakingIndex<N: Network> {
+    /// The validator of each staker.
+    validators: IndexMap<Address<N>, Address<N>>,
+    /// The stakers of each validator.
+    stakers: IndexMap<Address<N>, IndexSet<Address<N>>>,
+}
+
+impl<N: Network> Default for StakingIndex<N> {
+    /// Initializes an empty staking index.
+    fn default() -> Self {
+        Self { validators: Default::default(), stakers: Default::default() }
+    }
+}
+
+impl<N: Network> StakingIndex<N> {
+    /// Sets the validator of the given staker, or removes the staker if it is no longer bonded.
+    fn update(&mut self, staker: Address<N>, validator: Option<Address<N>>) {
+        // Remove the staker from its previous validator.
+        if let Some(previous) = self.validators.shift_remove(&staker) {
+            if let Some(stakers) = self.stakers.get_mut(&previous) {
+                stakers.shift_remove(&staker);
+                if stakers.is_empty() {
+                    self.stakers.shift_remove(&previous);
+                }
+            }
+        }
+        // Add the staker to its current validator.
+        if let Some(validator) = validator {
+            self.validators.insert(staker, validator);
+            self.stakers.entry(validator).or_default().insert(staker);
+        }
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Returns the bond state of the given staker, if it is bonded.
+    pub fn get_bond_state(&self, staker: &Address<N>) -> Result<Option<BondState<N>>> {
+        let Some(value) = self.get_credits_value("bonded", staker)? else {
+            return Ok(None);
+        };
+        Ok(Some(BondState {
+            validator: get_member(&value, "validator", "bond state", |literal| match literal {
+                Literal::Address(validator) => Some(*validator),
+                _ => None,
+            })?,
+            microcredits: get_member(&value, "microcredits", "bond state", |literal| match literal {
+                Literal::U64(microcredits) => Some(**microcredits),
+                _ => None,
+            })?,
+        }))
+    }
+
+    /// Returns the unbond state of the given staker, if it is unbonding.
+    pub fn get_unbond_state(&self, staker: &Address<N>) -> Result<Option<UnbondState>> {
+        let Some(value) = self.get_credits_value("unbonding", staker)? else {
+            return Ok(None);
+        };
+        Ok(Some(UnbondState {
+            microcredits: get_member(&value, "microcredits", "unbond state", |literal| match literal {
+                Literal::U64(microcredits) => Some(**microcredits),
+                _ => None,
+            })?,
+            claimable_height: get_member(&value, "height", "unbond state", |literal| match literal {
+                Literal::U32(height) => Some(**height),
+                _ => None,
+            })?,
+        }))
+    }
+
+    /// Returns the state of the given validator, if it is in the committee.
+    pub fn get_validator_state(&self, validator: &Address<N>) -> Result<Option<ValidatorState>> {
+        let Some(value) = self.get_credits_value("committee", validator)? else {
+            return Ok(None);
+        };
+        Ok(Some(ValidatorState {
+            microcredits: get_member(&value, "microcredits", "committee state", |literal| match literal {
+                Literal::U64(microcredits) => Some(**microcredits),
+                _ => None,
+            })?,
+            is_open: get_member(&value, "is_open", "committee state", |literal| match literal {
+                Literal::Boolean(is_open) => Some(**is_open),
+                _ => None,
+            })?,
+            // Note: A validator without an entry in the `commission` mapping charges a commission of 0.
+            commission: match self.get_credits_value("commission", validator)? {
+                Some(Value::Plaintext(Plaintext::Literal(Literal::U8(commission), _))) => *commission,
+                Some(value) => bail!("Invalid commission (missing u8) - {value}"),
+                None => 0,
+            },
+        }))
+    }
+
+    /// Returns the delegators bonded to the given validator, along with their bonded microcredits,
+    /// in the order they were first bonded. The validator's own bond is not included.
+    pub fn get_delegators(&self, validator: &Address<N>) -> Result<IndexMap<Address<N>, u64>> {
+        // Retrieve the stakers of the validator from the index.
+        let stakers = self.staking_index.read().stakers.get(validator).cloned().unwrap_or_default();
+        // Retrieve the bonded microcredits of each delegator.
+        stakers
+            .into_iter()
+            .filter(|staker| staker != validator)
+            .map(|delegator| match self.get_bond_state(&delegator)? {
+                Some(bond_state) => Ok((delegator, bond_state.microcredits())),
+                None => bail!("Missing the bond state for delegator '{delegator}'"),
+            })
+            .collect()
+    }
+
+    /// Returns the total microcredits delegated to the given validator, excluding the validator's own bond.
+    pub fn get_total_delegated(&self, validator: &Address<N>) -> Result<u64> {
+        self.get_delegators(validator)?
+            .values()
+            .try_fold(0u64, |total, microcredits| total.checked_add(*microcredits))
+            .ok_or_else(|| anyhow!("The total delegated to '{validator}' overflowed"))
+    }
+
+    /// Rebuilds the staking index from the `bonded` mapping of `credits.aleo`.
+    pub(crate) fn rebuild_staking_index(&self) -> Result<()> {
+        // Retrieve the bonded mapping.
+        let bonded = self
+            .vm
+            .finalize_store()
+            .get_mapping_confirmed(ProgramID::from_str("credits.aleo")?, Identifier::from_str("bonded")?)?;
+        // Construct the staking index.
+        let mut index = StakingIndex::default();
+        for (key, value) in bonded {
+            let staker = match key {
+                Plaintext::Literal(Literal::Address(staker), _) => staker,
+                _ => bail!("Invalid bonded key (missing staker) - {key}"),
+            };
+            let validator = get_member(&value, "validator", "bond state", |literal| match literal {
+                Literal::Address(validator) => Some(*validator),
+                _ => None,
+            })?;
+            index.update(staker, Some(validator));
+        }
+        // Replace the staking index.
+        *self.staking_index.write() = index;
+        Ok(())
+    }
+
+    /// Updates the staking index with the bond states that may have changed in the given block.
+    pub(crate) fn update_staking_index(&self, block: &Block<N>) -> Result<()> {
+        // If the block contains a ratification that rewrites the stakers, rebuild the staking index.
+        if block.ratifications().iter().any(|ratify| matches!(ratify, Ratify::Genesis(..) | Ratify::Slash(..))) {
+            return self.rebuild_staking_index();
+        }
+
+        // Collect the addresses passed to the finalize scopes of `credits.aleo`, which are the only stakers
+        // whose bond states may have changed, besides the bonded amounts of the staking rewards.
+        let credits_program_id = ProgramID::from_str("credits.aleo")?;
+        let mut addresses = IndexSet::new();
+        for transition in block.transactions().transitions() {
+            if transition.program_id() == &credits_program_id {
+                for output in transition.outputs() {
+                    if let Output::Future(_, Some(future)) = output {
+                        collect_addresses(future.arguments(), &mut addresses);
+                    }
+                }
+            }
+        }
+
+        // Refresh the bond states of the addresses.
+        for address in addresses {
+            let validator = self.get_bond_state(&address)?.map(|bond_state| bond_state.validator());
+            self.staking_index.write().update(address, validator);
+        }
+        Ok(())
+    }
+
+    /// Returns the value of the given address in the given `credits.aleo` mapping, if it exists.
+    fn get_credits_value(&self, mapping_name: &str, address: &Address<N>) -> Result<Option<Value<N>>> {
+        self.vm.finalize_store().get_value_confirmed(
+            ProgramID::from_str("credits.aleo")?,
+            Identifier::from_str(mapping_name)?,
+            &Plaintext::from(Literal::Address(*address)),
+        )
+    }
+}
+
+/// Returns the literal member with the given name from the given struct value, using the given conversion.
+/// Note: The members are matched by name, so that additional members in `credits.aleo` are ignored.
+fn get_member<N: Network, T>(
+    value: &Value<N>,
+    name: &str,
+    kind: &str,
+    convert: impl FnOnce(&Literal<N>) -> Option<T>,
+) -> Result<T> {
+    let Value::Plaintext(Plaintext::Struct(members, _)) = value else {
+        bail!("Invalid {kind} (missing struct) - {value}");
+    };
+    match members.get(&Identifier::from_str(name)?) {
+        Some(Plaintext::Literal(literal, _)) => match convert(literal) {
+            Some(member) => Ok(member),
+            None => bail!("Invalid {kind} (invalid {name}) - {value}"),
+        },
+        _ => bail!("Invalid {kind} (missing {name}) - {value}"),
+    }
+}
+
+/// Collects the addresses in the given future arguments, including those of nested futures.
+fn collect_addresses<N: Network>(arguments: &[Argument<N>], addresses: &mut IndexSet<Address<N>>) {
+    for argument in arguments {
+        match argument {
+            Argument::Plaintext(Plaintext::Literal(Literal::Address(address), _)) => {
+                addresses.insert(*address);
+            }
+            Argument::Plaintext(_) => (),
+            Argument::Future(future) => collect_addresses(future.arguments(), addresses),
+        }
+    }
+}
@@ -14,16 +14,33 @@
 
 use crate::{
     test_helpers::{CurrentLedger, CurrentNetwork},
+    ProgramCall,
     RecordsFilter,
+    Snapshot,
 };
 use console::{
     account::{Address, PrivateKey},
     network::prelude::*,
     program::{Entry, Identifier, Literal, Plaintext, ProgramID, Value},
+    types::Field,
+};
+use ledger_block::{ConfirmedTransaction, Ratify, Rejected, Transaction};
+use ledger_committee::{Committee, ElectionMode, MIN_VALIDATOR_STAKE};
+use ledger_narwhal::{BatchCertificate, BatchHeader, Subdag};
+use ledger_store::{
+    helpers::{memory::ConsensusMemory, PrunedError, StorageMode, TransitionPosition},
+    ConsensusStore,
+};
+use synthesizer::{
+    program::Program,
+    vm::{TransactionReplay, VM},
+    FinalizeTraceEvent,
+    MappingAccess,
 };
-use ledger_block::{ConfirmedTransaction, Rejected, Transaction};
-use ledger_store::{helpers::memory::ConsensusMemory, ConsensusStore};
-use synthesizer::{program::Program, vm::VM};
+
+use indexmap::indexset;
+use std::collections::BTreeMap;
+use time::OffsetDateTime;
 
 #[test]
 fn test_load() {
@@ -636,3 +653,619 @@ fn test_aborted_transaction_indexing() {
     // Add the deployment block to the ledger.
     ledger.advance_to_next_block(&block).unwrap();
 }
+
+#[test]
+fn test_snapshot() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Deploy a test program to the ledger.
+    let program_id = ProgramID::<CurrentNetwork>::from_str("dummy_program.aleo").unwrap();
+    let program = Program::<CurrentNetwork>::from_str(&format!(
+        "
+program {program_id};
+mapping data:
+    key as u8.public;
+    value as u8.public;
+function foo:
+    input r0 as u8.private;
+    async foo r0 into r1;
+    output r1 as {program_id}/foo.future;
+finalize foo:
+    input r0 as u8.public;
+    set r0 into data[r0];",
+    ))
+    .unwrap();
+    let transaction = ledger.vm.deploy(&private_key, &program, None, 0, None, rng).unwrap();
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Advance past the deployment, so that it is not among the recent blocks of the snapshot.
+    for _ in 0..3 {
+        let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![], rng).unwrap();
+        ledger.advance_to_next_block(&block).unwrap();
+    }
+    assert_eq!(ledger.latest_height(), 4);
+
+    // Only the latest block can be snapshotted.
+    assert!(ledger.prepare_snapshot(3, 2).is_err());
+
+    // Write a snapshot with the last 2 blocks in full.
+    let snapshot = ledger.prepare_snapshot(4, 2).unwrap();
+    assert_eq!(snapshot.height(), 4);
+    assert_eq!(snapshot.hash(), ledger.latest_hash());
+    assert_eq!(snapshot.recent_blocks().len(), 2);
+    let directory = std::env::temp_dir().join(format!("snarkvm-snapshot-{}", rng.gen::<u64>()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("snapshot");
+    std::fs::write(&path, snapshot.to_bytes_le().unwrap()).unwrap();
+
+    // Check the snapshot bytes.
+    assert_eq!(snapshot, Snapshot::read_le(&std::fs::read(&path).unwrap()[..]).unwrap());
+
+    let genesis = ledger.get_block(0).unwrap();
+    let (hash, state_root) = (ledger.latest_hash(), ledger.latest_state_root());
+    let checksum = ledger.vm().finalize_store().get_checksum_confirmed().unwrap();
+
+    // Ensure the snapshot is rejected for the wrong trusted block hash, state root, or finalize checksum.
+    let wrong_hash = ledger.get_hash(3).unwrap();
+    let wrong_state_root = ledger.get_state_root(3).unwrap().unwrap();
+    let wrong_checksum = checksum + Field::one();
+    let mut storage = || StorageMode::Custom(directory.join(format!("ledger-{}", rng.gen::<u64>())));
+    assert!(
+        CurrentLedger::load_from_snapshot(genesis.clone(), &path, wrong_hash, state_root, checksum, storage()).is_err()
+    );
+    assert!(
+        CurrentLedger::load_from_snapshot(genesis.clone(), &path, hash, wrong_state_root, checksum, storage()).is_err()
+    );
+    assert!(
+        CurrentLedger::load_from_snapshot(genesis.clone(), &path, hash, state_root, wrong_checksum, storage()).is_err()
+    );
+
+    // Ensure the snapshot is rejected if its finalize operations are tampered with.
+    let mut bytes = std::fs::read(&path).unwrap();
+    *bytes.last_mut().unwrap() ^= 1;
+    let tampered_path = directory.join("tampered");
+    std::fs::write(&tampered_path, bytes).unwrap();
+    assert!(
+        CurrentLedger::load_from_snapshot(genesis.clone(), &tampered_path, hash, state_root, checksum, storage())
+            .is_err()
+    );
+
+    // Load the ledger from the snapshot.
+    let restored_storage = storage();
+    let restored =
+        CurrentLedger::load_from_snapshot(genesis, &path, hash, state_root, checksum, restored_storage.clone())
+            .unwrap();
+    assert_eq!(restored.latest_block(), ledger.latest_block());
+    assert_eq!(restored.latest_state_root(), state_root);
+    assert_eq!(restored.latest_committee().unwrap(), ledger.latest_committee().unwrap());
+    assert_eq!(restored.get_program(program_id).unwrap(), program);
+
+    // Ensure the older blocks are available by their block hash, header, and state root.
+    for height in 0..=4 {
+        assert_eq!(restored.get_hash(height).unwrap(), ledger.get_hash(height).unwrap());
+        assert_eq!(restored.get_header(height).unwrap(), ledger.get_header(height).unwrap());
+        assert_eq!(restored.get_state_root(height).unwrap(), ledger.get_state_root(height).unwrap());
+        assert_eq!(restored.get_committee(height).unwrap(), ledger.get_committee(height).unwrap());
+    }
+    assert!(restored.get_block(1).unwrap_err().downcast_ref::<PrunedError>().is_some());
+
+    // Ensure the mappings are restored.
+    let credits = ProgramID::from_str("credits.aleo").unwrap();
+    let account = Identifier::from_str("account").unwrap();
+    let key = Plaintext::from(Literal::Address(address));
+    assert_eq!(
+        restored.vm().finalize_store().get_value_confirmed(credits, account, &key).unwrap(),
+        ledger.vm().finalize_store().get_value_confirmed(credits, account, &key).unwrap(),
+    );
+    assert_eq!(
+        restored.vm().finalize_store().get_checksum_confirmed().unwrap(),
+        ledger.vm().finalize_store().get_checksum_confirmed().unwrap()
+    );
+
+    // Ensure the restored ledger can be snapshotted in turn.
+    assert_eq!(restored.prepare_snapshot(4, 2).unwrap().checksum(), snapshot.checksum());
+    // Ensure the ledger is not loaded into a non-empty storage.
+    #[cfg(feature = "rocks")]
+    {
+        drop(restored);
+        let genesis = ledger.get_block(0).unwrap();
+        assert!(
+            CurrentLedger::load_from_snapshot(genesis, &path, hash, state_root, checksum, restored_storage).is_err()
+        );
+    }
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_pruning() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Sample a recipient account.
+    let recipient_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let recipient_address = Address::try_from(&recipient_private_key).unwrap();
+
+    // Add a block with a transfer.
+    let inputs = [Value::from_str(&format!("{recipient_address}")).unwrap(), Value::from_str("1000u64").unwrap()];
+    let transaction = ledger
+        .vm
+        .execute(&private_key, ("credits.aleo", "transfer_public"), inputs.iter(), None, 0, None, rng)
+        .unwrap();
+    let transaction_id = transaction.id();
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Add 2 more blocks.
+    for _ in 0..2 {
+        let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![], rng).unwrap();
+        ledger.advance_to_next_block(&block).unwrap();
+    }
+    assert_eq!(ledger.latest_height(), 3);
+    assert_eq!(ledger.pruned_height().unwrap(), None);
+
+    // The pruning depth must keep the latest block.
+    assert!(ledger.enable_pruning(0).is_err());
+    // Enable pruning, which prunes blocks 0 and 1.
+    ledger.enable_pruning(2).unwrap();
+    assert_eq!(ledger.pruning_depth(), Some(2));
+    assert_eq!(ledger.vm.block_store().pruning_depth().unwrap(), Some(2));
+    assert_eq!(ledger.pruned_height().unwrap(), Some(1));
+
+    // Ensure the pruned block and transaction return a pruned error.
+    let error = ledger.get_block(1).unwrap_err();
+    assert_eq!(error.downcast_ref::<PrunedError>(), Some(&PrunedError::new(1)));
+    assert!(ledger.get_block_by_hash(&block.hash()).unwrap_err().downcast_ref::<PrunedError>().is_some());
+    assert!(ledger.get_transactions(1).unwrap_err().downcast_ref::<PrunedError>().is_some());
+    assert!(ledger.get_transaction(transaction_id).unwrap_err().downcast_ref::<PrunedError>().is_some());
+    assert!(ledger.get_confirmed_transaction(transaction_id).unwrap_err().downcast_ref::<PrunedError>().is_some());
+
+    // Ensure the headers, the state paths, and the recent blocks are still available.
+    assert_eq!(ledger.get_header(1).unwrap(), *block.header());
+    assert!(ledger.contains_transaction_id(&transaction_id).unwrap());
+    let genesis = ledger.get_block(0).unwrap();
+    assert_eq!(ledger.get_block_by_hash(&genesis.hash()).unwrap(), genesis);
+    let commitment = genesis.transactions().commitments().next().unwrap();
+    ledger.get_state_path_for_commitment(commitment).unwrap();
+    ledger.get_block(2).unwrap();
+
+    // Ensure the ledger prunes as it advances.
+    let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+    assert_eq!(ledger.pruned_height().unwrap(), Some(2));
+    assert!(ledger.get_block(2).unwrap_err().downcast_ref::<PrunedError>().is_some());
+    ledger.get_block(3).unwrap();
+
+    // Ensure the pruned blocks cannot be removed.
+    let error = ledger.vm.block_store().remove_last_n(3).unwrap_err();
+    assert_eq!(error.downcast_ref::<PrunedError>(), Some(&PrunedError::new(2)));
+
+    // Ensure disabling pruning clears the stored pruning depth.
+    ledger.disable_pruning().unwrap();
+    assert_eq!(ledger.pruning_depth(), None);
+    assert_eq!(ledger.vm.block_store().pruning_depth().unwrap(), None);
+}
+
+#[test]
+fn test_program_calls() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Sample a recipient account.
+    let recipient_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let recipient_address = Address::try_from(&recipient_private_key).unwrap();
+
+    // Add 2 blocks, each with a public transfer.
+    let mut expected = Vec::new();
+    for height in 1..=2 {
+        let inputs = [Value::from_str(&format!("{recipient_address}")).unwrap(), Value::from_str("1000u64").unwrap()];
+        let transaction = ledger
+            .vm
+            .execute(&private_key, ("credits.aleo", "transfer_public"), inputs.iter(), None, 0, None, rng)
+            .unwrap();
+        expected.push((height, transaction.id(), *transaction.transitions().next().unwrap().id()));
+        let block =
+            ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+        ledger.advance_to_next_block(&block).unwrap();
+    }
+
+    let program_id = ProgramID::from_str("credits.aleo").unwrap();
+    let transfer_public = Identifier::from_str("transfer_public").unwrap();
+    let to_tuple = |call: &ProgramCall<CurrentNetwork>| (call.height(), *call.transaction_id(), *call.transition_id());
+
+    // Ensure the calls to the function are returned in height order.
+    let calls = ledger.get_program_calls(&program_id, Some(&transfer_public), None, 10).unwrap();
+    assert_eq!(calls.iter().map(to_tuple).collect::<Vec<_>>(), expected);
+    assert!(calls.iter().all(|call| call.function_name() == &transfer_public));
+
+    // Ensure the calls are paginated with the cursor.
+    let page = ledger.get_program_calls(&program_id, Some(&transfer_public), None, 1).unwrap();
+    assert_eq!(page.iter().map(to_tuple).collect::<Vec<_>>(), &expected[..1]);
+    let page = ledger.get_program_calls(&program_id, Some(&transfer_public), Some(page[0].cursor()), 10).unwrap();
+    assert_eq!(page.iter().map(to_tuple).collect::<Vec<_>>(), &expected[1..]);
+    assert!(
+        ledger.get_program_calls(&program_id, Some(&transfer_public), Some(page[0].cursor()), 10).unwrap().is_empty()
+    );
+
+    // Ensure the calls to all functions of the program include the fees, in height order.
+    let calls = ledger.program_calls(&program_id, None, None).unwrap().map(|call| call.unwrap()).collect::<Vec<_>>();
+    assert!(calls.len() > expected.len());
+    assert!(calls.windows(2).all(|pair| pair[0].cursor() < pair[1].cursor()));
+    assert!(calls.iter().any(|call| call.height() == 2 && call.function_name().to_string() == "fee_public"));
+
+    // Ensure removing the latest block removes its calls.
+    ledger.vm.block_store().remove_last_n(1).unwrap();
+    let calls = ledger.get_program_calls(&program_id, Some(&transfer_public), None, 10).unwrap();
+    assert_eq!(calls.iter().map(to_tuple).collect::<Vec<_>>(), &expected[..1]);
+}
+
+#[test]
+fn test_address_activity() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Sample a recipient account.
+    let recipient_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let recipient_address = Address::try_from(&recipient_private_key).unwrap();
+
+    // Prepares a block with a public transfer to the recipient, and returns the block and the transfer transition ID.
+    let transfer = |rng: &mut TestRng| {
+        let inputs = [Value::from_str(&format!("{recipient_address}")).unwrap(), Value::from_str("1000u64").unwrap()];
+        let transaction = ledger
+            .vm
+            .execute(&private_key, ("credits.aleo", "transfer_public"), inputs.iter(), None, 0, None, rng)
+            .unwrap();
+        let transition_id = *transaction.transitions().next().unwrap().id();
+        let block =
+            ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+        (block, transition_id)
+    };
+
+    // Returns the `(block height, transition ID)` pairs of the given activity.
+    let to_heights = |activity: Vec<(TransitionPosition, _)>| {
+        activity.into_iter().map(|(position, transition_id)| (position.height(), transition_id)).collect::<Vec<_>>()
+    };
+
+    // Add a block with a transfer, before the address index is enabled.
+    let (block, first_transition_id) = transfer(rng);
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure the address index must be enabled to query the activity.
+    assert!(!ledger.is_address_index_enabled().unwrap());
+    assert!(ledger.get_address_activity(&recipient_address, None, 10).is_err());
+
+    // Enable the address index, which indexes the existing blocks.
+    ledger.enable_address_index().unwrap();
+    assert!(ledger.is_address_index_enabled().unwrap());
+    assert_eq!(to_heights(ledger.get_address_activity(&recipient_address, None, 10).unwrap()), vec![(
+        1,
+        first_transition_id
+    )]);
+
+    // Add a block with another transfer, which is indexed on insertion.
+    let (block, second_transition_id) = transfer(rng);
+    ledger.advance_to_next_block(&block).unwrap();
+    assert_eq!(ledger.get_address_activity_count(&recipient_address).unwrap(), 2);
+    assert_eq!(to_heights(ledger.get_address_activity(&recipient_address, None, 10).unwrap()), vec![
+        (1, first_transition_id),
+        (2, second_transition_id)
+    ]);
+
+    // Ensure the activity is paginated with the position of the last transition as the cursor.
+    let first_page = ledger.get_address_activity(&recipient_address, None, 1).unwrap();
+    assert_eq!(to_heights(first_page.clone()), vec![(1, first_transition_id)]);
+    let second_page = ledger.get_address_activity(&recipient_address, Some(first_page[0].0), 1).unwrap();
+    assert_eq!(to_heights(second_page.clone()), vec![(2, second_transition_id)]);
+    assert!(ledger.get_address_activity(&recipient_address, Some(second_page[0].0), 10).unwrap().is_empty());
+
+    // Ensure the sender is indexed for the transfers and the fee payments.
+    let sender_activity = to_heights(ledger.get_address_activity(&address, None, usize::MAX).unwrap());
+    assert!(sender_activity.contains(&(1, first_transition_id)));
+    assert!(sender_activity.contains(&(2, second_transition_id)));
+    assert!(sender_activity.iter().filter(|(height, _)| *height == 2).count() >= 2);
+
+    // Ensure removing the latest block removes its activity.
+    ledger.vm.block_store().remove_last_n(1).unwrap();
+    assert_eq!(to_heights(ledger.get_address_activity(&recipient_address, None, 10).unwrap()), vec![(
+        1,
+        first_transition_id
+    )]);
+    let sender_activity = ledger.get_address_activity(&address, None, usize::MAX).unwrap();
+    assert!(sender_activity.iter().all(|(position, _)| position.height() < 2));
+    // Ensure the sender's activity is in ledger order.
+    assert!(sender_activity.windows(2).all(|pair| pair[0].0 < pair[1].0));
+}
+
+#[test]
+fn test_staking_queries() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Ensure the genesis validator is in the committee, and bonded to itself.
+    let validator_state = ledger.get_validator_state(&address).unwrap().unwrap();
+    assert!(validator_state.is_open());
+    assert_eq!(validator_state.commission(), 0);
+    assert!(validator_state.microcredits() >= ledger_committee::MIN_VALIDATOR_STAKE);
+    assert_eq!(ledger.get_bond_state(&address).unwrap().unwrap().validator(), address);
+    assert!(ledger.get_unbond_state(&address).unwrap().is_none());
+    // Ensure the validator's own bond is not a delegation.
+    assert!(ledger.get_delegators(&address).unwrap().is_empty());
+    assert_eq!(ledger.get_total_delegated(&address).unwrap(), 0);
+
+    // Sample a delegator.
+    let delegator_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let delegator_address = Address::try_from(&delegator_private_key).unwrap();
+
+    // Executes the given `credits.aleo` function, and adds a block with the transaction to the ledger.
+    let execute = |private_key: &PrivateKey<CurrentNetwork>,
+                   function_name: &str,
+                   inputs: Vec<String>,
+                   rng: &mut TestRng| {
+        let inputs = inputs.iter().map(|input| Value::<CurrentNetwork>::from_str(input).unwrap()).collect::<Vec<_>>();
+        let transaction =
+            ledger.vm.execute(private_key, ("credits.aleo", function_name), inputs.iter(), None, 0, None, rng).unwrap();
+        let block =
+            ledger.prepare_advance_to_next_beacon_block(private_key, vec![], vec![], vec![transaction], rng).unwrap();
+        ledger.advance_to_next_block(&block).unwrap();
+    };
+
+    // Fund the delegator, and bond 50 credits to the validator.
+    execute(&private_key, "transfer_public", vec![delegator_address.to_string(), "100000000u64".to_string()], rng);
+    execute(&delegator_private_key, "bond_public", vec![address.to_string(), "50000000u64".to_string()], rng);
+
+    // Ensure the delegator is bonded to the validator.
+    let bond_state = ledger.get_bond_state(&delegator_address).unwrap().unwrap();
+    assert_eq!(bond_state.validator(), address);
+    assert!(bond_state.microcredits() >= 50_000_000);
+    assert!(ledger.get_validator_state(&delegator_address).unwrap().is_none());
+    // Ensure the delegator is indexed for the validator.
+    let delegators = ledger.get_delegators(&address).unwrap();
+    assert_eq!(delegators, indexmap::indexmap! { delegator_address => bond_state.microcredits() });
+    assert_eq!(ledger.get_total_delegated(&address).unwrap(), bond_state.microcredits());
+
+    // Unbond the entire bond of the delegator.
+    let unbond_amount = bond_state.microcredits();
+    execute(&delegator_private_key, "unbond_public", vec![format!("{unbond_amount}u64")], rng);
+    let unbond_height = ledger.latest_height();
+
+    // Ensure the delegator is unbonding, and no longer indexed for the validator.
+    assert!(ledger.get_bond_state(&delegator_address).unwrap().is_none());
+    assert!(ledger.get_delegators(&address).unwrap().is_empty());
+    assert_eq!(ledger.get_total_delegated(&address).unwrap(), 0);
+    let unbond_state = ledger.get_unbond_state(&delegator_address).unwrap().unwrap();
+    assert_eq!(unbond_state.microcredits(), unbond_amount);
+    assert_eq!(unbond_state.claimable_height(), unbond_height + 360);
+    assert!(!unbond_state.is_claimable(unbond_height));
+    assert!(unbond_state.is_claimable(unbond_height + 360));
+
+    // Ensure the incrementally-updated index matches a rebuilt index.
+    let index = ledger.staking_index.read().clone();
+    ledger.rebuild_staking_index().unwrap();
+    assert_eq!(*ledger.staking_index.read(), index);
+}
+
+#[test]
+fn test_slash_validator() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+    // Note: The genesis validators are bonded with the minimum stake, so the slash removes the validator.
+    let validator_state = ledger.get_validator_state(&address).unwrap().unwrap();
+    assert_eq!(validator_state.microcredits(), MIN_VALIDATOR_STAKE);
+
+    // Sample equivocation evidence from the validator, for the round of the next block.
+    let evidence = ledger_narwhal::evidence::test_helpers::sample_equivocation_evidence_for_round(&private_key, 1, rng);
+    let slash = Ratify::Slash(Box::new(evidence));
+
+    // Ensure ratifications other than slashes are rejected.
+    assert!(
+        ledger
+            .prepare_advance_to_next_beacon_block(&private_key, vec![Ratify::BlockReward(0)], vec![], vec![], rng)
+            .is_err()
+    );
+
+    // Construct the next block, with the slash.
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![slash.clone()], vec![], vec![], rng).unwrap();
+    assert!(block.ratifications().iter().any(|ratify| ratify == &slash));
+    // Check and add the block to the ledger.
+    ledger.check_next_block(&block, rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure the validator is removed from the committee, and its remaining bond is unbonding.
+    assert!(ledger.get_validator_state(&address).unwrap().is_none());
+    assert!(ledger.get_bond_state(&address).unwrap().is_none());
+    let unbond_state = ledger.get_unbond_state(&address).unwrap().unwrap();
+    assert!(unbond_state.microcredits() < MIN_VALIDATOR_STAKE);
+
+    // Ensure the same offense cannot be slashed again.
+    assert!(ledger.prepare_advance_to_next_beacon_block(&private_key, vec![slash], vec![], vec![], rng).is_err());
+}
+
+#[test]
+fn test_check_next_block_with_reputation_election() {
+    let rng = &mut TestRng::default();
+
+    // Sample the validators.
+    let private_keys = (0..4).map(|_| PrivateKey::<CurrentNetwork>::new(rng).unwrap()).collect::<Vec<_>>();
+    let addresses = private_keys.iter().map(|private_key| Address::try_from(private_key).unwrap()).collect::<Vec<_>>();
+    // Construct the committee and the public balances.
+    let members = addresses.iter().map(|address| (*address, (MIN_VALIDATOR_STAKE, true, 0u8))).collect();
+    let committee = Committee::<CurrentNetwork>::new_genesis(members).unwrap();
+    let remaining_supply = CurrentNetwork::STARTING_SUPPLY - MIN_VALIDATOR_STAKE * 4;
+    let public_balances = addresses.iter().map(|address| (*address, remaining_supply / 4)).collect();
+
+    // Initialize the ledger.
+    let store = ConsensusStore::<_, ConsensusMemory<_>>::open(None).unwrap();
+    let genesis = VM::from(store).unwrap().genesis_quorum(&private_keys[0], committee, public_balances, rng).unwrap();
+    let ledger = CurrentLedger::load(genesis, None).unwrap();
+    // Ensure the leaders are elected with reputation.
+    assert_eq!(ledger.election_mode(), ElectionMode::Reputation);
+
+    // Returns the next quorum block, whose subdag is the leader certificate of the given leader for the given round.
+    let sample_quorum_block = |leader: Address<CurrentNetwork>, round: u64, rng: &mut TestRng| {
+        let private_key = &private_keys[addresses.iter().position(|address| *address == leader).unwrap()];
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        let previous_certificate_ids = indexset![Field::rand(rng)];
+        let batch_header =
+            BatchHeader::new(private_key, round, timestamp, Default::default(), previous_certificate_ids, rng).unwrap();
+        let signatures = private_keys
+            .iter()
+            .filter(|signer| *signer != private_key)
+            .map(|signer| signer.sign(&[batch_header.batch_id()], rng).unwrap())
+            .collect();
+        let certificate = BatchCertificate::from(batch_header, signatures).unwrap();
+        let subdag = Subdag::from(BTreeMap::from([(round, indexset![certificate])])).unwrap();
+        ledger.prepare_advance_to_next_quorum_block(subdag, Default::default(), vec![]).unwrap()
+    };
+
+    // Skip a leader round before each block, until a validator is demoted and is elected leader by stake.
+    for _ in 0..25 {
+        let round = ledger.latest_round() + 4;
+        let committee = ledger.latest_committee().unwrap();
+        let leader = committee.get_leader_with_reputation(round, &ledger.get_leader_reputation()).unwrap();
+        let stake_leader = committee.get_leader(round).unwrap();
+
+        if leader != stake_leader {
+            // Ensure a block led by the leader elected by stake is rejected.
+            let block = sample_quorum_block(stake_leader, round, rng);
+            let error = ledger.check_next_block(&block, rng).unwrap_err();
+            assert!(error.to_string().contains("unexpected leader"), "{error}");
+
+            // Ensure a block led by the leader elected with reputation is accepted.
+            let block = sample_quorum_block(leader, round, rng);
+            ledger.check_next_block(&block, rng).unwrap();
+            ledger.advance_to_next_block(&block).unwrap();
+
+            // Ensure the incrementally-updated reputation matches a rebuilt reputation.
+            let reputation = ledger.get_leader_reputation();
+            ledger.rebuild_leader_reputation().unwrap();
+            assert_eq!(ledger.get_leader_reputation(), reputation);
+            return;
+        }
+
+        // Advance the ledger with a block led by the elected leader.
+        let block = sample_quorum_block(leader, round, rng);
+        ledger.check_next_block(&block, rng).unwrap();
+        ledger.advance_to_next_block(&block).unwrap();
+    }
+    panic!("No demoted validator was elected leader by stake");
+}
+
+#[test]
+fn test_replay_block() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Deploy a test program to the ledger.
+    let program_id = "test_replay.aleo";
+    let program = Program::<CurrentNetwork>::from_str(&format!(
+        "
+program {program_id};
+
+mapping counter:
+    key as u8.public;
+    value as u64.public;
+
+function increment:
+    async increment into r0;
+    output r0 as {program_id}/increment.future;
+
+finalize increment:
+    get.or_use counter[0u8] 0u64 into r0;
+    add r0 1u64 into r1;
+    set r1 into counter[0u8];
+
+function failed_assert:
+    async failed_assert into r0;
+    output r0 as {program_id}/failed_assert.future;
+
+finalize failed_assert:
+    assert.eq false true;"
+    ))
+    .unwrap();
+    let transaction = ledger.vm().deploy(&private_key, &program, None, 0, None, rng).unwrap();
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Construct a block with an accepted and a rejected execution.
+    let mut execute = |function_name: &str| {
+        let inputs = Vec::<Value<_>>::new().into_iter();
+        ledger.vm().execute(&private_key, (program_id, function_name), inputs, None, 0, None, rng).unwrap()
+    };
+    let transactions = vec![execute("increment"), execute("failed_assert")];
+    let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], transactions, rng).unwrap();
+
+    // Replay the block on top of the latest block.
+    let report = ledger.replay_next_block(&block).unwrap();
+    assert!(report.is_match(), "{report}");
+    assert_eq!(report.height(), 2);
+    assert_eq!(report.block_hash(), block.hash());
+    assert_eq!(report.transactions().len(), 2);
+    assert_eq!(report.mismatches().count(), 0);
+    // Ensure the replay did not change the ledger.
+    assert_eq!(ledger.latest_height(), 1);
+
+    // Ensure the accepted execution traces the mapping read and write, followed by the public fee.
+    let accepted = &report.transactions()[0];
+    assert!(accepted.expected().is_accepted());
+    let key = Plaintext::from_str("0u8").unwrap();
+    let counter = Identifier::from_str("counter").unwrap();
+    let program_id = ProgramID::from_str(program_id).unwrap();
+    let accesses = accepted.trace().iter().filter_map(|event| event.access()).cloned().collect::<Vec<_>>();
+    assert_eq!(accesses[0], MappingAccess::Get(program_id, counter, key.clone(), None));
+    assert_eq!(accesses[1], MappingAccess::Set(program_id, counter, key, Value::from_str("1u64").unwrap()));
+    let operations = accepted.trace().iter().filter_map(|event| event.operation()).copied().collect::<Vec<_>>();
+    assert_eq!(&operations, accepted.expected().finalize_operations());
+    assert!(accepted.trace().iter().all(|event| !event.is_error()));
+
+    // Ensure the rejected execution traces the failed command, followed by the public fee.
+    let rejected = &report.transactions()[1];
+    assert!(rejected.expected().is_rejected());
+    assert!(rejected.trace().iter().any(|event| event.is_error()));
+    assert!(matches!(rejected.trace().last(), Some(FinalizeTraceEvent::Command { .. })));
+
+    // Ensure a tampered transaction is reported as a mismatch.
+    let tampered = ConfirmedTransaction::accepted_execute(
+        accepted.expected().index(),
+        accepted.expected().transaction().clone(),
+        vec![],
+    )
+    .unwrap();
+    let tampered = TransactionReplay::new(tampered, accepted.replayed().cloned(), vec![]);
+    assert!(!tampered.is_match());
+    assert_eq!(tampered.mismatched_operations().len(), operations.len());
+
+    // Advance the ledger, and ensure replaying the block from genesis reproduces the same report.
+    ledger.advance_to_next_block(&block).unwrap();
+    assert_eq!(ledger.replay_block(2).unwrap(), report);
+    assert!(ledger.replay_block(1).unwrap().is_match());
+
+    // Ensure invalid heights and blocks are rejected.
+    assert!(ledger.replay_block(0).is_err());
+    assert!(ledger.replay_block(3).is_err());
+    assert!(ledger.replay_next_block(&block).is_err());
+
+    // Ensure the replay is rejected once a block is pruned.
+    ledger.prune(1).unwrap();
+    let error = ledger.replay_block(2).unwrap_err();
+    assert_eq!(error.downcast_ref::<PrunedError>(), Some(&PrunedError::new(1)));
+}
@@ -16,15 +16,28 @@ use crate::{
     atomic_batch_scope,
     cow_to_cloned,
     cow_to_copied,
-    helpers::{Map, MapRead},
+    helpers::{
+        BlockMap,
+        ConsistencyReport,
+        Map,
+        MapID,
+        MapRead,
+        NestedMap,
+        NestedMapRead,
+        PrunedError,
+        StorageMode,
+        StorageStats,
+        TransitionPosition,
+    },
     TransactionStorage,
     TransactionStore,
     TransitionStorage,
     TransitionStore,
 };
 use console::{
+    account::Address,
     network::prelude::*,
-    program::{BlockTree, HeaderLeaf, ProgramID, StatePath},
+    program::{Argument, BlockTree, Future, HeaderLeaf, Literal, Plaintext, ProgramID, StatePath, TRANSACTIONS_DEPTH},
     types::Field,
 };
 use ledger_authority::Authority;
@@ -32,7 +45,9 @@ use ledger_block::{
     Block,
     ConfirmedTransaction,
     Header,
+    Input,
     NumFinalizeSize,
+    Output,
     Ratifications,
     Rejected,
     Transaction,
@@ -43,12 +58,17 @@ use ledger_narwhal_batch_certificate::BatchCertificate;
 use synthesizer_program::Program;
 
 use anyhow::Result;
+use indexmap::IndexSet;
 use parking_lot::RwLock;
 use std::{borrow::Cow, io::Cursor, sync::Arc};
 
 #[cfg(not(feature = "serial"))]
 use rayon::prelude::*;
 
+const PRUNED_HEIGHT_KEY: u8 = 0;
+const PRUNING_DEPTH_KEY: u8 = 1;
+const ADDRESS_INDEX_KEY: u8 = 0;
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ConfirmedTxType {
     /// A deploy transaction that was accepted.
@@ -167,6 +187,58 @@ fn to_confirmed_transaction<N: Network>(
     }
 }
 
+/// Returns the `(address, transition position, transition ID)` triples for the addresses
+/// that appear in the public inputs and outputs of the given transactions, including the payers of public fees.
+fn to_address_activity<N: Network>(
+    height: u32,
+    transactions: &Transactions<N>,
+) -> Vec<(Address<N>, TransitionPosition, N::TransitionID)> {
+    /// Collects the addresses in the given plaintext.
+    fn collect_plaintext<N: Network>(plaintext: &Plaintext<N>, addresses: &mut IndexSet<Address<N>>) {
+        match plaintext {
+            Plaintext::Literal(Literal::Address(address), _) => {
+                addresses.insert(*address);
+            }
+            Plaintext::Literal(..) => (),
+            Plaintext::Struct(members, _) => members.values().for_each(|member| collect_plaintext(member, addresses)),
+            Plaintext::Array(elements, _) => elements.iter().for_each(|element| collect_plaintext(element, addresses)),
+        }
+    }
+
+    /// Collects the addresses in the given future, which includes the payer of a public fee.
+    fn collect_future<N: Network>(future: &Future<N>, addresses: &mut IndexSet<Address<N>>) {
+        for argument in future.arguments() {
+            match argument {
+                Argument::Plaintext(plaintext) => collect_plaintext(plaintext, addresses),
+                Argument::Future(future) => collect_future(future, addresses),
+            }
+        }
+    }
+
+    let mut activity = Vec::new();
+    for (index, transition) in (0u32..).zip(transactions.transitions()) {
+        // Collect the addresses in the public inputs and outputs.
+        let mut addresses = IndexSet::new();
+        for input in transition.inputs() {
+            if let Input::Constant(_, Some(plaintext)) | Input::Public(_, Some(plaintext)) = input {
+                collect_plaintext(plaintext, &mut addresses);
+            }
+        }
+        for output in transition.outputs() {
+            match output {
+                Output::Constant(_, Some(plaintext)) | Output::Public(_, Some(plaintext)) => {
+                    collect_plaintext(plaintext, &mut addresses)
+                }
+                Output::Future(_, Some(future)) => collect_future(future, &mut addresses),
+                _ => (),
+            }
+        }
+        let position = TransitionPosition::new(height, index);
+        activity.extend(addresses.into_iter().map(|address| (address, position, *transition.id())));
+    }
+    activity
+}
+
 /// A trait for block storage.
 pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
     /// The mapping of `block height` to `state root`.
@@ -201,13 +273,19 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
     type ConfirmedTransactionsMap: for<'a> Map<'a, N::TransactionID, (N::BlockHash, ConfirmedTxType, Vec<u8>)>;
     /// The rejected deployment or execution map.
     type RejectedDeploymentOrExecutionMap: for<'a> Map<'a, Field<N>, Rejected<N>>;
+    /// The mapping of `pruned height key` to `pruned height`, and of `pruning depth key` to `pruning depth`.
+    type PrunedHeightMap: for<'a> Map<'a, u8, u32>;
+    /// The mapping of `address` to `transition position` to `transition ID`, for the public activity of each address.
+    type AddressActivityMap: for<'a> NestedMap<'a, Address<N>, TransitionPosition, N::TransitionID>;
+    /// The mapping of `()` to whether the address index is enabled.
+    type AddressIndexMap: for<'a> Map<'a, u8, bool>;
     /// The transaction storage.
     type TransactionStorage: TransactionStorage<N, TransitionStorage = Self::TransitionStorage>;
     /// The transition storage.
     type TransitionStorage: TransitionStorage<N>;
 
     /// Initializes the block storage.
-    fn open(dev: Option<u16>) -> Result<Self>;
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self>;
 
     /// Returns the state root map.
     fn state_root_map(&self) -> &Self::StateRootMap;
@@ -239,6 +317,12 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
     fn confirmed_transactions_map(&self) -> &Self::ConfirmedTransactionsMap;
     /// Returns the rejected deployment or execution map.
     fn rejected_deployment_or_execution_map(&self) -> &Self::RejectedDeploymentOrExecutionMap;
+    /// Returns the pruned height map.
+    fn pruned_height_map(&self) -> &Self::PrunedHeightMap;
+    /// Returns the address activity map.
+    fn address_activity_map(&self) -> &Self::AddressActivityMap;
+    /// Returns the address index map.
+    fn address_index_map(&self) -> &Self::AddressIndexMap;
     /// Returns the transaction store.
     fn transaction_store(&self) -> &TransactionStore<N, Self::TransactionStorage>;
 
@@ -246,10 +330,10 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
     fn transition_store(&self) -> &TransitionStore<N, Self::TransitionStorage> {
         self.transaction_store().transition_store()
     }
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16> {
-        debug_assert!(self.transaction_store().dev() == self.transition_store().dev());
-        self.transition_store().dev()
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        debug_assert!(self.transaction_store().storage_mode() == self.transition_store().storage_mode());
+        self.transition_store().storage_mode()
     }
 
     /// Starts an atomic batch write operation.
@@ -269,6 +353,9 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().start_atomic();
         self.confirmed_transactions_map().start_atomic();
         self.rejected_deployment_or_execution_map().start_atomic();
+        self.pruned_height_map().start_atomic();
+        self.address_activity_map().start_atomic();
+        self.address_index_map().start_atomic();
         self.transaction_store().start_atomic();
     }
 
@@ -289,6 +376,9 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
             || self.rejected_or_aborted_transaction_id_map().is_atomic_in_progress()
             || self.confirmed_transactions_map().is_atomic_in_progress()
             || self.rejected_deployment_or_execution_map().is_atomic_in_progress()
+            || self.pruned_height_map().is_atomic_in_progress()
+            || self.address_activity_map().is_atomic_in_progress()
+            || self.address_index_map().is_atomic_in_progress()
             || self.transaction_store().is_atomic_in_progress()
     }
 
@@ -309,6 +399,9 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().atomic_checkpoint();
         self.confirmed_transactions_map().atomic_checkpoint();
         self.rejected_deployment_or_execution_map().atomic_checkpoint();
+        self.pruned_height_map().atomic_checkpoint();
+        self.address_activity_map().atomic_checkpoint();
+        self.address_index_map().atomic_checkpoint();
         self.transaction_store().atomic_checkpoint();
     }
 
@@ -329,6 +422,9 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().clear_latest_checkpoint();
         self.confirmed_transactions_map().clear_latest_checkpoint();
         self.rejected_deployment_or_execution_map().clear_latest_checkpoint();
+        self.pruned_height_map().clear_latest_checkpoint();
+        self.address_activity_map().clear_latest_checkpoint();
+        self.address_index_map().clear_latest_checkpoint();
         self.transaction_store().clear_latest_checkpoint();
     }
 
@@ -349,6 +445,9 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().atomic_rewind();
         self.confirmed_transactions_map().atomic_rewind();
         self.rejected_deployment_or_execution_map().atomic_rewind();
+        self.pruned_height_map().atomic_rewind();
+        self.address_activity_map().atomic_rewind();
+        self.address_index_map().atomic_rewind();
         self.transaction_store().atomic_rewind();
     }
 
@@ -369,6 +468,9 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().abort_atomic();
         self.confirmed_transactions_map().abort_atomic();
         self.rejected_deployment_or_execution_map().abort_atomic();
+        self.pruned_height_map().abort_atomic();
+        self.address_activity_map().abort_atomic();
+        self.address_index_map().abort_atomic();
         self.transaction_store().abort_atomic();
     }
 
@@ -389,6 +491,9 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().finish_atomic()?;
         self.confirmed_transactions_map().finish_atomic()?;
         self.rejected_deployment_or_execution_map().finish_atomic()?;
+        self.pruned_height_map().finish_atomic()?;
+        self.address_activity_map().finish_atomic()?;
+        self.address_index_map().finish_atomic()?;
         self.transaction_store().finish_atomic()
     }
 
@@ -478,10 +583,268 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
                 self.transaction_store().insert(&transaction)?;
             }
 
+            // Store the program calls.
+            for (index, transition) in (0u32..).zip(block.transactions().transitions()) {
+                self.transition_store().insert_call(transition, block.height(), index)?;
+            }
+
+            // Store the address activity, if the address index is enabled.
+            if self.is_address_index_enabled()? {
+                for (address, position, transition_id) in to_address_activity(block.height(), block.transactions()) {
+                    self.address_activity_map().insert(address, position, transition_id)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Stores the given `(block hash, block header, state root)` triples into storage, without the rest of their blocks.
+    fn insert_headers(&self, entries: &[(N::BlockHash, Header<N>, N::StateRoot)]) -> Result<()> {
+        atomic_batch_scope!(self, {
+            for (block_hash, header, state_root) in entries {
+                // Store the (block height, state root) pair.
+                self.state_root_map().insert(header.height(), *state_root)?;
+                // Store the (state root, block height) pair.
+                self.reverse_state_root_map().insert(*state_root, header.height())?;
+
+                // Store the block hash.
+                self.id_map().insert(header.height(), *block_hash)?;
+                // Store the block height.
+                self.reverse_id_map().insert(*block_hash, header.height())?;
+                // Store the block header.
+                self.header_map().insert(*block_hash, *header)?;
+            }
+            // Mark the blocks as pruned, as their transactions are not in storage.
+            if let Some((_, header, _)) = entries.last() {
+                self.pruned_height_map().insert(PRUNED_HEIGHT_KEY, header.height())?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Prunes the transaction bodies of the block at the given `height`, which must be the next unpruned block.
+    fn prune(&self, height: u32) -> Result<()> {
+        // Ensure the block height is the next block height to prune.
+        let next_height = match self.get_pruned_height()? {
+            Some(pruned_height) => pruned_height.saturating_add(1),
+            None => 0,
+        };
+        ensure!(height == next_height, "Failed to prune block {height}: the next block to prune is {next_height}");
+        // Retrieve the block hash.
+        let block_hash = match self.get_block_hash(height)? {
+            Some(block_hash) => block_hash,
+            None => bail!("Failed to prune block {height}: missing block hash"),
+        };
+        // Retrieve the transaction IDs.
+        let transaction_ids = match self.transactions_map().get_confirmed(&block_hash)? {
+            Some(transaction_ids) => transaction_ids,
+            None => bail!("Failed to prune block {height}: missing transactions for block '{block_hash}'"),
+        };
+
+        atomic_batch_scope!(self, {
+            // Prune the transactions.
+            for transaction_id in transaction_ids.iter() {
+                self.transaction_store().prune(transaction_id)?;
+            }
+            // Store the pruned height.
+            self.pruned_height_map().insert(PRUNED_HEIGHT_KEY, height)?;
+
             Ok(())
         })
     }
 
+    /// Records the statistics of the block maps and the transaction store in the given stats.
+    fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        stats.record_map(MapID::Block(BlockMap::StateRoot), self.state_root_map())?;
+        stats.record_map(MapID::Block(BlockMap::ReverseStateRoot), self.reverse_state_root_map())?;
+        stats.record_map(MapID::Block(BlockMap::ID), self.id_map())?;
+        stats.record_map(MapID::Block(BlockMap::ReverseID), self.reverse_id_map())?;
+        stats.record_map(MapID::Block(BlockMap::Header), self.header_map())?;
+        stats.record_map(MapID::Block(BlockMap::Authority), self.authority_map())?;
+        stats.record_map(MapID::Block(BlockMap::Certificate), self.certificate_map())?;
+        stats.record_map(MapID::Block(BlockMap::Ratifications), self.ratifications_map())?;
+        stats.record_map(MapID::Block(BlockMap::Solutions), self.solutions_map())?;
+        stats.record_map(MapID::Block(BlockMap::PuzzleCommitments), self.puzzle_commitments_map())?;
+        stats.record_map(MapID::Block(BlockMap::Transactions), self.transactions_map())?;
+        stats.record_map(MapID::Block(BlockMap::AbortedTransactionIDs), self.aborted_transaction_ids_map())?;
+        stats.record_map(
+            MapID::Block(BlockMap::RejectedOrAbortedTransactionID),
+            self.rejected_or_aborted_transaction_id_map(),
+        )?;
+        stats.record_map(MapID::Block(BlockMap::ConfirmedTransactions), self.confirmed_transactions_map())?;
+        stats.record_map(
+            MapID::Block(BlockMap::RejectedDeploymentOrExecution),
+            self.rejected_deployment_or_execution_map(),
+        )?;
+        stats.record_map(MapID::Block(BlockMap::PrunedHeight), self.pruned_height_map())?;
+        stats.record_nested_map(MapID::Block(BlockMap::AddressActivity), self.address_activity_map())?;
+        stats.record_map(MapID::Block(BlockMap::AddressIndex), self.address_index_map())?;
+        self.transaction_store().record_stats(stats)?;
+        Ok(())
+    }
+
+    /// Checks that the block maps agree with each other and with the transaction store,
+    /// recording any inconsistencies in the given report. If `repair` is `true`, the derived indexes are repaired.
+    fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        // Retrieve the pruned height, as the blocks restored from a snapshot only have their headers.
+        let pruned_height = self.get_pruned_height()?;
+
+        atomic_batch_scope!(self, {
+            for (height, block_hash) in self.id_map().iter_confirmed() {
+                let (height, block_hash) = (cow_to_copied!(height), cow_to_copied!(block_hash));
+                report.add_block();
+
+                // Ensure the block height and state root are indexed.
+                report.check_index("reverse block ID", self.reverse_id_map(), block_hash, height, repair)?;
+                match self.state_root_map().get_confirmed(&height)? {
+                    Some(root) => report.check_index(
+                        "reverse state root",
+                        self.reverse_state_root_map(),
+                        *root,
+                        height,
+                        repair,
+                    )?,
+                    None => report.found("state root", format!("Block {height} is missing its state root")),
+                }
+                // Ensure the block header exists, and is for this block height.
+                let header = match self.header_map().get_confirmed(&block_hash)? {
+                    Some(header) => cow_to_copied!(header),
+                    None => {
+                        report.found("header", format!("Block {height} is missing its header"));
+                        continue;
+                    }
+                };
+                if header.height() != height {
+                    report.found("header", format!("Block {height} has the header of block {}", header.height()));
+                }
+
+                // Retrieve the transaction IDs.
+                let transaction_ids = match self.transactions_map().get_confirmed(&block_hash)? {
+                    Some(transaction_ids) => cow_to_cloned!(transaction_ids),
+                    None => {
+                        if pruned_height.map_or(true, |pruned_height| height > pruned_height) {
+                            report.found("transactions", format!("Block {height} is missing its transactions"));
+                        }
+                        continue;
+                    }
+                };
+                // Ensure the transaction IDs match the transactions root.
+                let leaves = transaction_ids.iter().map(|id| id.to_bits_le()).collect::<Vec<_>>();
+                if *N::merkle_tree_bhp::<TRANSACTIONS_DEPTH>(&leaves)?.root() != header.transactions_root() {
+                    report.found("transactions", format!("Block {height} does not match its transactions root"));
+                }
+                // Ensure the transactions belong to the block, and exist in the transaction store.
+                for transaction_id in &transaction_ids {
+                    match self.confirmed_transactions_map().get_confirmed(transaction_id)? {
+                        Some(confirmed) if confirmed.0 == block_hash => (),
+                        Some(_) => report
+                            .found("confirmed", format!("Transaction '{transaction_id}' belongs to another block")),
+                        None => report.found("confirmed", format!("Transaction '{transaction_id}' is not confirmed")),
+                    }
+                    if !self.transaction_store().contains_transaction_id(transaction_id)? {
+                        report.found(
+                            "transaction",
+                            format!("Transaction '{transaction_id}' of block {height} is missing"),
+                        );
+                    }
+                }
+                // Ensure the program calls are indexed, if the transactions have not been pruned.
+                if pruned_height.map_or(true, |pruned_height| height > pruned_height) {
+                    if let Some(transactions) = self.get_block_transactions(&block_hash)? {
+                        for (index, transition) in (0u32..).zip(transactions.transitions()) {
+                            let position = TransitionPosition::new(height, index);
+                            let transition_store = self.transition_store();
+                            let (program_id, function_name) = (transition.program_id(), transition.function_name());
+                            let message = match transition_store.get_call(program_id, function_name, position)? {
+                                Some(existing) if existing == *transition.id() => continue,
+                                Some(_) => format!("'{}' maps to an incorrect value", transition.id()),
+                                None => format!("'{}' is missing", transition.id()),
+                            };
+                            if repair {
+                                transition_store.insert_call(transition, height, index)?;
+                            }
+                            report.record("call", message, repair);
+                        }
+                    }
+                }
+
+                // Ensure the authority exists, and its certificates are indexed.
+                match self.authority_map().get_confirmed(&block_hash)? {
+                    Some(authority) => {
+                        if let Authority::Quorum(subdag) = &*authority {
+                            for (round, certificates) in subdag.iter() {
+                                for certificate in certificates {
+                                    let value = (height, *round);
+                                    report.check_index(
+                                        "certificate",
+                                        self.certificate_map(),
+                                        certificate.id(),
+                                        value,
+                                        repair,
+                                    )?;
+                                }
+                            }
+                        }
+                    }
+                    None => report.found("authority", format!("Block {height} is missing its authority")),
+                }
+                // Ensure the ratifications exist.
+                if !self.ratifications_map().contains_key_confirmed(&block_hash)? {
+                    report.found("ratifications", format!("Block {height} is missing its ratifications"));
+                }
+                // Ensure the solutions exist, and their puzzle commitments are indexed.
+                match self.solutions_map().get_confirmed(&block_hash)? {
+                    Some(solutions) => {
+                        if let Some(solutions) = &*solutions {
+                            for puzzle_commitment in solutions.keys() {
+                                let map = self.puzzle_commitments_map();
+                                report.check_index("puzzle commitment", map, *puzzle_commitment, height, repair)?;
+                            }
+                        }
+                    }
+                    None => report.found("solutions", format!("Block {height} is missing its solutions")),
+                }
+                // Ensure the aborted transaction IDs exist, and are indexed.
+                match self.aborted_transaction_ids_map().get_confirmed(&block_hash)? {
+                    Some(aborted_ids) => {
+                        for aborted_id in aborted_ids.iter() {
+                            let map = self.rejected_or_aborted_transaction_id_map();
+                            report.check_index("aborted transaction ID", map, *aborted_id, block_hash, repair)?;
+                        }
+                    }
+                    None => report.found("aborted", format!("Block {height} is missing its aborted transaction IDs")),
+                }
+            }
+
+            // Ensure every reverse block ID and reverse state root belongs to its block.
+            for (block_hash, height) in self.reverse_id_map().iter_confirmed().collect::<Vec<_>>() {
+                if self.get_block_hash(*height)?.as_ref() != Some(&*block_hash) {
+                    report.remove_dangling("reverse block ID", self.reverse_id_map(), *block_hash, repair)?;
+                }
+            }
+            for (state_root, height) in self.reverse_state_root_map().iter_confirmed().collect::<Vec<_>>() {
+                let is_valid = match self.state_root_map().get_confirmed(&height)? {
+                    Some(root) => *root == *state_root,
+                    None => false,
+                };
+                if !is_valid {
+                    report.remove_dangling("reverse state root", self.reverse_state_root_map(), *state_root, repair)?;
+                }
+            }
+            // Ensure every confirmed transaction belongs to a block.
+            for (transaction_id, confirmed) in self.confirmed_transactions_map().iter_confirmed() {
+                if !self.reverse_id_map().contains_key_confirmed(&confirmed.0)? {
+                    report.found("confirmed", format!("Transaction '{}' belongs to a missing block", *transaction_id));
+                }
+            }
+
+            // Check the transactions.
+            self.transaction_store().check_consistency(repair, report)
+        })
+    }
+
     /// Removes the block for the given `block hash`.
     fn remove(&self, block_hash: &N::BlockHash) -> Result<()> {
         // Retrieve the block height.
@@ -513,8 +876,11 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
             None => Vec::new(),
         };
 
+        // Retrieve the block transactions.
+        let transactions = self.get_block_transactions(block_hash)?;
+
         // Retrieve the rejected transaction IDs, and the deployment or execution ID.
-        let rejected_transaction_ids_and_deployment_or_execution_id = match self.get_block_transactions(block_hash)? {
+        let rejected_transaction_ids_and_deployment_or_execution_id = match &transactions {
             Some(transactions) => transactions
                 .iter()
                 .filter(|tx| tx.is_rejected())
@@ -523,6 +889,12 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
             None => Vec::new(),
         };
 
+        // Retrieve the address activity, if the address index is enabled.
+        let address_activity = match (&transactions, self.is_address_index_enabled()?) {
+            (Some(transactions), true) => to_address_activity(block_height, transactions),
+            _ => Vec::new(),
+        };
+
         // Determine the certificate IDs to remove.
         let certificate_ids_to_remove = match self.authority_map().get_confirmed(block_hash)? {
             Some(authority) => match authority {
@@ -595,6 +967,18 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
                 self.transaction_store().remove(transaction_id)?;
             }
 
+            // Remove the program calls.
+            if let Some(transactions) = &transactions {
+                for (index, transition) in (0u32..).zip(transactions.transitions()) {
+                    self.transition_store().remove_call(transition, block_height, index)?;
+                }
+            }
+
+            // Remove the address activity.
+            for (address, position, _) in address_activity {
+                self.address_activity_map().remove_key(&address, &position)?;
+            }
+
             Ok(())
         })
     }
@@ -752,6 +1136,78 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         }
     }
 
+    /// Returns `true` if the address index is enabled.
+    fn is_address_index_enabled(&self) -> Result<bool> {
+        match self.address_index_map().get_confirmed(&ADDRESS_INDEX_KEY)? {
+            Some(is_enabled) => Ok(cow_to_copied!(is_enabled)),
+            None => Ok(false),
+        }
+    }
+
+    /// Enables the address index, and indexes the public activity of the blocks in storage.
+    /// Note: The blocks whose transactions have been pruned have no public values left to index.
+    fn enable_address_index(&self) -> Result<()> {
+        // Retrieve the block heights.
+        let heights = self.id_map().keys_confirmed().map(|height| cow_to_copied!(height)).collect::<Vec<_>>();
+
+        atomic_batch_scope!(self, {
+            for height in heights {
+                // Retrieve the block hash.
+                let Some(block_hash) = self.get_block_hash(height)? else { continue };
+                // Store the address activity of the block, if its transactions are in storage.
+                if let Some(transactions) = self.get_block_transactions(&block_hash)? {
+                    for (address, position, transition_id) in to_address_activity(height, &transactions) {
+                        self.address_activity_map().insert(address, position, transition_id)?;
+                    }
+                }
+            }
+            // Enable the address index.
+            self.address_index_map().insert(ADDRESS_INDEX_KEY, true)?;
+
+            Ok(())
+        })
+    }
+
+    /// Returns up to `limit` of the `(transition position, transition ID)` pairs for the public activity of the given address,
+    /// in ledger order, starting at the given position (inclusive), or at the first transition if `start` is `None`.
+    fn get_address_activity(
+        &self,
+        address: &Address<N>,
+        start: Option<TransitionPosition>,
+        limit: usize,
+    ) -> Result<Vec<(TransitionPosition, N::TransitionID)>> {
+        self.address_activity_map().get_map_range_confirmed(address, start.as_ref(), limit)
+    }
+
+    /// Returns the number of transitions in the public activity of the given address.
+    fn get_address_activity_count(&self, address: &Address<N>) -> Result<usize> {
+        Ok(self.address_activity_map().get_map_confirmed(address)?.len())
+    }
+
+    /// Returns the height of the latest pruned block, if any blocks have been pruned.
+    fn get_pruned_height(&self) -> Result<Option<u32>> {
+        match self.pruned_height_map().get_confirmed(&PRUNED_HEIGHT_KEY)? {
+            Some(height) => Ok(Some(cow_to_copied!(height))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the pruning depth, if pruning is enabled.
+    fn get_pruning_depth(&self) -> Result<Option<u32>> {
+        match self.pruned_height_map().get_confirmed(&PRUNING_DEPTH_KEY)? {
+            Some(depth) => Ok(Some(cow_to_copied!(depth))),
+            None => Ok(None),
+        }
+    }
+
+    /// Stores the pruning depth, or removes it if pruning is disabled.
+    fn set_pruning_depth(&self, depth: Option<u32>) -> Result<()> {
+        match depth {
+            Some(depth) => self.pruned_height_map().insert(PRUNING_DEPTH_KEY, depth),
+            None => self.pruned_height_map().remove(&PRUNING_DEPTH_KEY),
+        }
+    }
+
     /// Returns the block height for the given `block hash`.
     fn get_block_height(&self, block_hash: &N::BlockHash) -> Result<Option<u32>> {
         match self.reverse_id_map().get_confirmed(block_hash)? {
@@ -992,32 +1448,58 @@ pub struct BlockStore<N: Network, B: BlockStorage<N>> {
 
 impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
     /// Initializes the block store.
-    pub fn open(dev: Option<u16>) -> Result<Self> {
+    pub fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
         // Initialize the block storage.
-        let storage = B::open(dev)?;
-
+        let storage = B::open(storage)?;
         // Compute the block tree.
-        let tree = {
-            // Prepare an iterator over the block heights.
-            let heights = storage.id_map().keys_confirmed();
-            // Prepare the leaves of the block tree.
-            let hashes = match heights.max() {
-                Some(height) => cfg_into_iter!(0..=cow_to_copied!(height))
-                    .map(|height| match storage.get_block_hash(height)? {
-                        Some(hash) => Ok(hash.to_bits_le()),
-                        None => bail!("Missing block hash for block {height}"),
-                    })
-                    .collect::<Result<Vec<Vec<bool>>>>()?,
-                None => vec![],
-            };
-            // Construct the block tree.
-            Arc::new(RwLock::new(N::merkle_tree_bhp(&hashes)?))
-        };
-
+        let tree = Arc::new(RwLock::new(N::merkle_tree_bhp(&Self::block_hashes(&storage, 0)?)?));
         // Return the block store.
         Ok(Self { storage, tree })
     }
 
+    /// Returns the leaves of the block tree for the blocks in storage, from the given height onwards.
+    fn block_hashes(storage: &B, start_height: u32) -> Result<Vec<Vec<bool>>> {
+        // Prepare an iterator over the block heights.
+        let heights = storage.id_map().keys_confirmed();
+        // Prepare the leaves of the block tree.
+        match heights.max() {
+            Some(height) => cfg_into_iter!(start_height..=cow_to_copied!(height))
+                .map(|height| match storage.get_block_hash(height)? {
+                    Some(hash) => Ok(hash.to_bits_le()),
+                    None => bail!("Missing block hash for block {height}"),
+                })
+                .collect::<Result<Vec<Vec<bool>>>>(),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Updates the block tree with the blocks that were written to storage by another instance,
+    /// such as the primary of a read-only secondary database.
+    pub fn sync_tree(&self) -> Result<()> {
+        // Acquire the write lock on the block tree.
+        let mut tree = self.tree.write();
+        // Determine if the block tree is still a prefix of the blocks in storage.
+        let num_leaves = u32::try_from(tree.number_of_leaves())?;
+        let is_prefix = match num_leaves.checked_sub(1) {
+            Some(height) => {
+                let expected_root: N::StateRoot = (*tree.root()).into();
+                self.storage.state_root_map().get_confirmed(&height)?.map_or(false, |root| *root == expected_root)
+            }
+            None => true,
+        };
+        // Extend the block tree with the new blocks, or recompute it if blocks were removed.
+        match is_prefix {
+            true => {
+                let hashes = Self::block_hashes(&self.storage, num_leaves)?;
+                if !hashes.is_empty() {
+                    *tree = tree.prepare_append(&hashes)?;
+                }
+            }
+            false => *tree = N::merkle_tree_bhp(&Self::block_hashes(&self.storage, 0)?)?,
+        }
+        Ok(())
+    }
+
     /// Stores the given block into storage.
     pub fn insert(&self, block: &Block<N>) -> Result<()> {
         // Acquire the write lock on the block tree.
@@ -1036,6 +1518,103 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         Ok(())
     }
 
+    /// Stores the given `(block hash, block header, state root)` triples into storage, without the rest of their blocks.
+    /// This is used to restore the history of the ledger from a snapshot, where the state roots are taken from the next
+    /// block headers, and therefore, the state root of the last entry is checked against the updated block tree.
+    pub fn insert_headers(&self, entries: &[(N::BlockHash, Header<N>, N::StateRoot)]) -> Result<()> {
+        // Retrieve the last state root.
+        let Some((_, _, last_state_root)) = entries.last() else { return Ok(()) };
+
+        // Acquire the write lock on the block tree.
+        let mut tree = self.tree.write();
+        // Ensure the block heights are sequential from the next block height.
+        for (index, (_, header, _)) in entries.iter().enumerate() {
+            if header.height() as usize != tree.number_of_leaves() + index {
+                bail!("Attempted to insert a block header at the incorrect height into storage")
+            }
+        }
+        // Prepare an updated Merkle tree containing the new block hashes.
+        let leaves = entries.iter().map(|(block_hash, ..)| block_hash.to_bits_le()).collect::<Vec<_>>();
+        let updated_tree = tree.prepare_append(&leaves)?;
+        // Ensure the last state root matches the updated Merkle tree.
+        if *last_state_root != (*updated_tree.root()).into() {
+            bail!("Attempted to insert block headers with an incorrect state root into storage")
+        }
+        // Insert the (block hash, block header, state root) triples.
+        self.storage.insert_headers(entries)?;
+        // Update the block tree.
+        *tree = updated_tree;
+        // Return success.
+        Ok(())
+    }
+
+    /// Prunes the transaction bodies of all blocks up to and including the given block height.
+    /// The block IDs, headers, transaction IDs, and state roots are kept, so state paths remain available.
+    pub fn prune(&self, height: u32) -> Result<()> {
+        // Ensure the block height exists.
+        let latest_height = u32::try_from(self.tree.read().number_of_leaves())?.saturating_sub(1);
+        ensure!(height <= latest_height, "Cannot prune block {height}: the latest block height is {latest_height}");
+        // Determine the next block height to prune.
+        let start_height = match self.storage.get_pruned_height()? {
+            Some(pruned_height) => pruned_height.saturating_add(1),
+            None => 0,
+        };
+        // Prune the blocks, in ascending order.
+        for block_height in start_height..=height {
+            self.storage.prune(block_height)?;
+        }
+        Ok(())
+    }
+
+    /// Enables the address index, and indexes the public activity of the blocks in storage.
+    pub fn enable_address_index(&self) -> Result<()> {
+        self.storage.enable_address_index()
+    }
+
+    /// Records the statistics of the block maps and the transaction store in the given stats.
+    pub fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        self.storage.record_stats(stats)
+    }
+
+    /// Checks that the block maps, the block tree, and the transaction store agree with each other,
+    /// recording any inconsistencies in the given report. If `repair` is `true`, the derived indexes are repaired.
+    pub fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        // Check the block maps.
+        self.storage.check_consistency(repair, report)?;
+
+        // Retrieve the block heights, in ascending order.
+        let mut heights =
+            self.storage.id_map().keys_confirmed().map(|height| cow_to_copied!(height)).collect::<Vec<_>>();
+        heights.sort_unstable();
+        // Ensure the block heights are contiguous from genesis.
+        if let Some((index, height)) = heights.iter().enumerate().find(|(index, height)| *index != **height as usize) {
+            report.found("block ID", format!("Block {index} is missing (found block {height} in its place)"));
+            return Ok(());
+        }
+
+        // Recompute the block tree from the block hashes, and ensure it produces the state root of each block.
+        let mut expected_tree: BlockTree<N> = N::merkle_tree_bhp(&[])?;
+        for height in heights {
+            let Some(block_hash) = self.storage.get_block_hash(height)? else {
+                bail!("Missing block hash for block {height}")
+            };
+            expected_tree = expected_tree.prepare_append(&[block_hash.to_bits_le()])?;
+            // Note: A missing state root is reported by the storage check.
+            let expected_root: N::StateRoot = (*expected_tree.root()).into();
+            if matches!(self.storage.get_state_root(height)?, Some(state_root) if state_root != expected_root) {
+                report.found("block tree", format!("The block tree does not match the state root of block {height}"));
+            }
+        }
+        // Ensure the in-memory block tree is up to date.
+        if self.tree.read().root() != expected_tree.root() {
+            if repair {
+                *self.tree.write() = expected_tree;
+            }
+            report.record("block tree", "The in-memory block tree does not match the block hashes", repair);
+        }
+        Ok(())
+    }
+
     /// Removes the last 'n' blocks from storage.
     pub fn remove_last_n(&self, n: u32) -> Result<()> {
         // Ensure 'n' is non-zero.
@@ -1055,6 +1634,13 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
                     .ok_or_else(|| anyhow!("Failed to remove last '{n}' blocks: block height underflow"))?;
                 // Ensure the block height matches the number of leaves in the Merkle tree.
                 ensure!(end_height == u32::try_from(tree.number_of_leaves())? - 1, "Block height mismatch");
+                // Ensure none of the blocks to remove have been pruned.
+                if let Some(pruned_height) = self.storage.get_pruned_height()? {
+                    if start_height <= pruned_height {
+                        let error = Error::new(PrunedError::new(start_height));
+                        return Err(error.context(format!("Failed to remove last '{n}' blocks")));
+                    }
+                }
                 // Output the block heights.
                 start_height..=end_height
             }
@@ -1131,9 +1717,9 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         self.storage.finish_atomic()
     }
 
-    /// Returns the optional development ID.
-    pub fn dev(&self) -> Option<u16> {
-        self.storage.dev()
+    /// Returns the storage mode.
+    pub fn storage_mode(&self) -> &StorageMode {
+        self.storage.storage_mode()
     }
 }
 
@@ -1183,6 +1769,50 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         self.storage.get_block_hash(height)
     }
 
+    /// Returns the height of the latest pruned block, if any blocks have been pruned.
+    pub fn pruned_height(&self) -> Result<Option<u32>> {
+        self.storage.get_pruned_height()
+    }
+
+    /// Returns the pruning depth, if pruning is enabled.
+    pub fn pruning_depth(&self) -> Result<Option<u32>> {
+        self.storage.get_pruning_depth()
+    }
+
+    /// Stores the pruning depth, or removes it if pruning is disabled.
+    pub fn set_pruning_depth(&self, depth: Option<u32>) -> Result<()> {
+        self.storage.set_pruning_depth(depth)
+    }
+
+    /// Returns `true` if the address index is enabled.
+    pub fn is_address_index_enabled(&self) -> Result<bool> {
+        self.storage.is_address_index_enabled()
+    }
+
+    /// Returns up to `limit` of the `(transition position, transition ID)` pairs for the public activity of the given address,
+    /// in ledger order, starting at the given position (inclusive), or at the first transition if `start` is `None`.
+    pub fn get_address_activity(
+        &self,
+        address: &Address<N>,
+        start: Option<TransitionPosition>,
+        limit: usize,
+    ) -> Result<Vec<(TransitionPosition, N::TransitionID)>> {
+        self.storage.get_address_activity(address, start, limit)
+    }
+
+    /// Returns the number of transitions in the public activity of the given address.
+    pub fn get_address_activity_count(&self, address: &Address<N>) -> Result<usize> {
+        self.storage.get_address_activity_count(address)
+    }
+
+    /// Returns a `PrunedError` if the block at the given height has been pruned.
+    pub fn check_pruned(&self, height: u32) -> Result<()> {
+        match self.storage.get_pruned_height()? {
+            Some(pruned_height) if height <= pruned_height => Err(PrunedError::new(height).into()),
+            _ => Ok(()),
+        }
+    }
+
     /// Returns the block height for the given `block hash`.
     pub fn get_block_height(&self, block_hash: &N::BlockHash) -> Result<Option<u32>> {
         self.storage.get_block_height(block_hash)
@@ -1327,7 +1957,7 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::helpers::memory::BlockMemory;
+    use crate::helpers::{memory::BlockMemory, TransactionMap};
 
     type CurrentNetwork = console::network::Testnet3;
 
@@ -1461,4 +2091,173 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_prune() {
+        let rng = &mut TestRng::default();
+
+        // Sample the block.
+        let block = ledger_test_helpers::sample_genesis_block(rng);
+        let block_hash = block.hash();
+        assert!(block.transactions().num_accepted() > 0, "This test must be run with at least one transaction.");
+
+        // Initialize a new block store.
+        let block_store = BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).unwrap();
+        // Insert the block.
+        block_store.insert(&block).unwrap();
+        assert_eq!(block_store.pruned_height().unwrap(), None);
+        block_store.check_pruned(0).unwrap();
+
+        // Ensure a missing block cannot be pruned.
+        assert!(block_store.prune(1).is_err());
+        // Prune the block.
+        block_store.prune(0).unwrap();
+        assert_eq!(block_store.pruned_height().unwrap(), Some(0));
+        assert_eq!(block_store.check_pruned(0).unwrap_err().downcast_ref::<PrunedError>(), Some(&PrunedError::new(0)));
+
+        // Ensure the transactions keep their IDs, but not their proofs or payloads.
+        for confirmed in block.transactions().iter() {
+            let transaction = block_store.get_transaction(&confirmed.id()).unwrap().unwrap();
+            assert_eq!(transaction.id(), confirmed.id());
+            assert_ne!(&transaction, confirmed.transaction());
+            if let Some(execution) = transaction.execution() {
+                assert!(execution.proof().is_none());
+            }
+        }
+        // Ensure the block is unchanged, other than its transactions.
+        let candidate = block_store.get_block(&block_hash).unwrap().unwrap();
+        assert_eq!(candidate.hash(), block_hash);
+        assert_eq!(candidate.header(), block.header());
+
+        // Ensure the pruned block cannot be removed.
+        let error = block_store.remove_last_n(1).unwrap_err();
+        assert_eq!(error.downcast_ref::<PrunedError>(), Some(&PrunedError::new(0)));
+
+        // Ensure the pruning depth is stored.
+        assert_eq!(block_store.pruning_depth().unwrap(), None);
+        block_store.set_pruning_depth(Some(5)).unwrap();
+        assert_eq!(block_store.pruning_depth().unwrap(), Some(5));
+        assert_eq!(block_store.pruned_height().unwrap(), Some(0));
+        block_store.set_pruning_depth(None).unwrap();
+        assert_eq!(block_store.pruning_depth().unwrap(), None);
+    }
+
+    #[test]
+    fn test_check_consistency() {
+        let rng = &mut TestRng::default();
+
+        // Sample the block.
+        let block = ledger_test_helpers::sample_genesis_block(rng);
+        let block_hash = block.hash();
+
+        // Initialize a new block store.
+        let block_store = BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).unwrap();
+        // Insert the block.
+        block_store.insert(&block).unwrap();
+
+        // Ensure the block store is consistent.
+        let mut report = ConsistencyReport::default();
+        block_store.check_consistency(false, &mut report).unwrap();
+        assert!(report.inconsistencies().is_empty(), "{report}");
+        assert_eq!(report.num_blocks(), 1);
+        assert_eq!(report.num_transactions(), block.transactions().len());
+
+        // Corrupt the derived indexes.
+        let state_root = block_store.get_state_root(0).unwrap().unwrap();
+        block_store.storage.reverse_id_map().remove(&block_hash).unwrap();
+        block_store.storage.reverse_state_root_map().insert(Field::<CurrentNetwork>::from_u64(1).into(), 0).unwrap();
+
+        // Ensure the inconsistencies are found, without being repaired.
+        let mut report = ConsistencyReport::default();
+        block_store.check_consistency(false, &mut report).unwrap();
+        assert_eq!(report.inconsistencies().len(), 2, "{report}");
+        assert!(!report.is_consistent());
+        assert_eq!(block_store.get_block_height(&block_hash).unwrap(), None);
+
+        // Ensure the inconsistencies are repaired.
+        let mut report = ConsistencyReport::default();
+        block_store.check_consistency(true, &mut report).unwrap();
+        assert_eq!(report.num_repaired(), 2, "{report}");
+        assert!(report.is_consistent());
+        assert_eq!(block_store.get_block_height(&block_hash).unwrap(), Some(0));
+        assert_eq!(block_store.find_block_height_from_state_root(state_root).unwrap(), Some(0));
+
+        // Ensure the block store is consistent again.
+        let mut report = ConsistencyReport::default();
+        block_store.check_consistency(false, &mut report).unwrap();
+        assert!(report.inconsistencies().is_empty(), "{report}");
+    }
+
+    #[test]
+    fn test_check_consistency_state_root() {
+        let rng = &mut TestRng::default();
+
+        // Sample the block.
+        let block = ledger_test_helpers::sample_genesis_block(rng);
+
+        // Initialize a new block store.
+        let block_store = BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).unwrap();
+        // Insert the block.
+        block_store.insert(&block).unwrap();
+
+        // Corrupt the state root of the block.
+        let state_root: <CurrentNetwork as Network>::StateRoot = Field::<CurrentNetwork>::from_u64(1).into();
+        block_store.storage.state_root_map().insert(0, state_root).unwrap();
+
+        // Ensure the state root does not match the block tree.
+        let mut report = ConsistencyReport::default();
+        block_store.check_consistency(false, &mut report).unwrap();
+        assert!(!report.is_consistent());
+        let mismatch = report.inconsistencies().iter().find(|inconsistency| inconsistency.map() == "block tree");
+        assert_eq!(
+            mismatch.map(|inconsistency| inconsistency.message()),
+            Some("The block tree does not match the state root of block 0"),
+            "{report}"
+        );
+    }
+
+    #[test]
+    fn test_record_stats() {
+        let rng = &mut TestRng::default();
+
+        // Sample the block.
+        let block = ledger_test_helpers::sample_genesis_block(rng);
+        let block_hash = block.hash();
+
+        // Initialize a new block store.
+        let block_store = BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).unwrap();
+        // Insert the block.
+        block_store.insert(&block).unwrap();
+
+        // Ensure the entries are counted per map.
+        let mut stats = StorageStats::default();
+        block_store.record_stats(&mut stats).unwrap();
+        let ids = stats.get(&MapID::Block(BlockMap::ID)).unwrap();
+        assert_eq!(ids.num_entries(), 1);
+        assert!(ids.num_bytes() > 0);
+        assert_eq!(
+            stats.get(&MapID::Transaction(TransactionMap::ID)).unwrap().num_entries(),
+            block.transactions().len() as u64
+        );
+        assert_eq!(stats.get(&MapID::Block(BlockMap::AddressActivity)).unwrap().num_entries(), 0);
+        assert_eq!(stats.num_pending(), 0);
+        assert_eq!(stats.checkpoint_depth(), 0);
+        assert!(stats.num_bytes() >= ids.num_bytes());
+
+        // Ensure the same maps cannot be recorded twice.
+        assert!(block_store.record_stats(&mut stats).is_err());
+
+        // Ensure the pending operations and checkpoints are counted.
+        block_store.start_atomic();
+        block_store.atomic_checkpoint();
+        block_store.storage.reverse_id_map().remove(&block_hash).unwrap();
+        let mut stats = StorageStats::default();
+        block_store.record_stats(&mut stats).unwrap();
+        let reverse_ids = stats.get(&MapID::Block(BlockMap::ReverseID)).unwrap();
+        assert_eq!(reverse_ids.num_entries(), 1);
+        assert_eq!(reverse_ids.num_pending(), 1);
+        assert_eq!(reverse_ids.checkpoint_depth(), 1);
+        assert_eq!(stats.num_pending(), 1);
+        block_store.abort_atomic();
+    }
 }
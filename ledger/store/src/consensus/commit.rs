@@ -0,0 +1,75 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The steps of a block commit, in the order in which they are made durable.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CommitStep {
+    /// The commit marker for the block has been written.
+    Marked,
+    /// The block has been inserted into the block store.
+    Inserted,
+    /// The atomic batch that finalizes the block is being committed, and only part of it has been finished.
+    /// Note: A crash injected at this step panics, so that the commit is not rolled back in-process.
+    Finishing,
+    /// The block has been finalized, and the commit marker has been cleared in the same atomic batch.
+    Finalized,
+}
+
+impl CommitStep {
+    /// The commit steps, in order.
+    pub const ALL: [Self; 4] = [Self::Marked, Self::Inserted, Self::Finishing, Self::Finalized];
+}
+
+impl<N: Network, C: ConsensusStorage<N>> ConsensusStore<N, C> {
+    /// Rolls back the block commit that was interrupted, if any, and returns its block height.
+    ///
+    /// A commit is interrupted if its marker was written, but the atomic batch that finalizes the block
+    /// (and clears the marker) was not. In this case, the block is removed if it has already been inserted.
+    pub fn recover(&self) -> Result<Option<u32>> {
+        // Retrieve the height of the interrupted commit.
+        let Some(height) = self.finalize_store().pending_commit()? else {
+            return Ok(None);
+        };
+        // If the block was inserted, remove it, as its finalize operations were never committed.
+        if self.block_store().contains_block_height(height)? {
+            // Ensure the block is the latest block, as commits are sequential.
+            let latest_height = self.block_store().heights().max().map(|height| *height);
+            ensure!(latest_height == Some(height), "The interrupted commit of block {height} is not the latest block");
+            self.block_store().remove_last_n(1)?;
+        }
+        // Clear the commit marker.
+        self.finalize_store().end_commit()?;
+        Ok(Some(height))
+    }
+
+    /// Returns an error if a crash was injected after the given commit step, and does nothing otherwise.
+    #[inline]
+    pub fn commit_step(&self, step: CommitStep) -> Result<()> {
+        #[cfg(any(test, feature = "test"))]
+        if self.finalize_store().crash_step() == Some(step) {
+            bail!("Injected a crash after the '{step:?}' commit step")
+        }
+        #[cfg(not(any(test, feature = "test")))]
+        let _ = step;
+        Ok(())
+    }
+
+    /// Sets the commit step after which every block commit is interrupted, to simulate a crash.
+    #[cfg(any(test, feature = "test"))]
+    pub fn crash_after(&self, step: Option<CommitStep>) {
+        self.finalize_store().set_crash_step(step);
+    }
+}
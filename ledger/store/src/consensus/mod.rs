@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod commit;
+pub use commit::*;
+
 use crate::{
+    helpers::{BackendStats, ConsistencyReport, StorageMode, StorageStats},
     BlockStorage,
     BlockStore,
     FinalizeStorage,
@@ -22,7 +26,7 @@ use crate::{
     TransitionStorage,
     TransitionStore,
 };
-use console::network::prelude::*;
+use console::{network::prelude::*, program::ProgramID};
 
 use anyhow::Result;
 use core::marker::PhantomData;
@@ -39,7 +43,7 @@ pub trait ConsensusStorage<N: Network>: 'static + Clone + Send + Sync {
     type TransitionStorage: TransitionStorage<N>;
 
     /// Initializes the consensus storage.
-    fn open(dev: Option<u16>) -> Result<Self>;
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self>;
 
     /// Returns the finalize storage.
     fn finalize_store(&self) -> &FinalizeStore<N, Self::FinalizeStorage>;
@@ -53,11 +57,33 @@ pub trait ConsensusStorage<N: Network>: 'static + Clone + Send + Sync {
     fn transition_store(&self) -> &TransitionStore<N, Self::TransitionStorage> {
         self.block_store().transition_store()
     }
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16> {
-        debug_assert!(self.block_store().dev() == self.transaction_store().dev());
-        debug_assert!(self.transaction_store().dev() == self.transition_store().dev());
-        self.transition_store().dev()
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        debug_assert!(self.block_store().storage_mode() == self.transaction_store().storage_mode());
+        debug_assert!(self.transaction_store().storage_mode() == self.transition_store().storage_mode());
+        self.transition_store().storage_mode()
+    }
+
+    /// Catches up with the writes of the primary database, if the storage is a read-only secondary instance.
+    fn catch_up_with_primary(&self) -> Result<()> {
+        bail!("The consensus storage does not support catching up with a primary database")
+    }
+
+    /// Returns the statistics that the storage backend estimates for the whole storage, if it provides them.
+    fn backend_stats(&self) -> Result<Option<BackendStats>> {
+        Ok(None)
+    }
+
+    /// Opens the staging storage for the given (empty) storage, into which a replacement for its contents is written,
+    /// before it is swapped in with `swap_in`. By default, the storage is staged in place.
+    fn open_staging<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        Self::open(storage)
+    }
+
+    /// Swaps the staging storage in for the given storage, and returns the swapped-in storage.
+    /// By default, the storage is staged in place, so this returns the staging storage as is.
+    fn swap_in<S: Clone + Into<StorageMode>>(self, _storage: S) -> Result<Self> {
+        Ok(self)
     }
 
     /// Starts an atomic batch write operation.
@@ -113,11 +139,35 @@ pub struct ConsensusStore<N: Network, C: ConsensusStorage<N>> {
 
 impl<N: Network, C: ConsensusStorage<N>> ConsensusStore<N, C> {
     /// Initializes the consensus store.
-    pub fn open(dev: Option<u16>) -> Result<Self> {
+    pub fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
         // Initialize the consensus storage.
-        let storage = C::open(dev)?;
+        let storage = C::open(storage)?;
+        // Initialize the consensus store.
+        let store = Self::from(storage);
+        // Roll back the block commit that was interrupted, if any.
+        if !store.is_read_only() {
+            store.recover()?;
+        }
         // Return the consensus store.
-        Ok(Self { storage, _phantom: PhantomData })
+        Ok(store)
+    }
+
+    /// Opens the staging consensus store for the given (empty) storage, into which a replacement for its contents
+    /// is written, before it is swapped in with `swap_in`. If writing to the staging store fails, it should be discarded.
+    pub fn open_staging<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        // Ensure the storage is writable.
+        let storage_mode = storage.into();
+        ensure!(!storage_mode.is_read_only(), "Cannot stage a read-only storage");
+        // Initialize the staging storage.
+        Ok(Self::from(C::open_staging(storage_mode)?))
+    }
+
+    /// Swaps this staging store in for the given storage, and returns the swapped-in consensus store.
+    pub fn swap_in<S: Clone + Into<StorageMode>>(self, storage: S) -> Result<Self> {
+        // Ensure no atomic batch is in progress.
+        ensure!(!self.is_atomic_in_progress(), "Cannot swap in a store while an atomic batch is in progress");
+        // Swap in the staging storage.
+        Ok(Self::from(self.storage.swap_in(storage)?))
     }
 
     /// Initializes a consensus store from storage.
@@ -180,8 +230,78 @@ impl<N: Network, C: ConsensusStorage<N>> ConsensusStore<N, C> {
         self.storage.finish_atomic()
     }
 
-    /// Returns the optional development ID.
-    pub fn dev(&self) -> Option<u16> {
-        self.storage.dev()
+    /// Returns the storage mode.
+    pub fn storage_mode(&self) -> &StorageMode {
+        self.storage.storage_mode()
+    }
+
+    /// Returns `true` if the storage is a read-only secondary instance.
+    pub fn is_read_only(&self) -> bool {
+        self.storage.storage_mode().is_read_only()
+    }
+
+    /// Catches up with the writes of the primary database, and updates the block tree with the new blocks.
+    /// This method is only supported by read-only secondary instances.
+    pub fn catch_up_with_primary(&self) -> Result<()> {
+        self.storage.catch_up_with_primary()?;
+        self.storage.block_store().sync_tree()
+    }
+
+    /// Returns the statistics that the storage backend estimates for the whole storage, if it provides them.
+    /// Unlike `storage_stats`, this does not scan the storage, so it is cheap enough to poll regularly.
+    pub fn backend_stats(&self) -> Result<Option<BackendStats>> {
+        self.storage.backend_stats()
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> ConsensusStore<N, C> {
+    /// Checks that the block, transaction, transition, and finalize stores agree with each other,
+    /// and returns a report of the inconsistencies. If `repair` is `true`, the derived indexes are repaired.
+    pub fn check_consistency(&self, repair: bool) -> Result<ConsistencyReport> {
+        let mut report = ConsistencyReport::default();
+
+        // Check the block store, which includes the transaction and transition stores.
+        self.block_store().check_consistency(repair, &mut report)?;
+        // Check the finalize store, against the finalize root of the latest block.
+        let latest_height = self.block_store().heights().max().map(|height| *height);
+        let latest_block = match latest_height {
+            Some(height) => match self.block_store().get_block_hash(height)? {
+                Some(block_hash) => self.block_store().get_block(&block_hash)?,
+                None => None,
+            },
+            None => None,
+        };
+        self.finalize_store().check_consistency(latest_block.as_ref(), &mut report)?;
+
+        // Ensure every program with mappings has been deployed, except for 'credits.aleo', which is a default program.
+        let credits_program_id = ProgramID::<N>::from_str("credits.aleo")?;
+        for program_id in self.finalize_store().program_ids() {
+            if *program_id != credits_program_id && !self.transaction_store().contains_program_id(&program_id)? {
+                report.found("program", format!("Program '{}' has mappings but is not deployed", *program_id));
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Returns the entry counts, approximate sizes, pending operations, and checkpoint depths
+    /// of every map in the block, transaction, transition, and finalize stores,
+    /// along with the statistics of the storage backend, if it provides them.
+    /// Note: This iterates over every map, and is as expensive as a full scan of the storage.
+    /// For regular monitoring, use the cheap estimates of `backend_stats` instead.
+    pub fn storage_stats(&self) -> Result<StorageStats> {
+        let mut stats = StorageStats::default();
+        // Record the block store, which includes the transaction and transition stores.
+        self.block_store().record_stats(&mut stats)?;
+        // Record the finalize store, which includes the committee store.
+        self.finalize_store().record_stats(&mut stats)?;
+        // Record the statistics of the storage backend.
+        if let Some(backend) = self.storage.backend_stats()? {
+            stats.record_backend(backend)?;
+        }
+        // Publish the statistics.
+        #[cfg(feature = "metrics")]
+        stats.publish_metrics();
+        Ok(stats)
     }
 }
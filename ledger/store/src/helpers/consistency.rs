@@ -0,0 +1,202 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::Map;
+use console::network::prelude::*;
+
+use core::hash::Hash;
+
+/// An inconsistency between the maps of the ledger storage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Inconsistency {
+    /// The name of the map that is inconsistent.
+    map: &'static str,
+    /// The description of the inconsistency.
+    message: String,
+    /// Whether the inconsistency has been repaired.
+    repaired: bool,
+}
+
+impl Inconsistency {
+    /// Returns the name of the map that is inconsistent.
+    pub const fn map(&self) -> &'static str {
+        self.map
+    }
+
+    /// Returns the description of the inconsistency.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns `true` if the inconsistency has been repaired.
+    pub const fn is_repaired(&self) -> bool {
+        self.repaired
+    }
+}
+
+impl Display for Inconsistency {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let status = if self.repaired { "repaired" } else { "found" };
+        write!(f, "[{status}] {}: {}", self.map, self.message)
+    }
+}
+
+/// The report of a consistency check of the ledger storage.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    /// The number of blocks checked.
+    num_blocks: usize,
+    /// The number of transactions checked.
+    num_transactions: usize,
+    /// The number of transitions checked.
+    num_transitions: usize,
+    /// The finalize checksum, if it could be computed.
+    finalize_checksum: Option<String>,
+    /// The inconsistencies that were found.
+    inconsistencies: Vec<Inconsistency>,
+}
+
+impl ConsistencyReport {
+    /// Returns `true` if there are no unrepaired inconsistencies.
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.iter().all(|inconsistency| inconsistency.repaired)
+    }
+
+    /// Returns the inconsistencies that were found.
+    pub fn inconsistencies(&self) -> &[Inconsistency] {
+        &self.inconsistencies
+    }
+
+    /// Returns the number of inconsistencies that were repaired.
+    pub fn num_repaired(&self) -> usize {
+        self.inconsistencies.iter().filter(|inconsistency| inconsistency.repaired).count()
+    }
+
+    /// Returns the number of blocks checked.
+    pub const fn num_blocks(&self) -> usize {
+        self.num_blocks
+    }
+
+    /// Returns the number of transactions checked.
+    pub const fn num_transactions(&self) -> usize {
+        self.num_transactions
+    }
+
+    /// Returns the number of transitions checked.
+    pub const fn num_transitions(&self) -> usize {
+        self.num_transitions
+    }
+
+    /// Returns the finalize checksum, if it could be computed.
+    pub fn finalize_checksum(&self) -> Option<&str> {
+        self.finalize_checksum.as_deref()
+    }
+}
+
+impl ConsistencyReport {
+    /// Records that a block was checked.
+    pub(crate) fn add_block(&mut self) {
+        self.num_blocks += 1;
+    }
+
+    /// Records that a transaction was checked.
+    pub(crate) fn add_transaction(&mut self) {
+        self.num_transactions += 1;
+    }
+
+    /// Records that a transition was checked.
+    pub(crate) fn add_transition(&mut self) {
+        self.num_transitions += 1;
+    }
+
+    /// Records the finalize checksum.
+    pub(crate) fn set_finalize_checksum(&mut self, checksum: impl Display) {
+        self.finalize_checksum = Some(checksum.to_string());
+    }
+
+    /// Records an inconsistency that cannot be repaired.
+    pub(crate) fn found(&mut self, map: &'static str, message: impl Into<String>) {
+        self.inconsistencies.push(Inconsistency { map, message: message.into(), repaired: false });
+    }
+
+    /// Records an inconsistency, which has been repaired if `repaired` is `true`.
+    pub(crate) fn record(&mut self, map: &'static str, message: impl Into<String>, repaired: bool) {
+        self.inconsistencies.push(Inconsistency { map, message: message.into(), repaired });
+    }
+
+    /// Ensures the given derived index maps `key` to `value`.
+    /// If `repair` is `true`, a missing or incorrect entry is rewritten.
+    pub(crate) fn check_index<K, V, M>(
+        &mut self,
+        name: &'static str,
+        map: &M,
+        key: K,
+        value: V,
+        repair: bool,
+    ) -> Result<()>
+    where
+        K: 'static + Copy + Eq + Hash + Serialize + DeserializeOwned + Send + Sync + Debug,
+        V: 'static + Clone + Eq + Serialize + DeserializeOwned + Send + Sync,
+        M: for<'a> Map<'a, K, V>,
+    {
+        let message = match map.get_confirmed(&key)? {
+            Some(existing) if *existing == value => return Ok(()),
+            Some(_) => format!("'{key:?}' maps to an incorrect value"),
+            None => format!("'{key:?}' is missing"),
+        };
+        if repair {
+            map.insert(key, value)?;
+        }
+        self.record(name, message, repair);
+        Ok(())
+    }
+
+    /// Removes the given entry from a derived index, as it does not correspond to any primary entry.
+    /// The entry is only removed if `repair` is `true`.
+    pub(crate) fn remove_dangling<K, V, M>(&mut self, name: &'static str, map: &M, key: K, repair: bool) -> Result<()>
+    where
+        K: 'static + Copy + Eq + Hash + Serialize + DeserializeOwned + Send + Sync + Debug,
+        V: 'static + Clone + Eq + Serialize + DeserializeOwned + Send + Sync,
+        M: for<'a> Map<'a, K, V>,
+    {
+        if repair {
+            map.remove(&key)?;
+        }
+        self.record(name, format!("'{key:?}' is dangling"), repair);
+        Ok(())
+    }
+}
+
+impl Display for ConsistencyReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Checked {} blocks, {} transactions, and {} transitions",
+            self.num_blocks, self.num_transactions, self.num_transitions
+        )?;
+        if let Some(checksum) = &self.finalize_checksum {
+            writeln!(f, "Finalize checksum: {checksum}")?;
+        }
+        match self.inconsistencies.is_empty() {
+            true => write!(f, "No inconsistencies found"),
+            false => {
+                write!(f, "Found {} inconsistencies ({} repaired)", self.inconsistencies.len(), self.num_repaired())?;
+                for inconsistency in &self.inconsistencies {
+                    write!(f, "\n  {inconsistency}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-/// The RocksDB map prefix broken down into the entry category and the specific type of the entry.
+use core::fmt;
+
+/// The ID of a map in the ledger storage, which is also its RocksDB map prefix,
+/// broken down into the entry category and the specific type of the entry.
 // Note: the order of these variants can be changed at any point in time.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum MapID {
     BFT(BFTMap),
@@ -28,6 +31,7 @@ pub enum MapID {
     TransitionInput(TransitionInputMap),
     TransitionOutput(TransitionOutputMap),
     Program(ProgramMap),
+    Metadata(MetadataMap),
     #[cfg(test)]
     Test(TestMap),
 }
@@ -46,16 +50,38 @@ impl From<MapID> for u16 {
             MapID::TransitionInput(id) => id as u16,
             MapID::TransitionOutput(id) => id as u16,
             MapID::Program(id) => id as u16,
+            MapID::Metadata(id) => id as u16,
             #[cfg(test)]
             MapID::Test(id) => id as u16,
         }
     }
 }
 
+impl fmt::Display for MapID {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapID::BFT(id) => write!(f, "BFT::{id:?}"),
+            MapID::Block(id) => write!(f, "Block::{id:?}"),
+            MapID::Committee(id) => write!(f, "Committee::{id:?}"),
+            MapID::Deployment(id) => write!(f, "Deployment::{id:?}"),
+            MapID::Execution(id) => write!(f, "Execution::{id:?}"),
+            MapID::Fee(id) => write!(f, "Fee::{id:?}"),
+            MapID::Transaction(id) => write!(f, "Transaction::{id:?}"),
+            MapID::Transition(id) => write!(f, "Transition::{id:?}"),
+            MapID::TransitionInput(id) => write!(f, "TransitionInput::{id:?}"),
+            MapID::TransitionOutput(id) => write!(f, "TransitionOutput::{id:?}"),
+            MapID::Program(id) => write!(f, "Program::{id:?}"),
+            MapID::Metadata(id) => write!(f, "Metadata::{id:?}"),
+            #[cfg(test)]
+            MapID::Test(id) => write!(f, "Test::{id:?}"),
+        }
+    }
+}
+
 /// The RocksDB map prefix for BFT-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum BFTMap {
     Transmissions = DataID::BFTTransmissionsMap as u16,
@@ -64,7 +90,7 @@ pub enum BFTMap {
 /// The RocksDB map prefix for block-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum BlockMap {
     StateRoot = DataID::BlockStateRootMap as u16,
@@ -82,12 +108,15 @@ pub enum BlockMap {
     RejectedOrAbortedTransactionID = DataID::BlockRejectedOrAbortedTransactionIDMap as u16,
     ConfirmedTransactions = DataID::BlockConfirmedTransactionsMap as u16,
     RejectedDeploymentOrExecution = DataID::BlockRejectedDeploymentOrExecutionMap as u16,
+    PrunedHeight = DataID::BlockPrunedHeightMap as u16,
+    AddressActivity = DataID::BlockAddressActivityMap as u16,
+    AddressIndex = DataID::BlockAddressIndexMap as u16,
 }
 
 /// The RocksDB map prefix for committee-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum CommitteeMap {
     CurrentRound = DataID::CurrentRoundMap as u16,
@@ -98,7 +127,7 @@ pub enum CommitteeMap {
 /// The RocksDB map prefix for deployment-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum DeploymentMap {
     ID = DataID::DeploymentIDMap as u16,
@@ -113,7 +142,7 @@ pub enum DeploymentMap {
 /// The RocksDB map prefix for execution-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum ExecutionMap {
     ID = DataID::ExecutionIDMap as u16,
@@ -124,7 +153,7 @@ pub enum ExecutionMap {
 /// The RocksDB map prefix for fee-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum FeeMap {
     Fee = DataID::FeeFeeMap as u16,
@@ -134,7 +163,7 @@ pub enum FeeMap {
 /// The RocksDB map prefix for transition input entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum TransitionInputMap {
     ID = DataID::InputIDMap as u16,
@@ -150,7 +179,7 @@ pub enum TransitionInputMap {
 /// The RocksDB map prefix for transition output entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum TransitionOutputMap {
     ID = DataID::OutputIDMap as u16,
@@ -167,7 +196,7 @@ pub enum TransitionOutputMap {
 /// The RocksDB map prefix for transaction-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum TransactionMap {
     ID = DataID::TransactionIDMap as u16,
@@ -176,7 +205,7 @@ pub enum TransactionMap {
 /// The RocksDB map prefix for transition-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum TransitionMap {
     Locator = DataID::TransitionLocatorMap as u16,
@@ -184,22 +213,35 @@ pub enum TransitionMap {
     ReverseTPK = DataID::TransitionReverseTPKMap as u16,
     TCM = DataID::TransitionTCMMap as u16,
     ReverseTCM = DataID::TransitionReverseTCMMap as u16,
+    Call = DataID::TransitionCallMap as u16,
 }
 
 /// The RocksDB map prefix for program-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum ProgramMap {
     ProgramID = DataID::ProgramIDMap as u16,
     KeyValueID = DataID::KeyValueMap as u16,
+    Commit = DataID::ProgramCommitMap as u16,
+    Ratified = DataID::ProgramRatifiedMap as u16,
+}
+
+/// The RocksDB map prefix for database metadata entries.
+// Note: the order of these variants can be changed at any point in time,
+// as long as the corresponding DataID values remain the same.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u16)]
+pub enum MetadataMap {
+    SchemaVersion = DataID::MetadataSchemaVersion as u16,
+    MigrationCheckpoint = DataID::MetadataMigrationCheckpoint as u16,
 }
 
 /// The RocksDB map prefix for test-related entries.
 // Note: the order of these variants can be changed at any point in time.
 #[cfg(test)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
 pub enum TestMap {
     Test = DataID::Test as u16,
@@ -285,6 +327,18 @@ enum DataID {
     // TODO (howardwu): For mainnet - Reorder this up above.
     BlockRejectedDeploymentOrExecutionMap,
     BFTTransmissionsMap,
+    // Metadata
+    MetadataSchemaVersion,
+    MetadataMigrationCheckpoint,
+    // Block
+    BlockPrunedHeightMap,
+    BlockAddressActivityMap,
+    BlockAddressIndexMap,
+    // Transition
+    TransitionCallMap,
+    // Program
+    ProgramCommitMap,
+    ProgramRatifiedMap,
 
     // Testing
     #[cfg(test)]
@@ -13,13 +13,17 @@
 // limitations under the License.
 
 use crate::{
-    helpers::memory::{MemoryMap, TransactionMemory, TransitionMemory},
+    helpers::{
+        memory::{MemoryMap, NestedMemoryMap, TransactionMemory, TransitionMemory},
+        StorageMode,
+        TransitionPosition,
+    },
     BlockStorage,
     ConfirmedTxType,
     TransactionStore,
     TransitionStore,
 };
-use console::{prelude::*, types::Field};
+use console::{account::Address, prelude::*, types::Field};
 use ledger_authority::Authority;
 use ledger_block::{Header, Ratifications, Rejected};
 use ledger_coinbase::{CoinbaseSolution, PuzzleCommitment};
@@ -57,6 +61,12 @@ pub struct BlockMemory<N: Network> {
     confirmed_transactions_map: MemoryMap<N::TransactionID, (N::BlockHash, ConfirmedTxType, Vec<u8>)>,
     /// The rejected deployment or execution map.
     rejected_deployment_or_execution_map: MemoryMap<Field<N>, Rejected<N>>,
+    /// The pruned height map.
+    pruned_height_map: MemoryMap<u8, u32>,
+    /// The address activity map.
+    address_activity_map: NestedMemoryMap<Address<N>, TransitionPosition, N::TransitionID>,
+    /// The address index map.
+    address_index_map: MemoryMap<u8, bool>,
     /// The transaction store.
     transaction_store: TransactionStore<N, TransactionMemory<N>>,
 }
@@ -78,13 +88,16 @@ impl<N: Network> BlockStorage<N> for BlockMemory<N> {
     type RejectedOrAbortedTransactionIDMap = MemoryMap<N::TransactionID, N::BlockHash>;
     type ConfirmedTransactionsMap = MemoryMap<N::TransactionID, (N::BlockHash, ConfirmedTxType, Vec<u8>)>;
     type RejectedDeploymentOrExecutionMap = MemoryMap<Field<N>, Rejected<N>>;
+    type PrunedHeightMap = MemoryMap<u8, u32>;
+    type AddressActivityMap = NestedMemoryMap<Address<N>, TransitionPosition, N::TransitionID>;
+    type AddressIndexMap = MemoryMap<u8, bool>;
     type TransactionStorage = TransactionMemory<N>;
     type TransitionStorage = TransitionMemory<N>;
 
     /// Initializes the block storage.
-    fn open(dev: Option<u16>) -> Result<Self> {
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
         // Initialize the transition store.
-        let transition_store = TransitionStore::<N, TransitionMemory<N>>::open(dev)?;
+        let transition_store = TransitionStore::<N, TransitionMemory<N>>::open(storage.clone())?;
         // Initialize the transaction store.
         let transaction_store = TransactionStore::<N, TransactionMemory<N>>::open(transition_store)?;
         // Return the block storage.
@@ -104,6 +117,9 @@ impl<N: Network> BlockStorage<N> for BlockMemory<N> {
             rejected_or_aborted_transaction_id_map: MemoryMap::default(),
             confirmed_transactions_map: MemoryMap::default(),
             rejected_deployment_or_execution_map: MemoryMap::default(),
+            pruned_height_map: MemoryMap::default(),
+            address_activity_map: NestedMemoryMap::default(),
+            address_index_map: MemoryMap::default(),
             transaction_store,
         })
     }
@@ -183,6 +199,21 @@ impl<N: Network> BlockStorage<N> for BlockMemory<N> {
         &self.rejected_deployment_or_execution_map
     }
 
+    /// Returns the pruned height map.
+    fn pruned_height_map(&self) -> &Self::PrunedHeightMap {
+        &self.pruned_height_map
+    }
+
+    /// Returns the address activity map.
+    fn address_activity_map(&self) -> &Self::AddressActivityMap {
+        &self.address_activity_map
+    }
+
+    /// Returns the address index map.
+    fn address_index_map(&self) -> &Self::AddressIndexMap {
+        &self.address_index_map
+    }
+
     /// Returns the transaction store.
     fn transaction_store(&self) -> &TransactionStore<N, Self::TransactionStorage> {
         &self.transaction_store
@@ -13,7 +13,10 @@
 // limitations under the License.
 
 use crate::{
-    helpers::memory::{BlockMemory, FinalizeMemory, TransactionMemory, TransitionMemory},
+    helpers::{
+        memory::{BlockMemory, FinalizeMemory, TransactionMemory, TransitionMemory},
+        StorageMode,
+    },
     BlockStore,
     ConsensusStorage,
     FinalizeStore,
@@ -37,11 +40,14 @@ impl<N: Network> ConsensusStorage<N> for ConsensusMemory<N> {
     type TransitionStorage = TransitionMemory<N>;
 
     /// Initializes the consensus storage.
-    fn open(dev: Option<u16>) -> Result<Self> {
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        // Ensure the storage is not read-only, as there is no primary to follow in memory.
+        let storage_mode: StorageMode = storage.clone().into();
+        ensure!(!storage_mode.is_read_only(), "The in-memory consensus storage cannot be opened in read-only mode");
         // Initialize the finalize store.
-        let finalize_store = FinalizeStore::<N, FinalizeMemory<N>>::open(dev)?;
+        let finalize_store = FinalizeStore::<N, FinalizeMemory<N>>::open(storage.clone())?;
         // Initialize the block store.
-        let block_store = BlockStore::<N, BlockMemory<N>>::open(dev)?;
+        let block_store = BlockStore::<N, BlockMemory<N>>::open(storage.clone())?;
         // Return the consensus storage.
         Ok(Self {
             finalize_store,
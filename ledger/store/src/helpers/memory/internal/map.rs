@@ -159,6 +159,13 @@ impl<
         let _ = self.checkpoint.lock().pop();
     }
 
+    ///
+    /// Returns the number of atomic checkpoints that are currently set.
+    ///
+    fn checkpoint_depth(&self) -> usize {
+        self.checkpoint.lock().len()
+    }
+
     ///
     /// Removes all pending operations to the last `atomic_checkpoint`
     /// (or to `start_atomic` if no checkpoints have been created).
@@ -175,6 +175,13 @@ impl<
         let _ = self.checkpoint.lock().pop();
     }
 
+    ///
+    /// Returns the number of atomic checkpoints that are currently set.
+    ///
+    fn checkpoint_depth(&self) -> usize {
+        self.checkpoint.lock().len()
+    }
+
     ///
     /// Removes all pending operations to the last `atomic_checkpoint`
     /// (or to `start_atomic` if no checkpoints have been created).
@@ -334,6 +341,44 @@ impl<
         Ok(key_values)
     }
 
+    ///
+    /// Returns up to `limit` confirmed key-value pairs for the given map, in the order of their serialized keys,
+    /// starting at the given key (inclusive), or at the first key of the map if `start` is `None`.
+    ///
+    fn get_map_range_confirmed(&'a self, map: &M, start: Option<&K>, limit: usize) -> Result<Vec<(K, V)>> {
+        // Serialize 'm'.
+        let m = bincode::serialize(map)?;
+        // Serialize the start key, if one is given.
+        let start = start.map(bincode::serialize).transpose()?;
+
+        // Acquire the read locks on 'map' and 'map_inner'.
+        let map_lock = self.map.read();
+        let map_inner = self.map_inner.read();
+        // Retrieve the keys for the serialized map.
+        let Some(keys) = map_lock.get(&m) else {
+            return Ok(Default::default());
+        };
+
+        // Collect the key-value pairs, starting at the serialized start key.
+        let range = match &start {
+            Some(start) => keys.range::<Vec<u8>, _>(start..),
+            None => keys.range::<Vec<u8>, _>(..),
+        };
+        range
+            .take(limit)
+            .map(|k| {
+                // Deserialize 'k'.
+                let key: K = bincode::deserialize(k)?;
+                // Retrieve the value for the map-key.
+                let value = map_inner
+                    .get(&to_map_key(&m, k))
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Missing value in the nested map"))?;
+                Ok((key, value))
+            })
+            .collect()
+    }
+
     ///
     /// Returns the speculative key-value pairs for the given map, if it exists.
     ///
@@ -15,7 +15,10 @@
 #![allow(clippy::type_complexity)]
 
 use crate::{
-    helpers::memory::{MemoryMap, NestedMemoryMap},
+    helpers::{
+        memory::{MemoryMap, NestedMemoryMap},
+        StorageMode,
+    },
     CommitteeStorage,
     CommitteeStore,
     FinalizeStorage,
@@ -25,6 +28,7 @@ use console::{
     program::{Identifier, Plaintext, ProgramID, Value},
 };
 use ledger_committee::Committee;
+use synthesizer_program::FinalizeOperation;
 
 use indexmap::IndexSet;
 
@@ -37,8 +41,12 @@ pub struct FinalizeMemory<N: Network> {
     program_id_map: MemoryMap<ProgramID<N>, IndexSet<Identifier<N>>>,
     /// The key-value map.
     key_value_map: NestedMemoryMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>,
-    /// The optional development ID.
-    dev: Option<u16>,
+    /// The commit map.
+    commit_map: MemoryMap<u8, u32>,
+    /// The ratified map.
+    ratified_map: MemoryMap<u8, (u32, Vec<FinalizeOperation<N>>)>,
+    /// The storage mode.
+    storage_mode: StorageMode,
 }
 
 #[rustfmt::skip]
@@ -46,17 +54,21 @@ impl<N: Network> FinalizeStorage<N> for FinalizeMemory<N> {
     type CommitteeStorage = CommitteeMemory<N>;
     type ProgramIDMap = MemoryMap<ProgramID<N>, IndexSet<Identifier<N>>>;
     type KeyValueMap = NestedMemoryMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>;
+    type CommitMap = MemoryMap<u8, u32>;
+    type RatifiedMap = MemoryMap<u8, (u32, Vec<FinalizeOperation<N>>)>;
 
     /// Initializes the finalize storage.
-    fn open(dev: Option<u16>) -> Result<Self> {
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
         // Initialize the committee store.
-        let committee_store = CommitteeStore::<N, CommitteeMemory<N>>::open(dev)?;
+        let committee_store = CommitteeStore::<N, CommitteeMemory<N>>::open(storage.clone())?;
         // Return the finalize store.
         Ok(Self {
             committee_store,
             program_id_map: MemoryMap::default(),
             key_value_map: NestedMemoryMap::default(),
-            dev,
+            commit_map: MemoryMap::default(),
+            ratified_map: MemoryMap::default(),
+            storage_mode: storage.into(),
         })
     }
 
@@ -81,9 +93,19 @@ impl<N: Network> FinalizeStorage<N> for FinalizeMemory<N> {
         &self.key_value_map
     }
 
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16> {
-        self.dev
+    /// Returns the commit map.
+    fn commit_map(&self) -> &Self::CommitMap {
+        &self.commit_map
+    }
+
+    /// Returns the ratified map.
+    fn ratified_map(&self) -> &Self::RatifiedMap {
+        &self.ratified_map
+    }
+
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        &self.storage_mode
     }
 }
 
@@ -96,8 +118,8 @@ pub struct CommitteeMemory<N: Network> {
     round_to_height_map: MemoryMap<u64, u32>,
     /// The committee map.
     committee_map: MemoryMap<u32, Committee<N>>,
-    /// The optional development ID.
-    dev: Option<u16>,
+    /// The storage mode.
+    storage_mode: StorageMode,
 }
 
 #[rustfmt::skip]
@@ -107,12 +129,12 @@ impl<N: Network> CommitteeStorage<N> for CommitteeMemory<N> {
     type CommitteeMap = MemoryMap<u32, Committee<N>>;
 
     /// Initializes the committee storage.
-    fn open(dev: Option<u16>) -> Result<Self> {
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
         Ok(Self {
             current_round_map: MemoryMap::default(),
             round_to_height_map: MemoryMap::default(),
             committee_map: MemoryMap::default(),
-            dev,
+            storage_mode: storage.into(),
         })
     }
 
@@ -137,8 +159,8 @@ impl<N: Network> CommitteeStorage<N> for CommitteeMemory<N> {
         &self.committee_map
     }
 
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16> {
-        self.dev
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        &self.storage_mode
     }
 }
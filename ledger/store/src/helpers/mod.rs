@@ -19,6 +19,24 @@ pub mod rocksdb;
 #[cfg(test)]
 pub(crate) mod test_helpers;
 
+mod consistency;
+pub use consistency::*;
+
+mod id;
+pub use id::*;
+
+mod position;
+pub use position::*;
+
+mod pruned;
+pub use pruned::*;
+
+mod stats;
+pub use stats::*;
+
+mod storage_mode;
+pub use storage_mode::*;
+
 mod traits;
 pub use traits::*;
 
@@ -0,0 +1,102 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::network::prelude::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The position of a transition in the ledger, as the height of its block and its index in the block.
+/// A position is serialized as big-endian bytes, so the storage indexes that are keyed by positions
+/// are ordered by `(block height, transition index)`, and can be scanned from any position onwards.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TransitionPosition {
+    /// The block height.
+    height: u32,
+    /// The index of the transition in the block.
+    index: u32,
+}
+
+impl TransitionPosition {
+    /// Initializes a new position for the given block height and transition index.
+    pub const fn new(height: u32, index: u32) -> Self {
+        Self { height, index }
+    }
+
+    /// Returns the block height.
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the index of the transition in the block.
+    pub const fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the position that directly follows this one, if there is one.
+    pub fn successor(&self) -> Option<Self> {
+        match self.index.checked_add(1) {
+            Some(index) => Some(Self::new(self.height, index)),
+            None => self.height.checked_add(1).map(|height| Self::new(height, 0)),
+        }
+    }
+
+    /// Returns the big-endian bytes of the position.
+    fn to_be_bytes(self) -> [u8; 8] {
+        let mut bytes = [0u8; 8];
+        bytes[..4].copy_from_slice(&self.height.to_be_bytes());
+        bytes[4..].copy_from_slice(&self.index.to_be_bytes());
+        bytes
+    }
+}
+
+impl Serialize for TransitionPosition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_be_bytes().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TransitionPosition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = <[u8; 8]>::deserialize(deserializer)?;
+        let height = u32::from_be_bytes(bytes[..4].try_into().map_err(de::Error::custom)?);
+        let index = u32::from_be_bytes(bytes[4..].try_into().map_err(de::Error::custom)?);
+        Ok(Self::new(height, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialized_order() {
+        let positions = [
+            TransitionPosition::new(0, 0),
+            TransitionPosition::new(0, 1),
+            TransitionPosition::new(0, 256),
+            TransitionPosition::new(1, 0),
+            TransitionPosition::new(256, 0),
+            TransitionPosition::new(u32::MAX, u32::MAX),
+        ];
+        // Ensure the serialized positions are ordered like the positions, and round-trip.
+        for pair in positions.windows(2) {
+            let first = bincode::serialize(&pair[0]).unwrap();
+            let second = bincode::serialize(&pair[1]).unwrap();
+            assert!(first < second);
+            assert_eq!(bincode::deserialize::<TransitionPosition>(&first).unwrap(), pair[0]);
+        }
+        // Ensure the successor is the next position.
+        assert_eq!(TransitionPosition::new(1, 2).successor(), Some(TransitionPosition::new(1, 3)));
+        assert_eq!(TransitionPosition::new(1, u32::MAX).successor(), Some(TransitionPosition::new(2, 0)));
+        assert_eq!(TransitionPosition::new(u32::MAX, u32::MAX).successor(), None);
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+/// The error returned when a block is requested whose transaction bodies have been pruned from storage.
+/// Callers may distinguish it from other storage errors with `error.downcast_ref::<PrunedError>()`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PrunedError {
+    /// The height of the pruned block.
+    height: u32,
+}
+
+impl PrunedError {
+    /// Initializes a new pruned error for the given block height.
+    pub const fn new(height: u32) -> Self {
+        Self { height }
+    }
+
+    /// Returns the height of the pruned block.
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+impl fmt::Display for PrunedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Block {} has been pruned from storage", self.height)
+    }
+}
+
+impl std::error::Error for PrunedError {}
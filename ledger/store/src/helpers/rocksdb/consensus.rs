@@ -13,13 +13,19 @@
 // limitations under the License.
 
 use crate::{
-    helpers::rocksdb::{BlockDB, FinalizeDB, TransactionDB, TransitionDB},
+    helpers::{
+        rocksdb::{self, BlockDB, Database, FinalizeDB, TransactionDB, TransitionDB},
+        BackendStats,
+        StorageMode,
+    },
     BlockStore,
     ConsensusStorage,
     FinalizeStore,
 };
 use console::prelude::*;
 
+use std::path::{Path, PathBuf};
+
 /// An RocksDB consensus storage.
 #[derive(Clone)]
 pub struct ConsensusDB<N: Network> {
@@ -37,11 +43,11 @@ impl<N: Network> ConsensusStorage<N> for ConsensusDB<N> {
     type TransitionStorage = TransitionDB<N>;
 
     /// Initializes the consensus storage.
-    fn open(dev: Option<u16>) -> Result<Self> {
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
         // Initialize the finalize store.
-        let finalize_store = FinalizeStore::<N, FinalizeDB<N>>::open(dev)?;
+        let finalize_store = FinalizeStore::<N, FinalizeDB<N>>::open(storage.clone())?;
         // Initialize the block store.
-        let block_store = BlockStore::<N, BlockDB<N>>::open(dev)?;
+        let block_store = BlockStore::<N, BlockDB<N>>::open(storage.clone())?;
         // Return the consensus storage.
         Ok(Self {
             finalize_store,
@@ -58,4 +64,75 @@ impl<N: Network> ConsensusStorage<N> for ConsensusDB<N> {
     fn block_store(&self) -> &BlockStore<N, Self::BlockStorage> {
         &self.block_store
     }
+
+    /// Catches up with the writes of the primary database, if the storage is a read-only secondary instance.
+    fn catch_up_with_primary(&self) -> Result<()> {
+        rocksdb::RocksDB::open(N::ID, self.storage_mode())?.catch_up_with_primary()
+    }
+
+    /// Returns the statistics that RocksDB estimates for the whole database.
+    fn backend_stats(&self) -> Result<Option<BackendStats>> {
+        rocksdb::RocksDB::open(N::ID, self.storage_mode())?.backend_stats().map(Some)
+    }
+
+    /// Opens the staging storage for the given (empty) storage, in the sibling directory `{directory}.staging`.
+    /// Note: A staging directory that is left over from an interrupted swap is discarded.
+    fn open_staging<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        // Ensure the storage is empty.
+        let storage_mode: StorageMode = storage.into();
+        let directory = storage_mode.ledger_dir(N::ID);
+        ensure!(is_empty_dir(&directory)?, "Cannot stage the non-empty storage at '{}'", directory.display());
+        // Discard the leftover staging directory, if any.
+        let staging = staging_dir(&directory);
+        if staging.exists() {
+            std::fs::remove_dir_all(&staging)?;
+        }
+        // Open the staging storage.
+        Self::open(StorageMode::Custom(staging))
+    }
+
+    /// Closes the staging storage, moves its directory into the directory of the given storage,
+    /// and reopens it from there.
+    fn swap_in<S: Clone + Into<StorageMode>>(self, storage: S) -> Result<Self> {
+        let storage_mode = storage.into();
+        // Determine the staging directory and the directory of the given storage.
+        let staging = self.storage_mode().ledger_dir(N::ID);
+        let directory = storage_mode.ledger_dir(N::ID);
+        ensure!(
+            staging == staging_dir(&directory),
+            "The storage at '{}' is not staged for '{}'",
+            staging.display(),
+            directory.display()
+        );
+        // Close the staging storage.
+        // Note: The database is closed once its last handle is dropped, so the staging store must not be cloned.
+        drop(self);
+        // Ensure the storage is still empty, and move the staging directory in its place.
+        ensure!(
+            is_empty_dir(&directory)?,
+            "Cannot swap in the staging storage, as '{}' is not empty",
+            directory.display()
+        );
+        if directory.exists() {
+            std::fs::remove_dir(&directory)?;
+        }
+        std::fs::rename(&staging, &directory)?;
+        // Reopen the storage.
+        Self::open(storage_mode)
+    }
+}
+
+/// Returns the staging directory for the given directory.
+fn staging_dir(directory: &Path) -> PathBuf {
+    let mut staging = directory.as_os_str().to_owned();
+    staging.push(".staging");
+    PathBuf::from(staging)
+}
+
+/// Returns `true` if the given directory does not exist, or is empty.
+fn is_empty_dir(directory: &Path) -> Result<bool> {
+    match directory.exists() {
+        true => Ok(std::fs::read_dir(directory)?.next().is_none()),
+        false => Ok(true),
+    }
 }
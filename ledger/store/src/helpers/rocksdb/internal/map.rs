@@ -56,6 +56,9 @@ impl<
     /// Inserts the given key-value pair into the map.
     ///
     fn insert(&self, key: K, value: V) -> Result<()> {
+        // Ensure the database is writable.
+        self.database.ensure_writable()?;
+
         // Determine if an atomic batch is in progress.
         match self.is_atomic_in_progress() {
             // If a batch is in progress, add the key-value pair to the batch.
@@ -78,6 +81,9 @@ impl<
     /// Removes the key-value pair for the given key from the map.
     ///
     fn remove(&self, key: &K) -> Result<()> {
+        // Ensure the database is writable.
+        self.database.ensure_writable()?;
+
         // Determine if an atomic batch is in progress.
         match self.is_atomic_in_progress() {
             // If a batch is in progress, add the key to the batch.
@@ -137,6 +143,13 @@ impl<
         let _ = self.checkpoints.lock().pop();
     }
 
+    ///
+    /// Returns the number of atomic checkpoints that are currently set.
+    ///
+    fn checkpoint_depth(&self) -> usize {
+        self.checkpoints.lock().len()
+    }
+
     ///
     /// Removes all pending operations to the last `atomic_checkpoint`
     /// (or to `start_atomic` if no checkpoints have been created).
@@ -222,7 +235,10 @@ impl<
             // Empty the collection of pending operations.
             let batch = mem::take(&mut *self.database.atomic_batch.lock());
             // Execute all the operations atomically.
-            self.database.rocksdb.write(batch)?;
+            // Note: An empty batch is skipped, as a read-only database rejects all writes.
+            if !batch.is_empty() {
+                self.database.rocksdb.write(batch)?;
+            }
             // Ensure that the database atomic batch is empty.
             assert!(self.database.atomic_batch.lock().is_empty());
         }
@@ -12,8 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-mod id;
-pub use id::*;
+pub use crate::helpers::id::*;
 
 mod map;
 pub use map::*;
@@ -21,47 +20,65 @@ pub use map::*;
 mod nested_map;
 pub use nested_map::*;
 
+mod schema;
+pub use schema::*;
+
 #[cfg(test)]
 mod tests;
 
+use crate::helpers::{BackendStats, StorageMode};
+
 use anyhow::{bail, Result};
-use once_cell::sync::OnceCell;
+use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     borrow::Borrow,
+    collections::HashMap,
     marker::PhantomData,
     ops::Deref,
+    path::PathBuf,
     sync::{
         atomic::{AtomicBool, AtomicUsize},
         Arc,
+        Weak,
     },
 };
 
 pub const PREFIX_LEN: usize = 4; // N::ID (u16) + DataID (u16)
 
+/// The databases that are open in this process, by the canonical directory of each instance.
+/// The registry only holds weak references, so that a database is closed once its last handle is dropped.
+static DATABASES: Lazy<Mutex<HashMap<PathBuf, WeakRocksDB>>> = Lazy::new(Default::default);
+
 pub trait Database {
     /// Opens the database.
-    fn open(network_id: u16, dev: Option<u16>) -> Result<Self>
+    fn open<S: Clone + Into<StorageMode>>(network_id: u16, storage: S) -> Result<Self>
     where
         Self: Sized;
 
-    /// Opens the map with the given `network_id`, `(optional) development ID`, and `map_id` from storage.
-    fn open_map<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned, T: Into<u16>>(
+    /// Opens the map with the given `network_id`, `storage mode`, and `map_id` from storage.
+    fn open_map<
+        S: Clone + Into<StorageMode>,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+        T: Into<u16>,
+    >(
         network_id: u16,
-        dev: Option<u16>,
+        storage: S,
         map_id: T,
     ) -> Result<DataMap<K, V>>;
 
-    /// Opens the nested map with the given `network_id`, `(optional) development ID`, and `map_id` from storage.
+    /// Opens the nested map with the given `network_id`, `storage mode`, and `map_id` from storage.
     fn open_nested_map<
+        S: Clone + Into<StorageMode>,
         M: Serialize + DeserializeOwned,
         K: Serialize + DeserializeOwned,
         V: Serialize + DeserializeOwned,
         T: Into<u16>,
     >(
         network_id: u16,
-        dev: Option<u16>,
+        storage: S,
         map_id: T,
     ) -> Result<NestedDataMap<M, K, V>>;
 }
@@ -73,14 +90,17 @@ pub struct RocksDB {
     rocksdb: Arc<rocksdb::DB>,
     /// The network ID.
     network_id: u16,
-    /// The optional development ID.
-    dev: Option<u16>,
+    /// The storage mode.
+    storage_mode: StorageMode,
     /// The low-level database transaction that gets executed atomically at the end
     /// of a real-run `atomic_finalize` or the outermost `atomic_batch_scope`.
     pub(super) atomic_batch: Arc<Mutex<rocksdb::WriteBatch>>,
     /// The depth of the current atomic write batch; it gets incremented with every call
     /// to `start_atomic` and decremented with each call to `finish_atomic`.
     pub(super) atomic_depth: Arc<AtomicUsize>,
+    /// The registration of the database in the registry of open databases.
+    /// Note: This field is declared last, so that it is dropped after the RocksDB instance is closed.
+    registration: Arc<Registration>,
 }
 
 impl Deref for RocksDB {
@@ -91,59 +111,96 @@ impl Deref for RocksDB {
     }
 }
 
+/// The registration of an open RocksDB database, by the canonical directory of its instance.
+struct Registration {
+    /// The canonical directory of the instance.
+    instance: PathBuf,
+}
+
+impl Drop for Registration {
+    /// Removes the database from the registry of open databases, once its last handle has closed it.
+    fn drop(&mut self) {
+        let mut databases = DATABASES.lock();
+        if databases.get(&self.instance).map_or(false, |database| database.rocksdb.strong_count() == 0) {
+            databases.remove(&self.instance);
+        }
+    }
+}
+
+/// A weak reference to an open RocksDB database, which is held in the registry of open databases.
+struct WeakRocksDB {
+    /// The RocksDB instance.
+    rocksdb: Weak<rocksdb::DB>,
+    /// The network ID.
+    network_id: u16,
+    /// The storage mode.
+    storage_mode: StorageMode,
+    /// The low-level database transaction of the database.
+    atomic_batch: Weak<Mutex<rocksdb::WriteBatch>>,
+    /// The depth of the current atomic write batch of the database.
+    atomic_depth: Weak<AtomicUsize>,
+    /// The registration of the database.
+    registration: Weak<Registration>,
+}
+
+impl WeakRocksDB {
+    /// Initializes a weak reference to the given database.
+    fn new(database: &RocksDB) -> Self {
+        Self {
+            rocksdb: Arc::downgrade(&database.rocksdb),
+            network_id: database.network_id,
+            storage_mode: database.storage_mode.clone(),
+            atomic_batch: Arc::downgrade(&database.atomic_batch),
+            atomic_depth: Arc::downgrade(&database.atomic_depth),
+            registration: Arc::downgrade(&database.registration),
+        }
+    }
+
+    /// Returns a new handle to the database, if it is still open.
+    fn upgrade(&self) -> Option<RocksDB> {
+        Some(RocksDB {
+            rocksdb: self.rocksdb.upgrade()?,
+            network_id: self.network_id,
+            storage_mode: self.storage_mode.clone(),
+            atomic_batch: self.atomic_batch.upgrade()?,
+            atomic_depth: self.atomic_depth.upgrade()?,
+            registration: self.registration.upgrade()?,
+        })
+    }
+}
+
 impl Database for RocksDB {
     /// Opens the database.
     ///
     /// In production mode, the database opens directory `~/.aleo/storage/ledger-{network}`.
     /// In development mode, the database opens directory `/path/to/repo/.ledger-{network}-{id}`.
-    fn open(network_id: u16, dev: Option<u16>) -> Result<Self> {
-        static DB: OnceCell<RocksDB> = OnceCell::new();
-
-        // Retrieve the database.
-        let database = DB
-            .get_or_try_init(|| {
-                // Customize database options.
-                let mut options = rocksdb::Options::default();
-                options.set_compression_type(rocksdb::DBCompressionType::Lz4);
-
-                // Register the prefix length.
-                let prefix_extractor = rocksdb::SliceTransform::create_fixed_prefix(PREFIX_LEN);
-                options.set_prefix_extractor(prefix_extractor);
-
-                let primary = aleo_std::aleo_ledger_dir(network_id, dev);
-                let rocksdb = {
-                    options.increase_parallelism(2);
-                    options.set_max_background_jobs(4);
-                    options.create_if_missing(true);
-
-                    Arc::new(rocksdb::DB::open(&options, primary)?)
-                };
-
-                Ok::<_, anyhow::Error>(RocksDB {
-                    rocksdb,
-                    network_id,
-                    dev,
-                    atomic_batch: Default::default(),
-                    atomic_depth: Default::default(),
-                })
-            })?
-            .clone();
-
-        // Ensure the database network ID and development ID match.
-        match database.network_id == network_id && database.dev == dev {
-            true => Ok(database),
-            false => bail!("Mismatching network ID or development ID in the database"),
-        }
+    /// In custom mode, the database opens the given directory.
+    ///
+    /// Each directory is opened at most once per process, and subsequent calls return the same
+    /// instance, so multiple ledgers may be opened side by side from distinct directories.
+    ///
+    /// This method fails if the schema version of the database does not match `SCHEMA_VERSION`.
+    fn open<S: Clone + Into<StorageMode>>(network_id: u16, storage: S) -> Result<Self> {
+        // Open the database.
+        let database = Self::open_unchecked(network_id, storage)?;
+        // Ensure the schema of the database is compatible.
+        database.check_schema_version()?;
+        Ok(database)
     }
 
-    /// Opens the map with the given `network_id`, `(optional) development ID`, and `map_id` from storage.
-    fn open_map<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned, T: Into<u16>>(
+    /// Opens the map with the given `network_id`, `storage mode`, and `map_id` from storage.
+    fn open_map<
+        S: Clone + Into<StorageMode>,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+        T: Into<u16>,
+    >(
         network_id: u16,
-        dev: Option<u16>,
+        storage: S,
         map_id: T,
     ) -> Result<DataMap<K, V>> {
         // Open the RocksDB database.
-        let database = Self::open(network_id, dev)?;
+        let database = Self::open(network_id, storage)?;
 
         // Combine contexts to create a new scope.
         let mut context = database.network_id.to_le_bytes().to_vec();
@@ -159,19 +216,20 @@ impl Database for RocksDB {
         })))
     }
 
-    /// Opens the nested map with the given `network_id`, `(optional) development ID`, and `map_id` from storage.
+    /// Opens the nested map with the given `network_id`, `storage mode`, and `map_id` from storage.
     fn open_nested_map<
+        S: Clone + Into<StorageMode>,
         M: Serialize + DeserializeOwned,
         K: Serialize + DeserializeOwned,
         V: Serialize + DeserializeOwned,
         T: Into<u16>,
     >(
         network_id: u16,
-        dev: Option<u16>,
+        storage: S,
         map_id: T,
     ) -> Result<NestedDataMap<M, K, V>> {
         // Open the RocksDB database.
-        let database = Self::open(network_id, dev)?;
+        let database = Self::open(network_id, storage)?;
 
         // Combine contexts to create a new scope.
         let mut context = database.network_id.to_le_bytes().to_vec();
@@ -188,6 +246,134 @@ impl Database for RocksDB {
     }
 }
 
+impl RocksDB {
+    /// Returns the storage mode of the database.
+    pub fn storage_mode(&self) -> &StorageMode {
+        &self.storage_mode
+    }
+
+    /// Returns `true` if the database is a read-only secondary instance.
+    pub fn is_read_only(&self) -> bool {
+        self.storage_mode.is_read_only()
+    }
+
+    /// Catches up with the writes of the primary database, if this is a read-only secondary instance.
+    pub fn catch_up_with_primary(&self) -> Result<()> {
+        if !self.is_read_only() {
+            bail!("Only a read-only secondary database can catch up with its primary")
+        }
+        Ok(self.rocksdb.try_catch_up_with_primary()?)
+    }
+
+    /// Returns the statistics that RocksDB estimates for the whole database.
+    pub fn backend_stats(&self) -> Result<BackendStats> {
+        // Retrieves the given integer property of the database.
+        let property = |name: &str| -> Result<u64> {
+            match self.rocksdb.property_int_value(name)? {
+                Some(value) => Ok(value),
+                None => bail!("The RocksDB property '{name}' is not available"),
+            }
+        };
+        Ok(BackendStats::new(property("rocksdb.estimate-num-keys")?, property("rocksdb.total-sst-files-size")?))
+    }
+
+    /// Ensures the database is writable.
+    fn ensure_writable(&self) -> Result<()> {
+        match self.is_read_only() {
+            true => bail!("The database at '{}' is open in read-only mode", self.path().display()),
+            false => Ok(()),
+        }
+    }
+
+    /// Opens the database, without checking its schema version.
+    ///
+    /// In production mode, the database opens directory `~/.aleo/storage/ledger-{network}`.
+    /// In development mode, the database opens directory `/path/to/repo/.ledger-{network}-{id}`.
+    /// In custom mode, the database opens the given directory.
+    /// In secondary mode, the database of the primary storage mode is opened as a read-only secondary instance,
+    /// which follows the writes of the primary instance (possibly in another process) via `catch_up_with_primary`.
+    ///
+    /// Each directory is opened at most once per process, and subsequent calls return the same
+    /// instance while any handle to it is alive, so multiple ledgers may be opened side by side
+    /// from distinct directories. Directories are compared by their canonical paths.
+    /// If the last handle to the instance is being dropped, this method waits until the instance is closed.
+    pub(super) fn open_unchecked<S: Clone + Into<StorageMode>>(network_id: u16, storage: S) -> Result<Self> {
+        // Determine the directory of the database.
+        let storage_mode = storage.into();
+        let primary = storage_mode.ledger_dir(network_id);
+        // Determine the directory of the instance, as a secondary instance is distinct from its primary.
+        let instance = match &storage_mode {
+            StorageMode::Secondary(_, secondary) => secondary.clone(),
+            _ => primary.clone(),
+        };
+        // Resolve the canonical directory of the instance, so that aliases of a directory open the same instance.
+        // Note: The directory is created first, as it may not exist yet.
+        std::fs::create_dir_all(&instance)?;
+        let instance = instance.canonicalize()?;
+
+        // Retrieve the database, opening it if it is not open yet.
+        let database = loop {
+            let mut databases = DATABASES.lock();
+            match databases.get(&instance).map(WeakRocksDB::upgrade) {
+                Some(Some(database)) => break database,
+                // The last handle to the database is being dropped, and the directory remains locked until
+                // the database is closed, which removes it from the registry. Wait until it is removed.
+                Some(None) => {
+                    drop(databases);
+                    std::thread::yield_now();
+                }
+                None => {
+                    // Customize database options.
+                    let mut options = rocksdb::Options::default();
+                    options.set_compression_type(rocksdb::DBCompressionType::Lz4);
+
+                    // Register the prefix length.
+                    let prefix_extractor = rocksdb::SliceTransform::create_fixed_prefix(PREFIX_LEN);
+                    options.set_prefix_extractor(prefix_extractor);
+
+                    let rocksdb = {
+                        options.increase_parallelism(2);
+                        options.set_max_background_jobs(4);
+
+                        match &storage_mode {
+                            StorageMode::Secondary(_, secondary) => {
+                                // A secondary instance must keep all files open, to follow the primary.
+                                options.set_max_open_files(-1);
+                                Arc::new(rocksdb::DB::open_as_secondary(&options, &primary, secondary)?)
+                            }
+                            _ => {
+                                options.create_if_missing(true);
+                                Arc::new(rocksdb::DB::open(&options, &primary)?)
+                            }
+                        }
+                    };
+
+                    let database = RocksDB {
+                        rocksdb,
+                        network_id,
+                        storage_mode: storage_mode.clone(),
+                        atomic_batch: Default::default(),
+                        atomic_depth: Default::default(),
+                        registration: Arc::new(Registration { instance: instance.clone() }),
+                    };
+                    databases.insert(instance.clone(), WeakRocksDB::new(&database));
+                    break database;
+                }
+            }
+        };
+
+        // Ensure the database network ID, instance directory, and access mode match.
+        // Note: The storage modes may differ, as distinct storage modes may resolve to the same directory.
+        match database.network_id == network_id
+            && database.registration.instance == instance
+            && database.is_read_only() == storage_mode.is_read_only()
+        {
+            true => Ok(database),
+            false => bail!("Mismatching network ID, directory, or access mode in the database"),
+        }
+    }
+}
+
 impl RocksDB {
     /// Opens the test database.
     #[cfg(any(test, feature = "test"))]
@@ -229,20 +415,22 @@ impl RocksDB {
                 // options.set_bottommost_compression_type(rocksdb::DBCompressionType::None);
                 // options.set_write_buffer_size(1 << 28);
 
-                Arc::new(rocksdb::DB::open(&options, primary)?)
+                Arc::new(rocksdb::DB::open(&options, &primary)?)
             };
 
             Ok::<_, anyhow::Error>(RocksDB {
                 rocksdb,
                 network_id: u16::MAX,
-                dev,
+                storage_mode: dev.into(),
                 atomic_batch: Default::default(),
                 atomic_depth: Default::default(),
+                // Note: The test database is not added to the registry of open databases.
+                registration: Arc::new(Registration { instance: primary }),
             })
         }?;
 
         // Ensure the database development ID match.
-        match database.dev == dev {
+        match database.storage_mode.dev() == dev {
             true => Ok(database),
             false => bail!("Mismatching development ID in the test database"),
         }
@@ -108,6 +108,9 @@ impl<
     /// Inserts the given map-key-value pair.
     ///
     fn insert(&self, map: M, key: K, value: V) -> Result<()> {
+        // Ensure the database is writable.
+        self.database.ensure_writable()?;
+
         // Determine if an atomic batch is in progress.
         match self.is_atomic_in_progress() {
             // If a batch is in progress, add the map-key-value pair to the batch.
@@ -127,6 +130,9 @@ impl<
     /// Removes the given map.
     ///
     fn remove_map(&self, map: &M) -> Result<()> {
+        // Ensure the database is writable.
+        self.database.ensure_writable()?;
+
         // Determine if an atomic batch is in progress.
         match self.is_atomic_in_progress() {
             // If a batch is in progress, add the map-None pair to the batch.
@@ -172,6 +178,9 @@ impl<
     /// Removes the key-value pair for the given map and key.
     ///
     fn remove_key(&self, map: &M, key: &K) -> Result<()> {
+        // Ensure the database is writable.
+        self.database.ensure_writable()?;
+
         // Determine if an atomic batch is in progress.
         match self.is_atomic_in_progress() {
             // If a batch is in progress, add the key to the batch.
@@ -228,6 +237,13 @@ impl<
         let _ = self.checkpoints.lock().pop();
     }
 
+    ///
+    /// Returns the number of atomic checkpoints that are currently set.
+    ///
+    fn checkpoint_depth(&self) -> usize {
+        self.checkpoints.lock().len()
+    }
+
     ///
     /// Removes all pending operations to the last `atomic_checkpoint`
     /// (or to `start_atomic` if no checkpoints have been created).
@@ -325,7 +341,10 @@ impl<
             // Empty the collection of pending operations.
             let batch = mem::take(&mut *self.database.atomic_batch.lock());
             // Execute all the operations atomically.
-            self.database.rocksdb.write(batch)?;
+            // Note: An empty batch is skipped, as a read-only database rejects all writes.
+            if !batch.is_empty() {
+                self.database.rocksdb.write(batch)?;
+            }
             // Ensure that the database atomic batch is empty.
             assert!(self.database.atomic_batch.lock().is_empty());
         }
@@ -425,6 +444,43 @@ impl<
         Ok(entries)
     }
 
+    ///
+    /// Returns up to `limit` confirmed key-value pairs for the given map, in the order of their serialized keys,
+    /// starting at the given key (inclusive), or at the first key of the map if `start` is `None`.
+    ///
+    fn get_map_range_confirmed(&'a self, map: &M, start: Option<&K>, limit: usize) -> Result<Vec<(K, V)>> {
+        // Prepare the prefix of the map, and the raw key to start at.
+        let prefixed_map = self.create_prefixed_map(map)?;
+        let raw_start = match start {
+            Some(start) => self.create_prefixed_map_key(map, start)?,
+            None => prefixed_map.clone(),
+        };
+
+        // Initialize a vector for the entries.
+        let mut entries = Vec::new();
+
+        // Seek to the start key, and iterate forward over the entries of the map.
+        let mut read_options = rocksdb::ReadOptions::default();
+        read_options.set_prefix_same_as_start(true);
+        let iterator = self
+            .database
+            .iterator_opt(rocksdb::IteratorMode::From(&raw_start, rocksdb::Direction::Forward), read_options);
+        for entry in iterator {
+            if entries.len() >= limit {
+                break;
+            }
+            let (map_key, value) = entry?;
+            // If the entry no longer belongs to the map, we've moved past the relevant keys.
+            let Some(entry_key) = map_key.strip_prefix(prefixed_map.as_slice()) else {
+                break;
+            };
+            // Deserialize the key and the value, and push them to the vector.
+            entries.push((bincode::deserialize(entry_key)?, bincode::deserialize(&value)?));
+        }
+
+        Ok(entries)
+    }
+
     ///
     /// Returns the speculative key-value pairs for the given map, if it exists.
     ///
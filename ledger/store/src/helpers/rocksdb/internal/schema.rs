@@ -0,0 +1,552 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{helpers::TransitionPosition, TransactionType};
+
+use anyhow::{anyhow, ensure};
+use console::{
+    network::Network,
+    program::{Identifier, ProgramID},
+};
+use rocksdb::{Direction, IteratorMode, ReadOptions, WriteBatch};
+use std::collections::BTreeMap;
+use synthesizer_snark::Proof;
+
+/// The schema version of the databases written by this version of snarkVM.
+/// Note: This MUST be incremented, and a corresponding `Migration` registered in `Migrations::registered`,
+/// whenever the layout of a map or the serialization of its keys or values changes.
+pub const SCHEMA_VERSION: u32 = 6;
+
+/// The schema version of populated databases that were written before schema versions were recorded.
+pub const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
+/// The default number of entries that a migration rewrites in each write batch.
+pub const MIGRATION_BATCH_SIZE: usize = 10_000;
+
+/// The persisted state of an in-progress migration, as
+/// `(schema version, step index, last migrated raw key, number of migrated entries)`.
+type MigrationCheckpoint = (u32, u32, Option<Vec<u8>>, u64);
+
+/// A function that rewrites a raw entry of a map, given its key (without the map prefix) and value,
+/// into a new raw entry, or into `None` if the entry should be deleted.
+type RewriteFn = Box<dyn Fn(&[u8], &[u8]) -> Result<Option<(Vec<u8>, Vec<u8>)>> + Send + Sync>;
+
+/// A function that derives raw entries of a map from a raw entry of another map, given its key
+/// (without the map prefix) and value, and a reader for any other entries that it depends on.
+type BackfillFn = Box<dyn Fn(&MigrationReader, &[u8], &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>> + Send + Sync>;
+
+/// The action of a migration step on each entry of its source map.
+enum StepAction {
+    /// Rewrites the entry into the target map, or deletes it.
+    Rewrite(RewriteFn),
+    /// Keeps the entry, and writes the entries derived from it into the target map.
+    Backfill(BackfillFn),
+}
+
+/// A step of a migration, which rewrites each entry of a source map into a target map,
+/// or backfills the target map from the entries of the source map.
+pub struct MigrationStep {
+    /// The ID of the map to read the entries from.
+    source: u16,
+    /// The ID of the map to write the rewritten or derived entries to.
+    target: u16,
+    /// The action on each entry.
+    action: StepAction,
+}
+
+impl MigrationStep {
+    /// Initializes a step that rewrites each `(key, value)` entry of the `source` map into the `target` map,
+    /// or deletes it if `rewrite` returns `None`.
+    ///
+    /// If `source` and `target` are the same map, `rewrite` must preserve the key of each entry.
+    /// Otherwise, each entry is moved out of the `source` map.
+    pub fn new<K0, V0, K1, V1, F>(source: MapID, target: MapID, rewrite: F) -> Self
+    where
+        K0: DeserializeOwned,
+        V0: DeserializeOwned,
+        K1: Serialize,
+        V1: Serialize,
+        F: 'static + Fn(K0, V0) -> Result<Option<(K1, V1)>> + Send + Sync,
+    {
+        let rewrite = move |key: &[u8], value: &[u8]| -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+            match rewrite(bincode::deserialize(key)?, bincode::deserialize(value)?)? {
+                Some((key, value)) => Ok(Some((bincode::serialize(&key)?, bincode::serialize(&value)?))),
+                None => Ok(None),
+            }
+        };
+        Self { source: source.into(), target: target.into(), action: StepAction::Rewrite(Box::new(rewrite)) }
+    }
+
+    /// Initializes a step that deletes every entry of the given map, without deserializing it.
+    pub fn clear(map_id: MapID) -> Self {
+        let map_id = map_id.into();
+        Self { source: map_id, target: map_id, action: StepAction::Rewrite(Box::new(|_, _| Ok(None))) }
+    }
+
+    /// Initializes a step that keeps each `(key, value)` entry of the `source` map, and inserts the
+    /// `(map, key, value)` entries that `backfill` derives from it into the `target` nested map.
+    pub fn backfill_nested<K0, V0, M, K1, V1, F>(source: MapID, target: MapID, backfill: F) -> Self
+    where
+        K0: DeserializeOwned,
+        V0: DeserializeOwned,
+        M: Serialize,
+        K1: Serialize,
+        V1: Serialize,
+        F: 'static + Fn(&MigrationReader, K0, V0) -> Result<Vec<(M, K1, V1)>> + Send + Sync,
+    {
+        let backfill = move |reader: &MigrationReader, key: &[u8], value: &[u8]| {
+            let entries = backfill(reader, bincode::deserialize(key)?, bincode::deserialize(value)?)?;
+            entries
+                .into_iter()
+                .map(|(map, key, value)| {
+                    // Lay out the key as in `NestedDataMap`, which prefixes the map with its serialized size.
+                    let map_size: u32 = bincode::serialized_size(&map)?.try_into()?;
+                    let mut raw_key = map_size.to_le_bytes().to_vec();
+                    bincode::serialize_into(&mut raw_key, &map)?;
+                    bincode::serialize_into(&mut raw_key, &key)?;
+                    Ok((raw_key, bincode::serialize(&value)?))
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        Self { source: source.into(), target: target.into(), action: StepAction::Backfill(Box::new(backfill)) }
+    }
+
+    /// Initializes a step that rewrites the value of each entry of the given map in place.
+    pub fn rewrite_values<K, V0, V1, F>(map_id: MapID, rewrite: F) -> Self
+    where
+        K: Serialize + DeserializeOwned,
+        V0: DeserializeOwned,
+        V1: Serialize,
+        F: 'static + Fn(&K, V0) -> Result<V1> + Send + Sync,
+    {
+        Self::new(map_id, map_id, move |key: K, value: V0| {
+            let value = rewrite(&key, value)?;
+            Ok(Some((key, value)))
+        })
+    }
+}
+
+/// A reader of the entries of the database, for the steps that derive entries from other maps.
+pub struct MigrationReader<'a> {
+    /// The database being migrated.
+    database: &'a RocksDB,
+}
+
+impl MigrationReader<'_> {
+    /// Returns the value for the given key in the given map, if it exists.
+    pub fn get<K: Serialize, V: DeserializeOwned>(&self, map_id: MapID, key: &K) -> Result<Option<V>> {
+        let mut raw_key = self.database.map_prefix(map_id.into());
+        bincode::serialize_into(&mut raw_key, key)?;
+        match self.database.get_pinned(raw_key)? {
+            Some(value) => Ok(Some(bincode::deserialize(&value)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// A migration of the database from the previous schema version to `version`.
+pub struct Migration {
+    /// The schema version that the migration upgrades the database to.
+    version: u32,
+    /// A short description of the migration.
+    description: &'static str,
+    /// The steps of the migration, which are executed in order.
+    steps: Vec<MigrationStep>,
+}
+
+impl Migration {
+    /// Initializes a migration from `version - 1` to `version`.
+    pub fn new(version: u32, description: &'static str, steps: Vec<MigrationStep>) -> Self {
+        Self { version, description, steps }
+    }
+
+    /// Returns the schema version that the migration upgrades the database to.
+    pub const fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the description of the migration.
+    pub const fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+/// The progress of a migration, which is reported after each write batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationProgress {
+    /// The schema version that the database is being migrated to.
+    pub version: u32,
+    /// The description of the migration.
+    pub description: &'static str,
+    /// The index of the current step.
+    pub step: usize,
+    /// The number of steps in the migration.
+    pub num_steps: usize,
+    /// The number of entries of the current step that have been migrated.
+    pub num_migrated: u64,
+}
+
+/// A registry of migrations between schema versions.
+pub struct Migrations {
+    /// The migrations, keyed by the schema version that they upgrade the database to.
+    migrations: BTreeMap<u32, Migration>,
+    /// The number of entries rewritten in each write batch.
+    batch_size: usize,
+}
+
+impl Default for Migrations {
+    fn default() -> Self {
+        Self { migrations: Default::default(), batch_size: MIGRATION_BATCH_SIZE }
+    }
+}
+
+impl Migrations {
+    /// Returns the migrations between all schema versions up to `SCHEMA_VERSION`, for the given network.
+    pub fn registered<N: Network>() -> Self {
+        let mut migrations = Self::default();
+        for migration in [
+            // Schema version 2 records the schema version, and does not change the layout of any map.
+            Migration::new(2, "Record the schema version of the database", vec![]),
+            // Schema version 3 adds the pruned height and the pruning depth of the block store.
+            // Note: The earlier databases were never pruned, and do not enable pruning, which the missing entries denote.
+            Migration::new(3, "Track the pruned blocks", vec![]),
+            // Schema version 4 adds the address activity index, and the flag that enables it.
+            // Note: The index is disabled while its flag is missing, and `enable_address_index` backfills it.
+            Migration::new(4, "Add the address activity index", vec![]),
+            // Schema version 5 keys the program calls by `(program ID, function name)` and transition position.
+            // The entries of the earlier layout are removed, and the calls are backfilled from the stored blocks.
+            Migration::new(5, "Index the program calls by function and transition position", vec![
+                MigrationStep::clear(MapID::Transition(TransitionMap::Call)),
+                MigrationStep::backfill_nested(
+                    MapID::Block(BlockMap::ID),
+                    MapID::Transition(TransitionMap::Call),
+                    backfill_calls::<N>,
+                ),
+            ]),
+            // Schema version 6 adds the commit marker and the ratified finalize operations of the finalize store.
+            // Note: A missing commit marker denotes that no commit is in progress. The ratified finalize operations
+            // cannot be derived from storage, and are recorded from the next block onwards.
+            Migration::new(6, "Track the finalize commits and the ratified finalize operations", vec![]),
+        ] {
+            migrations.register(migration).expect("The registered migrations must have distinct schema versions");
+        }
+        migrations
+    }
+
+    /// Sets the number of entries rewritten in each write batch.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Registers the given migration.
+    pub fn register(&mut self, migration: Migration) -> Result<()> {
+        ensure!(migration.version > 1, "A migration must upgrade to a schema version greater than 1");
+        ensure!(
+            !self.migrations.contains_key(&migration.version),
+            "A migration to schema version {} is already registered",
+            migration.version
+        );
+        self.migrations.insert(migration.version, migration);
+        Ok(())
+    }
+
+    /// Migrates the given database to the `target` schema version, resuming any interrupted migration,
+    /// and invokes `progress` after each write batch. Returns the new schema version of the database.
+    pub fn migrate(
+        &self,
+        database: &RocksDB,
+        target: u32,
+        mut progress: impl FnMut(&MigrationProgress),
+    ) -> Result<u32> {
+        // Retrieve the current schema version of the database.
+        let mut version = match database.schema_version()? {
+            Some(version) => version,
+            // If the database is empty, there is nothing to migrate.
+            None if database.is_empty()? => {
+                database.set_schema_version(target)?;
+                return Ok(target);
+            }
+            None => UNVERSIONED_SCHEMA_VERSION,
+        };
+        ensure!(version <= target, "Cannot migrate the database from schema version {version} down to {target}");
+
+        // Retrieve the checkpoint of an interrupted migration, if there is one.
+        let mut checkpoint = database.migration_checkpoint()?;
+
+        while version < target {
+            let next = version + 1;
+            let migration = self
+                .migrations
+                .get(&next)
+                .ok_or_else(|| anyhow!("There is no migration from schema version {version} to {next}"))?;
+
+            // Resume from the checkpoint, if it belongs to this migration.
+            let (first_step, mut last_key, mut num_migrated) = match checkpoint.take() {
+                Some((checkpoint_version, step, last_key, num_migrated)) => {
+                    ensure!(
+                        checkpoint_version == next,
+                        "Found an interrupted migration to schema version {checkpoint_version}, expected {next}"
+                    );
+                    (step as usize, last_key, num_migrated)
+                }
+                None => (0, None, 0),
+            };
+
+            for (index, step) in migration.steps.iter().enumerate().skip(first_step) {
+                self.migrate_step(database, migration, index, step, last_key.take(), num_migrated, &mut progress)?;
+                num_migrated = 0;
+            }
+
+            // Record the new schema version, and clear the checkpoint.
+            let mut batch = WriteBatch::default();
+            batch.put(database.metadata_key(MetadataMap::SchemaVersion), bincode::serialize(&next)?);
+            batch.delete(database.metadata_key(MetadataMap::MigrationCheckpoint));
+            database.write(batch)?;
+
+            version = next;
+        }
+
+        Ok(version)
+    }
+
+    /// Rewrites the entries of the given step, starting after `last_key`, in batches.
+    /// Each batch is written together with a checkpoint, so that an interrupted step resumes after its last batch.
+    #[allow(clippy::too_many_arguments)]
+    fn migrate_step(
+        &self,
+        database: &RocksDB,
+        migration: &Migration,
+        index: usize,
+        step: &MigrationStep,
+        mut last_key: Option<Vec<u8>>,
+        mut num_migrated: u64,
+        progress: &mut impl FnMut(&MigrationProgress),
+    ) -> Result<()> {
+        let source_prefix = database.map_prefix(step.source);
+        let target_prefix = database.map_prefix(step.target);
+        let reader = MigrationReader { database };
+
+        loop {
+            let mut batch = WriteBatch::default();
+            let mut num_in_batch = 0;
+
+            // Iterate over the entries of the source map, starting at the last migrated key.
+            let mut read_options = ReadOptions::default();
+            read_options.set_prefix_same_as_start(true);
+            let start = last_key.clone().unwrap_or_else(|| source_prefix.clone());
+            for entry in database.iterator_opt(IteratorMode::From(&start, Direction::Forward), read_options) {
+                let (key, value) = entry?;
+                if !key.starts_with(&source_prefix) {
+                    break;
+                }
+                // Skip the last migrated key, which was already rewritten in the previous batch.
+                if last_key.as_deref() == Some(&*key) {
+                    continue;
+                }
+
+                match &step.action {
+                    StepAction::Rewrite(rewrite) => match rewrite(&key[PREFIX_LEN..], &value)? {
+                        Some((new_key, new_value)) => {
+                            if step.source == step.target {
+                                ensure!(new_key == key[PREFIX_LEN..], "A migration step cannot change keys in place");
+                            } else {
+                                batch.delete(&key);
+                            }
+                            let mut raw_key = target_prefix.clone();
+                            raw_key.extend_from_slice(&new_key);
+                            batch.put(raw_key, new_value);
+                        }
+                        None => batch.delete(&key),
+                    },
+                    StepAction::Backfill(backfill) => {
+                        ensure!(step.source != step.target, "A migration step cannot backfill a map from itself");
+                        for (new_key, new_value) in backfill(&reader, &key[PREFIX_LEN..], &value)? {
+                            let mut raw_key = target_prefix.clone();
+                            raw_key.extend_from_slice(&new_key);
+                            batch.put(raw_key, new_value);
+                        }
+                    }
+                }
+
+                last_key = Some(key.to_vec());
+                num_in_batch += 1;
+                if num_in_batch == self.batch_size {
+                    break;
+                }
+            }
+
+            // Write the batch along with the checkpoint.
+            num_migrated += num_in_batch as u64;
+            let checkpoint: MigrationCheckpoint = (migration.version, index as u32, last_key.clone(), num_migrated);
+            batch.put(database.metadata_key(MetadataMap::MigrationCheckpoint), bincode::serialize(&checkpoint)?);
+            database.write(batch)?;
+
+            progress(&MigrationProgress {
+                version: migration.version,
+                description: migration.description,
+                step: index,
+                num_steps: migration.steps.len(),
+                num_migrated,
+            });
+
+            if num_in_batch < self.batch_size {
+                break;
+            }
+        }
+
+        // Reset the checkpoint to the start of the next step.
+        let checkpoint: MigrationCheckpoint = (migration.version, index as u32 + 1, None, 0);
+        database.put(database.metadata_key(MetadataMap::MigrationCheckpoint), bincode::serialize(&checkpoint)?)?;
+        Ok(())
+    }
+}
+
+/// Returns the program calls of the block at the given height, as `((program ID, function name), transition position, transition ID)`,
+/// in the order of the transitions of the block. The calls of a block whose transactions are not stored are not indexed.
+#[allow(clippy::type_complexity)]
+fn backfill_calls<N: Network>(
+    reader: &MigrationReader,
+    height: u32,
+    block_hash: N::BlockHash,
+) -> Result<Vec<((ProgramID<N>, Identifier<N>), TransitionPosition, N::TransitionID)>> {
+    // Retrieve the transaction IDs of the block.
+    let Some(transaction_ids) =
+        reader.get::<_, Vec<N::TransactionID>>(MapID::Block(BlockMap::Transactions), &block_hash)?
+    else {
+        return Ok(vec![]);
+    };
+
+    // Retrieve the transition IDs, in the order of `Transaction::transitions`.
+    let mut transition_ids = Vec::new();
+    for transaction_id in transaction_ids {
+        // Retrieves the ID of the fee transition of the transaction.
+        let fee_transition_id = || match reader
+            .get::<_, (N::TransitionID, N::StateRoot, Option<Proof<N>>)>(MapID::Fee(FeeMap::Fee), &transaction_id)?
+        {
+            Some((transition_id, ..)) => Ok(transition_id),
+            None => bail!("Transaction '{transaction_id}' of block {height} is missing its fee"),
+        };
+        match reader.get(MapID::Transaction(TransactionMap::ID), &transaction_id)? {
+            Some(TransactionType::Execute) => {
+                match reader
+                    .get::<_, (Vec<N::TransitionID>, bool)>(MapID::Execution(ExecutionMap::ID), &transaction_id)?
+                {
+                    Some((execution_transition_ids, has_fee)) => {
+                        transition_ids.extend(execution_transition_ids);
+                        if has_fee {
+                            transition_ids.push(fee_transition_id()?);
+                        }
+                    }
+                    None => bail!("Transaction '{transaction_id}' of block {height} is missing its execution"),
+                }
+            }
+            Some(TransactionType::Deploy) | Some(TransactionType::Fee) => transition_ids.push(fee_transition_id()?),
+            None => bail!("Transaction '{transaction_id}' of block {height} is missing"),
+        }
+    }
+
+    // Retrieve the program ID and function name of each transition.
+    let mut calls = Vec::with_capacity(transition_ids.len());
+    for (index, transition_id) in (0u32..).zip(transition_ids) {
+        match reader.get(MapID::Transition(TransitionMap::Locator), &transition_id)? {
+            Some(locator) => calls.push((locator, TransitionPosition::new(height, index), transition_id)),
+            None => bail!("Transition '{transition_id}' of block {height} is missing"),
+        }
+    }
+    Ok(calls)
+}
+
+impl RocksDB {
+    /// Migrates the database with the given `network_id` and storage mode to `SCHEMA_VERSION`,
+    /// using the given migrations, and invokes `progress` after each write batch.
+    pub fn migrate<S: Clone + Into<StorageMode>>(
+        network_id: u16,
+        storage: S,
+        migrations: &Migrations,
+        progress: impl FnMut(&MigrationProgress),
+    ) -> Result<u32> {
+        let database = Self::open_unchecked(network_id, storage)?;
+        // Ensure the database is writable, as a secondary instance must wait for its primary to migrate.
+        database.ensure_writable()?;
+        migrations.migrate(&database, SCHEMA_VERSION, progress)
+    }
+
+    /// Returns the recorded schema version of the database, or `None` if it was not recorded.
+    pub fn schema_version(&self) -> Result<Option<u32>> {
+        match self.get_pinned(self.metadata_key(MetadataMap::SchemaVersion))? {
+            Some(version) => Ok(Some(bincode::deserialize(&version)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Ensures the schema version of the database is `SCHEMA_VERSION`, and records it if the database is new.
+    pub(super) fn check_schema_version(&self) -> Result<()> {
+        let version = match self.schema_version()? {
+            Some(version) => version,
+            None => {
+                let version = if self.is_empty()? { SCHEMA_VERSION } else { UNVERSIONED_SCHEMA_VERSION };
+                // Note: A read-only database cannot record its schema version, which is left to the primary.
+                if !self.is_read_only() {
+                    self.set_schema_version(version)?;
+                }
+                version
+            }
+        };
+
+        if self.migration_checkpoint()?.is_some() {
+            bail!("The database has an interrupted migration - resume it with 'RocksDB::migrate'")
+        }
+        match version.cmp(&SCHEMA_VERSION) {
+            core::cmp::Ordering::Equal => Ok(()),
+            core::cmp::Ordering::Less => bail!(
+                "The database has schema version {version}, and must be migrated to schema version {SCHEMA_VERSION} with 'RocksDB::migrate'"
+            ),
+            core::cmp::Ordering::Greater => bail!(
+                "The database has schema version {version}, which is newer than the supported schema version {SCHEMA_VERSION}"
+            ),
+        }
+    }
+
+    /// Records the given schema version of the database.
+    fn set_schema_version(&self, version: u32) -> Result<()> {
+        Ok(self.put(self.metadata_key(MetadataMap::SchemaVersion), bincode::serialize(&version)?)?)
+    }
+
+    /// Returns the checkpoint of an interrupted migration, if there is one.
+    fn migration_checkpoint(&self) -> Result<Option<MigrationCheckpoint>> {
+        match self.get_pinned(self.metadata_key(MetadataMap::MigrationCheckpoint))? {
+            Some(checkpoint) => Ok(Some(bincode::deserialize(&checkpoint)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `true` if the database contains no entries.
+    fn is_empty(&self) -> Result<bool> {
+        Ok(self.iterator(IteratorMode::Start).next().transpose()?.is_none())
+    }
+
+    /// Returns the prefix of the map with the given ID.
+    fn map_prefix(&self, map_id: u16) -> Vec<u8> {
+        let mut prefix = self.network_id.to_le_bytes().to_vec();
+        prefix.extend_from_slice(&map_id.to_le_bytes());
+        prefix
+    }
+
+    /// Returns the key of the given metadata entry.
+    fn metadata_key(&self, metadata: MetadataMap) -> Vec<u8> {
+        self.map_prefix(MapID::Metadata(metadata).into())
+    }
+}
@@ -12,10 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::helpers::{
-    rocksdb::{MapID, RocksDB, TestMap as TestMapID},
-    Map,
-    MapRead,
+use crate::{
+    helpers::{
+        rocksdb::{
+            BlockDB,
+            Database,
+            MapID,
+            MetadataMap,
+            Migration,
+            MigrationStep,
+            Migrations,
+            RocksDB,
+            TestMap as TestMapID,
+            SCHEMA_VERSION,
+            UNVERSIONED_SCHEMA_VERSION,
+        },
+        Map,
+        MapRead,
+        StorageMode,
+        TransitionPosition,
+    },
+    BlockStore,
 };
 use console::{
     network::{Network, Testnet3},
@@ -23,7 +40,12 @@ use console::{
     types::Scalar,
 };
 
+use anyhow::bail;
 use serial_test::serial;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 pub(crate) fn temp_dir() -> std::path::PathBuf {
     tempfile::tempdir().expect("Failed to open temporary directory").into_path()
@@ -39,6 +61,243 @@ fn test_open() {
     let _storage = RocksDB::open_testing(temp_dir(), None).expect("Failed to open storage");
 }
 
+#[test]
+#[serial]
+fn test_open_multiple_custom_paths() {
+    let (first_dir, second_dir) = (temp_dir(), temp_dir());
+
+    // Open two databases side by side.
+    let first = RocksDB::open_map(Testnet3::ID, first_dir.clone(), MapID::Test(TestMapID::Test))
+        .expect("Failed to open the first data map");
+    let second = RocksDB::open_map(Testnet3::ID, second_dir, MapID::Test(TestMapID::Test))
+        .expect("Failed to open the second data map");
+
+    first.insert(1u32, "first".to_string()).expect("Failed to insert");
+    second.insert(1u32, "second".to_string()).expect("Failed to insert");
+    assert_eq!(first.get_confirmed(&1).unwrap().map(|v| v.to_string()), Some("first".to_string()));
+    assert_eq!(second.get_confirmed(&1).unwrap().map(|v| v.to_string()), Some("second".to_string()));
+
+    // Ensure reopening a directory returns the same database.
+    let reopened =
+        RocksDB::open_map::<_, u32, String, _>(Testnet3::ID, first_dir.clone(), MapID::Test(TestMapID::Test))
+            .expect("Failed to reopen the first data map");
+    assert_eq!(reopened.get_confirmed(&1).unwrap().map(|v| v.to_string()), Some("first".to_string()));
+
+    // Ensure a directory cannot be reopened for a different network.
+    assert!(RocksDB::open(Testnet3::ID + 1, first_dir).is_err());
+}
+
+#[test]
+#[serial]
+fn test_open_registry() {
+    let directory = temp_dir();
+    let instance = directory.canonicalize().unwrap();
+
+    // Ensure aliases of a directory open the same database.
+    let database = RocksDB::open(Testnet3::ID, directory.clone()).expect("Failed to open the database");
+    let alias = RocksDB::open(Testnet3::ID, directory.join("nested").join("..")).expect("Failed to open the alias");
+    assert!(Arc::ptr_eq(&*database, &*alias));
+    assert!(super::DATABASES.lock().contains_key(&instance));
+
+    // Ensure the database remains registered while any handle is alive.
+    drop(alias);
+    assert!(super::DATABASES.lock().contains_key(&instance));
+
+    // Ensure the database is removed from the registry once its last handle is dropped.
+    drop(database);
+    assert!(!super::DATABASES.lock().contains_key(&instance));
+
+    // Ensure the database can be opened again.
+    let reopened = RocksDB::open(Testnet3::ID, directory).expect("Failed to reopen the database");
+    assert!(super::DATABASES.lock().contains_key(&instance));
+    drop(reopened);
+}
+
+#[test]
+#[serial]
+fn test_open_registry_concurrently() {
+    let directory = temp_dir();
+
+    // Ensure the database can be reopened while its last handle is dropped concurrently.
+    let threads = (0..4)
+        .map(|_| {
+            let directory = directory.clone();
+            std::thread::spawn(move || {
+                for _ in 0..25 {
+                    drop(RocksDB::open(Testnet3::ID, directory.clone()).expect("Failed to open the database"));
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().expect("Failed to join the thread");
+    }
+}
+
+#[test]
+#[serial]
+fn test_open_secondary() {
+    let (primary_dir, secondary_dir) = (temp_dir(), temp_dir());
+    let secondary_mode = StorageMode::secondary(primary_dir.clone(), secondary_dir);
+
+    // Open the primary database, and write an entry.
+    let primary = RocksDB::open_map(Testnet3::ID, primary_dir.clone(), MapID::Test(TestMapID::Test))
+        .expect("Failed to open the primary data map");
+    primary.insert(1u32, "first".to_string()).expect("Failed to insert");
+
+    // Open the secondary database, which reads the entries of the primary.
+    let secondary =
+        RocksDB::open_map::<_, u32, String, _>(Testnet3::ID, secondary_mode.clone(), MapID::Test(TestMapID::Test))
+            .expect("Failed to open the secondary data map");
+    assert_eq!(secondary.get_confirmed(&1).unwrap().map(|v| v.to_string()), Some("first".to_string()));
+
+    // Ensure the secondary database rejects all writes.
+    assert!(secondary.insert(2u32, "second".to_string()).is_err());
+    assert!(secondary.remove(&1).is_err());
+    secondary.start_atomic();
+    assert!(secondary.insert(2u32, "second".to_string()).is_err());
+    secondary.abort_atomic();
+    assert!(RocksDB::migrate(Testnet3::ID, secondary_mode.clone(), &Migrations::default(), |_| ()).is_err());
+
+    // Ensure the secondary database follows the primary once it catches up.
+    primary.insert(2u32, "second".to_string()).expect("Failed to insert");
+    let database = RocksDB::open(Testnet3::ID, secondary_mode).expect("Failed to reopen the secondary database");
+    assert!(database.is_read_only());
+    database.catch_up_with_primary().expect("Failed to catch up with the primary");
+    assert_eq!(secondary.get_confirmed(&2).unwrap().map(|v| v.to_string()), Some("second".to_string()));
+
+    // Ensure the primary database cannot catch up with itself.
+    let database = RocksDB::open(Testnet3::ID, primary_dir).expect("Failed to reopen the primary database");
+    assert!(database.catch_up_with_primary().is_err());
+}
+
+#[test]
+#[serial]
+fn test_schema_version() {
+    let dir = temp_dir();
+
+    // Ensure a new database records the current schema version.
+    let database = RocksDB::open(Testnet3::ID, dir.clone()).expect("Failed to open storage");
+    assert_eq!(database.schema_version().unwrap(), Some(SCHEMA_VERSION));
+
+    // Upgrade the database to a newer schema version.
+    let mut migrations = Migrations::default();
+    migrations.register(Migration::new(SCHEMA_VERSION + 1, "Upgrade the schema version", vec![])).unwrap();
+    assert_eq!(migrations.migrate(&database, SCHEMA_VERSION + 1, |_| ()).unwrap(), SCHEMA_VERSION + 1);
+
+    // Ensure the database is refused, as its schema version is newer than the supported one.
+    assert!(RocksDB::open(Testnet3::ID, dir).is_err());
+}
+
+#[test]
+#[serial]
+fn test_migrate_unversioned_database() {
+    let dir = temp_dir();
+
+    // Populate a database, and remove its schema version, as written before schema versions were recorded.
+    let map = RocksDB::open_map::<_, u32, u64, _>(Testnet3::ID, dir.clone(), MapID::Test(TestMapID::Test))
+        .expect("Failed to open data map");
+    map.insert(1, 1).expect("Failed to insert");
+    let database = RocksDB::open(Testnet3::ID, dir.clone()).expect("Failed to open storage");
+    let mut schema_version_key = Testnet3::ID.to_le_bytes().to_vec();
+    schema_version_key.extend_from_slice(&u16::from(MapID::Metadata(MetadataMap::SchemaVersion)).to_le_bytes());
+    database.delete(schema_version_key).expect("Failed to remove the schema version");
+
+    // Ensure the unversioned database is refused, and is recorded with the unversioned schema version.
+    assert!(RocksDB::open(Testnet3::ID, dir.clone()).is_err());
+    assert_eq!(database.schema_version().unwrap(), Some(UNVERSIONED_SCHEMA_VERSION));
+
+    // Migrate the database with the registered migrations, and ensure it is accepted.
+    let version = RocksDB::migrate(Testnet3::ID, dir.clone(), &Migrations::registered::<Testnet3>(), |_| ());
+    assert_eq!(version.unwrap(), SCHEMA_VERSION);
+    assert!(RocksDB::open(Testnet3::ID, dir).is_ok());
+    assert_eq!(map.get_confirmed(&1).unwrap().map(|value| *value), Some(1));
+}
+
+#[test]
+#[serial]
+fn test_migrate_backfills_calls() {
+    let rng = &mut TestRng::default();
+    let dir = temp_dir();
+
+    // Store the genesis block.
+    let block = ledger_test_helpers::sample_genesis_block(rng);
+    let block_store =
+        BlockStore::<Testnet3, BlockDB<Testnet3>>::open(dir.clone()).expect("Failed to open the block store");
+    block_store.insert(&block).expect("Failed to insert the block");
+
+    // Remove the program calls, and record the schema version before they were indexed by function and position.
+    let transition_store = block_store.transition_store();
+    for (index, transition) in (0u32..).zip(block.transactions().transitions()) {
+        transition_store.remove_call(transition, block.height(), index).expect("Failed to remove the call");
+    }
+    let database = RocksDB::open(Testnet3::ID, dir.clone()).expect("Failed to open storage");
+    let mut schema_version_key = Testnet3::ID.to_le_bytes().to_vec();
+    schema_version_key.extend_from_slice(&u16::from(MapID::Metadata(MetadataMap::SchemaVersion)).to_le_bytes());
+    database.put(schema_version_key, bincode::serialize(&4u32).unwrap()).expect("Failed to set the schema version");
+
+    // Migrate the database, and ensure the program calls are backfilled.
+    let version = RocksDB::migrate(Testnet3::ID, dir, &Migrations::registered::<Testnet3>(), |_| ());
+    assert_eq!(version.unwrap(), SCHEMA_VERSION);
+    let mut num_calls = 0;
+    for (index, transition) in (0u32..).zip(block.transactions().transitions()) {
+        let position = TransitionPosition::new(block.height(), index);
+        let call = transition_store.get_call(transition.program_id(), transition.function_name(), position).unwrap();
+        assert_eq!(call, Some(*transition.id()));
+        num_calls += 1;
+    }
+    assert!(num_calls > 0);
+}
+
+#[test]
+#[serial]
+fn test_migration_resumes_after_interruption() {
+    let dir = temp_dir();
+
+    let map = RocksDB::open_map::<_, u32, u64, _>(Testnet3::ID, dir.clone(), MapID::Test(TestMapID::Test))
+        .expect("Failed to open data map");
+    for i in 0..10 {
+        map.insert(i, i as u64).expect("Failed to insert");
+    }
+
+    // Register a migration that increments each value, and that is interrupted once at the 8th entry.
+    let is_interrupted = Arc::new(AtomicBool::new(true));
+    let interrupt = is_interrupted.clone();
+    let mut migrations = Migrations::default().with_batch_size(3);
+    migrations
+        .register(Migration::new(SCHEMA_VERSION + 1, "Increment the test values", vec![MigrationStep::rewrite_values(
+            MapID::Test(TestMapID::Test),
+            move |key: &u32, value: u64| {
+                if *key == 7 && interrupt.swap(false, Ordering::SeqCst) {
+                    bail!("Interrupted the migration")
+                }
+                Ok(value + 1)
+            },
+        )]))
+        .unwrap();
+
+    // Ensure the interrupted migration keeps the batches that were written.
+    let database = RocksDB::open(Testnet3::ID, dir.clone()).expect("Failed to open storage");
+    let mut num_migrated = vec![];
+    assert!(
+        migrations.migrate(&database, SCHEMA_VERSION + 1, |progress| num_migrated.push(progress.num_migrated)).is_err()
+    );
+    assert_eq!(num_migrated, vec![3, 6]);
+    assert!(!is_interrupted.load(Ordering::SeqCst));
+
+    // Ensure the database is refused while the migration is interrupted.
+    assert!(RocksDB::open(Testnet3::ID, dir).is_err());
+
+    // Resume the migration, and ensure each entry was migrated exactly once.
+    let version =
+        migrations.migrate(&database, SCHEMA_VERSION + 1, |progress| num_migrated.push(progress.num_migrated));
+    assert_eq!(version.unwrap(), SCHEMA_VERSION + 1);
+    assert_eq!(num_migrated, vec![3, 6, 9, 10]);
+    for i in 0..10 {
+        assert_eq!(map.get_confirmed(&i).unwrap().map(|value| *value), Some(i as u64 + 1));
+    }
+}
+
 #[test]
 #[serial]
 fn test_open_map() {
@@ -127,6 +386,26 @@ fn test_insert_and_values() {
     assert_eq!(None, values.next());
 }
 
+#[test]
+#[serial]
+fn test_backend_stats() {
+    let directory = temp_dir();
+    let database = RocksDB::open(Testnet3::ID, directory.clone()).expect("Failed to open the database");
+    let map =
+        RocksDB::open_map(Testnet3::ID, directory, MapID::Test(TestMapID::Test)).expect("Failed to open data map");
+
+    // Insert the entries, and flush them into an SST file.
+    for i in 0..10u32 {
+        map.insert(i, i.to_string()).expect("Failed to insert");
+    }
+    database.flush().expect("Failed to flush the database");
+
+    // Ensure the estimates account for the entries.
+    let stats = database.backend_stats().expect("Failed to retrieve the backend statistics");
+    assert!(stats.estimated_num_keys() >= 10);
+    assert!(stats.sst_files_size() > 0);
+}
+
 // #[test]
 // #[serial]
 // fn test_export_import() {
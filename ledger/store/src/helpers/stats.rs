@@ -0,0 +1,243 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::{Map, MapID, NestedMap};
+use console::network::prelude::*;
+
+use core::hash::Hash;
+use std::collections::BTreeMap;
+
+/// The statistics of a single map in the ledger storage.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MapStats {
+    /// The number of confirmed entries.
+    num_entries: u64,
+    /// The approximate size of the confirmed entries, in bytes.
+    num_bytes: u64,
+    /// The number of operations in the pending atomic batch.
+    num_pending: u64,
+    /// The number of atomic checkpoints that are currently set.
+    checkpoint_depth: u64,
+}
+
+impl MapStats {
+    /// Returns the number of confirmed entries.
+    pub const fn num_entries(&self) -> u64 {
+        self.num_entries
+    }
+
+    /// Returns the approximate size of the confirmed entries, in bytes.
+    /// This is the serialized size of the keys and values, and does not include any backend overhead.
+    pub const fn num_bytes(&self) -> u64 {
+        self.num_bytes
+    }
+
+    /// Returns the number of operations in the pending atomic batch.
+    pub const fn num_pending(&self) -> u64 {
+        self.num_pending
+    }
+
+    /// Returns the number of atomic checkpoints that are currently set.
+    pub const fn checkpoint_depth(&self) -> u64 {
+        self.checkpoint_depth
+    }
+}
+
+/// The statistics that the storage backend estimates for the whole database.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BackendStats {
+    /// The estimated number of keys, from the RocksDB property `rocksdb.estimate-num-keys`.
+    estimated_num_keys: u64,
+    /// The total size of the SST files, in bytes, from the RocksDB property `rocksdb.total-sst-files-size`.
+    sst_files_size: u64,
+}
+
+impl BackendStats {
+    /// Initializes the backend statistics.
+    pub const fn new(estimated_num_keys: u64, sst_files_size: u64) -> Self {
+        Self { estimated_num_keys, sst_files_size }
+    }
+
+    /// Returns the estimated number of keys in the database.
+    /// This is a cheap estimate, which counts overwritten and deleted keys until they are compacted.
+    pub const fn estimated_num_keys(&self) -> u64 {
+        self.estimated_num_keys
+    }
+
+    /// Returns the total size of the SST files of the database, in bytes.
+    /// This is the size on disk, and does not include the entries that are still in the memtables.
+    pub const fn sst_files_size(&self) -> u64 {
+        self.sst_files_size
+    }
+
+    /// Publishes the statistics as gauges.
+    #[cfg(feature = "metrics")]
+    pub fn publish_metrics(&self) {
+        use metrics::store;
+
+        metrics::gauge(store::ESTIMATED_KEYS, self.estimated_num_keys as f64);
+        metrics::gauge(store::SST_FILES_BYTES, self.sst_files_size as f64);
+    }
+}
+
+/// The statistics of the ledger storage, per map.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageStats {
+    /// The statistics of each map, keyed by map ID.
+    maps: BTreeMap<MapID, MapStats>,
+    /// The statistics of the storage backend, if it provides them.
+    backend: Option<BackendStats>,
+}
+
+impl StorageStats {
+    /// Returns the statistics of each map, keyed by map ID.
+    pub const fn maps(&self) -> &BTreeMap<MapID, MapStats> {
+        &self.maps
+    }
+
+    /// Returns the statistics of the given map, if it was recorded.
+    pub fn get(&self, map: &MapID) -> Option<&MapStats> {
+        self.maps.get(map)
+    }
+
+    /// Returns the statistics of the storage backend, if it provides them.
+    pub const fn backend(&self) -> Option<&BackendStats> {
+        self.backend.as_ref()
+    }
+
+    /// Returns the total number of confirmed entries.
+    pub fn num_entries(&self) -> u64 {
+        self.maps.values().map(MapStats::num_entries).sum()
+    }
+
+    /// Returns the total approximate size of the confirmed entries, in bytes.
+    pub fn num_bytes(&self) -> u64 {
+        self.maps.values().map(MapStats::num_bytes).sum()
+    }
+
+    /// Returns the total number of operations in the pending atomic batches.
+    pub fn num_pending(&self) -> u64 {
+        self.maps.values().map(MapStats::num_pending).sum()
+    }
+
+    /// Returns the deepest atomic checkpoint stack across all maps.
+    pub fn checkpoint_depth(&self) -> u64 {
+        self.maps.values().map(MapStats::checkpoint_depth).max().unwrap_or_default()
+    }
+
+    /// Returns the maps sorted by their approximate size, largest first.
+    pub fn largest_maps(&self) -> Vec<(MapID, MapStats)> {
+        let mut maps = self.maps.iter().map(|(id, stats)| (*id, *stats)).collect::<Vec<_>>();
+        maps.sort_by(|(id_a, a), (id_b, b)| b.num_bytes.cmp(&a.num_bytes).then(id_a.cmp(id_b)));
+        maps
+    }
+
+    /// Publishes the statistics as gauges, labeled by map ID.
+    #[cfg(feature = "metrics")]
+    pub fn publish_metrics(&self) {
+        use metrics::store;
+
+        for (id, stats) in &self.maps {
+            let label = id.to_string();
+            metrics::gauge_with_label(store::MAP_ENTRIES, stats.num_entries as f64, "map", label.clone());
+            metrics::gauge_with_label(store::MAP_BYTES, stats.num_bytes as f64, "map", label.clone());
+            metrics::gauge_with_label(store::PENDING_OPERATIONS, stats.num_pending as f64, "map", label.clone());
+            metrics::gauge_with_label(store::CHECKPOINT_DEPTH, stats.checkpoint_depth as f64, "map", label);
+        }
+        metrics::gauge(store::TOTAL_ENTRIES, self.num_entries() as f64);
+        metrics::gauge(store::TOTAL_BYTES, self.num_bytes() as f64);
+        if let Some(backend) = &self.backend {
+            backend.publish_metrics();
+        }
+    }
+}
+
+impl StorageStats {
+    /// Records the statistics of the storage backend.
+    pub(crate) fn record_backend(&mut self, backend: BackendStats) -> Result<()> {
+        ensure!(self.backend.replace(backend).is_none(), "The statistics of the storage backend were recorded twice");
+        Ok(())
+    }
+
+    /// Records the statistics of the given map.
+    pub(crate) fn record_map<'a, K, V, M>(&mut self, id: MapID, map: &'a M) -> Result<()>
+    where
+        K: 'a + Copy + Clone + PartialEq + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+        V: 'a + Clone + PartialEq + Eq + Serialize + DeserializeOwned + Send + Sync,
+        M: Map<'a, K, V>,
+    {
+        let mut stats = MapStats::default();
+        for (key, value) in map.iter_confirmed() {
+            stats.num_entries += 1;
+            stats.num_bytes += bincode::serialized_size(&*key)? + bincode::serialized_size(&*value)?;
+        }
+        stats.num_pending = map.iter_pending().count() as u64;
+        stats.checkpoint_depth = map.checkpoint_depth() as u64;
+        self.insert(id, stats)
+    }
+
+    /// Records the statistics of the given nested map.
+    pub(crate) fn record_nested_map<'a, M, K, V, NM>(&mut self, id: MapID, map: &'a NM) -> Result<()>
+    where
+        M: 'a + Copy + Clone + PartialEq + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+        K: 'a + Clone + PartialEq + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+        V: 'a + Clone + PartialEq + Eq + Serialize + DeserializeOwned + Send + Sync,
+        NM: NestedMap<'a, M, K, V>,
+    {
+        let mut stats = MapStats::default();
+        for (map_key, key, value) in map.iter_confirmed() {
+            stats.num_entries += 1;
+            stats.num_bytes += bincode::serialized_size(&*map_key)?
+                + bincode::serialized_size(&*key)?
+                + bincode::serialized_size(&*value)?;
+        }
+        stats.num_pending = map.iter_pending().count() as u64;
+        stats.checkpoint_depth = map.checkpoint_depth() as u64;
+        self.insert(id, stats)
+    }
+
+    /// Inserts the statistics of a map, ensuring that each map is only recorded once.
+    fn insert(&mut self, id: MapID, stats: MapStats) -> Result<()> {
+        ensure!(self.maps.insert(id, stats).is_none(), "The statistics of '{id}' were recorded twice");
+        Ok(())
+    }
+}
+
+impl Display for StorageStats {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} maps, {} entries, {} bytes, {} pending operations",
+            self.maps.len(),
+            self.num_entries(),
+            self.num_bytes(),
+            self.num_pending()
+        )?;
+        if let Some(backend) = &self.backend {
+            write!(
+                f,
+                "\n  backend: ~{} keys, {} bytes in SST files",
+                backend.estimated_num_keys, backend.sst_files_size
+            )?;
+        }
+        for (id, stats) in self.largest_maps() {
+            write!(
+                f,
+                "\n  {id}: {} entries, {} bytes, {} pending, checkpoint depth {}",
+                stats.num_entries, stats.num_bytes, stats.num_pending, stats.checkpoint_depth
+            )?;
+        }
+        Ok(())
+    }
+}
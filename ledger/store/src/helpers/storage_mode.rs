@@ -0,0 +1,122 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::{Path, PathBuf};
+
+/// The storage configuration of a ledger, which determines where its database is located.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StorageMode {
+    /// The production mode, which stores the ledger in `~/.aleo/storage/ledger-{network}`.
+    Production,
+    /// The development mode, which stores the ledger in `/path/to/repo/.ledger-{network}-{id}`.
+    Development(u16),
+    /// The custom mode, which stores the ledger in the given directory.
+    Custom(PathBuf),
+    /// The read-only secondary mode, which follows the ledger of the given primary storage mode,
+    /// and keeps its own metadata in the given directory.
+    Secondary(Box<StorageMode>, PathBuf),
+}
+
+impl StorageMode {
+    /// Returns the optional development ID.
+    pub const fn dev(&self) -> Option<u16> {
+        match self {
+            Self::Development(id) => Some(*id),
+            Self::Production | Self::Custom(..) => None,
+            Self::Secondary(primary, _) => primary.dev(),
+        }
+    }
+
+    /// Initializes the read-only secondary mode, which follows the ledger of the given primary storage mode,
+    /// and keeps its own metadata in the given directory.
+    pub fn secondary<S: Into<StorageMode>, P: Into<PathBuf>>(primary: S, path: P) -> Self {
+        Self::Secondary(Box::new(primary.into()), path.into())
+    }
+
+    /// Returns `true` if the storage is read-only.
+    pub const fn is_read_only(&self) -> bool {
+        matches!(self, Self::Secondary(..))
+    }
+
+    /// Returns the directory of the ledger for the given network ID.
+    #[cfg(feature = "rocks")]
+    pub fn ledger_dir(&self, network_id: u16) -> PathBuf {
+        match self {
+            Self::Production => aleo_std::aleo_ledger_dir(network_id, None),
+            Self::Development(id) => aleo_std::aleo_ledger_dir(network_id, Some(*id)),
+            Self::Custom(path) => path.clone(),
+            Self::Secondary(primary, _) => primary.ledger_dir(network_id),
+        }
+    }
+}
+
+impl From<Option<u16>> for StorageMode {
+    /// Initializes the storage mode from an optional development ID.
+    fn from(dev: Option<u16>) -> Self {
+        match dev {
+            Some(id) => Self::Development(id),
+            None => Self::Production,
+        }
+    }
+}
+
+impl From<u16> for StorageMode {
+    /// Initializes the development storage mode with the given ID.
+    fn from(id: u16) -> Self {
+        Self::Development(id)
+    }
+}
+
+impl From<PathBuf> for StorageMode {
+    /// Initializes the custom storage mode with the given directory.
+    fn from(path: PathBuf) -> Self {
+        Self::Custom(path)
+    }
+}
+
+impl From<&Path> for StorageMode {
+    /// Initializes the custom storage mode with the given directory.
+    fn from(path: &Path) -> Self {
+        Self::Custom(path.to_path_buf())
+    }
+}
+
+impl From<&StorageMode> for StorageMode {
+    fn from(storage: &StorageMode) -> Self {
+        storage.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_dev() {
+        assert_eq!(StorageMode::from(None), StorageMode::Production);
+        assert_eq!(StorageMode::from(Some(3)), StorageMode::Development(3));
+        assert_eq!(StorageMode::from(Some(3)).dev(), Some(3));
+        assert_eq!(StorageMode::from(PathBuf::from("/tmp/ledger")).dev(), None);
+    }
+
+    #[test]
+    fn test_secondary() {
+        let secondary = StorageMode::secondary(3, "/tmp/secondary");
+        assert_eq!(secondary, StorageMode::Secondary(Box::new(StorageMode::Development(3)), "/tmp/secondary".into()));
+        assert_eq!(secondary.dev(), Some(3));
+        assert!(secondary.is_read_only());
+        assert!(!StorageMode::Development(3).is_read_only());
+        assert!(!StorageMode::Production.is_read_only());
+    }
+}
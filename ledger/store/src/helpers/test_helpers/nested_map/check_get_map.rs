@@ -121,10 +121,48 @@ fn check_get_same_map(map: &impl for<'a> NestedMap<'a, usize, usize, String>) {
     ensure_map_is_empty(map);
 }
 
+fn check_get_map_range(map: &impl for<'a> NestedMap<'a, usize, usize, String>) {
+    ensure_map_is_empty(map);
+
+    const MAP: usize = 1;
+
+    // Insert the items into the map, and into the neighbouring maps.
+    for i in 0..NUM_ITEMS {
+        map.insert(MAP - 1, i, i.to_string()).unwrap();
+        map.insert(MAP, i, i.to_string()).unwrap();
+        map.insert(MAP + 1, i, i.to_string()).unwrap();
+    }
+    let items = (0..NUM_ITEMS).map(|i| (i, i.to_string())).collect::<Vec<_>>();
+
+    // Ensure the range is bounded by the limit and the map, and starts at the given key.
+    assert_eq!(map.get_map_range_confirmed(&MAP, None, NUM_TOTAL_ITEMS).unwrap(), items);
+    assert_eq!(map.get_map_range_confirmed(&MAP, None, 3).unwrap(), items[..3]);
+    assert_eq!(map.get_map_range_confirmed(&MAP, Some(&4), 3).unwrap(), items[4..7]);
+    assert_eq!(map.get_map_range_confirmed(&MAP, Some(&7), NUM_TOTAL_ITEMS).unwrap(), items[7..]);
+    assert_eq!(map.get_map_range_confirmed(&MAP, Some(&NUM_ITEMS), NUM_TOTAL_ITEMS).unwrap(), Vec::new());
+    assert_eq!(map.get_map_range_confirmed(&MAP, None, 0).unwrap(), Vec::new());
+    assert_eq!(map.get_map_range_confirmed(&NUM_TOTAL_ITEMS, None, NUM_TOTAL_ITEMS).unwrap(), Vec::new());
+
+    // Ensure pending items are not included.
+    map.start_atomic();
+    map.insert(MAP, NUM_ITEMS, NUM_ITEMS.to_string()).unwrap();
+    assert_eq!(map.get_map_range_confirmed(&MAP, Some(&7), NUM_TOTAL_ITEMS).unwrap(), items[7..]);
+    map.abort_atomic();
+
+    for map_id in MAP - 1..=MAP + 1 {
+        map.remove_map(&map_id).unwrap();
+    }
+
+    ensure_map_is_empty(map);
+}
+
 pub fn check_get_map(map: impl for<'a> NestedMap<'a, usize, usize, String>) {
     println!("Checking get unique maps");
     check_get_unique_maps(&map);
 
     println!("Checking get same map");
     check_get_same_map(&map);
+
+    println!("Checking get map range");
+    check_get_map_range(&map);
 }
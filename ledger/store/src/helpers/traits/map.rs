@@ -58,6 +58,11 @@ pub trait Map<
     ///
     fn clear_latest_checkpoint(&self);
 
+    ///
+    /// Returns the number of atomic checkpoints that are currently set.
+    ///
+    fn checkpoint_depth(&self) -> usize;
+
     ///
     /// Removes all pending operations to the last `atomic_checkpoint`
     /// (or to `start_atomic` if no checkpoints have been created).
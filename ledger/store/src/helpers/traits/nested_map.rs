@@ -64,6 +64,11 @@ pub trait NestedMap<
     ///
     fn clear_latest_checkpoint(&self);
 
+    ///
+    /// Returns the number of atomic checkpoints that are currently set.
+    ///
+    fn checkpoint_depth(&self) -> usize;
+
     ///
     /// Removes all pending operations to the last `atomic_checkpoint`
     /// (or to `start_atomic` if no checkpoints have been created).
@@ -110,6 +115,12 @@ pub trait NestedMapRead<
     ///
     fn get_map_confirmed(&'a self, map: &M) -> Result<Vec<(K, V)>>;
 
+    ///
+    /// Returns up to `limit` confirmed key-value pairs for the given map, in the order of their serialized keys,
+    /// starting at the given key (inclusive), or at the first key of the map if `start` is `None`.
+    ///
+    fn get_map_range_confirmed(&'a self, map: &M, start: Option<&K>, limit: usize) -> Result<Vec<(K, V)>>;
+
     ///
     /// Returns the speculative key-value pairs for the given map, if it exists.
     ///
@@ -16,7 +16,7 @@ use crate::{
     atomic_batch_scope,
     cow_to_cloned,
     cow_to_copied,
-    helpers::{Map, MapRead},
+    helpers::{CommitteeMap, Map, MapID, MapRead, StorageMode, StorageStats},
 };
 use console::network::prelude::*;
 use ledger_committee::Committee;
@@ -36,7 +36,7 @@ pub trait CommitteeStorage<N: Network>: 'static + Clone + Send + Sync {
     type CommitteeMap: for<'a> Map<'a, u32, Committee<N>>;
 
     /// Initializes the committee storage.
-    fn open(dev: Option<u16>) -> Result<Self>;
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self>;
 
     /// Initializes the test-variant of the storage.
     #[cfg(any(test, feature = "test"))]
@@ -49,8 +49,16 @@ pub trait CommitteeStorage<N: Network>: 'static + Clone + Send + Sync {
     /// Returns the committee map.
     fn committee_map(&self) -> &Self::CommitteeMap;
 
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16>;
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode;
+
+    /// Records the statistics of the committee maps in the given stats.
+    fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        stats.record_map(MapID::Committee(CommitteeMap::CurrentRound), self.current_round_map())?;
+        stats.record_map(MapID::Committee(CommitteeMap::RoundToHeight), self.round_to_height_map())?;
+        stats.record_map(MapID::Committee(CommitteeMap::Committee), self.committee_map())?;
+        Ok(())
+    }
 
     /// Starts an atomic batch write operation.
     fn start_atomic(&self) {
@@ -299,9 +307,9 @@ pub struct CommitteeStore<N: Network, C: CommitteeStorage<N>> {
 
 impl<N: Network, C: CommitteeStorage<N>> CommitteeStore<N, C> {
     /// Initializes the committee store.
-    pub fn open(dev: Option<u16>) -> Result<Self> {
+    pub fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
         // Initialize the committee storage.
-        let storage = C::open(dev)?;
+        let storage = C::open(storage)?;
         // Return the committee store.
         Ok(Self { storage, _phantom: PhantomData })
     }
@@ -355,9 +363,14 @@ impl<N: Network, C: CommitteeStorage<N>> CommitteeStore<N, C> {
         self.storage.finish_atomic()
     }
 
-    /// Returns the optional development ID.
-    pub fn dev(&self) -> Option<u16> {
-        self.storage.dev()
+    /// Returns the storage mode.
+    pub fn storage_mode(&self) -> &StorageMode {
+        self.storage.storage_mode()
+    }
+
+    /// Records the statistics of the committee maps in the given stats.
+    pub fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        self.storage.record_stats(stats)
     }
 }
 
@@ -16,19 +16,40 @@ use crate::{
     atomic_batch_scope,
     cow_to_cloned,
     cow_to_copied,
-    helpers::{Map, MapRead, NestedMap, NestedMapRead},
+    helpers::{
+        ConsistencyReport,
+        Map,
+        MapID,
+        MapRead,
+        NestedMap,
+        NestedMapRead,
+        ProgramMap,
+        StorageMode,
+        StorageStats,
+    },
     program::{CommitteeStorage, CommitteeStore},
 };
+#[cfg(any(test, feature = "test"))]
+use crate::CommitStep;
 use console::{
     network::prelude::*,
     program::{Identifier, Plaintext, ProgramID, Value},
     types::Field,
 };
+use ledger_block::Block;
 use synthesizer_program::{FinalizeOperation, FinalizeStoreTrait};
 
 use anyhow::Result;
 use core::marker::PhantomData;
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
+#[cfg(any(test, feature = "test"))]
+use parking_lot::RwLock;
+use std::borrow::Cow;
+#[cfg(any(test, feature = "test"))]
+use std::sync::Arc;
+
+const COMMIT_KEY: u8 = 0;
+const RATIFIED_KEY: u8 = 0;
 
 /// TODO (howardwu): Remove this.
 /// Returns the mapping ID for the given `program ID` and `mapping name`.
@@ -76,9 +97,13 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
     type ProgramIDMap: for<'a> Map<'a, ProgramID<N>, IndexSet<Identifier<N>>>;
     /// The mapping of `(program ID, mapping name)` to `[(key, value)]`.
     type KeyValueMap: for<'a> NestedMap<'a, (ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>;
+    /// The mapping of `commit key` to `block height` of the block being committed.
+    type CommitMap: for<'a> Map<'a, u8, u32>;
+    /// The mapping of `ratified key` to `(block height, [finalize operation])` of the ratifications of the latest block.
+    type RatifiedMap: for<'a> Map<'a, u8, (u32, Vec<FinalizeOperation<N>>)>;
 
     /// Initializes the program state storage.
-    fn open(dev: Option<u16>) -> Result<Self>;
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self>;
 
     /// Initializes the test-variant of the storage.
     #[cfg(any(test, feature = "test"))]
@@ -90,15 +115,21 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
     fn program_id_map(&self) -> &Self::ProgramIDMap;
     /// Returns the key-value map.
     fn key_value_map(&self) -> &Self::KeyValueMap;
+    /// Returns the commit map.
+    fn commit_map(&self) -> &Self::CommitMap;
+    /// Returns the ratified map.
+    fn ratified_map(&self) -> &Self::RatifiedMap;
 
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16>;
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode;
 
     /// Starts an atomic batch write operation.
     fn start_atomic(&self) {
         self.committee_store().start_atomic();
         self.program_id_map().start_atomic();
         self.key_value_map().start_atomic();
+        self.commit_map().start_atomic();
+        self.ratified_map().start_atomic();
     }
 
     /// Checks if an atomic batch is in progress.
@@ -106,6 +137,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().is_atomic_in_progress()
             || self.program_id_map().is_atomic_in_progress()
             || self.key_value_map().is_atomic_in_progress()
+            || self.commit_map().is_atomic_in_progress()
+            || self.ratified_map().is_atomic_in_progress()
     }
 
     /// Checkpoints the atomic batch.
@@ -113,6 +146,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().atomic_checkpoint();
         self.program_id_map().atomic_checkpoint();
         self.key_value_map().atomic_checkpoint();
+        self.commit_map().atomic_checkpoint();
+        self.ratified_map().atomic_checkpoint();
     }
 
     /// Clears the latest atomic batch checkpoint.
@@ -120,6 +155,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().clear_latest_checkpoint();
         self.program_id_map().clear_latest_checkpoint();
         self.key_value_map().clear_latest_checkpoint();
+        self.commit_map().clear_latest_checkpoint();
+        self.ratified_map().clear_latest_checkpoint();
     }
 
     /// Rewinds the atomic batch to the previous checkpoint.
@@ -127,6 +164,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().atomic_rewind();
         self.program_id_map().atomic_rewind();
         self.key_value_map().atomic_rewind();
+        self.commit_map().atomic_rewind();
+        self.ratified_map().atomic_rewind();
     }
 
     /// Aborts an atomic batch write operation.
@@ -134,13 +173,137 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().abort_atomic();
         self.program_id_map().abort_atomic();
         self.key_value_map().abort_atomic();
+        self.commit_map().abort_atomic();
+        self.ratified_map().abort_atomic();
     }
 
     /// Finishes an atomic batch write operation.
     fn finish_atomic(&self) -> Result<()> {
         self.committee_store().finish_atomic()?;
         self.program_id_map().finish_atomic()?;
-        self.key_value_map().finish_atomic()
+        self.key_value_map().finish_atomic()?;
+        self.commit_map().finish_atomic()?;
+        self.ratified_map().finish_atomic()
+    }
+
+    /// Marks the block at the given height as being committed.
+    /// Note: The marker is written immediately, and must be cleared by the atomic batch that finalizes the block,
+    /// so that an interrupted commit can be detected and rolled back when the storage is reopened.
+    fn begin_commit(&self, height: u32) -> Result<()> {
+        // Ensure the marker is not queued in an atomic batch.
+        ensure!(!self.is_atomic_in_progress(), "Cannot begin a commit while an atomic batch is in progress");
+        // Ensure there is no other commit in progress.
+        if let Some(pending) = self.pending_commit()? {
+            bail!("Cannot begin the commit of block {height}, as the commit of block {pending} was interrupted")
+        }
+        self.commit_map().insert(COMMIT_KEY, height)
+    }
+
+    /// Clears the commit marker. If an atomic batch is in progress, the marker is cleared as part of it.
+    fn end_commit(&self) -> Result<()> {
+        self.commit_map().remove(&COMMIT_KEY)
+    }
+
+    /// Returns the height of the block whose commit was started, but not finalized.
+    fn pending_commit(&self) -> Result<Option<u32>> {
+        match self.commit_map().get_confirmed(&COMMIT_KEY)? {
+            Some(height) => Ok(Some(cow_to_copied!(height))),
+            None => Ok(None),
+        }
+    }
+
+    /// Stores the finalize operations from the ratifications of the block at the given height,
+    /// replacing those of the previous block.
+    fn set_ratified_operations(&self, height: u32, operations: &[FinalizeOperation<N>]) -> Result<()> {
+        self.ratified_map().insert(RATIFIED_KEY, (height, operations.to_vec()))
+    }
+
+    /// Returns the finalize operations from the ratifications of the block at the given height,
+    /// if it is the latest finalized block.
+    fn get_ratified_operations(&self, height: u32) -> Result<Option<Vec<FinalizeOperation<N>>>> {
+        match self.ratified_map().get_confirmed(&RATIFIED_KEY)? {
+            Some(entry) => match cow_to_cloned!(entry) {
+                (ratified_height, operations) if ratified_height == height => Ok(Some(operations)),
+                _ => Ok(None),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Ensures the confirmed mappings hold the key-values that are last written by the given finalize operations.
+    fn check_finalize_operations<'a>(
+        &self,
+        operations: impl IntoIterator<Item = &'a FinalizeOperation<N>>,
+    ) -> Result<()> {
+        // Collect the value IDs that are last written to each key ID, by mapping ID.
+        let mut written = IndexMap::<Field<N>, IndexMap<Field<N>, Field<N>>>::new();
+        for operation in operations {
+            match operation {
+                FinalizeOperation::InsertKeyValue(mapping_id, key_id, value_id)
+                | FinalizeOperation::UpdateKeyValue(mapping_id, _, key_id, value_id) => {
+                    written.entry(*mapping_id).or_default().insert(*key_id, *value_id);
+                }
+                // Note: The key-values of a replaced or removed mapping, and the key of a removed key-value,
+                // are not committed to by the operation, so the earlier writes to the mapping are not checked.
+                FinalizeOperation::ReplaceMapping(mapping_id)
+                | FinalizeOperation::RemoveMapping(mapping_id)
+                | FinalizeOperation::RemoveKeyValue(mapping_id, _) => {
+                    written.shift_remove(mapping_id);
+                }
+                FinalizeOperation::InitializeMapping(..) => (),
+            }
+        }
+
+        // Check the written key-values against the confirmed mappings.
+        let program_ids = self.program_id_map().keys_confirmed().map(|id| cow_to_copied!(id)).collect::<Vec<_>>();
+        for program_id in program_ids {
+            for mapping_name in self.get_mapping_names_confirmed(&program_id)?.unwrap_or_default() {
+                let Some(mut values) = written.shift_remove(&to_mapping_id(&program_id, &mapping_name)?) else {
+                    continue;
+                };
+                for (key, value) in self.get_mapping_confirmed(program_id, mapping_name)? {
+                    let key_id = to_key_id(&program_id, &mapping_name, &key)?;
+                    if let Some(expected_value_id) = values.shift_remove(&key_id) {
+                        let value_id = N::hash_bhp1024(&(key_id, N::hash_bhp1024(&value.to_bits_le())?).to_bits_le())?;
+                        ensure!(
+                            value_id == expected_value_id,
+                            "The value of '{program_id}/{mapping_name}' key '{key}' does not match its finalize operation"
+                        );
+                    }
+                }
+                ensure!(
+                    values.is_empty(),
+                    "'{program_id}/{mapping_name}' is missing {} keys written by the finalize operations",
+                    values.len()
+                );
+            }
+        }
+        ensure!(written.is_empty(), "The finalize operations write to {} uninitialized mappings", written.len());
+        Ok(())
+    }
+
+    /// Ensures the finalize operations of the given block, which must be the latest finalized block,
+    /// produce its finalize root, and that the confirmed mappings hold the key-values written by them.
+    fn check_finalize_root(&self, block: &Block<N>) -> Result<()> {
+        let height = block.height();
+        // Retrieve the finalize operations from the ratifications of the block.
+        let Some(ratified_operations) = self.get_ratified_operations(height)? else {
+            bail!("The ratified finalize operations of block {height} are missing")
+        };
+        // Ensure the finalize operations produce the finalize root of the block.
+        ensure!(
+            block.transactions().to_finalize_root(ratified_operations.clone())? == block.finalize_root(),
+            "The finalize operations do not match the finalize root of block {height}"
+        );
+        // Ensure the mappings hold the key-values written by the finalize operations, in the order they were finalized.
+        // Note: The ratifications of the genesis block are finalized before its transactions, and after them otherwise.
+        let transaction_operations = block.transactions().finalize_operations();
+        let result = match block.is_genesis() {
+            true => self.check_finalize_operations(ratified_operations.iter().chain(transaction_operations)),
+            false => self.check_finalize_operations(transaction_operations.chain(&ratified_operations)),
+        };
+        result
+            .map_err(|error| anyhow!("The finalize state does not match the finalize root of block {height} - {error}"))
     }
 
     /// Initializes the given `program ID` and `mapping name` in storage.
@@ -483,6 +646,45 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         N::hash_bhp1024(&preimage.into_values().flatten().collect::<Vec<_>>())
     }
 
+    /// Records the statistics of the program maps and the committee store in the given stats.
+    fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        stats.record_map(MapID::Program(ProgramMap::ProgramID), self.program_id_map())?;
+        stats.record_nested_map(MapID::Program(ProgramMap::KeyValueID), self.key_value_map())?;
+        stats.record_map(MapID::Program(ProgramMap::Commit), self.commit_map())?;
+        stats.record_map(MapID::Program(ProgramMap::Ratified), self.ratified_map())?;
+        self.committee_store().record_stats(stats)?;
+        Ok(())
+    }
+
+    /// Checks that every mapping with key-value pairs is initialized, records the finalize checksum,
+    /// and checks the finalize state against the finalize root of the given latest block, if any,
+    /// recording any inconsistencies in the given report.
+    fn check_consistency(&self, latest_block: Option<&Block<N>>, report: &mut ConsistencyReport) -> Result<()> {
+        // Collect the mappings that contain key-value pairs.
+        let mappings: IndexSet<_> = self.key_value_map().keys_confirmed().map(|(m, _)| cow_to_copied!(m)).collect();
+        // Ensure each mapping is initialized.
+        for (program_id, mapping_name) in mappings {
+            if !self.contains_mapping_confirmed(&program_id, &mapping_name)? {
+                report.found(
+                    "mapping",
+                    format!("Mapping '{program_id}/{mapping_name}' has values but is not initialized"),
+                );
+            }
+        }
+        // Compute the finalize checksum.
+        match self.get_checksum_confirmed() {
+            Ok(checksum) => report.set_finalize_checksum(checksum),
+            Err(error) => report.found("checksum", format!("Failed to compute the finalize checksum - {error}")),
+        }
+        // Ensure the finalize state matches the finalize root of the latest block.
+        if let Some(block) = latest_block {
+            if let Err(error) = self.check_finalize_root(block) {
+                report.found("checksum", error.to_string());
+            }
+        }
+        Ok(())
+    }
+
     /// Returns the pending checksum of the finalize storage.
     fn get_checksum_pending(&self) -> Result<Field<N>> {
         // Compute all mapping checksums.
@@ -524,14 +726,17 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
 pub struct FinalizeStore<N: Network, P: FinalizeStorage<N>> {
     /// The finalize storage.
     storage: P,
+    /// The commit step after which every block commit is interrupted, to simulate a crash.
+    #[cfg(any(test, feature = "test"))]
+    crash_after: Arc<RwLock<Option<CommitStep>>>,
     /// PhantomData.
     _phantom: PhantomData<N>,
 }
 
 impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
     /// Initializes the finalize store.
-    pub fn open(dev: Option<u16>) -> Result<Self> {
-        Self::from(P::open(dev)?)
+    pub fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        Self::from(P::open(storage)?)
     }
 
     /// Initializes the test-variant of the storage.
@@ -543,7 +748,12 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
     /// Initializes a finalize store from storage.
     pub fn from(storage: P) -> Result<Self> {
         // Return the finalize store.
-        Ok(Self { storage, _phantom: PhantomData })
+        Ok(Self {
+            storage,
+            #[cfg(any(test, feature = "test"))]
+            crash_after: Default::default(),
+            _phantom: PhantomData,
+        })
     }
 
     /// Starts an atomic batch write operation.
@@ -578,12 +788,46 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
 
     /// Finishes an atomic batch write operation.
     pub fn finish_atomic(&self) -> Result<()> {
+        // If a crash was injected while finishing, finish the batch of the committee store only, and abandon the rest.
+        // Note: This panics instead of returning an error, so that the commit is not rolled back in-process.
+        #[cfg(any(test, feature = "test"))]
+        if *self.crash_after.read() == Some(CommitStep::Finishing) {
+            self.storage.committee_store().finish_atomic()?;
+            panic!("Injected a crash while finishing the atomic batch")
+        }
         self.storage.finish_atomic()
     }
 
-    /// Returns the optional development ID.
-    pub fn dev(&self) -> Option<u16> {
-        self.storage.dev()
+    /// Returns the commit step after which every block commit is interrupted, if a crash was injected.
+    #[cfg(any(test, feature = "test"))]
+    pub(crate) fn crash_step(&self) -> Option<CommitStep> {
+        *self.crash_after.read()
+    }
+
+    /// Sets the commit step after which every block commit is interrupted, to simulate a crash.
+    #[cfg(any(test, feature = "test"))]
+    pub(crate) fn set_crash_step(&self, step: Option<CommitStep>) {
+        *self.crash_after.write() = step;
+    }
+
+    /// Marks the block at the given height as being committed.
+    pub fn begin_commit(&self, height: u32) -> Result<()> {
+        self.storage.begin_commit(height)
+    }
+
+    /// Clears the commit marker. If an atomic batch is in progress, the marker is cleared as part of it.
+    pub fn end_commit(&self) -> Result<()> {
+        self.storage.end_commit()
+    }
+
+    /// Returns the height of the block whose commit was started, but not finalized.
+    pub fn pending_commit(&self) -> Result<Option<u32>> {
+        self.storage.pending_commit()
+    }
+
+    /// Returns the storage mode.
+    pub fn storage_mode(&self) -> &StorageMode {
+        self.storage.storage_mode()
     }
 }
 
@@ -697,6 +941,26 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
     }
 }
 
+impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
+    /// Stores the finalize operations from the ratifications of the block at the given height,
+    /// replacing those of the previous block.
+    pub fn set_ratified_operations(&self, height: u32, operations: &[FinalizeOperation<N>]) -> Result<()> {
+        self.storage.set_ratified_operations(height, operations)
+    }
+
+    /// Returns the finalize operations from the ratifications of the block at the given height,
+    /// if it is the latest finalized block.
+    pub fn get_ratified_operations(&self, height: u32) -> Result<Option<Vec<FinalizeOperation<N>>>> {
+        self.storage.get_ratified_operations(height)
+    }
+
+    /// Ensures the finalize operations of the given block, which must be the latest finalized block,
+    /// produce its finalize root, and that the confirmed mappings hold the key-values written by them.
+    pub fn check_finalize_root(&self, block: &Block<N>) -> Result<()> {
+        self.storage.check_finalize_root(block)
+    }
+}
+
 impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
     /// Returns `true` if the given `program ID` exist.
     pub fn contains_program_confirmed(&self, program_id: &ProgramID<N>) -> Result<bool> {
@@ -762,6 +1026,24 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
     pub fn get_checksum_confirmed(&self) -> Result<Field<N>> {
         self.storage.get_checksum_confirmed()
     }
+
+    /// Records the statistics of the program maps and the committee store in the given stats.
+    pub fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        self.storage.record_stats(stats)
+    }
+
+    /// Checks that every mapping with key-value pairs is initialized, records the finalize checksum,
+    /// and checks the finalize state against the finalize root of the given latest block, if any.
+    pub fn check_consistency(&self, latest_block: Option<&Block<N>>, report: &mut ConsistencyReport) -> Result<()> {
+        self.storage.check_consistency(latest_block, report)
+    }
+}
+
+impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
+    /// Returns an iterator over the program IDs, for all programs with initialized mappings.
+    pub fn program_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, ProgramID<N>>> {
+        self.storage.program_id_map().keys_confirmed()
+    }
 }
 
 #[cfg(test)]
@@ -16,7 +16,7 @@ use crate::{
     atomic_batch_scope,
     cow_to_cloned,
     cow_to_copied,
-    helpers::{Map, MapRead},
+    helpers::{ConsistencyReport, DeploymentMap, Map, MapID, MapRead, StorageMode, StorageStats},
     FeeStorage,
     FeeStore,
 };
@@ -71,9 +71,9 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
     /// Returns the fee storage.
     fn fee_store(&self) -> &FeeStore<N, Self::FeeStorage>;
 
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16> {
-        self.fee_store().dev()
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        self.fee_store().storage_mode()
     }
 
     /// Starts an atomic batch write operation.
@@ -255,6 +255,61 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Records the statistics of the deployment maps in the given stats.
+    fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        stats.record_map(MapID::Deployment(DeploymentMap::ID), self.id_map())?;
+        stats.record_map(MapID::Deployment(DeploymentMap::Edition), self.edition_map())?;
+        stats.record_map(MapID::Deployment(DeploymentMap::ReverseID), self.reverse_id_map())?;
+        stats.record_map(MapID::Deployment(DeploymentMap::Owner), self.owner_map())?;
+        stats.record_map(MapID::Deployment(DeploymentMap::Program), self.program_map())?;
+        stats.record_map(MapID::Deployment(DeploymentMap::VerifyingKey), self.verifying_key_map())?;
+        stats.record_map(MapID::Deployment(DeploymentMap::Certificate), self.certificate_map())?;
+        Ok(())
+    }
+
+    /// Checks that the deployment maps agree with each other, recording any inconsistencies in the given report.
+    /// If `repair` is `true`, the reverse program IDs are repaired.
+    fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        atomic_batch_scope!(self, {
+            for (transaction_id, program_id) in self.id_map().iter_confirmed() {
+                let (transaction_id, program_id) = (cow_to_copied!(transaction_id), cow_to_copied!(program_id));
+                // Retrieve the edition.
+                let edition = match self.edition_map().get_confirmed(&program_id)? {
+                    Some(edition) => cow_to_copied!(edition),
+                    None => {
+                        report.found("edition", format!("Program '{program_id}' is missing its edition"));
+                        continue;
+                    }
+                };
+                // Ensure the reverse program ID exists.
+                let key = (program_id, edition);
+                report.check_index("deployment reverse ID", self.reverse_id_map(), key, transaction_id, repair)?;
+                // Ensure the program and its owner exist.
+                if !self.program_map().contains_key_confirmed(&key)? {
+                    report.found("program", format!("Program '{program_id}' (edition {edition}) is missing"));
+                }
+                if !self.owner_map().contains_key_confirmed(&key)? {
+                    report.found("owner", format!("Program '{program_id}' (edition {edition}) is missing its owner"));
+                }
+                // Ensure the fee exists.
+                if !self.fee_store().contains_transaction_id(&transaction_id)? {
+                    report.found("fee", format!("Deployment '{transaction_id}' is missing its fee"));
+                }
+            }
+            // Ensure every reverse program ID belongs to its deployment.
+            for (key, transaction_id) in self.reverse_id_map().iter_confirmed().collect::<Vec<_>>() {
+                let is_valid = match self.id_map().get_confirmed(&transaction_id)? {
+                    Some(program_id) => *program_id == key.0,
+                    None => false,
+                };
+                if !is_valid {
+                    report.remove_dangling("deployment reverse ID", self.reverse_id_map(), *key, repair)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
     /// Returns the transaction ID that contains the given `program ID`.
     fn find_transaction_id_from_program_id(&self, program_id: &ProgramID<N>) -> Result<Option<N::TransactionID>> {
         // Check if the program ID is for 'credits.aleo'.
@@ -512,6 +567,16 @@ impl<N: Network, D: DeploymentStorage<N>> DeploymentStore<N, D> {
         self.storage.remove(transaction_id)
     }
 
+    /// Records the statistics of the deployment maps in the given stats.
+    pub fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        self.storage.record_stats(stats)
+    }
+
+    /// Checks that the deployment maps agree with each other, recording any inconsistencies in the given report.
+    pub fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        self.storage.check_consistency(repair, report)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -547,9 +612,9 @@ impl<N: Network, D: DeploymentStorage<N>> DeploymentStore<N, D> {
         self.storage.finish_atomic()
     }
 
-    /// Returns the optional development ID.
-    pub fn dev(&self) -> Option<u16> {
-        self.storage.dev()
+    /// Returns the storage mode.
+    pub fn storage_mode(&self) -> &StorageMode {
+        self.storage.storage_mode()
     }
 }
 
@@ -16,7 +16,7 @@ use crate::{
     atomic_batch_scope,
     cow_to_cloned,
     cow_to_copied,
-    helpers::{Map, MapRead},
+    helpers::{ConsistencyReport, ExecutionMap, Map, MapID, MapRead, StorageMode, StorageStats},
     FeeStorage,
     FeeStore,
     TransitionStore,
@@ -56,9 +56,9 @@ pub trait ExecutionStorage<N: Network>: Clone + Send + Sync {
         self.fee_store().transition_store()
     }
 
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16> {
-        self.transition_store().dev()
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        self.transition_store().storage_mode()
     }
 
     /// Starts an atomic batch write operation.
@@ -193,6 +193,91 @@ pub trait ExecutionStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the proof and the transition values for the given `transaction ID`.
+    fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        // Retrieve the transition IDs and fee boolean.
+        let (transition_ids, has_fee) = match self.id_map().get_confirmed(transaction_id)? {
+            Some(ids) => cow_to_cloned!(ids),
+            None => bail!("Failed to get the transition IDs for the transaction '{transaction_id}'"),
+        };
+        // Retrieve the global state root.
+        let global_state_root = match self.inclusion_map().get_confirmed(transaction_id)? {
+            Some(inclusion) => inclusion.0,
+            None => bail!("Failed to get the proof for the transaction '{transaction_id}'"),
+        };
+
+        atomic_batch_scope!(self, {
+            // Prune the proof.
+            self.inclusion_map().insert(*transaction_id, (global_state_root, None))?;
+
+            // Prune the transitions.
+            for transition_id in transition_ids {
+                self.transition_store().prune(&transition_id)?;
+            }
+
+            // Prune the fee.
+            if has_fee {
+                self.fee_store().prune(transaction_id)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Records the statistics of the execution maps in the given stats.
+    fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        stats.record_map(MapID::Execution(ExecutionMap::ID), self.id_map())?;
+        stats.record_map(MapID::Execution(ExecutionMap::ReverseID), self.reverse_id_map())?;
+        stats.record_map(MapID::Execution(ExecutionMap::Inclusion), self.inclusion_map())?;
+        Ok(())
+    }
+
+    /// Checks that the execution maps agree with each other, recording any inconsistencies in the given report.
+    /// If `repair` is `true`, the reverse transition IDs are repaired.
+    fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        atomic_batch_scope!(self, {
+            for (transaction_id, value) in self.id_map().iter_confirmed() {
+                let transaction_id = cow_to_copied!(transaction_id);
+                let (transition_ids, has_fee) = cow_to_cloned!(value);
+                for transition_id in transition_ids {
+                    // Ensure the reverse transition ID exists.
+                    report.check_index(
+                        "execution reverse ID",
+                        self.reverse_id_map(),
+                        transition_id,
+                        transaction_id,
+                        repair,
+                    )?;
+                    // Ensure the transition exists.
+                    if !self.transition_store().contains_transition_id(&transition_id)? {
+                        let message =
+                            format!("Transition '{transition_id}' of transaction '{transaction_id}' is missing");
+                        report.found("execution", message);
+                    }
+                }
+                // Ensure the global state root and proof exist.
+                if !self.inclusion_map().contains_key_confirmed(&transaction_id)? {
+                    report.found("inclusion", format!("Execution '{transaction_id}' is missing its inclusion entry"));
+                }
+                // Ensure the fee exists, if the execution has one.
+                if has_fee && !self.fee_store().contains_transaction_id(&transaction_id)? {
+                    report.found("fee", format!("Execution '{transaction_id}' is missing its fee"));
+                }
+            }
+            // Ensure every reverse transition ID belongs to its execution.
+            for (transition_id, transaction_id) in self.reverse_id_map().iter_confirmed().collect::<Vec<_>>() {
+                let is_valid = match self.id_map().get_confirmed(&transaction_id)? {
+                    Some(value) => value.0.contains(&transition_id),
+                    None => false,
+                };
+                if !is_valid {
+                    report.remove_dangling("execution reverse ID", self.reverse_id_map(), *transition_id, repair)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
     /// Returns the transaction ID that contains the given `transition ID`.
     fn find_transaction_id_from_transition_id(
         &self,
@@ -318,6 +403,21 @@ impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
         self.storage.remove(transaction_id)
     }
 
+    /// Prunes the proof and the transition values for the given `transaction ID`.
+    pub fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        self.storage.prune(transaction_id)
+    }
+
+    /// Records the statistics of the execution maps in the given stats.
+    pub fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        self.storage.record_stats(stats)
+    }
+
+    /// Checks that the execution maps agree with each other, recording any inconsistencies in the given report.
+    pub fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        self.storage.check_consistency(repair, report)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -353,9 +453,9 @@ impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
         self.storage.finish_atomic()
     }
 
-    /// Returns the optional development ID.
-    pub fn dev(&self) -> Option<u16> {
-        self.storage.dev()
+    /// Returns the storage mode.
+    pub fn storage_mode(&self) -> &StorageMode {
+        self.storage.storage_mode()
     }
 }
 
@@ -372,6 +472,11 @@ impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
 }
 
 impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
+    /// Returns `true` if the given `transaction ID` has an execution in storage.
+    pub fn contains_transaction_id(&self, transaction_id: &N::TransactionID) -> Result<bool> {
+        self.storage.id_map().contains_key_confirmed(transaction_id)
+    }
+
     /// Returns the transaction ID that executed the given `transition ID`.
     pub fn find_transaction_id_from_transition_id(
         &self,
@@ -16,7 +16,7 @@ use crate::{
     atomic_batch_scope,
     cow_to_cloned,
     cow_to_copied,
-    helpers::{Map, MapRead},
+    helpers::{ConsistencyReport, FeeMap, Map, MapID, MapRead, StorageMode, StorageStats},
     TransitionStorage,
     TransitionStore,
 };
@@ -47,9 +47,9 @@ pub trait FeeStorage<N: Network>: Clone + Send + Sync {
     /// Returns the transition storage.
     fn transition_store(&self) -> &TransitionStore<N, Self::TransitionStorage>;
 
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16> {
-        self.transition_store().dev()
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        self.transition_store().storage_mode()
     }
 
     /// Starts an atomic batch write operation.
@@ -136,6 +136,60 @@ pub trait FeeStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the proof and the fee transition values for the given `transaction ID`.
+    fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        // Retrieve the fee transition ID and global state root.
+        let (transition_id, global_state_root, _) = match self.fee_map().get_confirmed(transaction_id)? {
+            Some(fee) => cow_to_cloned!(fee),
+            None => bail!("Failed to locate the fee transition ID for transaction '{transaction_id}'"),
+        };
+
+        atomic_batch_scope!(self, {
+            // Prune the proof.
+            self.fee_map().insert(*transaction_id, (transition_id, global_state_root, None))?;
+            // Prune the fee transition.
+            self.transition_store().prune(&transition_id)?;
+
+            Ok(())
+        })
+    }
+
+    /// Records the statistics of the fee maps in the given stats.
+    fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        stats.record_map(MapID::Fee(FeeMap::Fee), self.fee_map())?;
+        stats.record_map(MapID::Fee(FeeMap::ReverseFee), self.reverse_fee_map())?;
+        Ok(())
+    }
+
+    /// Checks that the fee maps agree with each other, recording any inconsistencies in the given report.
+    /// If `repair` is `true`, the reverse fee entries are repaired.
+    fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        atomic_batch_scope!(self, {
+            for (transaction_id, fee) in self.fee_map().iter_confirmed() {
+                let (transaction_id, transition_id) = (cow_to_copied!(transaction_id), fee.0);
+                // Ensure the reverse fee entry exists.
+                report.check_index("reverse fee", self.reverse_fee_map(), transition_id, transaction_id, repair)?;
+                // Ensure the fee transition exists.
+                if !self.transition_store().contains_transition_id(&transition_id)? {
+                    let message =
+                        format!("Fee transition '{transition_id}' of transaction '{transaction_id}' is missing");
+                    report.found("fee", message);
+                }
+            }
+            // Ensure every reverse fee entry belongs to its fee.
+            for (transition_id, transaction_id) in self.reverse_fee_map().iter_confirmed().collect::<Vec<_>>() {
+                let is_valid = match self.fee_map().get_confirmed(&transaction_id)? {
+                    Some(fee) => fee.0 == *transition_id,
+                    None => false,
+                };
+                if !is_valid {
+                    report.remove_dangling("reverse fee", self.reverse_fee_map(), *transition_id, repair)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
     /// Returns the transaction ID that contains the given `transition ID`.
     fn find_transaction_id_from_transition_id(
         &self,
@@ -195,6 +249,21 @@ impl<N: Network, F: FeeStorage<N>> FeeStore<N, F> {
         self.storage.remove(transaction_id)
     }
 
+    /// Prunes the proof and the fee transition values for the given `transaction ID`.
+    pub fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        self.storage.prune(transaction_id)
+    }
+
+    /// Records the statistics of the fee maps in the given stats.
+    pub fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        self.storage.record_stats(stats)
+    }
+
+    /// Checks that the fee maps agree with each other, recording any inconsistencies in the given report.
+    pub fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        self.storage.check_consistency(repair, report)
+    }
+
     /// Returns the transition store.
     pub fn transition_store(&self) -> &TransitionStore<N, F::TransitionStorage> {
         self.storage.transition_store()
@@ -235,9 +304,9 @@ impl<N: Network, F: FeeStorage<N>> FeeStore<N, F> {
         self.storage.finish_atomic()
     }
 
-    /// Returns the optional development ID.
-    pub fn dev(&self) -> Option<u16> {
-        self.storage.dev()
+    /// Returns the storage mode.
+    pub fn storage_mode(&self) -> &StorageMode {
+        self.storage.storage_mode()
     }
 }
 
@@ -249,6 +318,11 @@ impl<N: Network, F: FeeStorage<N>> FeeStore<N, F> {
 }
 
 impl<N: Network, F: FeeStorage<N>> FeeStore<N, F> {
+    /// Returns `true` if the given `transaction ID` has a fee in storage.
+    pub fn contains_transaction_id(&self, transaction_id: &N::TransactionID) -> Result<bool> {
+        self.storage.fee_map().contains_key_confirmed(transaction_id)
+    }
+
     /// Returns the transaction ID that deployed the given `transition ID`.
     pub fn find_transaction_id_from_transition_id(
         &self,
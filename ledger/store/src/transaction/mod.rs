@@ -24,7 +24,7 @@ pub use fee::*;
 use crate::{
     atomic_batch_scope,
     cow_to_copied,
-    helpers::{Map, MapRead},
+    helpers::{ConsistencyReport, Map, MapID, MapRead, StorageMode, StorageStats, TransactionMap},
     TransitionStorage,
     TransitionStore,
 };
@@ -76,14 +76,14 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
     fn fee_store(&self) -> &FeeStore<N, Self::FeeStorage>;
     /// Returns the transition store.
     fn transition_store(&self) -> &TransitionStore<N, Self::TransitionStorage> {
-        debug_assert!(self.deployment_store().dev() == self.execution_store().dev());
-        debug_assert!(self.execution_store().dev() == self.fee_store().dev());
+        debug_assert!(self.deployment_store().storage_mode() == self.execution_store().storage_mode());
+        debug_assert!(self.execution_store().storage_mode() == self.fee_store().storage_mode());
         self.fee_store().transition_store()
     }
 
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16> {
-        self.transition_store().dev()
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        self.transition_store().storage_mode()
     }
 
     /// Starts an atomic batch write operation.
@@ -193,6 +193,62 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the proofs and the transition values for the given `transaction ID`.
+    /// The program, verifying keys, and certificates of a deployment are kept, as they are needed to execute it.
+    fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        // Retrieve the transaction type.
+        let transaction_type = match self.id_map().get_confirmed(transaction_id)? {
+            Some(transaction_type) => cow_to_copied!(transaction_type),
+            None => bail!("Failed to get the type for transaction '{transaction_id}'"),
+        };
+
+        match transaction_type {
+            // Prune the fee of the deployment transaction.
+            TransactionType::Deploy => self.fee_store().prune(transaction_id),
+            // Prune the execution transaction.
+            TransactionType::Execute => self.execution_store().prune(transaction_id),
+            // Prune the fee transaction.
+            TransactionType::Fee => self.fee_store().prune(transaction_id),
+        }
+    }
+
+    /// Records the statistics of the transaction maps, and of the deployment, execution, fee,
+    /// and transition stores, in the given stats.
+    fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        stats.record_map(MapID::Transaction(TransactionMap::ID), self.id_map())?;
+        self.deployment_store().record_stats(stats)?;
+        self.execution_store().record_stats(stats)?;
+        self.fee_store().record_stats(stats)?;
+        self.transition_store().record_stats(stats)?;
+        Ok(())
+    }
+
+    /// Checks that the transaction maps agree with each other, recording any inconsistencies in the given report.
+    /// If `repair` is `true`, the derived indexes are repaired.
+    fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        atomic_batch_scope!(self, {
+            for (transaction_id, transaction_type) in self.id_map().iter_confirmed() {
+                let transaction_id = cow_to_copied!(transaction_id);
+                report.add_transaction();
+                // Ensure the transaction exists in the store for its type.
+                let exists = match cow_to_copied!(transaction_type) {
+                    TransactionType::Deploy => self.deployment_store().get_program_id(&transaction_id)?.is_some(),
+                    TransactionType::Execute => self.execution_store().contains_transaction_id(&transaction_id)?,
+                    TransactionType::Fee => self.fee_store().contains_transaction_id(&transaction_id)?,
+                };
+                if !exists {
+                    report.found("transaction", format!("Transaction '{transaction_id}' is missing its contents"));
+                }
+            }
+            // Check the deployments, executions, fees, and transitions.
+            self.deployment_store().check_consistency(repair, report)?;
+            self.execution_store().check_consistency(repair, report)?;
+            self.fee_store().check_consistency(repair, report)?;
+            self.transition_store().check_consistency(repair, report)?;
+            Ok(())
+        })
+    }
+
     /// Returns the transaction ID that contains the given `transition ID`.
     fn find_transaction_id_from_transition_id(
         &self,
@@ -261,6 +317,22 @@ impl<N: Network, T: TransactionStorage<N>> TransactionStore<N, T> {
         self.storage.remove(transaction_id)
     }
 
+    /// Prunes the proofs and the transition values for the given `transaction ID`.
+    pub fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        self.storage.prune(transaction_id)
+    }
+
+    /// Records the statistics of the transaction maps, and of the deployment, execution, fee,
+    /// and transition stores, in the given stats.
+    pub fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        self.storage.record_stats(stats)
+    }
+
+    /// Checks that the transaction maps agree with each other, recording any inconsistencies in the given report.
+    pub fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        self.storage.check_consistency(repair, report)
+    }
+
     /// Returns the deployment store.
     pub fn deployment_store(&self) -> &DeploymentStore<N, T::DeploymentStorage> {
         self.storage.deployment_store()
@@ -306,9 +378,9 @@ impl<N: Network, T: TransactionStorage<N>> TransactionStore<N, T> {
         self.storage.finish_atomic()
     }
 
-    /// Returns the optional development ID.
-    pub fn dev(&self) -> Option<u16> {
-        self.storage.dev()
+    /// Returns the storage mode.
+    pub fn storage_mode(&self) -> &StorageMode {
+        self.storage.storage_mode()
     }
 }
 
@@ -14,7 +14,8 @@
 
 use crate::{
     atomic_batch_scope,
-    helpers::{Map, MapRead},
+    cow_to_copied,
+    helpers::{ConsistencyReport, Map, MapID, MapRead, StorageMode, StorageStats, TransitionInputMap},
 };
 use console::{
     network::prelude::*,
@@ -46,7 +47,7 @@ pub trait InputStorage<N: Network>: Clone + Send + Sync {
     type ExternalRecordMap: for<'a> Map<'a, Field<N>, ()>;
 
     /// Initializes the transition input storage.
-    fn open(dev: Option<u16>) -> Result<Self>;
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self>;
 
     /// Returns the ID map.
     fn id_map(&self) -> &Self::IDMap;
@@ -65,8 +66,8 @@ pub trait InputStorage<N: Network>: Clone + Send + Sync {
     /// Returns the external record map.
     fn external_record_map(&self) -> &Self::ExternalRecordMap;
 
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16>;
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode;
 
     /// Starts an atomic batch write operation.
     fn start_atomic(&self) {
@@ -216,6 +217,77 @@ pub trait InputStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the input values for the given `transition ID`,
+    /// while keeping the input IDs, and the serial numbers and tags of the record inputs.
+    fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        // Retrieve the input IDs.
+        let input_ids = self.get_ids(transition_id)?;
+
+        atomic_batch_scope!(self, {
+            for input_id in input_ids {
+                // Prune the input value, if one exists.
+                if self.constant_map().contains_key_confirmed(&input_id)? {
+                    self.constant_map().insert(input_id, None)?;
+                }
+                if self.public_map().contains_key_confirmed(&input_id)? {
+                    self.public_map().insert(input_id, None)?;
+                }
+                if self.private_map().contains_key_confirmed(&input_id)? {
+                    self.private_map().insert(input_id, None)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Records the statistics of the input maps in the given stats.
+    fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        stats.record_map(MapID::TransitionInput(TransitionInputMap::ID), self.id_map())?;
+        stats.record_map(MapID::TransitionInput(TransitionInputMap::ReverseID), self.reverse_id_map())?;
+        stats.record_map(MapID::TransitionInput(TransitionInputMap::Constant), self.constant_map())?;
+        stats.record_map(MapID::TransitionInput(TransitionInputMap::Public), self.public_map())?;
+        stats.record_map(MapID::TransitionInput(TransitionInputMap::Private), self.private_map())?;
+        stats.record_map(MapID::TransitionInput(TransitionInputMap::Record), self.record_map())?;
+        stats.record_map(MapID::TransitionInput(TransitionInputMap::RecordTag), self.record_tag_map())?;
+        stats.record_map(MapID::TransitionInput(TransitionInputMap::ExternalRecord), self.external_record_map())?;
+        Ok(())
+    }
+
+    /// Checks that the input maps agree with each other, recording any inconsistencies in the given report.
+    /// If `repair` is `true`, the reverse input IDs and record tags are repaired.
+    fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        atomic_batch_scope!(self, {
+            for (transition_id, input_ids) in self.id_map().iter_confirmed() {
+                let transition_id = cow_to_copied!(transition_id);
+                for input_id in input_ids.iter() {
+                    // Ensure the reverse input ID exists.
+                    report.check_index("input reverse ID", self.reverse_id_map(), *input_id, transition_id, repair)?;
+                    // Ensure the record tag exists, if the input is a record.
+                    if let Some(tag) = self.record_map().get_confirmed(input_id)? {
+                        report.check_index("input record tag", self.record_tag_map(), *tag, *input_id, repair)?;
+                        continue;
+                    }
+                    // Ensure the input exists.
+                    if !self.constant_map().contains_key_confirmed(input_id)?
+                        && !self.public_map().contains_key_confirmed(input_id)?
+                        && !self.private_map().contains_key_confirmed(input_id)?
+                        && !self.external_record_map().contains_key_confirmed(input_id)?
+                    {
+                        report.found("input", format!("Input '{input_id}' of transition '{transition_id}' is missing"));
+                    }
+                }
+            }
+            // Ensure every reverse input ID belongs to its transition.
+            for (input_id, transition_id) in self.reverse_id_map().iter_confirmed().collect::<Vec<_>>() {
+                if !self.get_ids(&transition_id)?.contains(&input_id) {
+                    report.remove_dangling("input reverse ID", self.reverse_id_map(), *input_id, repair)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
     /// Returns the transition ID that contains the given `input ID`.
     fn find_transition_id(&self, input_id: &Field<N>) -> Result<Option<N::TransitionID>> {
         match self.reverse_id_map().get_confirmed(input_id)? {
@@ -305,9 +377,9 @@ pub struct InputStore<N: Network, I: InputStorage<N>> {
 
 impl<N: Network, I: InputStorage<N>> InputStore<N, I> {
     /// Initializes the transition input store.
-    pub fn open(dev: Option<u16>) -> Result<Self> {
+    pub fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
         // Initialize a new transition input storage.
-        let storage = I::open(dev)?;
+        let storage = I::open(storage)?;
         // Return the transition input store.
         Ok(Self {
             constant: storage.constant_map().clone(),
@@ -343,6 +415,21 @@ impl<N: Network, I: InputStorage<N>> InputStore<N, I> {
         self.storage.remove(transition_id)
     }
 
+    /// Prunes the input values for the given `transition ID`.
+    pub fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        self.storage.prune(transition_id)
+    }
+
+    /// Records the statistics of the input maps in the given stats.
+    pub fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        self.storage.record_stats(stats)
+    }
+
+    /// Checks that the input maps agree with each other, recording any inconsistencies in the given report.
+    pub fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        self.storage.check_consistency(repair, report)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -378,9 +465,9 @@ impl<N: Network, I: InputStorage<N>> InputStore<N, I> {
         self.storage.finish_atomic()
     }
 
-    /// Returns the optional development ID.
-    pub fn dev(&self) -> Option<u16> {
-        self.storage.dev()
+    /// Returns the storage mode.
+    pub fn storage_mode(&self) -> &StorageMode {
+        self.storage.storage_mode()
     }
 }
 
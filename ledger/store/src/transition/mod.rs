@@ -22,7 +22,18 @@ use crate::{
     atomic_batch_scope,
     cow_to_cloned,
     cow_to_copied,
-    helpers::{Map, MapRead},
+    helpers::{
+        ConsistencyReport,
+        Map,
+        MapID,
+        MapRead,
+        NestedMap,
+        NestedMapRead,
+        StorageMode,
+        StorageStats,
+        TransitionMap,
+        TransitionPosition,
+    },
 };
 use console::{
     network::prelude::*,
@@ -50,9 +61,11 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
     type TCMMap: for<'a> Map<'a, N::TransitionID, Field<N>>;
     /// The mapping of `transition commitment` to `transition ID`.
     type ReverseTCMMap: for<'a> Map<'a, Field<N>, N::TransitionID>;
+    /// The mapping of `(program ID, function name)` to `transition position` to `transition ID`, for the calls of each function.
+    type CallMap: for<'a> NestedMap<'a, (ProgramID<N>, Identifier<N>), TransitionPosition, N::TransitionID>;
 
     /// Initializes the transition storage.
-    fn open(dev: Option<u16>) -> Result<Self>;
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self>;
 
     /// Returns the transition program IDs and function names.
     fn locator_map(&self) -> &Self::LocatorMap;
@@ -68,11 +81,13 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
     fn tcm_map(&self) -> &Self::TCMMap;
     /// Returns the reverse `tcm` map.
     fn reverse_tcm_map(&self) -> &Self::ReverseTCMMap;
+    /// Returns the call map.
+    fn call_map(&self) -> &Self::CallMap;
 
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16> {
-        debug_assert!(self.input_store().dev() == self.output_store().dev());
-        self.input_store().dev()
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        debug_assert!(self.input_store().storage_mode() == self.output_store().storage_mode());
+        self.input_store().storage_mode()
     }
 
     /// Starts an atomic batch write operation.
@@ -84,6 +99,7 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.reverse_tpk_map().start_atomic();
         self.tcm_map().start_atomic();
         self.reverse_tcm_map().start_atomic();
+        self.call_map().start_atomic();
     }
 
     /// Checks if an atomic batch is in progress.
@@ -95,6 +111,7 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
             || self.reverse_tpk_map().is_atomic_in_progress()
             || self.tcm_map().is_atomic_in_progress()
             || self.reverse_tcm_map().is_atomic_in_progress()
+            || self.call_map().is_atomic_in_progress()
     }
 
     /// Checkpoints the atomic batch.
@@ -106,6 +123,7 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.reverse_tpk_map().atomic_checkpoint();
         self.tcm_map().atomic_checkpoint();
         self.reverse_tcm_map().atomic_checkpoint();
+        self.call_map().atomic_checkpoint();
     }
 
     /// Clears the latest atomic batch checkpoint.
@@ -117,6 +135,7 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.reverse_tpk_map().clear_latest_checkpoint();
         self.tcm_map().clear_latest_checkpoint();
         self.reverse_tcm_map().clear_latest_checkpoint();
+        self.call_map().clear_latest_checkpoint();
     }
 
     /// Rewinds the atomic batch to the previous checkpoint.
@@ -128,6 +147,7 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.reverse_tpk_map().atomic_rewind();
         self.tcm_map().atomic_rewind();
         self.reverse_tcm_map().atomic_rewind();
+        self.call_map().atomic_rewind();
     }
 
     /// Aborts an atomic batch write operation.
@@ -139,6 +159,7 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.reverse_tpk_map().abort_atomic();
         self.tcm_map().abort_atomic();
         self.reverse_tcm_map().abort_atomic();
+        self.call_map().abort_atomic();
     }
 
     /// Finishes an atomic batch write operation.
@@ -149,7 +170,8 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.tpk_map().finish_atomic()?;
         self.reverse_tpk_map().finish_atomic()?;
         self.tcm_map().finish_atomic()?;
-        self.reverse_tcm_map().finish_atomic()
+        self.reverse_tcm_map().finish_atomic()?;
+        self.call_map().finish_atomic()
     }
 
     /// Stores the given `transition` into storage.
@@ -176,8 +198,29 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Stores the call of the given `transition`, at the given transition index of the block at the given height.
+    fn insert_call(&self, transition: &Transition<N>, height: u32, index: u32) -> Result<()> {
+        self.call_map().insert(
+            (*transition.program_id(), *transition.function_name()),
+            TransitionPosition::new(height, index),
+            *transition.id(),
+        )
+    }
+
+    /// Removes the call of the given `transition`, at the given transition index of the block at the given height.
+    fn remove_call(&self, transition: &Transition<N>, height: u32, index: u32) -> Result<()> {
+        self.call_map().remove_key(
+            &(*transition.program_id(), *transition.function_name()),
+            &TransitionPosition::new(height, index),
+        )
+    }
+
     /// Removes the input for the given `transition ID`.
     fn remove(&self, transition_id: &N::TransitionID) -> Result<()> {
+        // Ensure the transition exists.
+        if !self.locator_map().contains_key_confirmed(transition_id)? {
+            return Ok(());
+        }
         // Retrieve the `tpk`.
         let tpk = match self.tpk_map().get_confirmed(transition_id)? {
             Some(tpk) => cow_to_copied!(tpk),
@@ -209,6 +252,96 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the input and output values for the given `transition ID`.
+    fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        atomic_batch_scope!(self, {
+            // Prune the inputs.
+            self.input_store().prune(transition_id)?;
+            // Prune the outputs.
+            self.output_store().prune(transition_id)?;
+
+            Ok(())
+        })
+    }
+
+    /// Records the statistics of the transition maps and the input and output stores in the given stats.
+    fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        stats.record_map(MapID::Transition(TransitionMap::Locator), self.locator_map())?;
+        stats.record_map(MapID::Transition(TransitionMap::TPK), self.tpk_map())?;
+        stats.record_map(MapID::Transition(TransitionMap::ReverseTPK), self.reverse_tpk_map())?;
+        stats.record_map(MapID::Transition(TransitionMap::TCM), self.tcm_map())?;
+        stats.record_map(MapID::Transition(TransitionMap::ReverseTCM), self.reverse_tcm_map())?;
+        stats.record_nested_map(MapID::Transition(TransitionMap::Call), self.call_map())?;
+        self.input_store().record_stats(stats)?;
+        self.output_store().record_stats(stats)?;
+        Ok(())
+    }
+
+    /// Checks that the transition maps agree with each other, recording any inconsistencies in the given report.
+    /// If `repair` is `true`, the derived indexes are repaired.
+    fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        atomic_batch_scope!(self, {
+            for transition_id in self.locator_map().keys_confirmed() {
+                let transition_id = cow_to_copied!(transition_id);
+                report.add_transition();
+                // Ensure the `tpk` exists, along with its reverse entry.
+                match self.tpk_map().get_confirmed(&transition_id)? {
+                    Some(tpk) => {
+                        report.check_index("reverse tpk", self.reverse_tpk_map(), *tpk, transition_id, repair)?
+                    }
+                    None => report.found("tpk", format!("Transition '{transition_id}' is missing its tpk")),
+                }
+                // Ensure the `tcm` exists, along with its reverse entry.
+                match self.tcm_map().get_confirmed(&transition_id)? {
+                    Some(tcm) => {
+                        report.check_index("reverse tcm", self.reverse_tcm_map(), *tcm, transition_id, repair)?
+                    }
+                    None => report.found("tcm", format!("Transition '{transition_id}' is missing its tcm")),
+                }
+            }
+            // Ensure every reverse `tpk` and `tcm` entry belongs to a transition.
+            for (tpk, transition_id) in self.reverse_tpk_map().iter_confirmed().collect::<Vec<_>>() {
+                if !self.locator_map().contains_key_confirmed(&transition_id)? {
+                    report.remove_dangling("reverse tpk", self.reverse_tpk_map(), *tpk, repair)?;
+                }
+            }
+            for (tcm, transition_id) in self.reverse_tcm_map().iter_confirmed().collect::<Vec<_>>() {
+                if !self.locator_map().contains_key_confirmed(&transition_id)? {
+                    report.remove_dangling("reverse tcm", self.reverse_tcm_map(), *tcm, repair)?;
+                }
+            }
+            // Ensure every call belongs to a transition of its program and function.
+            for (function, position, transition_id) in self.call_map().iter_confirmed().collect::<Vec<_>>() {
+                let is_valid = match self.locator_map().get_confirmed(&transition_id)? {
+                    Some(locator) => *locator == *function,
+                    None => false,
+                };
+                if !is_valid {
+                    if repair {
+                        self.call_map().remove_key(&function, &position)?;
+                    }
+                    report.record("call", format!("'{}' is dangling", *transition_id), repair);
+                }
+            }
+            // Check the inputs and outputs.
+            self.input_store().check_consistency(repair, report)?;
+            self.output_store().check_consistency(repair, report)?;
+            Ok(())
+        })
+    }
+
+    /// Returns up to `limit` of the `(transition position, transition ID)` pairs for the calls to the given function,
+    /// in ledger order, starting at the given position (inclusive), or at the first call if `start` is `None`.
+    fn get_calls(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        start: Option<TransitionPosition>,
+        limit: usize,
+    ) -> Result<Vec<(TransitionPosition, N::TransitionID)>> {
+        self.call_map().get_map_range_confirmed(&(*program_id, *function_name), start.as_ref(), limit)
+    }
+
     /// Returns the transition for the given `transition ID`.
     fn get(&self, transition_id: &N::TransitionID) -> Result<Option<Transition<N>>> {
         // Retrieve the program ID and function name.
@@ -270,9 +403,9 @@ pub struct TransitionStore<N: Network, T: TransitionStorage<N>> {
 
 impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
     /// Initializes the transition store.
-    pub fn open(dev: Option<u16>) -> Result<Self> {
+    pub fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
         // Initialize the transition storage.
-        let storage = T::open(dev)?;
+        let storage = T::open(storage)?;
         // Return the transition store.
         Ok(Self {
             locator: storage.locator_map().clone(),
@@ -305,11 +438,36 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
         self.storage.insert(transition)
     }
 
+    /// Stores the call of the given `transition`, at the given transition index of the block at the given height.
+    pub fn insert_call(&self, transition: &Transition<N>, height: u32, index: u32) -> Result<()> {
+        self.storage.insert_call(transition, height, index)
+    }
+
+    /// Removes the call of the given `transition`, at the given transition index of the block at the given height.
+    pub fn remove_call(&self, transition: &Transition<N>, height: u32, index: u32) -> Result<()> {
+        self.storage.remove_call(transition, height, index)
+    }
+
     /// Removes the input for the given `transition ID`.
     pub fn remove(&self, transition_id: &N::TransitionID) -> Result<()> {
         self.storage.remove(transition_id)
     }
 
+    /// Prunes the input and output values for the given `transition ID`.
+    pub fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        self.storage.prune(transition_id)
+    }
+
+    /// Records the statistics of the transition maps and the input and output stores in the given stats.
+    pub fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        self.storage.record_stats(stats)
+    }
+
+    /// Checks that the transition maps agree with each other, recording any inconsistencies in the given report.
+    pub fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        self.storage.check_consistency(repair, report)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -345,9 +503,9 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
         self.storage.finish_atomic()
     }
 
-    /// Returns the optional development ID.
-    pub fn dev(&self) -> Option<u16> {
-        self.storage.dev()
+    /// Returns the storage mode.
+    pub fn storage_mode(&self) -> &StorageMode {
+        self.storage.storage_mode()
     }
 }
 
@@ -373,6 +531,29 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
         self.storage.get(transition_id)
     }
 
+    /// Returns up to `limit` of the `(transition position, transition ID)` pairs for the calls to the given function,
+    /// in ledger order, starting at the given position (inclusive), or at the first call if `start` is `None`.
+    pub fn get_calls(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        start: Option<TransitionPosition>,
+        limit: usize,
+    ) -> Result<Vec<(TransitionPosition, N::TransitionID)>> {
+        self.storage.get_calls(program_id, function_name, start, limit)
+    }
+
+    /// Returns the transition ID of the call to the given function at the given position, if it exists.
+    pub fn get_call(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        position: TransitionPosition,
+    ) -> Result<Option<N::TransitionID>> {
+        let function = (*program_id, *function_name);
+        Ok(self.storage.call_map().get_value_confirmed(&function, &position)?.map(|call| cow_to_copied!(call)))
+    }
+
     /// Returns the program ID for the given `transition ID`.
     pub fn get_program_id(&self, transition_id: &N::TransitionID) -> Result<Option<ProgramID<N>>> {
         Ok(self.locator.get_confirmed(transition_id)?.map(|locator| match locator {
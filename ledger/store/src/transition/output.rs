@@ -14,7 +14,8 @@
 
 use crate::{
     atomic_batch_scope,
-    helpers::{Map, MapRead},
+    cow_to_copied,
+    helpers::{ConsistencyReport, Map, MapID, MapRead, StorageMode, StorageStats, TransitionOutputMap},
 };
 use console::{
     network::prelude::*,
@@ -48,7 +49,7 @@ pub trait OutputStorage<N: Network>: Clone + Send + Sync {
     type FutureMap: for<'a> Map<'a, Field<N>, Option<Future<N>>>;
 
     /// Initializes the transition output storage.
-    fn open(dev: Option<u16>) -> Result<Self>;
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self>;
 
     /// Returns the ID map.
     fn id_map(&self) -> &Self::IDMap;
@@ -69,8 +70,8 @@ pub trait OutputStorage<N: Network>: Clone + Send + Sync {
     /// Returns the future map.
     fn future_map(&self) -> &Self::FutureMap;
 
-    /// Returns the optional development ID.
-    fn dev(&self) -> Option<u16>;
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode;
 
     /// Starts an atomic batch write operation.
     fn start_atomic(&self) {
@@ -233,6 +234,109 @@ pub trait OutputStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the output values for the given `transition ID`,
+    /// while keeping the output IDs, and the commitments, checksums, and nonces of the record outputs.
+    fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        // Retrieve the output IDs.
+        let output_ids = self.get_ids(transition_id)?;
+
+        atomic_batch_scope!(self, {
+            for output_id in output_ids {
+                // Prune the output value, if one exists.
+                if self.constant_map().contains_key_confirmed(&output_id)? {
+                    self.constant_map().insert(output_id, None)?;
+                }
+                if self.public_map().contains_key_confirmed(&output_id)? {
+                    self.public_map().insert(output_id, None)?;
+                }
+                if self.private_map().contains_key_confirmed(&output_id)? {
+                    self.private_map().insert(output_id, None)?;
+                }
+                if let Some(record) = self.record_map().get_confirmed(&output_id)? {
+                    let checksum = record.0;
+                    self.record_map().insert(output_id, (checksum, None))?;
+                }
+                if self.future_map().contains_key_confirmed(&output_id)? {
+                    self.future_map().insert(output_id, None)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Records the statistics of the output maps in the given stats.
+    fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        stats.record_map(MapID::TransitionOutput(TransitionOutputMap::ID), self.id_map())?;
+        stats.record_map(MapID::TransitionOutput(TransitionOutputMap::ReverseID), self.reverse_id_map())?;
+        stats.record_map(MapID::TransitionOutput(TransitionOutputMap::Constant), self.constant_map())?;
+        stats.record_map(MapID::TransitionOutput(TransitionOutputMap::Public), self.public_map())?;
+        stats.record_map(MapID::TransitionOutput(TransitionOutputMap::Private), self.private_map())?;
+        stats.record_map(MapID::TransitionOutput(TransitionOutputMap::Record), self.record_map())?;
+        stats.record_map(MapID::TransitionOutput(TransitionOutputMap::RecordNonce), self.record_nonce_map())?;
+        stats.record_map(MapID::TransitionOutput(TransitionOutputMap::ExternalRecord), self.external_record_map())?;
+        stats.record_map(MapID::TransitionOutput(TransitionOutputMap::Future), self.future_map())?;
+        Ok(())
+    }
+
+    /// Checks that the output maps agree with each other, recording any inconsistencies in the given report.
+    /// If `repair` is `true`, the reverse output IDs and record nonces are repaired.
+    fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        atomic_batch_scope!(self, {
+            for (transition_id, output_ids) in self.id_map().iter_confirmed() {
+                let transition_id = cow_to_copied!(transition_id);
+                for output_id in output_ids.iter() {
+                    // Ensure the reverse output ID exists.
+                    report.check_index(
+                        "output reverse ID",
+                        self.reverse_id_map(),
+                        *output_id,
+                        transition_id,
+                        repair,
+                    )?;
+                    // Ensure the record nonce maps to the commitment, if the output is a record.
+                    if let Some(record) = self.record_map().get_confirmed(output_id)? {
+                        if let Some(record) = &record.1 {
+                            report.check_index(
+                                "record nonce",
+                                self.record_nonce_map(),
+                                *record.nonce(),
+                                *output_id,
+                                repair,
+                            )?;
+                        }
+                        continue;
+                    }
+                    // Ensure the output exists.
+                    if !self.constant_map().contains_key_confirmed(output_id)?
+                        && !self.public_map().contains_key_confirmed(output_id)?
+                        && !self.private_map().contains_key_confirmed(output_id)?
+                        && !self.external_record_map().contains_key_confirmed(output_id)?
+                        && !self.future_map().contains_key_confirmed(output_id)?
+                    {
+                        report.found(
+                            "output",
+                            format!("Output '{output_id}' of transition '{transition_id}' is missing"),
+                        );
+                    }
+                }
+            }
+            // Ensure every reverse output ID belongs to its transition.
+            for (output_id, transition_id) in self.reverse_id_map().iter_confirmed().collect::<Vec<_>>() {
+                if !self.get_ids(&transition_id)?.contains(&output_id) {
+                    report.remove_dangling("output reverse ID", self.reverse_id_map(), *output_id, repair)?;
+                }
+            }
+            // Ensure every record nonce maps to a record commitment.
+            for (nonce, commitment) in self.record_nonce_map().iter_confirmed().collect::<Vec<_>>() {
+                if !self.record_map().contains_key_confirmed(&commitment)? {
+                    report.remove_dangling("record nonce", self.record_nonce_map(), *nonce, repair)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
     /// Returns the transition ID that contains the given `output ID`.
     fn find_transition_id(&self, output_id: &Field<N>) -> Result<Option<N::TransitionID>> {
         match self.reverse_id_map().get_confirmed(output_id)? {
@@ -326,9 +430,9 @@ pub struct OutputStore<N: Network, O: OutputStorage<N>> {
 
 impl<N: Network, O: OutputStorage<N>> OutputStore<N, O> {
     /// Initializes the transition output store.
-    pub fn open(dev: Option<u16>) -> Result<Self> {
+    pub fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
         // Initialize a new transition output storage.
-        let storage = O::open(dev)?;
+        let storage = O::open(storage)?;
         // Return the transition output store.
         Ok(Self {
             constant: storage.constant_map().clone(),
@@ -366,6 +470,21 @@ impl<N: Network, O: OutputStorage<N>> OutputStore<N, O> {
         self.storage.remove(transition_id)
     }
 
+    /// Prunes the output values for the given `transition ID`.
+    pub fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        self.storage.prune(transition_id)
+    }
+
+    /// Records the statistics of the output maps in the given stats.
+    pub fn record_stats(&self, stats: &mut StorageStats) -> Result<()> {
+        self.storage.record_stats(stats)
+    }
+
+    /// Checks that the output maps agree with each other, recording any inconsistencies in the given report.
+    pub fn check_consistency(&self, repair: bool, report: &mut ConsistencyReport) -> Result<()> {
+        self.storage.check_consistency(repair, report)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -401,9 +520,9 @@ impl<N: Network, O: OutputStorage<N>> OutputStore<N, O> {
         self.storage.finish_atomic()
     }
 
-    /// Returns the optional development ID.
-    pub fn dev(&self) -> Option<u16> {
-        self.storage.dev()
+    /// Returns the storage mode.
+    pub fn storage_mode(&self) -> &StorageMode {
+        self.storage.storage_mode()
     }
 }
 
@@ -14,12 +14,33 @@
 
 #![forbid(unsafe_code)]
 
-pub const GAUGE_NAMES: [&str; 1] = [committee::TOTAL_STAKE];
+pub const GAUGE_NAMES: [&str; 9] = [
+    committee::TOTAL_STAKE,
+    store::MAP_ENTRIES,
+    store::MAP_BYTES,
+    store::PENDING_OPERATIONS,
+    store::CHECKPOINT_DEPTH,
+    store::TOTAL_ENTRIES,
+    store::TOTAL_BYTES,
+    store::ESTIMATED_KEYS,
+    store::SST_FILES_BYTES,
+];
 
 pub mod committee {
     pub const TOTAL_STAKE: &str = "snarkvm_ledger_committee_total_stake";
 }
 
+pub mod store {
+    pub const MAP_ENTRIES: &str = "snarkvm_ledger_store_map_entries";
+    pub const MAP_BYTES: &str = "snarkvm_ledger_store_map_bytes";
+    pub const PENDING_OPERATIONS: &str = "snarkvm_ledger_store_pending_operations";
+    pub const CHECKPOINT_DEPTH: &str = "snarkvm_ledger_store_checkpoint_depth";
+    pub const TOTAL_ENTRIES: &str = "snarkvm_ledger_store_total_entries";
+    pub const TOTAL_BYTES: &str = "snarkvm_ledger_store_total_bytes";
+    pub const ESTIMATED_KEYS: &str = "snarkvm_ledger_store_estimated_keys";
+    pub const SST_FILES_BYTES: &str = "snarkvm_ledger_store_sst_files_bytes";
+}
+
 /// Registers all metrics.
 pub fn register_metrics() {
     for name in GAUGE_NAMES {
@@ -34,3 +55,16 @@ pub fn register_metrics() {
 pub fn gauge<V: Into<f64>>(name: &'static str, value: V) {
     ::metrics::gauge!(name, value.into());
 }
+
+/// Updates a gauge with the given name and label to the given value.
+///
+/// Each distinct label value is reported as a separate time series of the same gauge.
+pub fn gauge_with_label<V: Into<f64>, L: Into<String>>(
+    name: &'static str,
+    value: V,
+    label: &'static str,
+    label_value: L,
+) {
+    let label_value: String = label_value.into();
+    ::metrics::gauge!(name, value.into(), label => label_value);
+}
@@ -0,0 +1,251 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The accumulated powers of a powers-of-tau ceremony for the universal SRS.
+///
+/// Each contribution multiplies the secrets `beta` and `gamma` by fresh secrets of the contributor,
+/// so the resulting SRS is sound as long as at least one contributor discarded their secrets.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Accumulator<E: PairingEngine> {
+    /// Group elements of form `[G, \beta * G, \beta^2 * G, ..., \beta^d G]`.
+    powers_of_beta_g: Vec<E::G1Affine>,
+    /// Group elements of form `{ \beta^i \gamma G }`, where `i` is in the hiding window,
+    /// or in the window of a supported degree bound.
+    powers_of_beta_times_gamma_g: BTreeMap<usize, E::G1Affine>,
+    /// Group elements of form `{ \beta^{-(d - i)} H }`, where `i` is a supported degree bound.
+    negative_powers_of_beta_h: BTreeMap<usize, E::G2Affine>,
+    /// beta * h
+    beta_h: E::G2Affine,
+    /// gamma * h
+    gamma_h: E::G2Affine,
+}
+
+impl<E: PairingEngine> Accumulator<E> {
+    /// Initializes a new accumulator with `num_powers` powers, where all secrets are `1`.
+    pub fn new(num_powers: usize) -> Result<Self> {
+        ensure!(num_powers.is_power_of_two(), "The number of powers ({num_powers}) must be a power of two");
+        ensure!(num_powers >= 4, "The number of powers ({num_powers}) must be at least 4");
+        ensure!(num_powers <= MAX_NUM_POWERS, "The number of powers ({num_powers}) exceeds {MAX_NUM_POWERS}");
+
+        let g = E::G1Affine::prime_subgroup_generator();
+        let h = E::G2Affine::prime_subgroup_generator();
+
+        Ok(Self {
+            powers_of_beta_g: vec![g; num_powers],
+            powers_of_beta_times_gamma_g: gamma_indices(num_powers).into_iter().map(|i| (i, g)).collect(),
+            negative_powers_of_beta_h: degree_bounds(num_powers).map(|i| (i, h)).collect(),
+            beta_h: h,
+            gamma_h: h,
+        })
+    }
+
+    /// Returns the number of powers of beta G.
+    pub fn num_powers(&self) -> usize {
+        self.powers_of_beta_g.len()
+    }
+
+    /// Returns the maximum degree supported by the accumulator.
+    pub fn max_degree(&self) -> usize {
+        self.num_powers() - 1
+    }
+
+    /// Returns `beta * G`.
+    pub fn beta_g(&self) -> E::G1Affine {
+        self.powers_of_beta_g[1]
+    }
+
+    /// Returns `gamma * G`.
+    pub fn gamma_g(&self) -> E::G1Affine {
+        self.powers_of_beta_times_gamma_g[&0]
+    }
+
+    /// Returns `beta * H`.
+    pub fn beta_h(&self) -> E::G2Affine {
+        self.beta_h
+    }
+
+    /// Returns `gamma * H`.
+    pub fn gamma_h(&self) -> E::G2Affine {
+        self.gamma_h
+    }
+
+    /// Multiplies the secrets `beta` and `gamma` of the accumulator by `tau` and `delta` respectively.
+    pub(super) fn apply(&mut self, tau: E::Fr, delta: E::Fr) -> Result<()> {
+        ensure!(!tau.is_zero() && !delta.is_zero(), "The contributed secrets must be non-zero");
+        let tau_inverse = tau.inverse().ok_or_else(|| anyhow!("Failed to invert the contributed secret"))?;
+        let max_degree = self.max_degree();
+
+        // Update the powers of beta G.
+        let mut power = E::Fr::one();
+        for element in self.powers_of_beta_g.iter_mut() {
+            *element = (*element * power).to_affine();
+            power *= tau;
+        }
+        // Update the powers of beta times gamma G.
+        for (i, element) in self.powers_of_beta_times_gamma_g.iter_mut() {
+            *element = (*element * (delta * tau.pow([*i as u64]))).to_affine();
+        }
+        // Update the negative powers of beta H.
+        for (degree_bound, element) in self.negative_powers_of_beta_h.iter_mut() {
+            *element = (*element * tau_inverse.pow([(max_degree - degree_bound) as u64])).to_affine();
+        }
+        // Update beta H and gamma H.
+        self.beta_h = (self.beta_h * tau).to_affine();
+        self.gamma_h = (self.gamma_h * delta).to_affine();
+        Ok(())
+    }
+
+    /// Checks that the accumulator is well-formed, i.e. that all of its elements are
+    /// consistent with a single pair of secrets `beta` and `gamma`.
+    pub fn verify<R: Rng>(&self, rng: &mut R) -> Result<()> {
+        let num_powers = self.num_powers();
+        ensure!(num_powers.is_power_of_two() && num_powers >= 4, "Invalid number of powers ({num_powers})");
+        ensure!(num_powers <= MAX_NUM_POWERS, "The number of powers ({num_powers}) exceeds {MAX_NUM_POWERS}");
+
+        // Ensure the accumulator contains the expected indices.
+        ensure!(
+            self.powers_of_beta_times_gamma_g.keys().copied().eq(gamma_indices(num_powers)),
+            "The accumulator contains incorrect powers of beta gamma G"
+        );
+        ensure!(
+            self.negative_powers_of_beta_h.keys().copied().eq(degree_bounds(num_powers)),
+            "The accumulator contains incorrect negative powers of beta H"
+        );
+
+        let g = E::G1Affine::prime_subgroup_generator();
+        let h = E::G2Affine::prime_subgroup_generator();
+
+        // Ensure the powers of beta G start from the generator.
+        ensure!(self.powers_of_beta_g[0] == g, "The first power of beta G must be the generator");
+
+        // Ensure the powers of beta G are successive powers of beta.
+        let pairs = self.powers_of_beta_g.iter().zip(self.powers_of_beta_g.iter().skip(1));
+        let powers = merge_pairs(pairs.map(|(a, b)| (*a, *b)), rng);
+        ensure!(same_ratio::<E>(powers, (h, self.beta_h)), "The powers of beta G are inconsistent with beta H");
+
+        // Ensure the powers of beta gamma G are consistent with the powers of beta G and with gamma H.
+        let mut gamma_pairs = Vec::new();
+        let mut beta_pairs = Vec::new();
+        for (i, element) in self.powers_of_beta_times_gamma_g.iter() {
+            match self.powers_of_beta_g.get(*i) {
+                Some(power) => gamma_pairs.push((*power, *element)),
+                None => match self.powers_of_beta_times_gamma_g.get(&(i - 1)) {
+                    Some(previous) => beta_pairs.push((*previous, *element)),
+                    None => bail!("The accumulator is missing the power of beta gamma G at index {}", i - 1),
+                },
+            }
+        }
+        let gammas = merge_pairs(gamma_pairs.into_iter(), rng);
+        ensure!(same_ratio::<E>(gammas, (h, self.gamma_h)), "The powers of beta gamma G are inconsistent with gamma H");
+        if !beta_pairs.is_empty() {
+            let betas = merge_pairs(beta_pairs.into_iter(), rng);
+            ensure!(
+                same_ratio::<E>(betas, (h, self.beta_h)),
+                "The powers of beta gamma G are inconsistent with beta H"
+            );
+        }
+
+        // Ensure the negative powers of beta H are the inverses of the corresponding powers of beta G.
+        for (degree_bound, element) in self.negative_powers_of_beta_h.iter() {
+            let power = self.powers_of_beta_g[self.max_degree() - degree_bound];
+            ensure!(
+                same_ratio::<E>((g, power), (*element, h)),
+                "The negative power of beta H for degree bound {degree_bound} is incorrect"
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the powers of the universal SRS, for use with `PowersOfG::load`.
+    pub fn to_powers(&self) -> PowersOfG<E> {
+        PowersOfG::from_parts(
+            self.powers_of_beta_g.clone(),
+            self.powers_of_beta_times_gamma_g.clone(),
+            self.negative_powers_of_beta_h.clone(),
+            self.beta_h,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_curves::bls12_377::Bls12_377;
+    use snarkvm_utilities::TestRng;
+
+    type CurrentPairing = Bls12_377;
+
+    #[test]
+    fn test_new() {
+        let rng = &mut TestRng::default();
+
+        // Ensure the initial accumulator is well-formed.
+        let accumulator = Accumulator::<CurrentPairing>::new(16).unwrap();
+        assert_eq!(accumulator.num_powers(), 16);
+        assert_eq!(accumulator.max_degree(), 15);
+        accumulator.verify(rng).unwrap();
+
+        // Ensure invalid numbers of powers are rejected.
+        assert!(Accumulator::<CurrentPairing>::new(0).is_err());
+        assert!(Accumulator::<CurrentPairing>::new(2).is_err());
+        assert!(Accumulator::<CurrentPairing>::new(12).is_err());
+        assert!(Accumulator::<CurrentPairing>::new(MAX_NUM_POWERS * 2).is_err());
+    }
+
+    #[test]
+    fn test_apply() {
+        let rng = &mut TestRng::default();
+
+        let mut accumulator = Accumulator::<CurrentPairing>::new(16).unwrap();
+        for _ in 0..3 {
+            let (tau, delta) =
+                (sample_nonzero_scalar::<CurrentPairing, _>(rng), sample_nonzero_scalar::<CurrentPairing, _>(rng));
+            accumulator.apply(tau, delta).unwrap();
+            accumulator.verify(rng).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let rng = &mut TestRng::default();
+
+        let mut accumulator = Accumulator::<CurrentPairing>::new(16).unwrap();
+        let tau = sample_nonzero_scalar::<CurrentPairing, _>(rng);
+        let delta = sample_nonzero_scalar::<CurrentPairing, _>(rng);
+        accumulator.apply(tau, delta).unwrap();
+
+        // Tamper with a power of beta G.
+        let mut candidate = accumulator.clone();
+        candidate.powers_of_beta_g[7] = (candidate.powers_of_beta_g[7] * tau).to_affine();
+        assert!(candidate.verify(rng).is_err());
+
+        // Tamper with a power of beta gamma G.
+        let mut candidate = accumulator.clone();
+        let last = *candidate.powers_of_beta_times_gamma_g.keys().last().unwrap();
+        candidate.powers_of_beta_times_gamma_g.insert(last, CurrentPairing::G1Affine::prime_subgroup_generator());
+        assert!(candidate.verify(rng).is_err());
+
+        // Tamper with a negative power of beta H.
+        let mut candidate = accumulator.clone();
+        candidate.negative_powers_of_beta_h.insert(2, candidate.beta_h);
+        assert!(candidate.verify(rng).is_err());
+
+        // Tamper with gamma H.
+        let mut candidate = accumulator;
+        candidate.gamma_h = (candidate.gamma_h * tau).to_affine();
+        assert!(candidate.verify(rng).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The domain separator for the proof of knowledge of `tau`.
+const TAU_DOMAIN: &[u8] = b"AleoPowersOfTauBeta0";
+/// The domain separator for the proof of knowledge of `delta`.
+const DELTA_DOMAIN: &[u8] = b"AleoPowersOfTauGamma0";
+
+/// A single contribution to a powers-of-tau ceremony, which multiplies the secrets `beta` and `gamma`
+/// of the accumulator by the secrets `tau` and `delta` of the contributor.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Contribution<E: PairingEngine> {
+    /// `beta * G`, after this contribution.
+    beta_g: E::G1Affine,
+    /// `gamma * G`, after this contribution.
+    gamma_g: E::G1Affine,
+    /// `tau * H`.
+    tau_h: E::G2Affine,
+    /// `delta * H`.
+    delta_h: E::G2Affine,
+    /// The proof of knowledge of `tau`, i.e. `tau * R`, where `R` is bound to the transcript.
+    tau_proof: E::G1Affine,
+    /// The proof of knowledge of `delta`, i.e. `delta * R`, where `R` is bound to the transcript.
+    delta_proof: E::G1Affine,
+}
+
+impl<E: PairingEngine> Contribution<E> {
+    /// Initializes a new contribution of `tau` and `delta` on top of the given `beta * G` and `gamma * G`,
+    /// where `digest` is the digest of the transcript prior to this contribution.
+    pub(super) fn new(
+        digest: &[u8; 32],
+        previous_beta_g: E::G1Affine,
+        previous_gamma_g: E::G1Affine,
+        tau: E::Fr,
+        delta: E::Fr,
+    ) -> Result<Self> {
+        let h = E::G2Affine::prime_subgroup_generator();
+        let (tau_h, delta_h) = ((h * tau).to_affine(), (h * delta).to_affine());
+
+        // Compute the proofs of knowledge.
+        let tau_proof = (proof_base::<E>(TAU_DOMAIN, digest, &tau_h)? * tau).to_affine();
+        let delta_proof = (proof_base::<E>(DELTA_DOMAIN, digest, &delta_h)? * delta).to_affine();
+
+        Ok(Self {
+            beta_g: (previous_beta_g * tau).to_affine(),
+            gamma_g: (previous_gamma_g * delta).to_affine(),
+            tau_h,
+            delta_h,
+            tau_proof,
+            delta_proof,
+        })
+    }
+
+    /// Returns `beta * G`, after this contribution.
+    pub fn beta_g(&self) -> E::G1Affine {
+        self.beta_g
+    }
+
+    /// Returns `gamma * G`, after this contribution.
+    pub fn gamma_g(&self) -> E::G1Affine {
+        self.gamma_g
+    }
+
+    /// Returns `tau * H`.
+    pub fn tau_h(&self) -> E::G2Affine {
+        self.tau_h
+    }
+
+    /// Returns `delta * H`.
+    pub fn delta_h(&self) -> E::G2Affine {
+        self.delta_h
+    }
+
+    /// Checks that this contribution was applied to the given `beta * G` and `gamma * G`,
+    /// and that the contributor knows `tau` and `delta`. Here, `digest` is the digest
+    /// of the transcript prior to this contribution.
+    pub fn verify(&self, digest: &[u8; 32], previous_beta_g: E::G1Affine, previous_gamma_g: E::G1Affine) -> Result<()> {
+        let h = E::G2Affine::prime_subgroup_generator();
+
+        // Ensure the contributor knows `tau` and `delta`.
+        let tau_base = proof_base::<E>(TAU_DOMAIN, digest, &self.tau_h)?;
+        ensure!(same_ratio::<E>((tau_base, self.tau_proof), (h, self.tau_h)), "Invalid proof of knowledge of tau");
+        let delta_base = proof_base::<E>(DELTA_DOMAIN, digest, &self.delta_h)?;
+        ensure!(
+            same_ratio::<E>((delta_base, self.delta_proof), (h, self.delta_h)),
+            "Invalid proof of knowledge of delta"
+        );
+
+        // Ensure the contribution was applied to the previous `beta * G` and `gamma * G`.
+        ensure!(same_ratio::<E>((previous_beta_g, self.beta_g), (h, self.tau_h)), "Incorrect contribution to beta");
+        ensure!(
+            same_ratio::<E>((previous_gamma_g, self.gamma_g), (h, self.delta_h)),
+            "Incorrect contribution to gamma"
+        );
+        Ok(())
+    }
+
+    /// Returns the digest of the transcript after this contribution,
+    /// where `digest` is the digest of the transcript prior to this contribution.
+    pub fn next_digest(&self, digest: &[u8; 32]) -> Result<[u8; 32]> {
+        let mut hasher = Sha256::new();
+        hasher.update(digest);
+        hasher.update(self.to_bytes_le()?);
+        Ok(hasher.finalize().into())
+    }
+}
+
+/// Returns the base of a proof of knowledge, which is bound to the domain, the transcript digest, and the public key.
+fn proof_base<E: PairingEngine>(domain: &[u8], digest: &[u8; 32], public_key: &E::G2Affine) -> Result<E::G1Affine> {
+    let mut input = Vec::with_capacity(domain.len() + 32 + 192);
+    input.extend_from_slice(domain);
+    input.extend_from_slice(digest);
+    input.extend(public_key.to_bytes_le()?);
+    Ok(hash_to_g1::<E>(&input))
+}
+
+impl<E: PairingEngine> FromBytes for Contribution<E> {
+    /// Reads the contribution from the buffer.
+    fn read_le<R: Read>(reader: R) -> std::io::Result<Self> {
+        Self::deserialize_with_mode(reader, Compress::Yes, Validate::Yes).map_err(|e| e.into())
+    }
+}
+
+impl<E: PairingEngine> ToBytes for Contribution<E> {
+    /// Writes the contribution to the buffer.
+    fn write_le<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        self.serialize_with_mode(writer, Compress::Yes).map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_curves::bls12_377::Bls12_377;
+    use snarkvm_utilities::TestRng;
+
+    type CurrentPairing = Bls12_377;
+
+    #[test]
+    fn test_verify() {
+        let rng = &mut TestRng::default();
+
+        let digest = [7u8; 32];
+        let g = <CurrentPairing as PairingEngine>::G1Affine::prime_subgroup_generator();
+        let tau = sample_nonzero_scalar::<CurrentPairing, _>(rng);
+        let delta = sample_nonzero_scalar::<CurrentPairing, _>(rng);
+
+        let contribution = Contribution::<CurrentPairing>::new(&digest, g, g, tau, delta).unwrap();
+        contribution.verify(&digest, g, g).unwrap();
+
+        // Ensure the contribution is bound to the transcript digest.
+        assert!(contribution.verify(&[8u8; 32], g, g).is_err());
+        // Ensure the contribution is bound to the previous elements.
+        let other = (g * tau).to_affine();
+        assert!(contribution.verify(&digest, other, g).is_err());
+        assert!(contribution.verify(&digest, g, other).is_err());
+
+        // Ensure a proof of knowledge cannot be reused for a different secret.
+        let mut candidate = contribution;
+        candidate.beta_g = (g * (tau * tau)).to_affine();
+        candidate.tau_h =
+            (<CurrentPairing as PairingEngine>::G2Affine::prime_subgroup_generator() * (tau * tau)).to_affine();
+        assert!(candidate.verify(&digest, g, g).is_err());
+    }
+
+    #[test]
+    fn test_bytes() {
+        let rng = &mut TestRng::default();
+
+        let g = <CurrentPairing as PairingEngine>::G1Affine::prime_subgroup_generator();
+        let tau = sample_nonzero_scalar::<CurrentPairing, _>(rng);
+        let delta = sample_nonzero_scalar::<CurrentPairing, _>(rng);
+        let expected = Contribution::<CurrentPairing>::new(&[0u8; 32], g, g, tau, delta).unwrap();
+
+        let expected_bytes = expected.to_bytes_le().unwrap();
+        assert_eq!(expected, Contribution::read_le(&expected_bytes[..]).unwrap());
+        assert!(Contribution::<CurrentPairing>::read_le(&expected_bytes[1..]).is_err());
+    }
+}
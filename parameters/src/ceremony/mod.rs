@@ -0,0 +1,120 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod accumulator;
+pub use accumulator::*;
+
+mod contribution;
+pub use contribution::*;
+
+mod transcript;
+pub use transcript::*;
+
+use crate::testnet3::{powers::MAX_NUM_POWERS, PowersOfG};
+use snarkvm_curves::traits::{AffineCurve, PairingCurve, PairingEngine, ProjectiveCurve};
+use snarkvm_fields::{Field, One, Zero};
+use snarkvm_utilities::{
+    CanonicalDeserialize,
+    CanonicalSerialize,
+    Compress,
+    FromBytes,
+    Read,
+    ToBytes,
+    Uniform,
+    Validate,
+    Write,
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use rand::{CryptoRng, Rng};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The number of powers of beta times gamma G in each window, which supports a hiding bound of 1.
+const NUM_HIDING_POWERS: usize = 3;
+
+/// Returns the degree bounds supported by an SRS with `num_powers` powers,
+/// which are of the form `2^k - 2` for `k` in `1` to `log_2(num_powers) - 1`.
+fn degree_bounds(num_powers: usize) -> impl Iterator<Item = usize> {
+    (1..num_powers.trailing_zeros()).map(|k| (1usize << k) - 2)
+}
+
+/// Returns the indices of the powers of beta times gamma G in an SRS with `num_powers` powers.
+/// These consist of the powers required for hiding, and the powers required for each degree bound.
+fn gamma_indices(num_powers: usize) -> BTreeSet<usize> {
+    let max_degree = num_powers - 1;
+    let mut indices: BTreeSet<usize> = (0..NUM_HIDING_POWERS).collect();
+    for degree_bound in degree_bounds(num_powers) {
+        let shift_degree = max_degree - degree_bound;
+        indices.extend(shift_degree..shift_degree + NUM_HIDING_POWERS);
+    }
+    indices
+}
+
+/// Samples a random non-zero scalar.
+fn sample_nonzero_scalar<E: PairingEngine, R: Rng + CryptoRng>(rng: &mut R) -> E::Fr {
+    loop {
+        let scalar = E::Fr::rand(rng);
+        if !scalar.is_zero() {
+            return scalar;
+        }
+    }
+}
+
+/// Returns `true` if the ratio `g1.1 / g1.0` is equal to the ratio `g2.1 / g2.0`,
+/// i.e. if `e(g1.0, g2.1) == e(g1.1, g2.0)`. This rejects any pair that contains the identity.
+fn same_ratio<E: PairingEngine>(g1: (E::G1Affine, E::G1Affine), g2: (E::G2Affine, E::G2Affine)) -> bool {
+    if g1.0.is_zero() || g1.1.is_zero() || g2.0.is_zero() || g2.1.is_zero() {
+        return false;
+    }
+    let (g1_0, g1_1) = (g1.0.prepare(), (-g1.1).prepare());
+    let (g2_0, g2_1) = (g2.0.prepare(), g2.1.prepare());
+    E::product_of_pairings([(&g1_0, &g2_1), (&g1_1, &g2_0)].into_iter()).is_one()
+}
+
+/// Returns a random linear combination of the given pairs, such that the ratio of the combination
+/// equals a given ratio if and only if (with overwhelming probability) the ratio of every pair does.
+fn merge_pairs<G: AffineCurve, R: Rng>(pairs: impl Iterator<Item = (G, G)>, rng: &mut R) -> (G, G) {
+    let (mut left, mut right) = (G::Projective::zero(), G::Projective::zero());
+    for (a, b) in pairs {
+        let randomizer = G::ScalarField::rand(rng);
+        left += a * randomizer;
+        right += b * randomizer;
+    }
+    (left.to_affine(), right.to_affine())
+}
+
+/// Hashes the given input to a non-zero element in the prime-order subgroup of G1.
+fn hash_to_g1<E: PairingEngine>(input: &[u8]) -> E::G1Affine {
+    let mut counter = 0u64;
+    loop {
+        // Derive the candidate bytes from the input and counter.
+        let mut candidate = Vec::with_capacity(64);
+        for index in 0..2u8 {
+            let mut hasher = Sha256::new();
+            hasher.update(input);
+            hasher.update(counter.to_le_bytes());
+            hasher.update([index]);
+            candidate.extend_from_slice(&hasher.finalize());
+        }
+        // Attempt to map the candidate bytes to a point, and clear the cofactor.
+        if let Some(point) = E::G1Affine::from_random_bytes(&candidate) {
+            let point = point.mul_by_cofactor();
+            if !point.is_zero() {
+                return point;
+            }
+        }
+        counter += 1;
+    }
+}
@@ -0,0 +1,348 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The domain separator for the initial digest of a transcript.
+const TRANSCRIPT_DOMAIN: &[u8] = b"AleoPowersOfTauTranscript0";
+
+/// The transcript of a powers-of-tau ceremony, consisting of the current accumulator
+/// and the sequence of contributions that were applied to it.
+///
+/// Each time the ceremony is extended to a larger SRS, it starts a new phase with a fresh accumulator.
+/// The contributions to the earlier phases are retained, so that the transcript records the full ceremony.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Transcript<E: PairingEngine> {
+    /// The number of powers and the number of contributions of each earlier phase, in order.
+    phases: Vec<(u64, u64)>,
+    /// The current accumulator.
+    accumulator: Accumulator<E>,
+    /// The contributions to every phase of the ceremony, in order.
+    contributions: Vec<Contribution<E>>,
+}
+
+impl<E: PairingEngine> Transcript<E> {
+    /// Initializes a new transcript for an SRS with `num_powers` powers.
+    pub fn new(num_powers: usize) -> Result<Self> {
+        Ok(Self { phases: Vec::new(), accumulator: Accumulator::new(num_powers)?, contributions: Vec::new() })
+    }
+
+    /// Returns the number of phases of the ceremony, including the current phase.
+    pub fn num_phases(&self) -> usize {
+        self.phases.len() + 1
+    }
+
+    /// Returns the current accumulator.
+    pub const fn accumulator(&self) -> &Accumulator<E> {
+        &self.accumulator
+    }
+
+    /// Returns the contributions to every phase of the ceremony, in order.
+    pub fn contributions(&self) -> &[Contribution<E>] {
+        &self.contributions
+    }
+
+    /// Returns the contributions that were applied to the current accumulator.
+    pub fn current_contributions(&self) -> &[Contribution<E>] {
+        &self.contributions[self.num_earlier_contributions()..]
+    }
+
+    /// Returns the number of powers in the SRS.
+    pub fn num_powers(&self) -> usize {
+        self.accumulator.num_powers()
+    }
+
+    /// Returns the digest of the transcript, which each contributor should publish after contributing.
+    pub fn digest(&self) -> Result<[u8; 32]> {
+        let mut digest = None;
+        let mut contributions = self.contributions.iter();
+        for (num_powers, num_contributions) in self.phases_with_current() {
+            let mut phase_digest = initial_digest(num_powers, digest);
+            for contribution in contributions.by_ref().take(num_contributions as usize) {
+                phase_digest = contribution.next_digest(&phase_digest)?;
+            }
+            digest = Some(phase_digest);
+        }
+        // Note: There is always at least one phase, so the digest is always set.
+        digest.ok_or_else(|| anyhow!("The transcript does not contain any phases"))
+    }
+
+    /// Contributes fresh randomness to the transcript, and returns the resulting digest.
+    /// The randomness is discarded once this method returns.
+    pub fn contribute<R: Rng + CryptoRng>(&mut self, rng: &mut R) -> Result<[u8; 32]> {
+        let digest = self.digest()?;
+
+        // Sample the secrets of the contributor.
+        let tau = sample_nonzero_scalar::<E, R>(rng);
+        let delta = sample_nonzero_scalar::<E, R>(rng);
+
+        // Construct the contribution, and apply it to the accumulator.
+        let contribution =
+            Contribution::new(&digest, self.accumulator.beta_g(), self.accumulator.gamma_g(), tau, delta)?;
+        self.accumulator.apply(tau, delta)?;
+        ensure!(contribution.beta_g() == self.accumulator.beta_g(), "Failed to apply the contribution to beta");
+        ensure!(contribution.gamma_g() == self.accumulator.gamma_g(), "Failed to apply the contribution to gamma");
+
+        let next_digest = contribution.next_digest(&digest)?;
+        self.contributions.push(contribution);
+        Ok(next_digest)
+    }
+
+    /// Checks that every contribution in the transcript is valid, and that the accumulator
+    /// is well-formed and is the result of applying all of the contributions of the current phase in order.
+    pub fn verify<R: Rng>(&self, rng: &mut R) -> Result<()> {
+        ensure!(!self.current_contributions().is_empty(), "The transcript does not contain any contributions");
+
+        // Ensure each contribution builds on the previous one in its phase.
+        let g = E::G1Affine::prime_subgroup_generator();
+        let (mut beta_g, mut gamma_g) = (g, g);
+        let mut digest = None;
+        let mut contributions = self.contributions.iter().enumerate();
+        for (phase, (num_powers, num_contributions)) in self.phases_with_current().enumerate() {
+            ensure!(num_contributions > 0, "Phase {phase} of the ceremony does not contain any contributions");
+            (beta_g, gamma_g) = (g, g);
+            let mut phase_digest = initial_digest(num_powers, digest);
+            for (i, contribution) in contributions.by_ref().take(num_contributions as usize) {
+                if let Err(error) = contribution.verify(&phase_digest, beta_g, gamma_g) {
+                    bail!("Contribution {i} is invalid - {error}");
+                }
+                (beta_g, gamma_g) = (contribution.beta_g(), contribution.gamma_g());
+                phase_digest = contribution.next_digest(&phase_digest)?;
+            }
+            digest = Some(phase_digest);
+        }
+
+        // Ensure the accumulator is the result of the last contribution.
+        ensure!(self.accumulator.beta_g() == beta_g, "The accumulator does not match the last contribution to beta");
+        ensure!(self.accumulator.gamma_g() == gamma_g, "The accumulator does not match the last contribution to gamma");
+        self.accumulator.verify(rng)
+    }
+
+    /// Extends the ceremony to an SRS with `num_powers` powers.
+    ///
+    /// The secrets of the existing contributions are unknown, so the larger powers cannot be derived from
+    /// the current accumulator. Instead, the extended transcript retains the existing contributions, and starts
+    /// a new phase with a fresh accumulator, which must receive its own contributions before it can be exported.
+    pub fn extend(&self, num_powers: usize) -> Result<Self> {
+        ensure!(
+            num_powers > self.num_powers(),
+            "The transcript can only be extended to more than {} powers",
+            self.num_powers()
+        );
+        ensure!(!self.current_contributions().is_empty(), "The transcript does not contain any contributions");
+
+        let mut phases = self.phases.clone();
+        phases.push((self.num_powers() as u64, self.current_contributions().len() as u64));
+        Ok(Self { phases, accumulator: Accumulator::new(num_powers)?, contributions: self.contributions.clone() })
+    }
+
+    /// Returns the powers of the universal SRS, for use with `PowersOfG::load`.
+    pub fn to_powers(&self) -> Result<PowersOfG<E>> {
+        ensure!(!self.current_contributions().is_empty(), "The transcript does not contain any contributions");
+        Ok(self.accumulator.to_powers())
+    }
+
+    /// Returns the number of contributions to the earlier phases of the ceremony.
+    fn num_earlier_contributions(&self) -> usize {
+        self.phases.iter().map(|(_, num_contributions)| *num_contributions as usize).sum()
+    }
+
+    /// Returns the number of powers and the number of contributions of every phase, including the current phase.
+    fn phases_with_current(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        let current = (self.num_powers() as u64, self.current_contributions().len() as u64);
+        self.phases.iter().copied().chain(std::iter::once(current))
+    }
+}
+
+/// Returns the digest of a phase prior to any contributions, where `previous` is the digest of the previous phase.
+fn initial_digest(num_powers: u64, previous: Option<[u8; 32]>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(TRANSCRIPT_DOMAIN);
+    hasher.update(num_powers.to_le_bytes());
+    hasher.update(previous.unwrap_or_default());
+    hasher.finalize().into()
+}
+
+impl<E: PairingEngine> FromBytes for Transcript<E> {
+    /// Reads the transcript from the buffer.
+    fn read_le<R: Read>(reader: R) -> std::io::Result<Self> {
+        Self::deserialize_with_mode(reader, Compress::No, Validate::Yes).map_err(|e| e.into())
+    }
+}
+
+impl<E: PairingEngine> ToBytes for Transcript<E> {
+    /// Writes the transcript to the buffer.
+    fn write_le<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        self.serialize_with_mode(writer, Compress::No).map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_algorithms::{
+        crypto_hash::PoseidonSponge,
+        fft::DensePolynomial,
+        polycommit::{
+            kzg10::UniversalParams,
+            sonic_pc::{CommitterUnionKey, Evaluations, LabeledPolynomial, QuerySet, SonicKZG10},
+        },
+        AlgebraicSponge,
+    };
+    use snarkvm_curves::bls12_377::{Bls12_377, Fq};
+    use snarkvm_utilities::TestRng;
+
+    type CurrentPairing = Bls12_377;
+    type Sponge = PoseidonSponge<Fq, 2, 1>;
+
+    const NUM_POWERS: usize = 32;
+
+    #[test]
+    fn test_contribute_and_verify() {
+        let rng = &mut TestRng::default();
+
+        let mut transcript = Transcript::<CurrentPairing>::new(NUM_POWERS).unwrap();
+        assert!(transcript.verify(rng).is_err());
+        assert!(transcript.to_powers().is_err());
+
+        for i in 1..=3 {
+            let digest = transcript.contribute(rng).unwrap();
+            assert_eq!(digest, transcript.digest().unwrap());
+            assert_eq!(transcript.contributions().len(), i);
+            transcript.verify(rng).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_reordered_contributions() {
+        let rng = &mut TestRng::default();
+
+        let mut transcript = Transcript::<CurrentPairing>::new(NUM_POWERS).unwrap();
+        transcript.contribute(rng).unwrap();
+        transcript.contribute(rng).unwrap();
+
+        // Ensure the accumulator must match the contributions.
+        let mut candidate = transcript.clone();
+        candidate.contributions.pop();
+        assert!(candidate.verify(rng).is_err());
+
+        // Ensure the contributions must be in order.
+        let mut candidate = transcript;
+        candidate.contributions.swap(0, 1);
+        assert!(candidate.verify(rng).is_err());
+    }
+
+    #[test]
+    fn test_extend() {
+        let rng = &mut TestRng::default();
+
+        let mut transcript = Transcript::<CurrentPairing>::new(NUM_POWERS).unwrap();
+        assert!(transcript.extend(NUM_POWERS * 2).is_err());
+        transcript.contribute(rng).unwrap();
+        transcript.contribute(rng).unwrap();
+
+        assert!(transcript.extend(NUM_POWERS).is_err());
+        let mut extended = transcript.extend(NUM_POWERS * 2).unwrap();
+        assert_eq!(extended.num_phases(), 2);
+        assert_eq!(extended.num_powers(), NUM_POWERS * 2);
+        assert_eq!(extended.contributions(), transcript.contributions());
+        assert!(extended.current_contributions().is_empty());
+        assert_ne!(
+            extended.digest().unwrap(),
+            Transcript::<CurrentPairing>::new(NUM_POWERS * 2).unwrap().digest().unwrap()
+        );
+        assert!(extended.verify(rng).is_err());
+
+        // Ensure the contributions are appended to the earlier contributions.
+        extended.contribute(rng).unwrap();
+        assert_eq!(extended.contributions().len(), 3);
+        assert_eq!(extended.contributions()[..2], transcript.contributions()[..]);
+        assert_eq!(extended.current_contributions().len(), 1);
+        extended.verify(rng).unwrap();
+
+        // Ensure the contributions to the earlier phases are verified.
+        let mut candidate = extended.clone();
+        candidate.contributions.swap(0, 1);
+        assert!(candidate.verify(rng).is_err());
+
+        // Ensure the ceremony can be extended again.
+        let mut extended = extended.extend(NUM_POWERS * 4).unwrap();
+        extended.contribute(rng).unwrap();
+        assert_eq!(extended.num_phases(), 3);
+        assert_eq!(extended.contributions().len(), 4);
+        extended.verify(rng).unwrap();
+    }
+
+    #[test]
+    fn test_bytes() {
+        let rng = &mut TestRng::default();
+
+        let mut expected = Transcript::<CurrentPairing>::new(NUM_POWERS).unwrap();
+        expected.contribute(rng).unwrap();
+
+        let expected_bytes = expected.to_bytes_le().unwrap();
+        let candidate = Transcript::<CurrentPairing>::read_le(&expected_bytes[..]).unwrap();
+        assert_eq!(expected, candidate);
+        candidate.verify(rng).unwrap();
+    }
+
+    #[test]
+    fn test_powers_are_consumable_by_sonic_kzg10() {
+        let rng = &mut TestRng::default();
+
+        let mut transcript = Transcript::<CurrentPairing>::new(NUM_POWERS).unwrap();
+        transcript.contribute(rng).unwrap();
+        transcript.contribute(rng).unwrap();
+
+        // Reconstruct the universal parameters from the exported powers.
+        let powers = transcript.to_powers().unwrap();
+        let h = <CurrentPairing as PairingEngine>::G2Affine::prime_subgroup_generator();
+        let mut bytes = powers.to_bytes_le().unwrap();
+        bytes.extend(h.to_bytes_le().unwrap());
+        bytes.extend(h.prepare().to_bytes_le().unwrap());
+        bytes.extend(powers.beta_h().prepare().to_bytes_le().unwrap());
+        let pp = UniversalParams::<CurrentPairing>::read_le(&bytes[..]).unwrap();
+        assert_eq!(pp.max_degree(), NUM_POWERS - 1);
+        assert!(pp.powers_of_beta_g(0, NUM_POWERS + 1).is_err());
+
+        // Commit to and open a hiding polynomial with an enforced degree bound.
+        let degree_bound = 14;
+        let universal_prover = pp.to_universal_prover().unwrap();
+        let (ck, vk) = SonicKZG10::<CurrentPairing, Sponge>::trim(&pp, 16, None, 1, Some(&[degree_bound])).unwrap();
+        let ck = CommitterUnionKey::union(std::iter::once(&ck));
+
+        let polynomial = DensePolynomial::rand(degree_bound, rng);
+        let polynomials = vec![LabeledPolynomial::new("test".to_string(), polynomial, Some(degree_bound), Some(1))];
+        let (commitments, randomness) = SonicKZG10::<CurrentPairing, Sponge>::commit(
+            &universal_prover,
+            &ck,
+            polynomials.iter().map(Into::into),
+            Some(rng),
+        )
+        .unwrap();
+
+        let point = <CurrentPairing as PairingEngine>::Fr::rand(rng);
+        let mut query_set = QuerySet::new();
+        query_set.insert(("test".to_string(), ("rand".into(), point)));
+        let mut evaluations = Evaluations::new();
+        evaluations.insert(("test".to_string(), point), polynomials[0].evaluate(point));
+
+        let proof =
+            SonicKZG10::batch_open(&universal_prover, &ck, &polynomials, &query_set, &randomness, &mut Sponge::new())
+                .unwrap();
+        assert!(
+            SonicKZG10::batch_check(&vk, &commitments, &query_set, &evaluations, &proof, &mut Sponge::new()).unwrap()
+        );
+    }
+}
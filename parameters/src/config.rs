@@ -0,0 +1,279 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::ParameterError;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+/// The environment variable that overrides the directory in which parameter files are stored.
+pub const PARAMETERS_DIR_ENV: &str = "SNARKVM_PARAMETERS_DIR";
+/// The environment variable that enables the strict offline mode, if set to `1` or `true`.
+pub const OFFLINE_ENV: &str = "SNARKVM_OFFLINE";
+
+/// The filename of the checksum manifest in a parameter bundle.
+pub const BUNDLE_MANIFEST: &str = "checksums.sha256";
+
+/// The directory in which parameter files are stored, if set.
+static PARAMETERS_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// The flag indicating whether the strict offline mode is enabled.
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Sets the directory in which parameter files are stored, in place of the default Aleo directory.
+/// Note: The directory may only be set once per process, and must be set before any parameters are loaded.
+pub fn set_parameters_dir<P: Into<PathBuf>>(path: P) -> Result<(), ParameterError> {
+    PARAMETERS_DIR
+        .set(path.into())
+        .map_err(|_| ParameterError::Message("The parameters directory has already been set".to_string()))
+}
+
+/// Returns the configured directory in which parameter files are stored, if any.
+/// The directory set with `set_parameters_dir` takes precedence over the `SNARKVM_PARAMETERS_DIR` variable.
+pub fn parameters_dir() -> Option<PathBuf> {
+    match PARAMETERS_DIR.get() {
+        Some(path) => Some(path.clone()),
+        None => std::env::var_os(PARAMETERS_DIR_ENV).filter(|path| !path.is_empty()).map(PathBuf::from),
+    }
+}
+
+/// Enables or disables the strict offline mode, in which missing parameter files are never downloaded.
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::SeqCst);
+}
+
+/// Returns `true` if the strict offline mode is enabled, either with `set_offline` or the `SNARKVM_OFFLINE` variable.
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::SeqCst)
+        || std::env::var(OFFLINE_ENV).map(|value| value == "1" || value.eq_ignore_ascii_case("true")).unwrap_or(false)
+}
+
+/// Returns the local path of the parameter file with the given filename.
+///
+/// If a parameters directory is configured, the file resides directly in that directory.
+/// Otherwise, the file resides in `local_dir` within the Aleo directory.
+pub fn parameter_path(local_dir: &str, filename: &str) -> PathBuf {
+    match parameters_dir() {
+        Some(directory) => directory.join(filename),
+        None => aleo_std::aleo_dir().join(local_dir).join(filename),
+    }
+}
+
+/// A parameter file that is not embedded in the binary, and is loaded from the parameters directory.
+#[derive(Clone, Debug)]
+pub struct ParameterFile {
+    /// The name of the parameter.
+    name: &'static str,
+    /// The directory of the file, relative to the Aleo directory.
+    local_dir: &'static str,
+    /// The versioned filename of the file.
+    filename: String,
+    /// The expected SHA-256 checksum of the file.
+    checksum: String,
+    /// The expected size of the file in bytes.
+    size: usize,
+    /// The loader for the file, which verifies its size and checksum.
+    loader: fn() -> Result<Vec<u8>, ParameterError>,
+}
+
+impl ParameterFile {
+    /// Initializes a new parameter file.
+    pub fn new(
+        name: &'static str,
+        local_dir: &'static str,
+        filename: String,
+        checksum: String,
+        size: usize,
+        loader: fn() -> Result<Vec<u8>, ParameterError>,
+    ) -> Self {
+        Self { name, local_dir, filename, checksum, size, loader }
+    }
+
+    /// Returns the name of the parameter.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the versioned filename of the file.
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// Returns the expected SHA-256 checksum of the file.
+    pub fn checksum(&self) -> &str {
+        &self.checksum
+    }
+
+    /// Returns the expected size of the file in bytes.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the local path of the file.
+    pub fn path(&self) -> PathBuf {
+        parameter_path(self.local_dir, &self.filename)
+    }
+
+    /// Returns `true` if the file exists locally.
+    pub fn exists(&self) -> bool {
+        self.path().exists()
+    }
+
+    /// Loads the bytes of the file, downloading it if it is missing and the offline mode is disabled.
+    pub fn load_bytes(&self) -> Result<Vec<u8>, ParameterError> {
+        (self.loader)()
+    }
+
+    /// Checks that the given bytes match the expected size and checksum of the file.
+    pub fn check_bytes(&self, bytes: &[u8]) -> Result<(), ParameterError> {
+        if self.size != bytes.len() {
+            return Err(ParameterError::SizeMismatch(self.size, bytes.len()));
+        }
+        let candidate_checksum = checksum!(bytes);
+        if self.checksum != candidate_checksum {
+            return checksum_error!(self.checksum.clone(), candidate_checksum);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the local paths of the given parameter files that do not exist.
+pub fn find_missing(files: &[ParameterFile]) -> Vec<PathBuf> {
+    files.iter().map(ParameterFile::path).filter(|path| !path.exists()).collect()
+}
+
+/// Ensures all of the given parameter files exist locally, and otherwise returns the list of missing files.
+pub fn ensure_available(files: &[ParameterFile]) -> Result<(), ParameterError> {
+    let missing = find_missing(files);
+    match missing.is_empty() {
+        true => Ok(()),
+        false => Err(ParameterError::MissingFiles(missing.iter().map(|path| path.display().to_string()).collect())),
+    }
+}
+
+/// Writes the given parameter files into the `output` directory, along with a manifest of their checksums.
+/// Each file is loaded (and downloaded, if necessary) and checked against its expected checksum.
+/// Returns the path of the manifest.
+pub fn bundle(files: &[ParameterFile], output: &Path) -> Result<PathBuf, ParameterError> {
+    std::fs::create_dir_all(output)?;
+
+    let mut manifest = String::new();
+    for file in files {
+        let file_path = output.join(file.filename());
+        // Skip the file if it is already in the bundle.
+        let is_bundled = match std::fs::read(&file_path) {
+            Ok(bytes) => file.check_bytes(&bytes).is_ok(),
+            Err(_) => false,
+        };
+        if !is_bundled {
+            let bytes = file.load_bytes()?;
+            file.check_bytes(&bytes)?;
+            std::fs::write(&file_path, bytes)?;
+        }
+        // Append the file to the manifest, in the format of `sha256sum`.
+        manifest.push_str(&format!("{}  {}\n", file.checksum(), file.filename()));
+    }
+
+    let manifest_path = output.join(BUNDLE_MANIFEST);
+    std::fs::write(&manifest_path, manifest)?;
+    Ok(manifest_path)
+}
+
+/// Checks that all of the given parameter files exist locally with their expected checksums,
+/// and otherwise returns the list of missing or corrupt files.
+pub fn verify_files(files: &[ParameterFile]) -> Result<(), ParameterError> {
+    check_files(files.iter().map(|file| (file, file.path())))
+}
+
+/// Checks that the `directory` contains all of the given parameter files with their expected checksums,
+/// and otherwise returns the list of missing or corrupt files.
+pub fn verify_bundle(files: &[ParameterFile], directory: &Path) -> Result<(), ParameterError> {
+    check_files(files.iter().map(|file| (file, directory.join(file.filename()))))
+}
+
+/// Checks that each of the given paths contains the corresponding parameter file,
+/// and otherwise returns the list of missing or corrupt files.
+fn check_files<'a>(files: impl Iterator<Item = (&'a ParameterFile, PathBuf)>) -> Result<(), ParameterError> {
+    let mut invalid = Vec::new();
+    for (file, file_path) in files {
+        match std::fs::read(&file_path) {
+            Ok(bytes) => {
+                if let Err(error) = file.check_bytes(&bytes) {
+                    invalid.push(format!("{} ({error})", file_path.display()));
+                }
+            }
+            Err(_) => invalid.push(file_path.display().to_string()),
+        }
+    }
+    match invalid.is_empty() {
+        true => Ok(()),
+        false => Err(ParameterError::MissingFiles(invalid)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BYTES: &[u8] = b"parameters";
+
+    fn load_sample() -> Result<Vec<u8>, ParameterError> {
+        Ok(BYTES.to_vec())
+    }
+
+    fn sample_file() -> ParameterFile {
+        ParameterFile::new("Sample", "resources/", "sample.bin".to_string(), checksum!(BYTES), BYTES.len(), load_sample)
+    }
+
+    #[test]
+    fn test_check_bytes() {
+        let file = sample_file();
+        file.check_bytes(BYTES).unwrap();
+        assert!(matches!(file.check_bytes(b"parameter"), Err(ParameterError::SizeMismatch(..))));
+        assert!(matches!(file.check_bytes(b"Parameters"), Err(ParameterError::ChecksumMismatch(..))));
+    }
+
+    #[test]
+    fn test_bundle_and_verify() {
+        let directory = std::env::temp_dir().join(format!("snarkvm-parameters-bundle-{}", std::process::id()));
+        let files = vec![sample_file()];
+
+        // Ensure an empty directory lists the missing file.
+        match verify_bundle(&files, &directory) {
+            Err(ParameterError::MissingFiles(missing)) => {
+                assert_eq!(missing, vec![directory.join("sample.bin").display().to_string()])
+            }
+            _ => panic!("Expected the bundle to be missing a file"),
+        }
+
+        // Bundle the files, and ensure the bundle is valid.
+        let manifest = bundle(&files, &directory).unwrap();
+        verify_bundle(&files, &directory).unwrap();
+        assert_eq!(std::fs::read_to_string(manifest).unwrap(), format!("{}  sample.bin\n", checksum!(BYTES)));
+
+        // Corrupt the file, and ensure the bundle is invalid.
+        std::fs::write(directory.join("sample.bin"), b"Parameters").unwrap();
+        assert!(matches!(verify_bundle(&files, &directory), Err(ParameterError::MissingFiles(..))));
+
+        // Ensure bundling again repairs the file.
+        bundle(&files, &directory).unwrap();
+        verify_bundle(&files, &directory).unwrap();
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -25,6 +25,9 @@ pub enum ParameterError {
     #[error("{}", _0)]
     Message(String),
 
+    #[error("Missing or invalid parameter files:\n  {}", _0.join("\n  "))]
+    MissingFiles(Vec<String>),
+
     #[error("Remote fetch is disabled, enable compiler flag for feature")]
     RemoteFetchDisabled,
 
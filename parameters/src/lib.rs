@@ -27,6 +27,11 @@ extern crate thiserror;
 #[macro_use]
 pub mod macros;
 
+pub mod ceremony;
+
+pub mod config;
+pub use config::*;
+
 pub mod errors;
 pub use errors::*;
 
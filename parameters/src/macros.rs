@@ -167,15 +167,16 @@ macro_rules! impl_load_bytes_logic_local {
 }
 
 macro_rules! impl_load_bytes_logic_remote {
-    ($remote_url: expr, $local_dir: expr, $filename: expr, $metadata: expr, $expected_checksum: expr, $expected_size: expr) => {
+    ($remote_url: expr, $local_dir: expr, $filename: expr, $expected_checksum: expr, $expected_size: expr) => {
         // Compose the correct file path for the parameter file.
-        let mut file_path = aleo_std::aleo_dir();
-        file_path.push($local_dir);
-        file_path.push($filename);
+        let file_path = $crate::parameter_path($local_dir, $filename);
 
         let buffer = if file_path.exists() {
             // Attempts to load the parameter file locally with an absolute path.
             std::fs::read(&file_path)?
+        } else if $crate::is_offline() {
+            // In offline mode, fail fast instead of downloading the missing parameters.
+            return Err($crate::errors::ParameterError::MissingFiles(vec![file_path.display().to_string()]));
         } else {
             // Downloads the missing parameters and stores it in the local directory for use.
              #[cfg(not(feature = "no_std_out"))]
@@ -319,7 +320,8 @@ macro_rules! impl_remote {
 
             impl_store_and_remote_fetch!();
 
-            pub fn load_bytes() -> Result<Vec<u8>, $crate::errors::ParameterError> {
+            /// Returns the description of the parameter file.
+            pub fn parameter_file() -> $crate::ParameterFile {
                 let metadata: serde_json::Value =
                     serde_json::from_str(Self::METADATA).expect("Metadata was not well-formatted");
                 let expected_checksum: String =
@@ -333,14 +335,22 @@ macro_rules! impl_remote {
                     _ => format!("{}.{}", $fname, "usrs"),
                 };
 
-                impl_load_bytes_logic_remote!(
-                    $remote_url,
+                $crate::ParameterFile::new(
+                    stringify!($name),
                     $local_dir,
-                    &filename,
-                    metadata,
+                    filename,
                     expected_checksum,
-                    expected_size
-                );
+                    expected_size,
+                    Self::load_bytes,
+                )
+            }
+
+            pub fn load_bytes() -> Result<Vec<u8>, $crate::errors::ParameterError> {
+                let file = Self::parameter_file();
+                let expected_checksum = file.checksum().to_string();
+                let expected_size = file.size();
+
+                impl_load_bytes_logic_remote!($remote_url, $local_dir, file.filename(), expected_checksum, expected_size);
             }
         }
         paste::item! {
@@ -359,7 +369,8 @@ macro_rules! impl_remote {
 
             impl_store_and_remote_fetch!();
 
-            pub fn load_bytes() -> Result<Vec<u8>, $crate::errors::ParameterError> {
+            /// Returns the description of the parameter file.
+            pub fn parameter_file() -> $crate::ParameterFile {
                 let metadata: serde_json::Value =
                     serde_json::from_str(Self::METADATA).expect("Metadata was not well-formatted");
                 let expected_checksum: String =
@@ -373,14 +384,22 @@ macro_rules! impl_remote {
                     _ => format!("{}.{}", $fname, $ftype),
                 };
 
-                impl_load_bytes_logic_remote!(
-                    $remote_url,
+                $crate::ParameterFile::new(
+                    stringify!($name),
                     $local_dir,
-                    &filename,
-                    metadata,
+                    filename,
                     expected_checksum,
-                    expected_size
-                );
+                    expected_size,
+                    Self::load_bytes,
+                )
+            }
+
+            pub fn load_bytes() -> Result<Vec<u8>, $crate::errors::ParameterError> {
+                let file = Self::parameter_file();
+                let expected_checksum = file.checksum().to_string();
+                let expected_size = file.size();
+
+                impl_load_bytes_logic_remote!($remote_url, $local_dir, file.filename(), expected_checksum, expected_size);
             }
         }
 
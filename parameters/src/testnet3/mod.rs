@@ -146,12 +146,79 @@ lazy_static! {
         InclusionVerifier::load_bytes().expect("Failed to load inclusion verifying key");
 }
 
+/// Returns the parameter files that are not embedded in the binary, and are loaded from the parameters directory.
+pub fn remote_parameter_files() -> Vec<crate::ParameterFile> {
+    remote_powers_files().into_iter().chain(remote_key_files()).collect()
+}
+
+/// Returns the remote parameter files of the powers of the universal SRS, which are required to synthesize keys.
+pub fn remote_powers_files() -> Vec<crate::ParameterFile> {
+    vec![
+        Degree17::parameter_file(),
+        Degree18::parameter_file(),
+        Degree19::parameter_file(),
+        Degree20::parameter_file(),
+        Degree21::parameter_file(),
+        Degree22::parameter_file(),
+        Degree23::parameter_file(),
+        Degree24::parameter_file(),
+        Degree25::parameter_file(),
+        Degree26::parameter_file(),
+        Degree27::parameter_file(),
+        Degree28::parameter_file(),
+        ShiftedDegree16::parameter_file(),
+        ShiftedDegree17::parameter_file(),
+        ShiftedDegree18::parameter_file(),
+        ShiftedDegree19::parameter_file(),
+        ShiftedDegree20::parameter_file(),
+        ShiftedDegree21::parameter_file(),
+        ShiftedDegree22::parameter_file(),
+        ShiftedDegree23::parameter_file(),
+        ShiftedDegree24::parameter_file(),
+        ShiftedDegree25::parameter_file(),
+        ShiftedDegree26::parameter_file(),
+        ShiftedDegree27::parameter_file(),
+    ]
+}
+
+/// Returns the remote parameter files of the proving keys, which are required to prove executions.
+pub fn remote_key_files() -> Vec<crate::ParameterFile> {
+    vec![
+        BondPublicProver::parameter_file(),
+        UnbondPublicProver::parameter_file(),
+        UnbondDelegatorAsValidatorProver::parameter_file(),
+        ClaimUnbondPublicProver::parameter_file(),
+        SetValidatorStateProver::parameter_file(),
+        TransferPrivateProver::parameter_file(),
+        TransferPublicProver::parameter_file(),
+        TransferPrivateToPublicProver::parameter_file(),
+        TransferPublicToPrivateProver::parameter_file(),
+        JoinProver::parameter_file(),
+        SplitProver::parameter_file(),
+        FeePrivateProver::parameter_file(),
+        FeePublicProver::parameter_file(),
+        InclusionProver::parameter_file(),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use wasm_bindgen_test::*;
     wasm_bindgen_test_configure!(run_in_browser);
 
+    #[test]
+    fn test_remote_parameter_files() {
+        let files = remote_parameter_files();
+        // Ensure the filenames are versioned and unique.
+        let filenames = files.iter().map(|file| file.filename()).collect::<std::collections::HashSet<_>>();
+        assert_eq!(filenames.len(), files.len());
+        for file in &files {
+            assert!(file.filename().ends_with(&file.checksum()[0..7]));
+            assert!(file.size() > 0);
+        }
+    }
+
     #[wasm_bindgen_test]
     fn test_load_bytes() {
         Degree16::load_bytes().expect("Failed to load degree 16");
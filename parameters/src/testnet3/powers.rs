@@ -28,7 +28,11 @@ use snarkvm_utilities::{
 };
 
 use anyhow::{anyhow, bail, ensure, Result};
-use std::{collections::BTreeMap, ops::Range, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    ops::Range,
+    sync::{Arc, OnceLock},
+};
 
 const NUM_POWERS_15: usize = 1 << 15;
 const NUM_POWERS_16: usize = 1 << 16;
@@ -46,7 +50,7 @@ const NUM_POWERS_27: usize = 1 << 27;
 const NUM_POWERS_28: usize = 1 << 28;
 
 /// The maximum degree supported by the SRS.
-const MAX_NUM_POWERS: usize = NUM_POWERS_28;
+pub(crate) const MAX_NUM_POWERS: usize = NUM_POWERS_28;
 
 lazy_static::lazy_static! {
     static ref POWERS_OF_BETA_G_15: Vec<u8> = Degree15::load_bytes().expect("Failed to load powers of beta in universal SRS");
@@ -56,6 +60,22 @@ lazy_static::lazy_static! {
     static ref BETA_H: Vec<u8> = BetaH::load_bytes().expect("Failed to load negative powers of beta in universal SRS");
 }
 
+/// The serialized powers of a custom universal SRS, which (if set) replace the hard-coded powers.
+static CUSTOM_POWERS: OnceLock<Vec<u8>> = OnceLock::new();
+
+/// Sets the powers of a custom universal SRS (i.e. the output of a powers-of-tau ceremony),
+/// which are used in place of the hard-coded powers by all subsequent calls to `PowersOfG::load`.
+///
+/// The custom powers may only be set once per process, and must be set before the universal SRS is first loaded.
+pub fn set_custom_powers(bytes: Vec<u8>) -> Result<()> {
+    CUSTOM_POWERS.set(bytes).map_err(|_| anyhow!("The custom powers of the universal SRS have already been set"))
+}
+
+/// Returns `true` if the powers of a custom universal SRS have been set.
+pub fn has_custom_powers() -> bool {
+    CUSTOM_POWERS.get().is_some()
+}
+
 /// A vector of powers of beta G.
 #[derive(Debug, Clone)]
 pub struct PowersOfG<E: PairingEngine> {
@@ -76,8 +96,13 @@ pub struct PowersOfG<E: PairingEngine> {
 }
 
 impl<E: PairingEngine> PowersOfG<E> {
-    /// Initializes the hard-coded instance of the powers.
+    /// Initializes the hard-coded instance of the powers, or the custom powers if they have been set.
     pub fn load() -> Result<Self> {
+        // If custom powers have been set, load them instead of the hard-coded powers.
+        if let Some(bytes) = CUSTOM_POWERS.get() {
+            return Self::load_custom(bytes);
+        }
+
         let powers_of_beta_g = PowersOfBetaG::load()?;
 
         // Reconstruct powers of beta_times_gamma_g.
@@ -104,6 +129,43 @@ impl<E: PairingEngine> PowersOfG<E> {
         })
     }
 
+    /// Initializes the powers from the output of a powers-of-tau ceremony.
+    fn load_custom(bytes: &[u8]) -> Result<Self> {
+        // Deserialize the powers, ensuring every point is on the curve and in the correct subgroup,
+        // as the custom powers are untrusted input.
+        let powers = Self::deserialize_with_mode(bytes, Compress::No, Validate::Yes)?;
+        // Ensure the powers are complete, as custom powers cannot be downloaded.
+        ensure!(powers.powers_of_beta_g.shifted_powers_of_beta_g.is_empty(), "Custom powers must not be shifted");
+        ensure!(powers.num_powers().is_power_of_two(), "Custom powers must contain a power of two number of powers");
+        ensure!(powers.num_powers() <= MAX_NUM_POWERS, "Custom powers exceed the maximum number of powers");
+        ensure!(
+            !powers.powers_of_beta_times_gamma_g.is_empty(),
+            "Custom powers are missing the powers of beta gamma G"
+        );
+        Ok(powers)
+    }
+
+    /// Initializes the powers from their constituent parts.
+    /// Note: The given powers of beta G must be complete, i.e. contain all powers up to the maximum degree.
+    pub(crate) fn from_parts(
+        powers_of_beta_g: Vec<E::G1Affine>,
+        powers_of_beta_times_gamma_g: BTreeMap<usize, E::G1Affine>,
+        negative_powers_of_beta_h: BTreeMap<usize, E::G2Affine>,
+        beta_h: E::G2Affine,
+    ) -> Self {
+        // Compute the prepared negative powers of beta_h.
+        let prepared_negative_powers_of_beta_h =
+            Arc::new(negative_powers_of_beta_h.iter().map(|(d, affine)| (*d, affine.prepare())).collect());
+
+        Self {
+            powers_of_beta_g: PowersOfBetaG { powers_of_beta_g, shifted_powers_of_beta_g: Vec::new() },
+            powers_of_beta_times_gamma_g: Arc::new(powers_of_beta_times_gamma_g),
+            negative_powers_of_beta_h: Arc::new(negative_powers_of_beta_h),
+            prepared_negative_powers_of_beta_h,
+            beta_h,
+        }
+    }
+
     /// Download the powers of beta G specified by `range`.
     pub fn download_powers_for(&mut self, range: Range<usize>) -> Result<()> {
         self.powers_of_beta_g.download_powers_for(&range)
@@ -116,7 +178,7 @@ impl<E: PairingEngine> PowersOfG<E> {
 
     /// Returns the maximum possible number of contiguous powers of beta G starting from the 0-th power.
     pub fn max_num_powers(&self) -> usize {
-        MAX_NUM_POWERS
+        self.powers_of_beta_g.max_num_powers()
     }
 
     /// Returns the powers of beta * gamma G.
@@ -239,6 +301,15 @@ impl<E: PairingEngine> PowersOfBetaG<E> {
         self.powers_of_beta_g.len()
     }
 
+    /// Returns the maximum possible number of contiguous powers of beta G starting from the 0-th power.
+    pub fn max_num_powers(&self) -> usize {
+        match self.shifted_powers_of_beta_g.is_empty() {
+            // If there are no shifted powers, then all powers reside in `powers_of_beta_g`.
+            true => self.powers_of_beta_g.len(),
+            false => MAX_NUM_POWERS,
+        }
+    }
+
     /// Initializes the hard-coded instance of the powers.
     fn load() -> Result<Self> {
         // Deserialize the group elements.
@@ -265,9 +336,10 @@ impl<E: PairingEngine> PowersOfBetaG<E> {
             let lower_shifted_bound = MAX_NUM_POWERS - self.shifted_powers_of_beta_g.len();
             ((0..self.powers_of_beta_g.len()), (lower_shifted_bound..MAX_NUM_POWERS))
         } else {
-            // We can only be in this case if have downloaded all possible powers.
-            assert_eq!(self.powers_of_beta_g.len(), MAX_NUM_POWERS, "Incorrect number of powers in the recovered SRS");
-            ((0..MAX_NUM_POWERS), (0..MAX_NUM_POWERS))
+            // We can only be in this case if have downloaded all possible powers,
+            // or if the powers are from a custom universal SRS.
+            let max_num_powers = self.max_num_powers();
+            ((0..max_num_powers), (0..max_num_powers))
         }
     }
 
@@ -300,8 +372,7 @@ impl<E: PairingEngine> PowersOfBetaG<E> {
             "Requested range is not contained in the available shifted powers"
         );
 
-        if range.start < MAX_NUM_POWERS / 2 {
-            ensure!(self.shifted_powers_of_beta_g.is_empty());
+        if self.shifted_powers_of_beta_g.is_empty() {
             // In this case, we have downloaded all the powers, and so
             // all the powers reside in self.powers_of_beta_g.
             Ok(&self.powers_of_beta_g[range])
@@ -330,7 +401,7 @@ impl<E: PairingEngine> PowersOfBetaG<E> {
             return Ok(&self.powers_of_beta_g[0..0]);
         }
         ensure!(range.start < range.end, "Lower power must be less than upper power");
-        ensure!(range.end <= MAX_NUM_POWERS, "Upper bound must be less than the maximum number of powers");
+        ensure!(range.end <= self.max_num_powers(), "Upper bound must be less than the maximum number of powers");
         if !self.contains_powers(&range) {
             // We must download the powers.
             self.download_powers_for(&range)?;
@@ -345,6 +416,8 @@ impl<E: PairingEngine> PowersOfBetaG<E> {
         if self.contains_in_normal_powers(range) || self.contains_in_shifted_powers(range) {
             return Ok(());
         }
+        // If there are no shifted powers, then all available powers have been loaded.
+        ensure!(!self.shifted_powers_of_beta_g.is_empty(), "Requesting more powers than exist in the SRS");
         let half_max = MAX_NUM_POWERS / 2;
         if (range.start <= half_max) && (range.end > half_max) {
             // If the range contains the midpoint, then we must download all the powers.
@@ -529,3 +602,28 @@ impl<E: PairingEngine> ToBytes for PowersOfBetaG<E> {
         self.serialize_with_mode(writer, Compress::No).map_err(|e| e.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ceremony::Transcript;
+    use snarkvm_curves::bls12_377::Bls12_377;
+    use snarkvm_utilities::TestRng;
+
+    #[test]
+    fn test_load_custom_rejects_invalid_points() {
+        let rng = &mut TestRng::default();
+
+        // Run a ceremony, and ensure its output loads.
+        let mut transcript = Transcript::<Bls12_377>::new(32).unwrap();
+        transcript.contribute(rng).unwrap();
+        let bytes = transcript.to_powers().unwrap().to_bytes_le().unwrap();
+        assert!(PowersOfG::<Bls12_377>::load_custom(&bytes).is_ok());
+
+        // Corrupt the y-coordinate of the first power of beta G, which moves it off the curve.
+        // Note: The powers of beta G are prefixed by their length, and each point begins with its x-coordinate.
+        let mut corrupted = bytes;
+        corrupted[8 + 48] ^= 1;
+        assert!(PowersOfG::<Bls12_377>::load_custom(&corrupted).is_err());
+    }
+}
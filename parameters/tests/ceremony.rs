@@ -0,0 +1,56 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm_algorithms::polycommit::kzg10::UniversalParams;
+use snarkvm_curves::bls12_377::Bls12_377;
+use snarkvm_parameters::{
+    ceremony::Transcript,
+    testnet3::{has_custom_powers, set_custom_powers},
+};
+use snarkvm_utilities::{FromBytes, TestRng, ToBytes};
+
+type CurrentPairing = Bls12_377;
+
+const NUM_POWERS: usize = 32;
+
+// Note: The custom powers are set once per process, so this is the only test in this file.
+#[test]
+fn test_load_ceremony_output() {
+    let rng = &mut TestRng::default();
+
+    // Run a ceremony, and round-trip its transcript and its output through bytes.
+    let mut transcript = Transcript::<CurrentPairing>::new(NUM_POWERS).unwrap();
+    transcript.contribute(rng).unwrap();
+    let transcript = Transcript::<CurrentPairing>::read_le(&transcript.to_bytes_le().unwrap()[..]).unwrap();
+    let mut transcript = transcript.extend(NUM_POWERS * 2).unwrap();
+    transcript.contribute(rng).unwrap();
+    transcript.contribute(rng).unwrap();
+    transcript.verify(rng).unwrap();
+    let output = transcript.to_powers().unwrap().to_bytes_le().unwrap();
+
+    // Load the universal SRS from the ceremony output.
+    assert!(!has_custom_powers());
+    set_custom_powers(output.clone()).unwrap();
+    assert!(has_custom_powers());
+    assert!(set_custom_powers(output).is_err());
+
+    let mut expected = transcript.to_powers().unwrap();
+    let srs = UniversalParams::<CurrentPairing>::load().unwrap();
+    assert_eq!(srs.max_degree(), NUM_POWERS * 2 - 1);
+    assert_eq!(srs.powers_of_beta_g(0, NUM_POWERS * 2).unwrap(), expected.powers_of_beta_g(0..NUM_POWERS * 2).unwrap());
+    assert_eq!(srs.powers_of_beta_times_gamma_g(), expected.powers_of_beta_gamma_g());
+    assert_eq!(srs.beta_h(), expected.beta_h());
+    // Ensure the powers beyond the ceremony output are not downloaded.
+    assert!(srs.powers_of_beta_g(0, NUM_POWERS * 2 + 1).is_err());
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::*;
+use crate::finalize_trace::{trace_command, trace_enter, trace_error, trace_mapping_access};
 use console::program::{Future, Register};
 use synthesizer_program::{Await, FinalizeRegistersState, Operand};
 use utilities::handle_halting;
@@ -28,6 +29,22 @@ impl<N: Network> Process<N> {
         store: &FinalizeStore<N, P>,
         deployment: &Deployment<N>,
         fee: &Fee<N>,
+    ) -> Result<(Stack<N>, Vec<FinalizeOperation<N>>)> {
+        self.finalize_deployment_with_trace(state, store, deployment, fee, None)
+    }
+
+    /// Finalizes the deployment and fee.
+    /// If a trace is given, every evaluated finalize command is recorded in it.
+    /// This method assumes the given deployment **is valid**.
+    /// This method should **only** be called by `VM::finalize()`.
+    #[inline]
+    pub fn finalize_deployment_with_trace<P: FinalizeStorage<N>>(
+        &self,
+        state: FinalizeGlobalState,
+        store: &FinalizeStore<N, P>,
+        deployment: &Deployment<N>,
+        fee: &Fee<N>,
+        trace: Option<&mut Vec<FinalizeTraceEvent<N>>>,
     ) -> Result<(Stack<N>, Vec<FinalizeOperation<N>>)> {
         let timer = timer!("Process::finalize_deployment");
 
@@ -51,7 +68,7 @@ impl<N: Network> Process<N> {
             // Retrieve the fee stack.
             let fee_stack = self.get_stack(fee.program_id())?;
             // Finalize the fee transition.
-            finalize_operations.extend(finalize_fee_transition(state, store, fee_stack, fee)?);
+            finalize_operations.extend(finalize_fee_transition(state, store, fee_stack, fee, trace)?);
             lap!(timer, "Finalize transition for '{}/{}'", fee.program_id(), fee.function_name());
 
             /* Finalize the deployment. */
@@ -80,6 +97,22 @@ impl<N: Network> Process<N> {
         store: &FinalizeStore<N, P>,
         execution: &Execution<N>,
         fee: Option<&Fee<N>>,
+    ) -> Result<Vec<FinalizeOperation<N>>> {
+        self.finalize_execution_with_trace(state, store, execution, fee, None)
+    }
+
+    /// Finalizes the execution and fee.
+    /// If a trace is given, every evaluated finalize command is recorded in it.
+    /// This method assumes the given execution **is valid**.
+    /// This method should **only** be called by `VM::finalize()`.
+    #[inline]
+    pub fn finalize_execution_with_trace<P: FinalizeStorage<N>>(
+        &self,
+        state: FinalizeGlobalState,
+        store: &FinalizeStore<N, P>,
+        execution: &Execution<N>,
+        fee: Option<&Fee<N>>,
+        mut trace: Option<&mut Vec<FinalizeTraceEvent<N>>>,
     ) -> Result<Vec<FinalizeOperation<N>>> {
         let timer = timer!("Program::finalize_execution");
 
@@ -107,7 +140,8 @@ impl<N: Network> Process<N> {
             // Finalize the root transition.
             // Note that this will result in all the remaining transitions being finalized, since the number
             // of calls matches the number of transitions.
-            let mut finalize_operations = finalize_transition(state, store, stack, transition, call_graph)?;
+            let mut finalize_operations =
+                finalize_transition(state, store, stack, transition, call_graph, trace.as_deref_mut())?;
 
             /* Finalize the fee. */
 
@@ -115,7 +149,7 @@ impl<N: Network> Process<N> {
                 // Retrieve the fee stack.
                 let fee_stack = self.get_stack(fee.program_id())?;
                 // Finalize the fee transition.
-                finalize_operations.extend(finalize_fee_transition(state, store, fee_stack, fee)?);
+                finalize_operations.extend(finalize_fee_transition(state, store, fee_stack, fee, trace)?);
                 lap!(timer, "Finalize transition for '{}/{}'", fee.program_id(), fee.function_name());
             }
 
@@ -134,6 +168,21 @@ impl<N: Network> Process<N> {
         state: FinalizeGlobalState,
         store: &FinalizeStore<N, P>,
         fee: &Fee<N>,
+    ) -> Result<Vec<FinalizeOperation<N>>> {
+        self.finalize_fee_with_trace(state, store, fee, None)
+    }
+
+    /// Finalizes the fee.
+    /// If a trace is given, every evaluated finalize command is recorded in it.
+    /// This method assumes the given fee **is valid**.
+    /// This method should **only** be called by `VM::finalize()`.
+    #[inline]
+    pub fn finalize_fee_with_trace<P: FinalizeStorage<N>>(
+        &self,
+        state: FinalizeGlobalState,
+        store: &FinalizeStore<N, P>,
+        fee: &Fee<N>,
+        trace: Option<&mut Vec<FinalizeTraceEvent<N>>>,
     ) -> Result<Vec<FinalizeOperation<N>>> {
         let timer = timer!("Program::finalize_fee");
 
@@ -141,7 +190,7 @@ impl<N: Network> Process<N> {
             // Retrieve the stack.
             let stack = self.get_stack(fee.program_id())?;
             // Finalize the fee transition.
-            let result = finalize_fee_transition(state, store, stack, fee);
+            let result = finalize_fee_transition(state, store, stack, fee, trace);
             finish!(timer, "Finalize transition for '{}/{}'", fee.program_id(), fee.function_name());
             // Return the result.
             result
@@ -155,6 +204,7 @@ fn finalize_fee_transition<N: Network, P: FinalizeStorage<N>>(
     store: &FinalizeStore<N, P>,
     stack: &Stack<N>,
     fee: &Fee<N>,
+    trace: Option<&mut Vec<FinalizeTraceEvent<N>>>,
 ) -> Result<Vec<FinalizeOperation<N>>> {
     // Construct the call graph.
     let mut call_graph = HashMap::new();
@@ -162,7 +212,7 @@ fn finalize_fee_transition<N: Network, P: FinalizeStorage<N>>(
     call_graph.insert(*fee.transition_id(), Vec::new());
 
     // Finalize the transition.
-    match finalize_transition(state, store, stack, fee, call_graph) {
+    match finalize_transition(state, store, stack, fee, call_graph, trace) {
         // If the evaluation succeeds, return the finalize operations.
         Ok(finalize_operations) => Ok(finalize_operations),
         // If the evaluation fails, bail and return the error.
@@ -170,13 +220,24 @@ fn finalize_fee_transition<N: Network, P: FinalizeStorage<N>>(
     }
 }
 
+/// If tracing, records the failed command in the trace.
+macro_rules! record_error {
+    ($trace:expr, $stack:expr, $finalize:expr, $counter:expr, $command:expr, $error:expr) => {
+        if let Some(trace) = $trace.as_deref_mut() {
+            trace.push(trace_error($stack, $finalize, $counter, $command, $error));
+        }
+    };
+}
+
 /// Finalizes the given transition.
+/// If a trace is given, every evaluated command is recorded in it.
 fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
     state: FinalizeGlobalState,
     store: &FinalizeStore<N, P>,
     stack: &Stack<N>,
     transition: &Transition<N>,
     call_graph: HashMap<N::TransitionID, Vec<N::TransitionID>>,
+    mut trace: Option<&mut Vec<FinalizeTraceEvent<N>>>,
 ) -> Result<Vec<FinalizeOperation<N>>> {
     // Retrieve the program ID.
     let program_id = transition.program_id();
@@ -206,7 +267,12 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
     let mut states = Vec::new();
 
     // Initialize the top-level finalize state.
-    states.push(initialize_finalize_state(state, future, stack, *transition.id())?);
+    let top_level_state = initialize_finalize_state(state, future, stack, *transition.id())?;
+    // If tracing, record the entry into the top-level finalize block.
+    if let Some(trace) = trace.as_deref_mut() {
+        trace.push(trace_enter(top_level_state.stack, top_level_state.finalize, &top_level_state.registers));
+    }
+    states.push(top_level_state);
 
     // While there are active finalize states, finalize them.
     while let Some(FinalizeState {
@@ -230,12 +296,22 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                     }));
                     match result {
                         Ok(Ok(new_counter)) => {
+                            // If tracing, record the command.
+                            if let Some(trace) = trace.as_deref_mut() {
+                                trace.push(trace_command(stack, finalize, &registers, counter, command, None, None));
+                            }
                             counter = new_counter;
                         }
                         // If the evaluation fails, bail and return the error.
-                        Ok(Err(error)) => bail!("'finalize' failed to evaluate command ({command}): {error}"),
+                        Ok(Err(error)) => {
+                            record_error!(trace, stack, finalize, counter, command, error.to_string());
+                            bail!("'finalize' failed to evaluate command ({command}): {error}")
+                        }
                         // If the evaluation fails, bail and return the error.
-                        Err(_) => bail!("'finalize' failed to evaluate command ({command})"),
+                        Err(_) => {
+                            record_error!(trace, stack, finalize, counter, command, "halted".to_string());
+                            bail!("'finalize' failed to evaluate command ({command})")
+                        }
                     }
                 }
                 Command::BranchNeq(branch_neq) => {
@@ -244,12 +320,22 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                     }));
                     match result {
                         Ok(Ok(new_counter)) => {
+                            // If tracing, record the command.
+                            if let Some(trace) = trace.as_deref_mut() {
+                                trace.push(trace_command(stack, finalize, &registers, counter, command, None, None));
+                            }
                             counter = new_counter;
                         }
                         // If the evaluation fails, bail and return the error.
-                        Ok(Err(error)) => bail!("'finalize' failed to evaluate command ({command}): {error}"),
+                        Ok(Err(error)) => {
+                            record_error!(trace, stack, finalize, counter, command, error.to_string());
+                            bail!("'finalize' failed to evaluate command ({command}): {error}")
+                        }
                         // If the evaluation fails, bail and return the error.
-                        Err(_) => bail!("'finalize' failed to evaluate command ({command})"),
+                        Err(_) => {
+                            record_error!(trace, stack, finalize, counter, command, "halted".to_string());
+                            bail!("'finalize' failed to evaluate command ({command})")
+                        }
                     }
                 }
                 Command::Await(await_) => {
@@ -283,11 +369,23 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                     })) {
                         Ok(Ok(callee_state)) => callee_state,
                         // If the evaluation fails, bail and return the error.
-                        Ok(Err(error)) => bail!("'finalize' failed to evaluate command ({command}): {error}"),
+                        Ok(Err(error)) => {
+                            record_error!(trace, stack, finalize, counter, command, error.to_string());
+                            bail!("'finalize' failed to evaluate command ({command}): {error}")
+                        }
                         // If the evaluation fails, bail and return the error.
-                        Err(_) => bail!("'finalize' failed to evaluate command ({command})"),
+                        Err(_) => {
+                            record_error!(trace, stack, finalize, counter, command, "halted".to_string());
+                            bail!("'finalize' failed to evaluate command ({command})")
+                        }
                     };
 
+                    // If tracing, record the command, and the entry into the callee finalize block.
+                    if let Some(trace) = trace.as_deref_mut() {
+                        trace.push(trace_command(stack, finalize, &registers, counter, command, None, None));
+                        trace.push(trace_enter(callee_state.stack, callee_state.finalize, &callee_state.registers));
+                    }
+
                     // Set the last seen call locator.
                     recent_call_locator = Some(locator);
                     // Increment the call counter.
@@ -307,17 +405,39 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                     break;
                 }
                 _ => {
+                    // If tracing, observe the mapping access before the command is evaluated.
+                    let access = match trace.is_some() {
+                        true => trace_mapping_access(stack, store, &registers, command),
+                        false => None,
+                    };
                     let result =
                         handle_halting!(panic::AssertUnwindSafe(|| { command.finalize(stack, store, &mut registers) }));
                     match result {
-                        // If the evaluation succeeds with an operation, add it to the list.
-                        Ok(Ok(Some(finalize_operation))) => finalize_operations.push(finalize_operation),
-                        // If the evaluation succeeds with no operation, continue.
-                        Ok(Ok(None)) => {}
+                        // If the evaluation succeeds, record the command, and add the operation (if any) to the list.
+                        Ok(Ok(finalize_operation)) => {
+                            if let Some(trace) = trace.as_deref_mut() {
+                                trace.push(trace_command(
+                                    stack,
+                                    finalize,
+                                    &registers,
+                                    counter,
+                                    command,
+                                    access,
+                                    finalize_operation,
+                                ));
+                            }
+                            finalize_operations.extend(finalize_operation);
+                        }
                         // If the evaluation fails, bail and return the error.
-                        Ok(Err(error)) => bail!("'finalize' failed to evaluate command ({command}): {error}"),
+                        Ok(Err(error)) => {
+                            record_error!(trace, stack, finalize, counter, command, error.to_string());
+                            bail!("'finalize' failed to evaluate command ({command}): {error}")
+                        }
                         // If the evaluation fails, bail and return the error.
-                        Err(_) => bail!("'finalize' failed to evaluate command ({command})"),
+                        Err(_) => {
+                            record_error!(trace, stack, finalize, counter, command, "halted".to_string());
+                            bail!("'finalize' failed to evaluate command ({command})")
+                        }
                     }
                     counter += 1;
                 }
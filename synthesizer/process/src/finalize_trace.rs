@@ -0,0 +1,262 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use console::program::Register;
+use synthesizer_program::{CommandTrait, FinalizeRegistersState, FinalizeStoreTrait, MappingLocator, Operand};
+
+/// A read or write of a mapping value, made by a finalize command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MappingAccess<N: Network> {
+    /// A `contains` of the key, with whether the key exists.
+    Contains(ProgramID<N>, Identifier<N>, Plaintext<N>, bool),
+    /// A `get` or `get.or_use` of the key, with the stored value, if it exists.
+    Get(ProgramID<N>, Identifier<N>, Plaintext<N>, Option<Value<N>>),
+    /// A `set` of the key to the value.
+    Set(ProgramID<N>, Identifier<N>, Plaintext<N>, Value<N>),
+    /// A `remove` of the key.
+    Remove(ProgramID<N>, Identifier<N>, Plaintext<N>),
+}
+
+impl<N: Network> Display for MappingAccess<N> {
+    /// Prints the mapping access, as `<read|write> <program ID>/<mapping name>[<key>] <value>`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Contains(program_id, mapping_name, key, exists) => {
+                write!(f, "read {program_id}/{mapping_name}[{key}] exists={exists}")
+            }
+            Self::Get(program_id, mapping_name, key, Some(value)) => {
+                write!(f, "read {program_id}/{mapping_name}[{key}] = {value}")
+            }
+            Self::Get(program_id, mapping_name, key, None) => {
+                write!(f, "read {program_id}/{mapping_name}[{key}] = none")
+            }
+            Self::Set(program_id, mapping_name, key, value) => {
+                write!(f, "write {program_id}/{mapping_name}[{key}] = {value}")
+            }
+            Self::Remove(program_id, mapping_name, key) => {
+                write!(f, "write {program_id}/{mapping_name}[{key}] removed")
+            }
+        }
+    }
+}
+
+/// A step in the finalize scope of a transition.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FinalizeTraceEvent<N: Network> {
+    /// The start of a finalize block, with its input registers.
+    Enter {
+        transition_id: N::TransitionID,
+        program_id: ProgramID<N>,
+        function_name: Identifier<N>,
+        inputs: Vec<(Register<N>, Value<N>)>,
+    },
+    /// An evaluated command, with its destination registers, mapping access, and finalize operation.
+    Command {
+        program_id: ProgramID<N>,
+        function_name: Identifier<N>,
+        index: usize,
+        command: String,
+        outputs: Vec<(Register<N>, Value<N>)>,
+        access: Option<MappingAccess<N>>,
+        operation: Option<FinalizeOperation<N>>,
+    },
+    /// A command that failed to evaluate, halting the finalize scope.
+    Error { program_id: ProgramID<N>, function_name: Identifier<N>, index: usize, command: String, error: String },
+}
+
+impl<N: Network> FinalizeTraceEvent<N> {
+    /// Returns the transition ID, if the event enters a finalize block.
+    pub const fn transition_id(&self) -> Option<&N::TransitionID> {
+        match self {
+            Self::Enter { transition_id, .. } => Some(transition_id),
+            Self::Command { .. } | Self::Error { .. } => None,
+        }
+    }
+
+    /// Returns the mapping access of the event, if one exists.
+    pub const fn access(&self) -> Option<&MappingAccess<N>> {
+        match self {
+            Self::Command { access, .. } => access.as_ref(),
+            Self::Enter { .. } | Self::Error { .. } => None,
+        }
+    }
+
+    /// Returns the finalize operation of the event, if one exists.
+    pub const fn operation(&self) -> Option<&FinalizeOperation<N>> {
+        match self {
+            Self::Command { operation, .. } => operation.as_ref(),
+            Self::Enter { .. } | Self::Error { .. } => None,
+        }
+    }
+
+    /// Returns `true` if the event is a failed command.
+    pub const fn is_error(&self) -> bool {
+        matches!(self, Self::Error { .. })
+    }
+}
+
+impl<N: Network> Display for FinalizeTraceEvent<N> {
+    /// Prints the event as one line, followed by one indented line per register, mapping access, and operation.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Enter { transition_id, program_id, function_name, inputs } => {
+                write!(f, "enter {program_id}/{function_name} ({transition_id})")?;
+                for (register, value) in inputs {
+                    write!(f, "\n    {register} = {value}")?;
+                }
+                Ok(())
+            }
+            Self::Command { program_id, function_name, index, command, outputs, access, operation } => {
+                write!(f, "{program_id}/{function_name} #{index}: {command}")?;
+                for (register, value) in outputs {
+                    write!(f, "\n    {register} = {value}")?;
+                }
+                if let Some(access) = access {
+                    write!(f, "\n    {access}")?;
+                }
+                if let Some(operation) = operation {
+                    write!(f, "\n    {operation}")?;
+                }
+                Ok(())
+            }
+            Self::Error { program_id, function_name, index, command, error } => {
+                write!(f, "{program_id}/{function_name} #{index}: {command}\n    error: {error}")
+            }
+        }
+    }
+}
+
+/// Returns the event for entering the given finalize block, with the stored input registers.
+pub(crate) fn trace_enter<N: Network>(
+    stack: &Stack<N>,
+    finalize: &Finalize<N>,
+    registers: &FinalizeRegisters<N>,
+) -> FinalizeTraceEvent<N> {
+    let inputs = finalize
+        .inputs()
+        .iter()
+        .filter_map(|input| {
+            load_register(stack, registers, input.register()).map(|value| (input.register().clone(), value))
+        })
+        .collect();
+    FinalizeTraceEvent::Enter {
+        transition_id: *registers.transition_id(),
+        program_id: *stack.program_id(),
+        function_name: *finalize.name(),
+        inputs,
+    }
+}
+
+/// Returns the event for the given evaluated command, with its destination registers.
+pub(crate) fn trace_command<N: Network>(
+    stack: &Stack<N>,
+    finalize: &Finalize<N>,
+    registers: &FinalizeRegisters<N>,
+    index: usize,
+    command: &Command<N>,
+    access: Option<MappingAccess<N>>,
+    operation: Option<FinalizeOperation<N>>,
+) -> FinalizeTraceEvent<N> {
+    let outputs = command
+        .destinations()
+        .into_iter()
+        .filter_map(|register| load_register(stack, registers, &register).map(|value| (register, value)))
+        .collect();
+    FinalizeTraceEvent::Command {
+        program_id: *stack.program_id(),
+        function_name: *finalize.name(),
+        index,
+        command: command.to_string(),
+        outputs,
+        access,
+        operation,
+    }
+}
+
+/// Returns the event for the given failed command.
+pub(crate) fn trace_error<N: Network>(
+    stack: &Stack<N>,
+    finalize: &Finalize<N>,
+    index: usize,
+    command: &Command<N>,
+    error: String,
+) -> FinalizeTraceEvent<N> {
+    FinalizeTraceEvent::Error {
+        program_id: *stack.program_id(),
+        function_name: *finalize.name(),
+        index,
+        command: command.to_string(),
+        error,
+    }
+}
+
+/// Returns the mapping access of the given command, as observed before the command is evaluated.
+/// If the command does not access a mapping, or its key cannot be loaded, returns `None`.
+pub(crate) fn trace_mapping_access<N: Network, P: FinalizeStorage<N>>(
+    stack: &Stack<N>,
+    store: &FinalizeStore<N, P>,
+    registers: &FinalizeRegisters<N>,
+    command: &Command<N>,
+) -> Option<MappingAccess<N>> {
+    // Resolves the program ID and mapping name of a mapping locator.
+    let locate = |mapping: &MappingLocator<N>| match mapping {
+        MappingLocator::Locator(locator) => (*locator.program_id(), *locator.resource()),
+        MappingLocator::Resource(mapping_name) => (*stack.program_id(), *mapping_name),
+    };
+    // Loads the key operand as a plaintext.
+    let load_key = |key: &Operand<N>| registers.load_plaintext(stack, key).ok();
+
+    match command {
+        Command::Contains(contains) => {
+            let (program_id, mapping_name) = (*stack.program_id(), *contains.mapping_name());
+            let key = load_key(contains.key())?;
+            let exists = store.contains_key_speculative(program_id, mapping_name, &key).ok()?;
+            Some(MappingAccess::Contains(program_id, mapping_name, key, exists))
+        }
+        Command::Get(get) => {
+            let (program_id, mapping_name) = locate(get.mapping());
+            let key = load_key(get.key())?;
+            let value = store.get_value_speculative(program_id, mapping_name, &key).ok()?;
+            Some(MappingAccess::Get(program_id, mapping_name, key, value))
+        }
+        Command::GetOrUse(get_or_use) => {
+            let (program_id, mapping_name) = locate(get_or_use.mapping());
+            let key = load_key(get_or_use.key())?;
+            let value = store.get_value_speculative(program_id, mapping_name, &key).ok()?;
+            Some(MappingAccess::Get(program_id, mapping_name, key, value))
+        }
+        Command::Set(set) => {
+            let (program_id, mapping_name) = (*stack.program_id(), *set.mapping_name());
+            let key = load_key(set.key())?;
+            let value = registers.load(stack, set.value()).ok()?;
+            Some(MappingAccess::Set(program_id, mapping_name, key, value))
+        }
+        Command::Remove(remove) => {
+            let (program_id, mapping_name) = (*stack.program_id(), *remove.mapping_name());
+            let key = load_key(remove.key())?;
+            Some(MappingAccess::Remove(program_id, mapping_name, key))
+        }
+        _ => None,
+    }
+}
+
+/// Loads the value of the given register, if it has been assigned.
+fn load_register<N: Network>(
+    stack: &Stack<N>,
+    registers: &FinalizeRegisters<N>,
+    register: &Register<N>,
+) -> Option<Value<N>> {
+    registers.load(stack, &Operand::Register(register.clone())).ok()
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::*;
+use console::program::{Argument, FinalizeType};
 
 impl<N: Network> Stack<N> {
     /// Returns a value for the given value type.
@@ -64,6 +65,16 @@ impl<N: Network> Stack<N> {
         // Return the plaintext value.
         Ok(plaintext)
     }
+
+    /// Samples a future for the given locator, with arguments sampled from the finalize input types.
+    pub fn sample_future<R: Rng + CryptoRng>(&self, locator: &Locator<N>, rng: &mut R) -> Result<Future<N>> {
+        // Sample a future.
+        let future = self.sample_future_internal(locator, 0, rng)?;
+        // Ensure the future matches the locator.
+        self.matches_future(&future, locator)?;
+        // Return the future.
+        Ok(future)
+    }
 }
 
 impl<N: Network> Stack<N> {
@@ -182,4 +193,42 @@ impl<N: Network> Stack<N> {
         // Return the plaintext.
         Ok(plaintext)
     }
+
+    /// Samples a future for the given locator.
+    fn sample_future_internal<R: Rng + CryptoRng>(
+        &self,
+        locator: &Locator<N>,
+        depth: usize,
+        rng: &mut R,
+    ) -> Result<Future<N>> {
+        // If the depth exceeds the maximum depth, then the future type is invalid.
+        ensure!(depth <= N::MAX_DATA_DEPTH, "Future exceeded maximum depth of {}", N::MAX_DATA_DEPTH);
+
+        // Retrieve the stack that defines the future.
+        let stack = match locator.program_id() == self.program_id() {
+            true => self,
+            false => self.get_external_stack(locator.program_id())?.as_ref(),
+        };
+        // Retrieve the finalize inputs.
+        let inputs = match stack.get_function_ref(locator.resource())?.finalize_logic() {
+            Some(finalize_logic) => finalize_logic.inputs(),
+            None => bail!("Function '{locator}' does not have a finalize block"),
+        };
+
+        // Sample an argument for each finalize input.
+        let arguments = inputs
+            .iter()
+            .map(|input| match input.finalize_type() {
+                FinalizeType::Plaintext(plaintext_type) => {
+                    Ok(Argument::Plaintext(stack.sample_plaintext_internal(plaintext_type, depth + 1, rng)?))
+                }
+                FinalizeType::Future(locator) => {
+                    Ok(Argument::Future(stack.sample_future_internal(locator, depth + 1, rng)?))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Return the future.
+        Ok(Future::new(*locator.program_id(), *locator.resource(), arguments))
+    }
 }
@@ -15,6 +15,8 @@
 use crate::{
     traits::{StackEvaluate, StackExecute},
     CallStack,
+    FinalizeTraceEvent,
+    MappingAccess,
     Process,
     Trace,
 };
@@ -22,7 +24,7 @@ use circuit::{network::AleoV0, Aleo};
 use console::{
     account::{Address, PrivateKey, ViewKey},
     network::{prelude::*, Testnet3},
-    program::{Identifier, Literal, Plaintext, ProgramID, Record, Value},
+    program::{Identifier, Literal, Plaintext, ProgramID, Record, Register, Value},
     types::{Field, U64},
 };
 use ledger_block::Fee;
@@ -1303,6 +1305,114 @@ finalize compute:
     assert_eq!(candidate, Value::from_str("8u64").unwrap());
 }
 
+#[test]
+fn test_process_execute_and_finalize_with_trace() {
+    // Initialize a new program.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program testing.aleo;
+
+mapping account:
+    key as address.public;
+    value as u64.public;
+
+function compute:
+    input r0 as address.public;
+    input r1 as u64.public;
+    async compute r0 r1 into r2;
+    output r2 as testing.aleo/compute.future;
+
+finalize compute:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use account[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into account[r0];
+",
+    )
+    .unwrap();
+
+    // Declare the program ID.
+    let program_id = *program.id();
+    // Declare the mapping.
+    let mapping_name = Identifier::from_str("account").unwrap();
+    // Declare the function name.
+    let function_name = Identifier::from_str("compute").unwrap();
+
+    // Initialize the RNG.
+    let rng = &mut TestRng::default();
+
+    // Construct the process.
+    let mut process = Process::load().unwrap();
+
+    // Initialize a new block store.
+    let block_store = BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).unwrap();
+    // Initialize a new finalize store.
+    let finalize_store = FinalizeStore::<_, FinalizeMemory<_>>::open(None).unwrap();
+
+    // Deploy the program.
+    let deployment = process.deploy::<CurrentAleo, _>(&program, rng).unwrap();
+    // Compute the fee.
+    let fee = sample_fee::<_, CurrentAleo, _, _>(&process, &block_store, &finalize_store, rng);
+    // Finalize the deployment.
+    let (stack, _) = process.finalize_deployment(sample_finalize_state(1), &finalize_store, &deployment, &fee).unwrap();
+    // Add the stack *manually* to the process.
+    process.add_stack(stack);
+
+    // Initialize a new caller account.
+    let caller_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let caller = Address::try_from(&caller_private_key).unwrap();
+    let key = Plaintext::from(Literal::Address(caller));
+
+    // Execute the function.
+    let inputs = [Value::<CurrentNetwork>::from_str(&caller.to_string()).unwrap(), Value::from_str("5u64").unwrap()];
+    let authorization = process
+        .authorize::<CurrentAleo, _>(&caller_private_key, program.id(), function_name, inputs.iter(), rng)
+        .unwrap();
+    let (_, mut trace) = process.execute::<CurrentAleo, _>(authorization, rng).unwrap();
+    trace.prepare(Query::from(block_store)).unwrap();
+    let execution = trace.prove_execution::<CurrentAleo, _>("testing", rng).unwrap();
+    let transition_id = *execution.peek().unwrap().id();
+
+    // Finalize the execution with tracing.
+    let mut events = Vec::new();
+    let operations = process
+        .finalize_execution_with_trace(sample_finalize_state(1), &finalize_store, &execution, None, Some(&mut events))
+        .unwrap();
+
+    // Ensure the trace enters the finalize block, then evaluates each command.
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0].transition_id(), Some(&transition_id));
+    match &events[0] {
+        FinalizeTraceEvent::Enter { program_id: id, function_name: name, inputs, .. } => {
+            assert_eq!((id, name), (&program_id, &function_name));
+            assert_eq!(inputs[1], (Register::Locator(1), Value::from_str("5u64").unwrap()));
+        }
+        event => panic!("Expected an enter event, found {event:?}"),
+    }
+    // Ensure the `get.or_use` reads the missing key, and loads the default.
+    assert_eq!(events[1].access(), Some(&MappingAccess::Get(program_id, mapping_name, key.clone(), None)));
+    match &events[1] {
+        FinalizeTraceEvent::Command { index, outputs, .. } => {
+            assert_eq!(*index, 0);
+            assert_eq!(outputs, &[(Register::Locator(2), Value::from_str("0u64").unwrap())]);
+        }
+        event => panic!("Expected a command event, found {event:?}"),
+    }
+    // Ensure the `set` writes the new value, with the recorded finalize operation.
+    let value = Value::from_str("5u64").unwrap();
+    assert_eq!(events[3].access(), Some(&MappingAccess::Set(program_id, mapping_name, key.clone(), value)));
+    assert_eq!(events[3].operation(), operations.last());
+    assert!(events.iter().all(|event| !event.is_error()));
+
+    // Finalize the execution again, without tracing.
+    process.finalize_execution(sample_finalize_state(1), &finalize_store, &execution, None).unwrap();
+
+    // Ensure the account balance is now 10.
+    let candidate = finalize_store.get_value_speculative(program_id, mapping_name, &key).unwrap().unwrap();
+    assert_eq!(candidate, Value::from_str("10u64").unwrap());
+}
+
 #[test]
 fn test_process_execute_and_finalize_increment_decrement_via_get_set() {
     // Initialize a new program.
@@ -2357,6 +2467,48 @@ function compute:
     process.verify_execution(&execution).unwrap();
 }
 
+#[test]
+fn test_stack_verify_certificate() {
+    let rng = &mut TestRng::default();
+
+    // Initialize a new program.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program testing.aleo;
+
+function double:
+    input r0 as u32.private;
+    add r0 r0 into r1;
+    output r1 as u32.public;
+
+function triple:
+    input r0 as u32.private;
+    add r0 r0 into r1;
+    add r0 r1 into r2;
+    output r2 as u32.public;",
+    )
+    .unwrap();
+
+    // Construct the process.
+    let mut process = Process::load().unwrap();
+    // Compute the deployment.
+    let deployment = process.deploy::<CurrentAleo, _>(&program, rng).unwrap();
+    // Add the program to the process.
+    process.add_program(&program).unwrap();
+
+    // Retrieve the stack.
+    let stack = process.get_stack(program.id()).unwrap();
+    // Retrieve the verifying keys and certificates.
+    let (double, (double_verifying_key, double_certificate)) = &deployment.verifying_keys()[0];
+    let (_, (triple_verifying_key, triple_certificate)) = &deployment.verifying_keys()[1];
+
+    // Ensure the certificate is valid for its function.
+    stack.verify_certificate::<CurrentAleo, _>(double, double_verifying_key, double_certificate, rng).unwrap();
+    // Ensure the certificate of another function is not valid.
+    assert!(stack.verify_certificate::<CurrentAleo, _>(double, triple_verifying_key, triple_certificate, rng).is_err());
+    assert!(stack.verify_certificate::<CurrentAleo, _>(double, double_verifying_key, triple_certificate, rng).is_err());
+}
+
 #[test]
 fn test_process_deploy_credits_program() {
     let rng = &mut TestRng::default();
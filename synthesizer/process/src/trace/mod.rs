@@ -25,7 +25,7 @@ use console::{
 };
 use ledger_block::{Execution, Fee, Transition};
 use ledger_query::QueryTrait;
-use synthesizer_snark::{Proof, ProvingKey, VerifyingKey};
+use synthesizer_snark::{Proof, ProverMemoryMode, ProvingKey, VerifyingKey};
 
 use once_cell::sync::OnceCell;
 use std::collections::HashMap;
@@ -151,6 +151,17 @@ impl<N: Network> Trace<N> {
         &self,
         locator: &str,
         rng: &mut R,
+    ) -> Result<Execution<N>> {
+        self.prove_execution_with_memory_mode::<A, R>(locator, ProverMemoryMode::Parallel, rng)
+    }
+
+    /// Returns a new execution with a proof, for the current inclusion assignments and global state root,
+    /// in the given memory mode of the prover.
+    pub fn prove_execution_with_memory_mode<A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
+        &self,
+        locator: &str,
+        memory_mode: ProverMemoryMode,
+        rng: &mut R,
     ) -> Result<Execution<N>> {
         // Ensure this is not a fee.
         ensure!(!self.is_fee(), "The trace cannot call 'prove_execution' for a fee type");
@@ -168,14 +179,30 @@ impl<N: Network> Trace<N> {
         // Construct the proving tasks.
         let proving_tasks = self.transition_tasks.values().cloned().collect();
         // Compute the proof.
-        let (global_state_root, proof) =
-            Self::prove_batch::<A, R>(locator, proving_tasks, inclusion_assignments, *global_state_root, rng)?;
+        let (global_state_root, proof) = Self::prove_batch::<A, R>(
+            locator,
+            proving_tasks,
+            inclusion_assignments,
+            *global_state_root,
+            memory_mode,
+            rng,
+        )?;
         // Return the execution.
         Execution::from(self.transitions.iter().cloned(), global_state_root, Some(proof))
     }
 
     /// Returns a new fee with a proof, for the current inclusion assignment and global state root.
     pub fn prove_fee<A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(&self, rng: &mut R) -> Result<Fee<N>> {
+        self.prove_fee_with_memory_mode::<A, R>(ProverMemoryMode::Parallel, rng)
+    }
+
+    /// Returns a new fee with a proof, for the current inclusion assignment and global state root,
+    /// in the given memory mode of the prover.
+    pub fn prove_fee_with_memory_mode<A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
+        &self,
+        memory_mode: ProverMemoryMode,
+        rng: &mut R,
+    ) -> Result<Fee<N>> {
         // Ensure this is a fee.
         let is_fee_public = self.is_fee_public();
         let is_fee_private = self.is_fee_private();
@@ -201,12 +228,34 @@ impl<N: Network> Trace<N> {
             proving_tasks,
             inclusion_assignments,
             *global_state_root,
+            memory_mode,
             rng,
         )?;
         // Return the fee.
         Ok(Fee::from_unchecked(fee_transition.clone(), global_state_root, Some(proof)))
     }
 
+    /// Returns an estimate of the peak memory (in bytes) that the prover uses to prove the current transition(s)
+    /// in the given memory mode, for the current inclusion assignments.
+    pub fn estimate_prover_memory(&self, memory_mode: ProverMemoryMode) -> Result<usize> {
+        // Retrieve the inclusion assignments.
+        let inclusion_assignments =
+            self.inclusion_assignments.get().ok_or_else(|| anyhow!("Inclusion assignments have not been set"))?;
+        // Construct the batch sizes.
+        let mut batch_sizes = self
+            .transition_tasks
+            .values()
+            .map(|(proving_key, assignments)| (proving_key.clone(), assignments.len()))
+            .collect::<Vec<_>>();
+        if !inclusion_assignments.is_empty() {
+            // Insert the inclusion proving key and the number of inclusion assignments.
+            let proving_key = ProvingKey::<N>::new(N::inclusion_proving_key().clone());
+            batch_sizes.push((proving_key, inclusion_assignments.len()));
+        }
+        // Estimate the memory of the prover.
+        ProvingKey::estimate_prover_memory(&batch_sizes, memory_mode)
+    }
+
     /// Checks the proof for the execution.
     /// Note: This does *not* check that the global state root exists in the ledger.
     pub fn verify_execution_proof(
@@ -261,6 +310,7 @@ impl<N: Network> Trace<N> {
         mut proving_tasks: Vec<(ProvingKey<N>, Vec<Assignment<N::Field>>)>,
         inclusion_assignments: &[InclusionAssignment<N>],
         global_state_root: N::StateRoot,
+        memory_mode: ProverMemoryMode,
         rng: &mut R,
     ) -> Result<(N::StateRoot, Proof<N>)> {
         // Ensure the global state root is not zero.
@@ -290,7 +340,7 @@ impl<N: Network> Trace<N> {
         }
 
         // Compute the proof.
-        let proof = ProvingKey::prove_batch(locator, &proving_tasks, rng)?;
+        let proof = ProvingKey::prove_batch_with_memory_mode(locator, &proving_tasks, memory_mode, rng)?;
         // Return the global state root and proof.
         Ok((global_state_root, proof))
     }
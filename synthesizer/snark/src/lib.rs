@@ -28,6 +28,8 @@ use colored::Colorize;
 
 type Varuna<N> = varuna::VarunaSNARK<<N as Environment>::PairingCurve, FiatShamir<N>, varuna::VarunaHidingMode>;
 
+pub use varuna::ProverMemoryMode;
+
 mod certificate;
 pub use certificate::Certificate;
 
@@ -41,7 +43,7 @@ mod universal_srs;
 pub use universal_srs::UniversalSRS;
 
 mod verifying_key;
-pub use verifying_key::VerifyingKey;
+pub use verifying_key::{ProofCheck, VerifyingKey};
 
 #[cfg(test)]
 pub(crate) mod test_helpers {
@@ -160,4 +162,21 @@ mod test {
         println!("\nShould not verify (i.e. verifier messages should print below):");
         assert!(!verifying_key.verify("test", &[one, one + one], &proof));
     }
+
+    #[test]
+    fn test_check_proof() {
+        let (_, verifying_key) = crate::test_helpers::sample_keys();
+        let proof = crate::test_helpers::sample_proof();
+
+        let one = <Circuit as Environment>::BaseField::one();
+        verifying_key.check_proof(&[vec![one, one]], &proof).unwrap();
+
+        // Ensure each failed check is reported.
+        let error = verifying_key.check_proof(&[], &proof).unwrap_err();
+        assert!(error.to_string().contains("public inputs were provided for 0 instances"));
+        let (check, _) = verifying_key.check_proof_stages(&[], &proof).unwrap_err();
+        assert_eq!(check, ProofCheck::Instances);
+        let (check, _) = verifying_key.check_proof_stages(&[vec![one, one + one]], &proof).unwrap_err();
+        assert_eq!(check, ProofCheck::Opening);
+    }
 }
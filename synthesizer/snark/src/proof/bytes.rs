@@ -43,6 +43,9 @@ impl<N: Network> ToBytes for Proof<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use console::network::Testnet3;
+
+    type CurrentNetwork = Testnet3;
 
     #[test]
     fn test_bytes() -> Result<()> {
@@ -55,4 +58,16 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_max_size_in_bytes() -> Result<()> {
+        // Sample the proof.
+        let proof = crate::test_helpers::sample_proof();
+
+        // Ensure the proof does not exceed the upper bound for its batch sizes.
+        let size_in_bytes = proof.to_bytes_le()?.len();
+        assert!(size_in_bytes <= Proof::<CurrentNetwork>::max_size_in_bytes(proof.batch_sizes()));
+
+        Ok(())
+    }
 }
@@ -29,6 +29,12 @@ impl<N: Network> Proof<N> {
     pub(super) const fn new(proof: varuna::Proof<N::PairingCurve>) -> Self {
         Self { proof }
     }
+
+    /// Returns an upper bound on the size in bytes of a proof for the given batch sizes.
+    pub fn max_size_in_bytes(batch_sizes: &[usize]) -> usize {
+        // Account for the version byte.
+        1 + varuna::Proof::<N::PairingCurve>::max_compressed_size(batch_sizes)
+    }
 }
 
 impl<N: Network> Deref for Proof<N> {
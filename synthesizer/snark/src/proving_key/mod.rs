@@ -60,6 +60,17 @@ impl<N: Network> ProvingKey<N> {
         locator: &str,
         assignments: &[(ProvingKey<N>, Vec<circuit::Assignment<N::Field>>)],
         rng: &mut R,
+    ) -> Result<Proof<N>> {
+        Self::prove_batch_with_memory_mode(locator, assignments, ProverMemoryMode::Parallel, rng)
+    }
+
+    /// Returns a proof for the given batch of proving keys and assignments, in the given memory mode of the prover.
+    #[allow(clippy::type_complexity)]
+    pub fn prove_batch_with_memory_mode<R: Rng + CryptoRng>(
+        locator: &str,
+        assignments: &[(ProvingKey<N>, Vec<circuit::Assignment<N::Field>>)],
+        memory_mode: ProverMemoryMode,
+        rng: &mut R,
     ) -> Result<Proof<N>> {
         #[cfg(feature = "aleo-cli")]
         let timer = std::time::Instant::now();
@@ -75,13 +86,32 @@ impl<N: Network> ProvingKey<N> {
         let fiat_shamir = N::varuna_fs_parameters();
 
         // Compute the proof.
-        let batch_proof = Proof::new(Varuna::<N>::prove_batch(universal_prover, fiat_shamir, &instances, rng)?);
+        let batch_proof = Proof::new(Varuna::<N>::prove_batch_with_memory_mode(
+            universal_prover,
+            fiat_shamir,
+            &instances,
+            memory_mode,
+            rng,
+        )?);
 
         #[cfg(feature = "aleo-cli")]
         println!("{}", format!(" • Executed '{locator}' (in {} ms)", timer.elapsed().as_millis()).dimmed());
 
         Ok(batch_proof)
     }
+
+    /// Returns an estimate of the peak memory (in bytes) that the prover uses to prove a batch in the given
+    /// memory mode, where `batch_sizes` contains the number of assignments to prove for each proving key.
+    pub fn estimate_prover_memory(
+        batch_sizes: &[(ProvingKey<N>, usize)],
+        memory_mode: ProverMemoryMode,
+    ) -> Result<usize> {
+        // Prepare the batch sizes.
+        let batch_sizes: BTreeMap<_, _> =
+            batch_sizes.iter().map(|(proving_key, batch_size)| (proving_key.deref(), *batch_size)).collect();
+        // Estimate the memory of the prover.
+        Varuna::<N>::estimate_prover_memory(&batch_sizes, memory_mode)
+    }
 }
 
 impl<N: Network> Deref for ProvingKey<N> {
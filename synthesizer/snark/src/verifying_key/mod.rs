@@ -87,6 +87,147 @@ impl<N: Network> VerifyingKey<N> {
             }
         }
     }
+
+    /// Checks that the verifying key is well-formed, and otherwise returns an error describing the check that failed.
+    pub fn check_well_formed(&self) -> Result<()> {
+        let info = &self.circuit_info;
+        // Ensure the public inputs are padded to a power of two.
+        ensure!(
+            info.num_public_inputs.is_power_of_two(),
+            "The circuit has {} public inputs, which is not padded to a power of two",
+            info.num_public_inputs
+        );
+        // Ensure the variables include the public inputs.
+        ensure!(
+            info.num_variables >= info.num_public_inputs,
+            "The circuit has {} variables, but {} public inputs",
+            info.num_variables,
+            info.num_public_inputs
+        );
+        // Ensure there is a commitment for each indexed polynomial of the circuit.
+        let num_indexed_polynomials =
+            varuna::AHPForR1CS::<N::Field, varuna::VarunaHidingMode>::index_polynomial_info(core::iter::once(&self.id))
+                .len();
+        ensure!(
+            self.circuit_commitments.len() == num_indexed_polynomials,
+            "The verifying key has {} commitments, but the circuit has {num_indexed_polynomials} indexed polynomials",
+            self.circuit_commitments.len()
+        );
+        Ok(())
+    }
+
+    /// Checks that the proof is valid for the given public inputs of each instance,
+    /// and otherwise returns an error describing the check that failed.
+    pub fn check_proof(&self, inputs: &[Vec<N::Field>], proof: &Proof<N>) -> Result<()> {
+        self.check_proof_stages(inputs, proof).map_err(|(check, error)| anyhow!("The {check} check failed - {error}"))
+    }
+
+    /// Checks that the proof is valid for the given public inputs of each instance, performing the checks
+    /// in the order of `ProofCheck::ALL`, and otherwise returns the first check that failed and its error.
+    pub fn check_proof_stages(&self, inputs: &[Vec<N::Field>], proof: &Proof<N>) -> Result<(), (ProofCheck, Error)> {
+        // Ensure the verifying key is well-formed.
+        self.check_well_formed().map_err(|error| (ProofCheck::VerifyingKey, error))?;
+        // Ensure the proof is well-formed.
+        proof.check_batch_sizes().map_err(|error| (ProofCheck::Proof, anyhow!("The proof is malformed - {error}")))?;
+
+        // Ensure the proof is for a single circuit, and the public inputs are provided for each instance.
+        let batch_sizes = proof.batch_sizes();
+        let check_instances = || {
+            ensure!(
+                batch_sizes.len() == 1,
+                "The proof is for {} circuits, but only one verifying key was provided",
+                batch_sizes.len()
+            );
+            ensure!(
+                batch_sizes[0] == inputs.len(),
+                "The proof is for {} instances, but public inputs were provided for {} instances",
+                batch_sizes[0],
+                inputs.len()
+            );
+            Ok(())
+        };
+        check_instances().map_err(|error| (ProofCheck::Instances, error))?;
+
+        // Ensure each instance does not exceed the number of public inputs of the circuit.
+        let num_public_inputs = self.circuit_info.num_public_inputs;
+        for (i, instance_inputs) in inputs.iter().enumerate() {
+            if instance_inputs.len() > num_public_inputs {
+                let error = anyhow!(
+                    "Instance {i} has {} public inputs, but the circuit has at most {num_public_inputs}",
+                    instance_inputs.len()
+                );
+                return Err((ProofCheck::PublicInputs, error));
+            }
+        }
+
+        // Ensure the proof is hiding.
+        if !(proof.pc_proof.is_hiding() && proof.commitments.mask_poly.is_some()) {
+            let error = anyhow!("The proof is not hiding, but the verifying key expects a hiding proof");
+            return Err((ProofCheck::Hiding, error));
+        }
+
+        // Retrieve the verification parameters.
+        let universal_verifier = N::varuna_universal_verifier();
+        let fiat_shamir = N::varuna_fs_parameters();
+
+        // Verify the proof.
+        // Note: As the hiding mode is checked above, the verifier only returns `false` if the opening check fails.
+        let keys_to_inputs = BTreeMap::from([(self.deref(), inputs)]);
+        match Varuna::<N>::verify_batch(universal_verifier, fiat_shamir, &keys_to_inputs, proof) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err((
+                ProofCheck::Opening,
+                anyhow!("The evaluations of the proof do not open to its commitments for the given public inputs"),
+            )),
+            Err(error) => Err((ProofCheck::Verifier, error)),
+        }
+    }
+}
+
+/// A check on a proof, as performed by `VerifyingKey::check_proof`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProofCheck {
+    /// The verifying key is well-formed.
+    VerifyingKey,
+    /// The proof is well-formed.
+    Proof,
+    /// The proof is for the circuit of the verifying key, with public inputs for each instance.
+    Instances,
+    /// The public inputs of each instance fit in the circuit.
+    PublicInputs,
+    /// The proof is hiding.
+    Hiding,
+    /// The AHP verifier accepts the messages of the proof.
+    Verifier,
+    /// The evaluations of the proof open to its commitments.
+    Opening,
+}
+
+impl ProofCheck {
+    /// The checks on a proof, in the order they are performed.
+    pub const ALL: [Self; 7] = [
+        Self::VerifyingKey,
+        Self::Proof,
+        Self::Instances,
+        Self::PublicInputs,
+        Self::Hiding,
+        Self::Verifier,
+        Self::Opening,
+    ];
+}
+
+impl Display for ProofCheck {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::VerifyingKey => write!(f, "verifying key"),
+            Self::Proof => write!(f, "proof format"),
+            Self::Instances => write!(f, "instance count"),
+            Self::PublicInputs => write!(f, "public input"),
+            Self::Hiding => write!(f, "hiding mode"),
+            Self::Verifier => write!(f, "AHP verifier"),
+            Self::Opening => write!(f, "polynomial commitment opening"),
+        }
+    }
 }
 
 impl<N: Network> Deref for VerifyingKey<N> {
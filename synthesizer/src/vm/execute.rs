@@ -40,7 +40,8 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         // Determine if a priority fee is declared.
         let is_priority_fee_declared = priority_fee_in_microcredits > 0;
         // Compute the execution.
-        let execution = self.execute_authorization_raw(authorization, query.clone(), rng)?;
+        let execution =
+            self.execute_authorization_raw(authorization, query.clone(), ProverMemoryMode::Parallel, rng)?;
         // Compute the fee.
         let fee = match is_fee_required || is_priority_fee_declared {
             true => {
@@ -67,7 +68,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                     )?,
                 };
                 // Execute the fee.
-                Some(self.execute_fee_authorization_raw(authorization, query, rng)?)
+                Some(self.execute_fee_authorization_raw(authorization, query, ProverMemoryMode::Parallel, rng)?)
             }
             false => None,
         };
@@ -82,12 +83,31 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         fee_authorization: Option<Authorization<N>>,
         query: Option<Query<N, C::BlockStorage>>,
         rng: &mut R,
+    ) -> Result<Transaction<N>> {
+        self.execute_authorization_with_memory_mode(
+            execute_authorization,
+            fee_authorization,
+            query,
+            ProverMemoryMode::Parallel,
+            rng,
+        )
+    }
+
+    /// Returns a new execute transaction for the given authorization, in the given memory mode of the prover.
+    /// In the low-memory mode, the peak memory of the prover is bounded, at the cost of proving time.
+    pub fn execute_authorization_with_memory_mode<R: Rng + CryptoRng>(
+        &self,
+        execute_authorization: Authorization<N>,
+        fee_authorization: Option<Authorization<N>>,
+        query: Option<Query<N, C::BlockStorage>>,
+        memory_mode: ProverMemoryMode,
+        rng: &mut R,
     ) -> Result<Transaction<N>> {
         // Compute the execution.
-        let execution = self.execute_authorization_raw(execute_authorization, query.clone(), rng)?;
+        let execution = self.execute_authorization_raw(execute_authorization, query.clone(), memory_mode, rng)?;
         // Compute the fee.
         let fee = match fee_authorization {
-            Some(authorization) => Some(self.execute_fee_authorization_raw(authorization, query, rng)?),
+            Some(authorization) => Some(self.execute_fee_authorization_raw(authorization, query, memory_mode, rng)?),
             None => None,
         };
         // Return the execute transaction.
@@ -102,7 +122,37 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         rng: &mut R,
     ) -> Result<Fee<N>> {
         debug_assert!(authorization.is_fee_private() || authorization.is_fee_public(), "Expected a fee authorization");
-        self.execute_fee_authorization_raw(authorization, query, rng)
+        self.execute_fee_authorization_raw(authorization, query, ProverMemoryMode::Parallel, rng)
+    }
+
+    /// Returns an upper-bound estimate of the execution fee for a call to the program function with the given inputs,
+    /// without proving the execution. See `estimate_execution_cost` for the limitations of the estimate.
+    ///
+    /// The estimate does not include the fee transition, nor any priority fee.
+    pub fn estimate_execution_fee<R: Rng + CryptoRng>(
+        &self,
+        program_id: impl TryInto<ProgramID<N>>,
+        function_name: impl TryInto<Identifier<N>>,
+        inputs: impl IntoIterator<Item = impl TryInto<Value<N>>>,
+        rng: &mut R,
+    ) -> Result<ExecutionCostEstimate> {
+        // Prepare the program ID.
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        // Prepare the function name.
+        let function_name = function_name.try_into().map_err(|_| anyhow!("Invalid function name"))?;
+        // Prepare the inputs.
+        let inputs = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(index, input)| {
+                input
+                    .try_into()
+                    .map_err(|_| anyhow!("Failed to parse input #{index} for '{program_id}/{function_name}'"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Estimate the execution cost.
+        estimate_execution_cost(&self.process.read(), &program_id, &function_name, &inputs, rng)
     }
 }
 
@@ -114,6 +164,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         &self,
         authorization: Authorization<N>,
         query: Option<Query<N, C::BlockStorage>>,
+        memory_mode: ProverMemoryMode,
         rng: &mut R,
     ) -> Result<Execution<N>> {
         let timer = timer!("VM::execute_authorization_raw");
@@ -143,7 +194,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                 lap!(timer, "Prepare the assignments");
 
                 // Compute the proof and construct the execution.
-                let execution = trace.prove_execution::<$aleo, _>(&locator, rng)?;
+                let execution = trace.prove_execution_with_memory_mode::<$aleo, _>(&locator, memory_mode, rng)?;
                 lap!(timer, "Compute the proof");
 
                 // Return the execution.
@@ -164,6 +215,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         &self,
         authorization: Authorization<N>,
         query: Option<Query<N, C::BlockStorage>>,
+        memory_mode: ProverMemoryMode,
         rng: &mut R,
     ) -> Result<Fee<N>> {
         let timer = timer!("VM::execute_fee_authorization_raw");
@@ -188,7 +240,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                 lap!(timer, "Prepare the assignments");
 
                 // Compute the proof and construct the fee.
-                let fee = trace.prove_fee::<$aleo, _>(rng)?;
+                let fee = trace.prove_fee_with_memory_mode::<$aleo, _>(memory_mode, rng)?;
                 lap!(timer, "Compute the proof");
 
                 // Return the fee.
@@ -384,6 +436,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_execute_authorization_with_low_memory_mode() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a new caller.
+        let caller_private_key = crate::vm::test_helpers::sample_genesis_private_key(rng);
+        let caller_view_key = ViewKey::try_from(&caller_private_key).unwrap();
+
+        // Prepare the VM and records.
+        let (vm, records) = prepare_vm(rng).unwrap();
+
+        // Fetch the unspent record.
+        let record = records.values().next().unwrap().decrypt(&caller_view_key).unwrap();
+
+        // Prepare the inputs.
+        let inputs =
+            [Value::<CurrentNetwork>::Record(record), Value::<CurrentNetwork>::from_str("1u64").unwrap()].into_iter();
+
+        // Authorize the split.
+        let authorization = vm.authorize(&caller_private_key, "credits.aleo", "split", inputs, rng).unwrap();
+
+        // Execute in the low-memory mode of the prover.
+        let transaction = vm
+            .execute_authorization_with_memory_mode(authorization, None, None, ProverMemoryMode::LowMemory, rng)
+            .unwrap();
+
+        // Ensure the transaction is valid.
+        vm.check_transaction(&transaction, None, rng).unwrap();
+    }
+
+    #[test]
+    fn test_estimate_execution_fee() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a new caller.
+        let caller_private_key = crate::vm::test_helpers::sample_genesis_private_key(rng);
+        let caller_view_key = ViewKey::try_from(&caller_private_key).unwrap();
+        let address = Address::try_from(&caller_private_key).unwrap();
+
+        // Prepare the VM and records.
+        let (vm, records) = prepare_vm(rng).unwrap();
+
+        // Fetch the unspent record.
+        let record = records.values().next().unwrap().decrypt(&caller_view_key).unwrap();
+
+        // Prepare the calls.
+        let calls = [
+            ("transfer_public", vec![
+                Value::<CurrentNetwork>::from_str(&address.to_string()).unwrap(),
+                Value::<CurrentNetwork>::from_str("1u64").unwrap(),
+            ]),
+            ("transfer_private", vec![
+                Value::<CurrentNetwork>::Record(record),
+                Value::<CurrentNetwork>::from_str(&address.to_string()).unwrap(),
+                Value::<CurrentNetwork>::from_str("1u64").unwrap(),
+            ]),
+        ];
+
+        for (function_name, inputs) in calls {
+            // Estimate the execution fee.
+            let estimate = vm.estimate_execution_fee("credits.aleo", function_name, inputs.clone(), rng).unwrap();
+
+            // Execute.
+            let transaction = vm
+                .execute(&caller_private_key, ("credits.aleo", function_name), inputs.into_iter(), None, 0, None, rng)
+                .unwrap();
+            let execution = transaction.execution().unwrap();
+            let (total_cost, (storage_cost, finalize_cost)) = execution_cost(&vm, execution).unwrap();
+
+            // Ensure the estimate matches the execution.
+            assert_eq!(estimate.num_transitions(), execution.len());
+            assert_eq!(estimate.finalize_cost(), finalize_cost);
+            // The estimated proof size is an upper bound, which may only overcount the hiding evaluations.
+            assert!(estimate.storage_cost() >= storage_cost);
+            assert!(estimate.storage_cost() - storage_cost <= 3 * 32);
+            assert!(estimate.total_cost() >= total_cost);
+        }
+    }
+
+    #[test]
+    fn test_estimate_execution_fee_with_invalid_inputs() {
+        let rng = &mut TestRng::default();
+
+        // Prepare the VM.
+        let (vm, _) = prepare_vm(rng).unwrap();
+
+        // Ensure the number of inputs is checked.
+        let inputs = vec![Value::<CurrentNetwork>::from_str("1u64").unwrap()];
+        assert!(vm.estimate_execution_fee("credits.aleo", "transfer_public", inputs, rng).is_err());
+
+        // Ensure the input types are checked.
+        let inputs = vec![
+            Value::<CurrentNetwork>::from_str("1u64").unwrap(),
+            Value::<CurrentNetwork>::from_str("1u64").unwrap(),
+        ];
+        assert!(vm.estimate_execution_fee("credits.aleo", "transfer_public", inputs, rng).is_err());
+    }
+
     #[test]
     fn test_fee_private_transition_size() {
         let rng = &mut TestRng::default();
@@ -14,6 +14,8 @@
 
 use super::*;
 
+use std::collections::HashMap;
+
 impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     /// Speculates on the given list of transactions in the VM.
     ///
@@ -25,6 +27,8 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     ///   - If `coinbase_reward = Some(coinbase_reward)`, then the method will append a
     ///     `Ratify::BlockReward(block_reward)` and `Ratify::PuzzleReward(puzzle_reward)`
     ///     to the front of the `ratifications` list.
+    ///   - If the candidate transactions exceed the maximum number of confirmed transactions,
+    ///     they are speculated on in order of their priority fee (see `VM::order_candidate_transactions`).
     #[inline]
     pub fn speculate<'a>(
         &self,
@@ -36,6 +40,9 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     ) -> Result<(Ratifications<N>, Transactions<N>, Vec<N::TransactionID>, Vec<FinalizeOperation<N>>)> {
         let timer = timer!("VM::speculate");
 
+        // Order the candidate transactions.
+        let candidate_transactions = Self::order_candidate_transactions(candidate_transactions);
+
         // Performs a **dry-run** over the list of ratifications, solutions, and transactions.
         let (ratifications, confirmed_transactions, aborted_transactions, ratified_finalize_operations) = self
             .atomic_speculate(
@@ -43,7 +50,8 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                 coinbase_reward,
                 candidate_ratifications,
                 candidate_solutions,
-                candidate_transactions,
+                candidate_transactions.into_iter(),
+                None,
             )?;
 
         // Convert the aborted transactions into aborted transaction IDs.
@@ -85,7 +93,14 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
 
         // Performs a **dry-run** over the list of ratifications, solutions, and transactions.
         let (speculate_ratifications, confirmed_transactions, aborted_transactions, ratified_finalize_operations) =
-            self.atomic_speculate(state, None, candidate_ratifications, solutions, candidate_transactions.iter())?;
+            self.atomic_speculate(
+                state,
+                None,
+                candidate_ratifications,
+                solutions,
+                candidate_transactions.iter(),
+                None,
+            )?;
 
         // Ensure the ratifications after speculation match.
         if ratifications != &speculate_ratifications {
@@ -106,6 +121,80 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         Ok(ratified_finalize_operations)
     }
 
+    /// Replays the given block on top of the latest block, as a **dry-run**, and returns a report
+    /// with the trace of every finalize command evaluated for each transaction.
+    ///
+    /// Unlike `check_speculate`, this method does not fail on a mismatch. Instead, the report
+    /// compares the replayed transactions, ratifications, and finalize root against the block.
+    /// The VM must be at the height before the given block, and is left unchanged.
+    pub fn replay_block(&self, block: &Block<N>) -> Result<ReplayReport<N>> {
+        let timer = timer!("VM::replay_block");
+
+        // Ensure the block is the next block of the VM.
+        let height = block.height();
+        ensure!(height > 0, "Cannot replay the genesis block");
+        ensure!(
+            self.block_store().get_block_hash(height - 1)? == Some(block.previous_hash()),
+            "Cannot replay block {height}: its previous block is not the latest block"
+        );
+        ensure!(
+            self.block_store().get_block_hash(height)?.is_none(),
+            "Cannot replay block {height}: it already exists"
+        );
+
+        // Construct the finalize state.
+        let state = FinalizeGlobalState::new::<N>(
+            block.round(),
+            height,
+            block.cumulative_weight(),
+            block.cumulative_proof_target(),
+            block.previous_hash(),
+        )?;
+        // Reconstruct the candidate ratifications and unconfirmed transactions.
+        let candidate_ratifications = block.ratifications().iter().cloned().collect::<Vec<_>>();
+        let candidate_transactions = block
+            .transactions()
+            .iter()
+            .map(|confirmed| confirmed.to_unconfirmed_transaction())
+            .collect::<Result<Vec<_>>>()?;
+
+        // Performs a **dry-run** over the block, while tracing the finalize commands.
+        let mut events = Vec::new();
+        let (ratifications, confirmed_transactions, _, ratified_finalize_operations) = self.atomic_speculate(
+            state,
+            None,
+            candidate_ratifications,
+            block.solutions(),
+            candidate_transactions.iter(),
+            Some(&mut events),
+        )?;
+        lap!(timer, "Replay the block");
+
+        // Compare the replayed ratifications and finalize root against the block.
+        let is_ratifications_match = &ratifications == block.ratifications();
+        let replayed_transactions = confirmed_transactions.iter().cloned().collect::<Transactions<N>>();
+        let is_finalize_root_match =
+            replayed_transactions.to_finalize_root(ratified_finalize_operations)? == block.finalize_root();
+
+        // Match each transaction in the block with its replay, and its trace.
+        let mut replayed = confirmed_transactions
+            .into_iter()
+            .map(|confirmed| Ok((confirmed.to_unconfirmed_transaction_id()?, confirmed)))
+            .collect::<Result<HashMap<_, _>>>()?;
+        let traces = split_finalize_trace(&candidate_transactions, events);
+        let transactions = block
+            .transactions()
+            .iter()
+            .zip(candidate_transactions.iter().zip(traces))
+            .map(|(expected, (unconfirmed, trace))| {
+                TransactionReplay::new(expected.clone(), replayed.remove(&unconfirmed.id()), trace)
+            })
+            .collect();
+        finish!(timer, "Compare the replay against the block");
+
+        Ok(ReplayReport::new(height, block.hash(), transactions, is_ratifications_match, is_finalize_root_match))
+    }
+
     /// Finalizes the given transactions into the VM.
     ///
     /// Returns the finalize operations from pre-ratify and post-ratify.
@@ -136,16 +225,36 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     #[cfg(any(test, feature = "test"))]
     pub const MAXIMUM_CONFIRMED_TRANSACTIONS: usize = 8;
 
+    /// Returns the candidate transactions in the order in which they are speculated on.
+    ///
+    /// If the candidate transactions exceed the maximum number of confirmed transactions, then they are
+    /// ordered by priority fee, from highest to lowest. Transactions with the same priority fee keep their
+    /// candidate order, so that the ordering is deterministic, and ties are resolved first-come, first-served.
+    /// Otherwise, the candidate order is kept as is.
+    fn order_candidate_transactions<'a>(
+        transactions: impl ExactSizeIterator<Item = &'a Transaction<N>>,
+    ) -> Vec<&'a Transaction<N>> {
+        let mut transactions = transactions.collect::<Vec<_>>();
+        if transactions.len() > Self::MAXIMUM_CONFIRMED_TRANSACTIONS {
+            // Note: The sort is stable, and a transaction with a malformed fee is given the lowest priority.
+            transactions.sort_by_cached_key(|transaction| {
+                core::cmp::Reverse(transaction.priority_fee_amount().map_or(0, |priority_fee| *priority_fee))
+            });
+        }
+        transactions
+    }
+
     /// Performs atomic speculation over a list of transactions.
     ///
     /// Returns the ratifications, confirmed transactions, aborted transactions,
     /// and finalize operations from pre-ratify and post-ratify.
     ///
-    /// Note: This method is used by `VM::speculate` and `VM::check_speculate`.
+    /// Note: This method is used by `VM::speculate`, `VM::check_speculate`, and `VM::replay_block`.
     ///   - If `coinbase_reward = None`, then the `ratifications` will not be modified.
     ///   - If `coinbase_reward = Some(coinbase_reward)`, then the method will append a
     ///     `Ratify::BlockReward(block_reward)` and `Ratify::PuzzleReward(puzzle_reward)`
     ///     to the front of the `ratifications` list.
+    ///   - If a `trace` is given, every evaluated finalize command is recorded in it.
     fn atomic_speculate<'a>(
         &self,
         state: FinalizeGlobalState,
@@ -153,6 +262,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         ratifications: Vec<Ratify<N>>,
         solutions: Option<&CoinbaseSolution<N>>,
         transactions: impl ExactSizeIterator<Item = &'a Transaction<N>>,
+        mut trace: Option<&mut Vec<FinalizeTraceEvent<N>>>,
     ) -> Result<(
         Ratifications<N>,
         Vec<ConfirmedTransaction<N>>,
@@ -178,12 +288,12 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             // Initialize an iterator for ratifications before finalize.
             let pre_ratifications = ratifications.iter().filter(|r| match r {
                 Ratify::Genesis(_, _) => true,
-                Ratify::BlockReward(..) | Ratify::PuzzleReward(..) => false,
+                Ratify::BlockReward(..) | Ratify::PuzzleReward(..) | Ratify::Slash(..) => false,
             });
             // Initialize an iterator for ratifications after finalize.
             let post_ratifications = ratifications.iter().filter(|r| match r {
                 Ratify::Genesis(_, _) => false,
-                Ratify::BlockReward(..) | Ratify::PuzzleReward(..) => true,
+                Ratify::BlockReward(..) | Ratify::PuzzleReward(..) | Ratify::Slash(..) => true,
             });
 
             // Initialize a list of finalize operations.
@@ -233,7 +343,13 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                     // The finalize operation here involves appending the 'stack',
                     // and adding the program to the finalize tree.
                     Transaction::Deploy(_, program_owner, deployment, fee) => {
-                        match process.finalize_deployment(state, store, deployment, fee) {
+                        match process.finalize_deployment_with_trace(
+                            state,
+                            store,
+                            deployment,
+                            fee,
+                            trace.as_deref_mut(),
+                        ) {
                             // Construct the accepted deploy transaction.
                             Ok((_, finalize)) => {
                                 ConfirmedTransaction::accepted_deploy(counter, transaction.clone(), finalize)
@@ -242,9 +358,9 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                             // Construct the rejected deploy transaction.
                             Err(_error) => {
                                 // Finalize the fee, to ensure it is valid.
-                                match process.finalize_fee(state, store, fee).and_then(|finalize| {
-                                    Transaction::from_fee(fee.clone()).map(|fee_tx| (fee_tx, finalize))
-                                }) {
+                                match process.finalize_fee_with_trace(state, store, fee, trace.as_deref_mut()).and_then(
+                                    |finalize| Transaction::from_fee(fee.clone()).map(|fee_tx| (fee_tx, finalize)),
+                                ) {
                                     Ok((fee_tx, finalize)) => {
                                         // Construct the rejected deployment.
                                         let rejected = Rejected::new_deployment(*program_owner, *deployment.clone());
@@ -268,7 +384,13 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                     // The finalize operation here involves calling 'update_key_value',
                     // and update the respective leaves of the finalize tree.
                     Transaction::Execute(_, execution, fee) => {
-                        match process.finalize_execution(state, store, execution, fee.as_ref()) {
+                        match process.finalize_execution_with_trace(
+                            state,
+                            store,
+                            execution,
+                            fee.as_ref(),
+                            trace.as_deref_mut(),
+                        ) {
                             // Construct the accepted execute transaction.
                             Ok(finalize) => {
                                 ConfirmedTransaction::accepted_execute(counter, transaction.clone(), finalize)
@@ -278,9 +400,11 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                             Err(_error) => match fee {
                                 // Finalize the fee, to ensure it is valid.
                                 Some(fee) => {
-                                    match process.finalize_fee(state, store, fee).and_then(|finalize| {
-                                        Transaction::from_fee(fee.clone()).map(|fee_tx| (fee_tx, finalize))
-                                    }) {
+                                    match process
+                                        .finalize_fee_with_trace(state, store, fee, trace.as_deref_mut())
+                                        .and_then(|finalize| {
+                                            Transaction::from_fee(fee.clone()).map(|fee_tx| (fee_tx, finalize))
+                                        }) {
                                         Ok((fee_tx, finalize)) => {
                                             // Construct the rejected execution.
                                             let rejected = Rejected::new_execution(execution.clone());
@@ -410,12 +534,12 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             // Initialize an iterator for ratifications before finalize.
             let pre_ratifications = ratifications.iter().filter(|r| match r {
                 Ratify::Genesis(_, _) => true,
-                Ratify::BlockReward(..) | Ratify::PuzzleReward(..) => false,
+                Ratify::BlockReward(..) | Ratify::PuzzleReward(..) | Ratify::Slash(..) => false,
             });
             // Initialize an iterator for ratifications after finalize.
             let post_ratifications = ratifications.iter().filter(|r| match r {
                 Ratify::Genesis(_, _) => false,
-                Ratify::BlockReward(..) | Ratify::PuzzleReward(..) => true,
+                Ratify::BlockReward(..) | Ratify::PuzzleReward(..) | Ratify::Slash(..) => true,
             });
 
             // Initialize a list of finalize operations.
@@ -614,6 +738,14 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                 Err(e) => return Err(format!("Failed to post-ratify - {e}")),
             }
 
+            // Store the ratified finalize operations, so that the finalize root of the block can be recomputed.
+            store
+                .set_ratified_operations(state.block_height(), &ratified_finalize_operations)
+                .map_err(|e| format!("Failed to store the ratified finalize operations - {e}"))?;
+
+            // Clear the commit marker, so that it is committed in the same atomic batch as the finalize operations.
+            store.end_commit().map_err(|e| format!("Failed to clear the commit marker - {e}"))?;
+
             /* Start the commit process. */
 
             // Commit all of the stacks to the process.
@@ -642,6 +774,8 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         let bonded_mapping = Identifier::from_str("bonded")?;
         // Construct the account mapping name.
         let account_mapping = Identifier::from_str("account")?;
+        // Construct the commission mapping name.
+        let commission_mapping = Identifier::from_str("commission")?;
 
         // Initialize a list of finalize operations.
         let mut finalize_operations = Vec::new();
@@ -678,7 +812,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                     // Initialize the stakers.
                     let mut stakers = IndexMap::with_capacity(committee.members().len());
                     // Iterate over the committee members.
-                    for (validator, (microcredits, _)) in committee.members() {
+                    for (validator, (microcredits, _, _)) in committee.members() {
                         // Insert the validator into the stakers.
                         stakers.insert(*validator, (*validator, *microcredits));
                     }
@@ -696,6 +830,18 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                         // Replace the bonded mapping in storage.
                         store.replace_mapping(program_id, bonded_mapping, next_bonded_map)?,
                     ]);
+                    // Store the commissions of the genesis committee.
+                    // Note: A commission of 0 is not stored, as it is the default for validators without an entry.
+                    for (validator, (_, _, commission)) in committee.members() {
+                        if *commission > 0 {
+                            finalize_operations.push(store.update_key_value(
+                                program_id,
+                                commission_mapping,
+                                Plaintext::from(Literal::Address(*validator)),
+                                Value::from(Literal::U8(U8::new(*commission))),
+                            )?);
+                        }
+                    }
 
                     // Iterate over the public balances.
                     for (address, amount) in public_balances {
@@ -719,7 +865,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                     // Set the genesis ratification flag.
                     is_genesis_ratified = true;
                 }
-                Ratify::BlockReward(..) | Ratify::PuzzleReward(..) => continue,
+                Ratify::BlockReward(..) | Ratify::PuzzleReward(..) | Ratify::Slash(..) => continue,
             }
         }
 
@@ -743,6 +889,14 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         let bonded_mapping = Identifier::from_str("bonded")?;
         // Construct the account mapping name.
         let account_mapping = Identifier::from_str("account")?;
+        // Construct the slashed mapping name.
+        let slashed_mapping = Identifier::from_str("slashed")?;
+        // Construct the commission mapping name.
+        let commission_mapping = Identifier::from_str("commission")?;
+        // Construct the unbonding mapping name, along with the identifiers of the unbond state.
+        let unbonding_mapping = Identifier::from_str("unbonding")?;
+        let microcredits_identifier = Identifier::from_str("microcredits")?;
+        let height_identifier = Identifier::from_str("height")?;
 
         // Initialize a list of finalize operations.
         let mut finalize_operations = Vec::new();
@@ -760,10 +914,15 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                     // Ensure the block reward has not been ratified yet.
                     ensure!(!is_block_reward_ratified, "Ratify::BlockReward(..) has already been ratified");
 
-                    // Retrieve the committee mapping from storage.
+                    // Retrieve the committee mapping and commission mapping from storage.
                     let current_committee_map = store.get_mapping_speculative(program_id, committee_mapping)?;
+                    let current_commission_map = store.get_mapping_speculative(program_id, commission_mapping)?;
                     // Convert the committee mapping into a committee.
-                    let current_committee = committee_map_into_committee(state.block_round(), current_committee_map)?;
+                    let current_committee = committee_map_into_committee(
+                        state.block_round(),
+                        current_committee_map,
+                        current_commission_map,
+                    )?;
                     // Retrieve the bonded mapping from storage.
                     let current_bonded_map = store.get_mapping_speculative(program_id, bonded_mapping)?;
                     // Convert the bonded map into stakers.
@@ -832,6 +991,108 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                     // Set the puzzle reward ratification flag.
                     is_puzzle_reward_ratified = true;
                 }
+                Ratify::Slash(evidence) => {
+                    // Construct the key, from the evidence ID.
+                    let key = Plaintext::from(Literal::Field(evidence.to_id()?));
+                    // Ensure the offense has not been slashed yet.
+                    ensure!(
+                        store.get_value_speculative(program_id, slashed_mapping, &key)?.is_none(),
+                        "Ratify::Slash(..) has already been ratified for '{}' in round {}",
+                        evidence.author(),
+                        evidence.round()
+                    );
+
+                    // Ensure the evidence is not from a future round, and is recent enough to be slashed.
+                    ensure!(
+                        evidence.round() <= state.block_round(),
+                        "Ratify::Slash(..) contains evidence from a future round {}",
+                        evidence.round()
+                    );
+                    ensure!(
+                        state.block_round() - evidence.round() <= MAX_EVIDENCE_AGE_IN_ROUNDS,
+                        "Ratify::Slash(..) contains evidence from round {}, which is too old to be slashed",
+                        evidence.round()
+                    );
+                    // Retrieve the committee for the round of the evidence.
+                    let Some(evidence_committee) = store.committee_store().get_committee_for_round(evidence.round())?
+                    else {
+                        bail!("Ratify::Slash(..) is missing the committee for round {}", evidence.round())
+                    };
+                    // Ensure the evidence is valid for the committee of its round.
+                    evidence.verify(&evidence_committee)?;
+
+                    // Retrieve the committee mapping and commission mapping from storage.
+                    let current_committee_map = store.get_mapping_speculative(program_id, committee_mapping)?;
+                    let current_commission_map = store.get_mapping_speculative(program_id, commission_mapping)?;
+                    // Convert the committee mapping into a committee.
+                    let current_committee = committee_map_into_committee(
+                        state.block_round(),
+                        current_committee_map,
+                        current_commission_map,
+                    )?;
+
+                    // Retrieve the bonded mapping from storage.
+                    let current_bonded_map = store.get_mapping_speculative(program_id, bonded_mapping)?;
+                    // Convert the bonded map into stakers.
+                    let current_stakers = bonded_map_into_stakers(current_bonded_map)?;
+                    // Ensure the committee matches the bonded mapping.
+                    ensure_stakers_matches(&current_committee, &current_stakers)?;
+
+                    // Compute the updated stakers, by slashing the validator.
+                    let (next_stakers, amount, unbonded) =
+                        slash_stakers(&current_stakers, &current_committee, evidence.author())?;
+                    // Compute the updated committee, using the stakers.
+                    let next_committee = to_next_committee(&current_committee, state.block_round(), &next_stakers)?;
+                    // Construct the next committee map and next bonded map.
+                    let (next_committee_map, next_bonded_map) =
+                        to_next_commitee_map_and_bonded_map(&next_committee, &next_stakers);
+
+                    // Store the finalize operations for updating the committee, bonded, and slashed mapping.
+                    // Note: The committee store is updated from these mappings by the next block reward ratification.
+                    finalize_operations.extend(&[
+                        // Replace the committee mapping in storage.
+                        store.replace_mapping(program_id, committee_mapping, next_committee_map)?,
+                        // Replace the bonded mapping in storage.
+                        store.replace_mapping(program_id, bonded_mapping, next_bonded_map)?,
+                        // Record the slashed amount for the offense in storage.
+                        store.update_key_value(
+                            program_id,
+                            slashed_mapping,
+                            key,
+                            Value::from(Literal::U64(U64::new(amount))),
+                        )?,
+                    ]);
+
+                    // If the validator was removed from the committee, unbond the remaining stakers.
+                    let claimable_height = state.block_height().saturating_add(UNBONDING_PERIOD_IN_BLOCKS);
+                    for (staker, microcredits) in unbonded {
+                        // Construct the key.
+                        let key = Plaintext::from(Literal::Address(staker));
+                        // Retrieve the current unbonding microcredits.
+                        let current_microcredits =
+                            match store.get_value_speculative(program_id, unbonding_mapping, &key)? {
+                                Some(Value::Plaintext(Plaintext::Struct(unbond_state, _))) => {
+                                    match unbond_state.get(&microcredits_identifier) {
+                                        Some(Plaintext::Literal(Literal::U64(microcredits), _)) => **microcredits,
+                                        _ => bail!("Critical bug in post-ratify slash - Invalid unbond state"),
+                                    }
+                                }
+                                None => 0,
+                                v => bail!("Critical bug in post-ratify slash - Invalid unbond state ({v:?})"),
+                            };
+                        // Construct the next unbond state.
+                        // Note: As in `unbond_public`, the claimable height is reset for the entire unbonding amount.
+                        let next_state = indexmap::indexmap! {
+                            microcredits_identifier => Plaintext::from(Literal::U64(U64::new(
+                                current_microcredits.saturating_add(microcredits),
+                            ))),
+                            height_identifier => Plaintext::from(Literal::U32(U32::new(claimable_height))),
+                        };
+                        // Update the unbond state in finalize storage.
+                        let value = Value::Plaintext(Plaintext::Struct(next_state, Default::default()));
+                        finalize_operations.push(store.update_key_value(program_id, unbonding_mapping, key, value)?);
+                    }
+                }
             }
         }
 
@@ -1034,6 +1295,30 @@ finalize transfer_public:
         inputs: Vec<Value<CurrentNetwork>>,
         unspent_records: &mut Vec<Record<CurrentNetwork, Ciphertext<CurrentNetwork>>>,
         rng: &mut TestRng,
+    ) -> Transaction<CurrentNetwork> {
+        create_execution_with_priority_fee(
+            vm,
+            caller_private_key,
+            program_id,
+            function_name,
+            inputs,
+            1,
+            unspent_records,
+            rng,
+        )
+    }
+
+    /// Create an execution transaction with the given priority fee.
+    #[allow(clippy::too_many_arguments)]
+    fn create_execution_with_priority_fee(
+        vm: &VM<CurrentNetwork, ConsensusMemory<CurrentNetwork>>,
+        caller_private_key: PrivateKey<CurrentNetwork>,
+        program_id: &str,
+        function_name: &str,
+        inputs: Vec<Value<CurrentNetwork>>,
+        priority_fee_in_microcredits: u64,
+        unspent_records: &mut Vec<Record<CurrentNetwork, Ciphertext<CurrentNetwork>>>,
+        rng: &mut TestRng,
     ) -> Transaction<CurrentNetwork> {
         assert!(vm.contains_program(&ProgramID::from_str(program_id).unwrap()));
 
@@ -1043,7 +1328,15 @@ finalize transfer_public:
 
         // Execute.
         let transaction = vm
-            .execute(&caller_private_key, (program_id, function_name), inputs.into_iter(), credits, 1, None, rng)
+            .execute(
+                &caller_private_key,
+                (program_id, function_name),
+                inputs.into_iter(),
+                credits,
+                priority_fee_in_microcredits,
+                None,
+                rng,
+            )
             .unwrap();
         // Verify.
         vm.check_transaction(&transaction, None, rng).unwrap();
@@ -1141,8 +1434,9 @@ finalize transfer_public:
         assert!(vm.contains_program(&program_id));
 
         // Ensure the dry run of the redeployment will cause a reject transaction to be created.
-        let (_, candidate_transactions, aborted_transaction_ids, _) =
-            vm.atomic_speculate(sample_finalize_state(1), None, vec![], None, [deployment_transaction].iter()).unwrap();
+        let (_, candidate_transactions, aborted_transaction_ids, _) = vm
+            .atomic_speculate(sample_finalize_state(1), None, vec![], None, [deployment_transaction].iter(), None)
+            .unwrap();
         assert_eq!(candidate_transactions.len(), 1);
         assert!(matches!(candidate_transactions[0], ConfirmedTransaction::RejectedDeploy(..)));
         assert!(aborted_transaction_ids.is_empty());
@@ -1244,7 +1538,7 @@ finalize transfer_public:
         {
             let transactions = [mint_10.clone(), transfer_10.clone(), transfer_20.clone()];
             let (_, confirmed_transactions, aborted_transaction_ids, _) =
-                vm.atomic_speculate(sample_finalize_state(1), None, vec![], None, transactions.iter()).unwrap();
+                vm.atomic_speculate(sample_finalize_state(1), None, vec![], None, transactions.iter(), None).unwrap();
 
             // Assert that all the transactions are accepted.
             assert_eq!(confirmed_transactions.len(), 3);
@@ -1264,7 +1558,7 @@ finalize transfer_public:
         {
             let transactions = [transfer_20.clone(), mint_10.clone(), mint_20.clone(), transfer_30.clone()];
             let (_, confirmed_transactions, aborted_transaction_ids, _) =
-                vm.atomic_speculate(sample_finalize_state(1), None, vec![], None, transactions.iter()).unwrap();
+                vm.atomic_speculate(sample_finalize_state(1), None, vec![], None, transactions.iter(), None).unwrap();
 
             // Assert that all the transactions are accepted.
             assert_eq!(confirmed_transactions.len(), 4);
@@ -1284,7 +1578,7 @@ finalize transfer_public:
         {
             let transactions = [transfer_20.clone(), transfer_10.clone()];
             let (_, confirmed_transactions, aborted_transaction_ids, _) =
-                vm.atomic_speculate(sample_finalize_state(1), None, vec![], None, transactions.iter()).unwrap();
+                vm.atomic_speculate(sample_finalize_state(1), None, vec![], None, transactions.iter(), None).unwrap();
 
             // Assert that the accepted and rejected transactions are correct.
             assert_eq!(confirmed_transactions.len(), 2);
@@ -1308,7 +1602,7 @@ finalize transfer_public:
         {
             let transactions = [mint_20.clone(), transfer_30.clone(), transfer_20.clone(), transfer_10.clone()];
             let (_, confirmed_transactions, aborted_transaction_ids, _) =
-                vm.atomic_speculate(sample_finalize_state(1), None, vec![], None, transactions.iter()).unwrap();
+                vm.atomic_speculate(sample_finalize_state(1), None, vec![], None, transactions.iter(), None).unwrap();
 
             // Assert that the accepted and rejected transactions are correct.
             assert_eq!(confirmed_transactions.len(), 4);
@@ -1631,4 +1925,188 @@ finalize compute:
             VM::<CurrentNetwork, ConsensusMemory<_>>::MAXIMUM_CONFIRMED_TRANSACTIONS
         );
     }
+
+    #[test]
+    fn test_excess_transactions_are_ordered_by_priority_fee() {
+        let rng = &mut TestRng::default();
+
+        // Sample a private key.
+        let caller_private_key = test_helpers::sample_genesis_private_key(rng);
+        let caller_address = Address::try_from(&caller_private_key).unwrap();
+
+        // Initialize the vm.
+        let vm = test_helpers::sample_vm_with_genesis_block(rng);
+
+        // Deploy a new program.
+        let genesis =
+            vm.block_store().get_block(&vm.block_store().get_block_hash(0).unwrap().unwrap()).unwrap().unwrap();
+
+        // Get the unspent records.
+        let mut unspent_records = genesis
+            .transitions()
+            .cloned()
+            .flat_map(Transition::into_records)
+            .map(|(_, record)| record)
+            .collect::<Vec<_>>();
+
+        // Construct the deployment block.
+        let (program_id, deployment_block) =
+            new_program_deployment(&vm, &caller_private_key, &genesis, &mut unspent_records, rng).unwrap();
+
+        // Add the deployment block to the VM.
+        vm.add_next_block(&deployment_block).unwrap();
+
+        // Generate more records to use for the next block.
+        let mut previous_block = deployment_block;
+        for _ in 0..3 {
+            let splits_block =
+                generate_splits(&vm, &caller_private_key, &previous_block, &mut unspent_records, rng).unwrap();
+            vm.add_next_block(&splits_block).unwrap();
+            previous_block = splits_block;
+        }
+
+        // Generate the transactions, with priority fees that exceed the block limit by 2 transactions.
+        let priority_fees = [1, 5, 3, 5, 2, 1, 4, 1, 6, 1];
+        assert_eq!(priority_fees.len(), VM::<CurrentNetwork, ConsensusMemory<_>>::MAXIMUM_CONFIRMED_TRANSACTIONS + 2);
+
+        let transactions = priority_fees
+            .iter()
+            .map(|priority_fee| {
+                let inputs = vec![
+                    Value::<CurrentNetwork>::from_str(&caller_address.to_string()).unwrap(),
+                    Value::<CurrentNetwork>::from_str("10u64").unwrap(),
+                ];
+                create_execution_with_priority_fee(
+                    &vm,
+                    caller_private_key,
+                    &program_id,
+                    "mint_public",
+                    inputs,
+                    *priority_fee,
+                    &mut unspent_records,
+                    rng,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // Within the block limit, the candidate order is kept.
+        let within_limit = &transactions[..VM::<CurrentNetwork, ConsensusMemory<_>>::MAXIMUM_CONFIRMED_TRANSACTIONS];
+        let ordered = VM::<CurrentNetwork, ConsensusMemory<_>>::order_candidate_transactions(within_limit.iter());
+        assert_eq!(
+            ordered.iter().map(|tx| tx.id()).collect::<Vec<_>>(),
+            within_limit.iter().map(|tx| tx.id()).collect::<Vec<_>>()
+        );
+
+        // Beyond the block limit, the highest priority fees are confirmed first, and ties are first-come, first-served.
+        let expected_confirmed = [8, 1, 3, 6, 2, 4, 0, 5].map(|index| transactions[index].id());
+        let expected_aborted = [7, 9].map(|index| transactions[index].id());
+
+        // Construct the next block.
+        let next_block =
+            sample_next_block(&vm, &caller_private_key, &transactions, &previous_block, &mut unspent_records, rng)
+                .unwrap();
+
+        // Ensure the transactions were ordered by priority fee.
+        assert_eq!(next_block.transactions().transaction_ids().copied().collect::<Vec<_>>(), expected_confirmed);
+        assert_eq!(next_block.aborted_transaction_ids(), &expected_aborted);
+
+        // Ensure the ordering does not depend on the candidate order of transactions with distinct priority fees.
+        let mut shuffled = transactions.clone();
+        shuffled.swap(2, 6);
+        let ordered = VM::<CurrentNetwork, ConsensusMemory<_>>::order_candidate_transactions(shuffled.iter());
+        assert_eq!(ordered.iter().map(|tx| tx.id()).collect::<Vec<_>>()[..8], expected_confirmed);
+    }
+
+    #[test]
+    fn test_ratify_slash() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the VM.
+        let vm = crate::vm::test_helpers::sample_vm_with_genesis_block(rng);
+        // Retrieve the genesis private key, which belongs to a committee member.
+        let private_key = crate::vm::test_helpers::sample_genesis_private_key(rng);
+        let address = Address::try_from(&private_key).unwrap();
+
+        // Sample the equivocation evidence.
+        let evidence =
+            ledger_narwhal_evidence::test_helpers::sample_equivocation_evidence_for_round(&private_key, 1, rng);
+        let key = Plaintext::from(Literal::Field(evidence.to_id().unwrap()));
+        let slash = Ratify::Slash(Box::new(evidence));
+
+        // Ensure the slash is ratified.
+        let (ratifications, _, _, _) =
+            vm.speculate(sample_finalize_state(1), None, vec![slash.clone()], None, [].iter()).unwrap();
+        assert!(ratifications.iter().any(|ratify| ratify == &slash));
+
+        // Ensure the same offense cannot be slashed twice.
+        assert!(
+            vm.speculate(sample_finalize_state(1), None, vec![slash.clone(), slash.clone()], None, [].iter()).is_err()
+        );
+
+        // Ensure evidence from a validator that is not in the committee is rejected.
+        let evidence = ledger_narwhal_evidence::test_helpers::sample_equivocation_evidence(rng);
+        let invalid = Ratify::Slash(Box::new(evidence));
+        assert!(vm.speculate(sample_finalize_state(1), None, vec![invalid], None, [].iter()).is_err());
+        // Ensure evidence from a future round is rejected.
+        assert!(vm.speculate(sample_finalize_state(0), None, vec![slash.clone()], None, [].iter()).is_err());
+        // Ensure evidence that is too old is rejected.
+        let height = 2 + MAX_EVIDENCE_AGE_IN_ROUNDS as u32;
+        assert!(vm.speculate(sample_finalize_state(height), None, vec![slash.clone()], None, [].iter()).is_err());
+
+        // Retrieve the stakes before the slash.
+        let store = vm.finalize_store();
+        let program_id = ProgramID::from_str("credits.aleo").unwrap();
+        let get_mapping = |name: &str| store.get_mapping_confirmed(program_id, Identifier::from_str(name).unwrap());
+        let committee =
+            committee_map_into_committee(0, get_mapping("committee").unwrap(), get_mapping("commission").unwrap())
+                .unwrap();
+        let stakers = bonded_map_into_stakers(get_mapping("bonded").unwrap()).unwrap();
+        let (_, bond) = stakers[&address];
+        // Note: The genesis validators are bonded with the minimum stake, so the slash removes the validator.
+        assert_eq!(bond, ledger_committee::MIN_VALIDATOR_STAKE);
+        let amount = bond * SLASH_PERCENTAGE / 100;
+
+        // Ratify the slash.
+        VM::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::atomic_post_ratify(
+            store,
+            sample_finalize_state(1),
+            [slash].iter(),
+            None,
+        )
+        .unwrap();
+
+        // Ensure the full amount is slashed, and recorded for the offense.
+        let slashed = store.get_value_confirmed(program_id, Identifier::from_str("slashed").unwrap(), &key).unwrap();
+        assert_eq!(slashed, Some(Value::from(Literal::U64(U64::new(amount)))));
+
+        // Ensure the validator is removed from the committee and the bonded mapping.
+        let next_committee =
+            committee_map_into_committee(1, get_mapping("committee").unwrap(), get_mapping("commission").unwrap())
+                .unwrap();
+        let next_stakers = bonded_map_into_stakers(get_mapping("bonded").unwrap()).unwrap();
+        assert!(!next_committee.is_committee_member(address));
+        assert!(!next_stakers.contains_key(&address));
+        // Ensure the committee stake and bonded stake dropped by the stake of the validator.
+        let total_bonded = |stakers: &IndexMap<Address<CurrentNetwork>, (Address<CurrentNetwork>, u64)>| {
+            stakers.values().map(|(_, microcredits)| microcredits).sum::<u64>()
+        };
+        assert_eq!(next_committee.total_stake(), committee.total_stake() - committee.get_stake(address));
+        assert_eq!(total_bonded(&next_stakers), total_bonded(&stakers) - bond);
+
+        // Ensure the remaining bond of the validator is unbonding.
+        let unbond_state = store
+            .get_value_confirmed(
+                program_id,
+                Identifier::from_str("unbonding").unwrap(),
+                &Plaintext::from(Literal::Address(address)),
+            )
+            .unwrap();
+        let expected = Value::from_str(&format!(
+            "{{ microcredits: {}u64, height: {}u32 }}",
+            bond - amount,
+            1 + UNBONDING_PERIOD_IN_BLOCKS
+        ))
+        .unwrap();
+        assert_eq!(unbond_state, Some(expected));
+    }
 }
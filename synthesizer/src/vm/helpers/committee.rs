@@ -30,15 +30,34 @@ use std::str::FromStr;
 #[cfg(not(feature = "serial"))]
 use rayon::prelude::*;
 
-/// Returns the committee given the committee map from finalize storage.
+/// Returns the committee given the committee map and commission map from finalize storage.
+/// Note: A validator without an entry in the commission map charges a commission of 0.
 pub fn committee_map_into_committee<N: Network>(
     starting_round: u64,
     committee_map: Vec<(Plaintext<N>, Value<N>)>,
+    commission_map: Vec<(Plaintext<N>, Value<N>)>,
 ) -> Result<Committee<N>> {
     // Prepare the identifiers.
     let microcredits_identifier = Identifier::from_str("microcredits")?;
     let is_open_identifier = Identifier::from_str("is_open")?;
 
+    // Extract the commissions.
+    let commissions = commission_map
+        .iter()
+        .map(|(key, value)| {
+            // Extract the address from the key.
+            let address = match key {
+                Plaintext::Literal(Literal::Address(address), _) => address,
+                _ => bail!("Invalid commission key (missing address) - {key}"),
+            };
+            // Extract the commission from the value.
+            match value {
+                Value::Plaintext(Plaintext::Literal(Literal::U8(commission), _)) => Ok((*address, **commission)),
+                _ => bail!("Invalid commission value (missing u8) - {value}"),
+            }
+        })
+        .collect::<Result<IndexMap<_, _>>>()?;
+
     // Extract the committee members.
     let committee_members = committee_map
         .iter()
@@ -61,8 +80,10 @@ pub fn committee_map_into_committee<N: Network>(
                         Some(Plaintext::Literal(Literal::Boolean(is_open), _)) => **is_open,
                         _ => bail!("Invalid committee state (missing boolean) - {value}"),
                     };
+                    // Retrieve the commission, which is 0 if it was never set.
+                    let commission = commissions.get(address).copied().unwrap_or(0);
                     // Return the committee state.
-                    Ok((*address, (microcredits, is_open)))
+                    Ok((*address, (microcredits, is_open, commission)))
                 }
                 _ => bail!("Invalid committee value (missing struct) - {value}"),
             }
@@ -143,7 +164,7 @@ pub fn ensure_stakers_matches<N: Network>(
     ensure!(committee.total_stake() == total_microcredits, "Committee and validator map total stake do not match");
 
     // Iterate over the committee and ensure the committee and validators match.
-    for (validator, (microcredits, _)) in committee.members() {
+    for (validator, (microcredits, _, _)) in committee.members() {
         let candidate_microcredits = validator_map.get(validator);
         ensure!(candidate_microcredits.is_some(), "A validator is missing in finalize storage");
         ensure!(
@@ -178,7 +199,14 @@ pub fn to_next_committee<N: Network>(
     let mut members = IndexMap::with_capacity(validator_map.len());
     // Iterate over the validators.
     for (validator, microcredits) in validator_map {
-        members.insert(validator, (microcredits, current_committee.is_committee_member_open(validator)));
+        members.insert(
+            validator,
+            (
+                microcredits,
+                current_committee.is_committee_member_open(validator),
+                current_committee.get_commission(validator),
+            ),
+        );
     }
     // Return the next committee.
     Committee::new(next_round, members)
@@ -195,8 +223,9 @@ pub fn to_next_commitee_map_and_bonded_map<N: Network>(
     let is_open_identifier = Identifier::from_str("is_open").expect("Failed to parse 'is_open'");
 
     // Construct the committee map.
+    // Note: The commissions are stored in a separate mapping, which is not derived from the committee.
     let committee_map = cfg_iter!(next_committee.members())
-        .map(|(validator, (microcredits, is_open))| {
+        .map(|(validator, (microcredits, is_open, _))| {
             // Construct the committee state.
             let committee_state = indexmap! {
                 microcredits_identifier => Plaintext::from(Literal::U64(U64::new(*microcredits))),
@@ -237,15 +266,15 @@ pub(crate) mod test_helpers {
 
     use rand::{CryptoRng, Rng};
 
-    /// Returns the stakers, given the map of `(validator, (microcredits, is_open))` entries.
+    /// Returns the stakers, given the map of `(validator, (microcredits, is_open, commission))` entries.
     /// This method simulates the existence of delegators for the members.
     pub(crate) fn to_stakers<N: Network, R: Rng + CryptoRng>(
-        members: &IndexMap<Address<N>, (u64, bool)>,
+        members: &IndexMap<Address<N>, (u64, bool, u8)>,
         rng: &mut R,
     ) -> IndexMap<Address<N>, (Address<N>, u64)> {
         members
             .into_iter()
-            .flat_map(|(validator, (microcredits, _))| {
+            .flat_map(|(validator, (microcredits, _, _))| {
                 // Keep a tally of the remaining microcredits.
                 let remaining_microcredits = microcredits.saturating_sub(MIN_VALIDATOR_STAKE);
                 // Set the staker amount to 10 credit.
@@ -283,17 +312,17 @@ pub(crate) mod test_helpers {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use console::prelude::TestRng;
+    use console::{prelude::TestRng, types::U8};
 
     #[allow(unused_imports)]
     use rayon::prelude::*;
     use std::str::FromStr;
 
-    /// Returns the committee map, given the map of `(validator, (microcredits, is_open))` entries.
-    fn to_committee_map<N: Network>(members: &IndexMap<Address<N>, (u64, bool)>) -> Vec<(Plaintext<N>, Value<N>)> {
+    /// Returns the committee map, given the map of `(validator, (microcredits, is_open, commission))` entries.
+    fn to_committee_map<N: Network>(members: &IndexMap<Address<N>, (u64, bool, u8)>) -> Vec<(Plaintext<N>, Value<N>)> {
         members
             .par_iter()
-            .map(|(validator, (microcredits, is_open))| {
+            .map(|(validator, (microcredits, is_open, _))| {
                 let microcredits = U64::<N>::new(*microcredits);
                 let is_open = Boolean::<N>::new(*is_open);
                 (
@@ -304,6 +333,18 @@ mod tests {
             .collect()
     }
 
+    /// Returns the commission map, given the map of `(validator, (microcredits, is_open, commission))` entries.
+    /// Note: Validators with a commission of 0 are omitted, as they would be if they never set a commission.
+    fn to_commission_map<N: Network>(members: &IndexMap<Address<N>, (u64, bool, u8)>) -> Vec<(Plaintext<N>, Value<N>)> {
+        members
+            .iter()
+            .filter(|(_, (_, _, commission))| *commission > 0)
+            .map(|(validator, (_, _, commission))| {
+                (Plaintext::from(Literal::Address(*validator)), Value::from(Literal::U8(U8::new(*commission))))
+            })
+            .collect()
+    }
+
     /// Returns the bonded map, given the staker, validator and microcredits.
     fn to_bonded_map<N: Network>(stakers: &IndexMap<Address<N>, (Address<N>, u64)>) -> Vec<(Plaintext<N>, Value<N>)> {
         // Prepare the identifiers.
@@ -334,15 +375,24 @@ mod tests {
         // Sample a committee.
         let committee = ledger_committee::test_helpers::sample_committee_for_round_and_size(1, 100, rng);
 
-        // Initialize the committee map.
+        // Initialize the committee map and commission map.
         let committee_map = to_committee_map(committee.members());
+        let commission_map = to_commission_map(committee.members());
 
         // Start a timer.
         let timer = std::time::Instant::now();
         // Convert the committee map into a committee.
-        let candidate_committee = committee_map_into_committee(committee.starting_round(), committee_map).unwrap();
+        let candidate_committee =
+            committee_map_into_committee(committee.starting_round(), committee_map.clone(), commission_map).unwrap();
         println!("committee_map_into_committee: {}ms", timer.elapsed().as_millis());
         assert_eq!(candidate_committee, committee);
+
+        // Ensure a missing commission map reads as a commission of 0 for every member.
+        let candidate_committee =
+            committee_map_into_committee(committee.starting_round(), committee_map, vec![]).unwrap();
+        for (address, (stake, is_open, _)) in committee.members() {
+            assert_eq!(candidate_committee.members().get(address), Some(&(*stake, *is_open, 0)));
+        }
     }
 
     #[test]
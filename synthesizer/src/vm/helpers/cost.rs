@@ -14,12 +14,16 @@
 
 use crate::VM;
 use console::{
+    account::{Address, PrivateKey},
     prelude::*,
-    program::{LiteralType, PlaintextType},
+    program::{Identifier, LiteralType, PlaintextType, ProgramID, Value, ValueType},
+    types::{Field, Group},
 };
-use ledger_block::{Deployment, Execution};
+use ledger_block::{Deployment, Execution, Fee, Input, Output, Transition};
 use ledger_store::ConsensusStorage;
-use synthesizer_program::{Command, Finalize, Instruction};
+use synthesizer_process::{Process, Stack};
+use synthesizer_program::{CallOperator, Command, Finalize, Instruction, StackMatches, StackProgram};
+use synthesizer_snark::Proof;
 
 use std::collections::HashMap;
 
@@ -97,6 +101,242 @@ pub fn execution_cost<N: Network, C: ConsensusStorage<N>>(
     Ok((total_cost, (storage_cost, finalize_cost)))
 }
 
+/// Returns the amounts in microcredits of the given fee (base fee, priority fee), which are accounted for separately.
+/// The base fee pays for the deployment or execution cost, while the priority fee is the surplus paid for prioritization.
+pub fn fee_amounts<N: Network>(fee: &Fee<N>) -> Result<(u64, u64)> {
+    // Retrieve the base fee and the priority fee.
+    let base_fee_amount = *fee.base_amount()?;
+    let priority_fee_amount = *fee.priority_amount()?;
+    // Ensure each fee does not exceed the limit.
+    ensure!(base_fee_amount <= N::MAX_FEE, "The base fee exceeds the maximum limit");
+    ensure!(priority_fee_amount <= N::MAX_FEE, "The priority fee exceeds the maximum limit");
+    // Ensure the total fee does not exceed the limit.
+    match base_fee_amount.checked_add(priority_fee_amount) {
+        Some(fee_amount) if fee_amount <= N::MAX_FEE => Ok((base_fee_amount, priority_fee_amount)),
+        _ => bail!("The total of the base fee and priority fee exceeds the maximum limit"),
+    }
+}
+
+/// A breakdown of the estimated cost in microcredits to publish an execution.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionCostEstimate {
+    /// The number of transitions in the execution.
+    num_transitions: usize,
+    /// The estimated size of the execution proof in bytes.
+    proof_size_in_bytes: u64,
+    /// The estimated storage cost in microcredits.
+    storage_cost: u64,
+    /// The finalize cost in microcredits.
+    finalize_cost: u64,
+    /// The estimated total cost in microcredits.
+    total_cost: u64,
+}
+
+impl ExecutionCostEstimate {
+    /// Returns the number of transitions in the execution.
+    pub const fn num_transitions(&self) -> usize {
+        self.num_transitions
+    }
+
+    /// Returns the estimated size of the execution proof in bytes.
+    pub const fn proof_size_in_bytes(&self) -> u64 {
+        self.proof_size_in_bytes
+    }
+
+    /// Returns the estimated storage cost in microcredits.
+    pub const fn storage_cost(&self) -> u64 {
+        self.storage_cost
+    }
+
+    /// Returns the finalize cost in microcredits.
+    pub const fn finalize_cost(&self) -> u64 {
+        self.finalize_cost
+    }
+
+    /// Returns the estimated total cost in microcredits.
+    pub const fn total_cost(&self) -> u64 {
+        self.total_cost
+    }
+}
+
+/// Returns an *upper-bound* estimate of the cost in microcredits to publish an execution of the given function,
+/// without proving the execution.
+///
+/// As functions do not branch, the transitions are derived from the call graph of the function,
+/// and the finalize cost is exact. The proof size is the upper bound for the resulting batch of circuits,
+/// so the estimate may exceed the cost of the proven execution.
+///
+/// Note: The given inputs are only used for the first transition. The inputs of the internal calls and
+/// all outputs are sampled from their types, rather than evaluated. This preserves their size, except for
+/// string literals, which are sampled with up to a quarter of the maximum length. For functions that
+/// output or call with longer strings, the estimate is not an upper bound.
+pub fn estimate_execution_cost<N: Network, R: Rng + CryptoRng>(
+    process: &Process<N>,
+    program_id: &ProgramID<N>,
+    function_name: &Identifier<N>,
+    inputs: &[Value<N>],
+    rng: &mut R,
+) -> Result<ExecutionCostEstimate> {
+    // Retrieve the stack.
+    let stack = process.get_stack(program_id)?;
+    // Retrieve the function.
+    let function = stack.get_function_ref(function_name)?;
+    // Ensure the number of inputs matches.
+    ensure!(
+        function.inputs().len() == inputs.len(),
+        "Function '{program_id}/{function_name}' expects {} inputs, but {} were provided",
+        function.inputs().len(),
+        inputs.len()
+    );
+    // Ensure the inputs match their types.
+    for (input, input_type) in inputs.iter().zip(function.input_types().iter()) {
+        stack.matches_value_type(input, input_type)?;
+    }
+
+    // Sample a burner address, to own the sampled output records.
+    let burner_address = Address::try_from(&PrivateKey::new(rng)?)?;
+    // Sample the transitions.
+    let mut transitions = Vec::new();
+    sample_transitions(stack, function_name, Some(inputs), &burner_address, &mut transitions, rng)?;
+
+    // Determine the batch sizes of the proof, and compute the finalize cost in microcredits.
+    let mut batch_sizes = HashMap::new();
+    let mut num_inclusions = 0;
+    let mut finalize_cost = 0u64;
+    for transition in &transitions {
+        // Retrieve the program ID.
+        let program_id = transition.program_id();
+        // Retrieve the function name.
+        let function_name = transition.function_name();
+        // Increment the batch size of the function circuit.
+        *batch_sizes.entry((*program_id, *function_name)).or_insert(0) += 1;
+        // Each record input requires an inclusion proof.
+        num_inclusions += transition.inputs().iter().filter(|input| matches!(input, Input::Record(..))).count();
+        // Retrieve the finalize cost.
+        let cost = match process.get_program(program_id)?.get_function_ref(function_name)?.finalize_logic() {
+            Some(finalize) => cost_in_microcredits(finalize)?,
+            None => continue,
+        };
+        // Accumulate the finalize cost.
+        finalize_cost = finalize_cost
+            .checked_add(cost)
+            .ok_or(anyhow!("The finalize cost computation overflowed for an execution estimate"))?;
+    }
+    let mut batch_sizes = batch_sizes.into_values().collect::<Vec<_>>();
+    if num_inclusions > 0 {
+        batch_sizes.push(num_inclusions);
+    }
+
+    // Compute the size of the execution without the proof, which is then accounted for separately.
+    let num_transitions = transitions.len();
+    let execution = Execution::from(transitions.into_iter(), N::StateRoot::default(), None)?;
+    let proof_size_in_bytes = u64::try_from(Proof::<N>::max_size_in_bytes(&batch_sizes))?;
+
+    // Compute the storage cost in microcredits.
+    let storage_cost = execution
+        .size_in_bytes()?
+        .checked_add(proof_size_in_bytes)
+        .ok_or(anyhow!("The storage cost computation overflowed for an execution estimate"))?;
+
+    // Compute the total cost in microcredits.
+    let total_cost = storage_cost
+        .checked_add(finalize_cost)
+        .ok_or(anyhow!("The total cost computation overflowed for an execution estimate"))?;
+
+    Ok(ExecutionCostEstimate { num_transitions, proof_size_in_bytes, storage_cost, finalize_cost, total_cost })
+}
+
+/// Samples the transitions for a call to the given function, in execution order.
+/// If no inputs are given, the inputs are sampled from their types.
+fn sample_transitions<N: Network, R: Rng + CryptoRng>(
+    stack: &Stack<N>,
+    function_name: &Identifier<N>,
+    inputs: Option<&[Value<N>]>,
+    burner_address: &Address<N>,
+    transitions: &mut Vec<Transition<N>>,
+    rng: &mut R,
+) -> Result<()> {
+    // Retrieve the function.
+    let function = stack.get_function_ref(function_name)?;
+
+    // Sample the transitions of the function calls, as they precede the transition of the caller.
+    for instruction in function.instructions() {
+        if let Instruction::Call(call) = instruction {
+            if call.is_function_call(stack)? {
+                match call.operator() {
+                    CallOperator::Locator(locator) => {
+                        let external_stack = stack.get_external_stack(locator.program_id())?;
+                        sample_transitions(external_stack, locator.resource(), None, burner_address, transitions, rng)?
+                    }
+                    CallOperator::Resource(resource) => {
+                        sample_transitions(stack, resource, None, burner_address, transitions, rng)?
+                    }
+                }
+            }
+        }
+    }
+
+    // Prepare the transition inputs.
+    let mut transition_inputs = Vec::with_capacity(function.inputs().len());
+    for (index, input_type) in function.input_types().iter().enumerate() {
+        // Use the given plaintext, or sample one if no inputs are given.
+        let mut plaintext = |plaintext_type: &PlaintextType<N>| match inputs {
+            Some(inputs) => match &inputs[index] {
+                Value::Plaintext(plaintext) => Ok(plaintext.clone()),
+                value => bail!("Expected a plaintext for input #{index}, found '{value}'"),
+            },
+            None => stack.sample_plaintext(plaintext_type, rng),
+        };
+        transition_inputs.push(match input_type {
+            ValueType::Constant(plaintext_type) => Input::Constant(Field::zero(), Some(plaintext(plaintext_type)?)),
+            ValueType::Public(plaintext_type) => Input::Public(Field::zero(), Some(plaintext(plaintext_type)?)),
+            ValueType::Private(plaintext_type) => {
+                Input::Private(Field::zero(), Some(plaintext(plaintext_type)?.encrypt_symmetric(Field::zero())?))
+            }
+            ValueType::Record(..) => Input::Record(Field::zero(), Field::zero()),
+            ValueType::ExternalRecord(..) => Input::ExternalRecord(Field::zero()),
+            ValueType::Future(..) => bail!("Function '{function_name}' cannot take a future as input"),
+        });
+    }
+
+    // Prepare the transition outputs.
+    let mut transition_outputs = Vec::with_capacity(function.outputs().len());
+    for output_type in function.output_types() {
+        transition_outputs.push(match output_type {
+            ValueType::Constant(plaintext_type) => {
+                Output::Constant(Field::zero(), Some(stack.sample_plaintext(&plaintext_type, rng)?))
+            }
+            ValueType::Public(plaintext_type) => {
+                Output::Public(Field::zero(), Some(stack.sample_plaintext(&plaintext_type, rng)?))
+            }
+            ValueType::Private(plaintext_type) => {
+                let plaintext = stack.sample_plaintext(&plaintext_type, rng)?;
+                Output::Private(Field::zero(), Some(plaintext.encrypt_symmetric(Field::zero())?))
+            }
+            ValueType::Record(record_name) => {
+                let record = stack.sample_record(burner_address, &record_name, rng)?;
+                Output::Record(Field::zero(), Field::zero(), Some(record.encrypt_symmetric_unchecked(&Field::zero())?))
+            }
+            ValueType::ExternalRecord(..) => Output::ExternalRecord(Field::zero()),
+            ValueType::Future(locator) => Output::Future(Field::zero(), Some(stack.sample_future(&locator, rng)?)),
+        });
+    }
+
+    // Use a distinct commitment for each transition, so that the transition IDs are distinct.
+    let tcm = Field::from_u64(u64::try_from(transitions.len())?);
+    // Construct the transition.
+    let transition = Transition::new(
+        *stack.program_id(),
+        *function_name,
+        transition_inputs,
+        transition_outputs,
+        Group::zero(),
+        tcm,
+    )?;
+    transitions.push(transition);
+    Ok(())
+}
+
 /// Returns the minimum number of microcredits required to run the finalize.
 pub fn cost_in_microcredits<N: Network>(finalize: &Finalize<N>) -> Result<u64> {
     // Defines the cost of each command.
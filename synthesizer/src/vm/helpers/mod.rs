@@ -22,3 +22,12 @@ mod macros;
 
 mod rewards;
 pub use rewards::*;
+
+mod replay;
+pub use replay::*;
+
+mod simulation;
+pub use simulation::*;
+
+mod slashing;
+pub use slashing::*;
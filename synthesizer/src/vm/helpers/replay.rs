@@ -0,0 +1,202 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::network::prelude::*;
+use ledger_block::{ConfirmedTransaction, Transaction};
+use synthesizer_process::FinalizeTraceEvent;
+use synthesizer_program::FinalizeOperation;
+
+use std::collections::HashMap;
+
+/// The replay of a transaction, with the trace of its finalize commands.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionReplay<N: Network> {
+    /// The confirmed transaction, as recorded in the block.
+    expected: ConfirmedTransaction<N>,
+    /// The confirmed transaction, as replayed, or `None` if the replay aborted the transaction.
+    replayed: Option<ConfirmedTransaction<N>>,
+    /// The finalize commands evaluated for the transaction, in order.
+    trace: Vec<FinalizeTraceEvent<N>>,
+}
+
+impl<N: Network> TransactionReplay<N> {
+    /// Initializes a new transaction replay.
+    pub const fn new(
+        expected: ConfirmedTransaction<N>,
+        replayed: Option<ConfirmedTransaction<N>>,
+        trace: Vec<FinalizeTraceEvent<N>>,
+    ) -> Self {
+        Self { expected, replayed, trace }
+    }
+
+    /// Returns the confirmed transaction, as recorded in the block.
+    pub const fn expected(&self) -> &ConfirmedTransaction<N> {
+        &self.expected
+    }
+
+    /// Returns the confirmed transaction, as replayed, or `None` if the replay aborted the transaction.
+    pub const fn replayed(&self) -> Option<&ConfirmedTransaction<N>> {
+        self.replayed.as_ref()
+    }
+
+    /// Returns the finalize commands evaluated for the transaction, in order.
+    pub fn trace(&self) -> &[FinalizeTraceEvent<N>] {
+        &self.trace
+    }
+
+    /// Returns `true` if the replayed transaction matches the one recorded in the block.
+    pub fn is_match(&self) -> bool {
+        self.replayed.as_ref() == Some(&self.expected)
+    }
+
+    /// Returns the finalize operations that differ between the block and the replay,
+    /// as `(index, expected operation, replayed operation)`.
+    pub fn mismatched_operations(&self) -> Vec<(usize, Option<&FinalizeOperation<N>>, Option<&FinalizeOperation<N>>)> {
+        let expected = self.expected.finalize_operations();
+        let replayed = self.replayed.as_ref().map(|replayed| replayed.finalize_operations().as_slice()).unwrap_or(&[]);
+        (0..expected.len().max(replayed.len()))
+            .map(|index| (index, expected.get(index), replayed.get(index)))
+            .filter(|(_, expected, replayed)| expected != replayed)
+            .collect()
+    }
+}
+
+/// The replay of a block on top of its parent state, which compares the replayed transactions
+/// and finalize root against those recorded in the block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayReport<N: Network> {
+    /// The height of the replayed block.
+    height: u32,
+    /// The hash of the replayed block.
+    block_hash: N::BlockHash,
+    /// The replay of each transaction in the block.
+    transactions: Vec<TransactionReplay<N>>,
+    /// `true` if the replayed ratifications match the block.
+    is_ratifications_match: bool,
+    /// `true` if the replayed finalize root matches the block header.
+    is_finalize_root_match: bool,
+}
+
+impl<N: Network> ReplayReport<N> {
+    /// Initializes a new replay report.
+    pub const fn new(
+        height: u32,
+        block_hash: N::BlockHash,
+        transactions: Vec<TransactionReplay<N>>,
+        is_ratifications_match: bool,
+        is_finalize_root_match: bool,
+    ) -> Self {
+        Self { height, block_hash, transactions, is_ratifications_match, is_finalize_root_match }
+    }
+
+    /// Returns the height of the replayed block.
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the hash of the replayed block.
+    pub const fn block_hash(&self) -> N::BlockHash {
+        self.block_hash
+    }
+
+    /// Returns the replay of each transaction in the block.
+    pub fn transactions(&self) -> &[TransactionReplay<N>] {
+        &self.transactions
+    }
+
+    /// Returns `true` if the replayed ratifications match the block.
+    pub const fn is_ratifications_match(&self) -> bool {
+        self.is_ratifications_match
+    }
+
+    /// Returns `true` if the replayed finalize root matches the block header.
+    pub const fn is_finalize_root_match(&self) -> bool {
+        self.is_finalize_root_match
+    }
+
+    /// Returns `true` if the replay reproduces the block.
+    pub fn is_match(&self) -> bool {
+        self.is_ratifications_match
+            && self.is_finalize_root_match
+            && self.transactions.iter().all(TransactionReplay::is_match)
+    }
+
+    /// Returns an iterator over the transactions whose replay does not match the block.
+    pub fn mismatches(&self) -> impl '_ + Iterator<Item = &TransactionReplay<N>> {
+        self.transactions.iter().filter(|transaction| !transaction.is_match())
+    }
+}
+
+impl<N: Network> Display for ReplayReport<N> {
+    /// Prints the report, with the trace of each transaction, and the mismatched finalize operations.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let status = |is_match: bool| if is_match { "match" } else { "MISMATCH" };
+
+        writeln!(f, "Replay of block {} ({}): {}", self.height, self.block_hash, status(self.is_match()))?;
+        writeln!(f, "  ratifications: {}", status(self.is_ratifications_match))?;
+        writeln!(f, "  finalize root: {}", status(self.is_finalize_root_match))?;
+        for transaction in &self.transactions {
+            let expected = transaction.expected();
+            writeln!(
+                f,
+                "\n{} {} ({}): {}",
+                expected.variant(),
+                expected.transaction().id(),
+                expected.index(),
+                status(transaction.is_match())
+            )?;
+            if let Some(replayed) = transaction.replayed().filter(|replayed| replayed.variant() != expected.variant()) {
+                writeln!(f, "  replayed as {}", replayed.variant())?;
+            }
+            if transaction.replayed().is_none() {
+                writeln!(f, "  replay aborted the transaction")?;
+            }
+            for event in transaction.trace() {
+                writeln!(f, "  {}", event.to_string().replace('\n', "\n  "))?;
+            }
+            for (index, expected, replayed) in transaction.mismatched_operations() {
+                let print =
+                    |operation: Option<&FinalizeOperation<N>>| operation.map_or("none".to_string(), |o| o.to_string());
+                writeln!(f, "  operation #{index}: expected {}, replayed {}", print(expected), print(replayed))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Splits the finalize trace into the events of each of the given transactions, in order.
+/// Each event is attributed to the transaction of the most recently entered transition.
+pub(crate) fn split_finalize_trace<N: Network>(
+    transactions: &[Transaction<N>],
+    events: Vec<FinalizeTraceEvent<N>>,
+) -> Vec<Vec<FinalizeTraceEvent<N>>> {
+    // Map each transition ID to the index of its transaction.
+    let indices: HashMap<_, _> = transactions
+        .iter()
+        .enumerate()
+        .flat_map(|(index, transaction)| transaction.transition_ids().map(move |id| (*id, index)))
+        .collect();
+
+    let mut traces = vec![Vec::new(); transactions.len()];
+    let mut current = None;
+    for event in events {
+        if let Some(transition_id) = event.transition_id() {
+            current = indices.get(transition_id).copied();
+        }
+        if let Some(index) = current {
+            traces[index].push(event);
+        }
+    }
+    traces
+}
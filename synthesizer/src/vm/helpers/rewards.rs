@@ -33,6 +33,9 @@ const MAX_COINBASE_REWARD: u64 = ledger_block::MAX_COINBASE_REWARD; // Coinbase
 /// The choice of 25% is to ensure at least 4 validators are operational at any given time,
 /// since our security model adheres to 3f+1, where f=1. As such, we tolerate Byzantine behavior
 /// up to 33% of the total stake.
+///
+/// For delegators, the validator's commission (in percent) is deducted from the staking reward,
+/// and accrues to the validator's own stake. Validators do not pay commission on their own stake.
 pub fn staking_rewards<N: Network>(
     stakers: &IndexMap<Address<N>, (Address<N>, u64)>,
     committee: &Committee<N>,
@@ -43,18 +46,18 @@ pub fn staking_rewards<N: Network>(
         return stakers.clone();
     }
 
-    // Compute the updated stakers.
-    cfg_iter!(stakers)
+    // Compute the updated stakers, along with the commission owed to their validator.
+    let rewards = cfg_iter!(stakers)
         .map(|(staker, (validator, stake))| {
             // If the validator has more than 25% of the total stake, skip the staker.
             if committee.get_stake(*validator) > committee.total_stake().saturating_div(4) {
                 trace!("Validator {validator} has more than 25% of the total stake - skipping {staker}");
-                return (*staker, (*validator, *stake));
+                return (*staker, (*validator, *stake), 0);
             }
             // If the staker has less than the minimum required stake, skip the staker.
             if *stake < MIN_DELEGATOR_STAKE {
                 trace!("Staker has less than {MIN_DELEGATOR_STAKE} microcredits - skipping {staker}");
-                return (*staker, (*validator, *stake));
+                return (*staker, (*validator, *stake), 0);
             }
 
             // Compute the numerator.
@@ -67,15 +70,37 @@ pub fn staking_rewards<N: Network>(
             // Ensure the staking reward is within a safe bound.
             if quotient > MAX_COINBASE_REWARD as u128 {
                 error!("Staking reward ({quotient}) is too large - skipping {staker}");
-                return (*staker, (*validator, *stake));
+                return (*staker, (*validator, *stake), 0);
             }
             // Cast the staking reward as a u64.
             // Note: This '.expect' is guaranteed to be safe, as we ensure the quotient is within a safe bound.
             let staking_reward = u64::try_from(quotient).expect("Staking reward is too large");
-            // Return the staker and the updated stake.
-            (*staker, (*validator, stake.saturating_add(staking_reward)))
+            // Compute the commission owed to the validator.
+            // Note: The commission is only charged if the validator is bonded as a staker, to ensure it can be credited.
+            let commission = match staker != validator && stakers.contains_key(validator) {
+                true => {
+                    // Note: The commission rate is in percent, and guaranteed to be at most 100 by the committee.
+                    let rate = u64::from(committee.get_commission(*validator));
+                    staking_reward.saturating_mul(rate).saturating_div(100)
+                }
+                false => 0,
+            };
+            // Return the staker, the updated stake, and the commission.
+            (*staker, (*validator, stake.saturating_add(staking_reward.saturating_sub(commission))), commission)
         })
-        .collect()
+        .collect::<Vec<_>>();
+
+    // Construct the updated stakers.
+    let mut next_stakers: IndexMap<_, _> = rewards.iter().map(|(staker, state, _)| (*staker, *state)).collect();
+    // Credit each validator with the commission from its delegators.
+    for (_, (validator, _), commission) in rewards {
+        if commission > 0 {
+            if let Some((_, stake)) = next_stakers.get_mut(&validator) {
+                *stake = stake.saturating_add(commission);
+            }
+        }
+    }
+    next_stakers
 }
 
 /// Returns the proving rewards for a given coinbase reward and list of prover solutions.
@@ -128,6 +153,7 @@ pub fn proving_rewards<N: Network>(
 mod tests {
     use super::*;
     use console::prelude::TestRng;
+    use ledger_committee::MIN_VALIDATOR_STAKE;
 
     use indexmap::indexmap;
 
@@ -177,13 +203,70 @@ mod tests {
         let next_stakers = staking_rewards::<CurrentNetwork>(&stakers, &committee, block_reward);
         println!("staking_rewards: {}ms", timer.elapsed().as_millis());
         assert_eq!(next_stakers.len(), stakers.len());
+        // Compute the expected commission for each validator.
+        let mut commissions = IndexMap::<_, u64>::new();
+        for (staker, (validator, stake)) in &stakers {
+            if staker != validator {
+                let reward =
+                    u64::try_from(block_reward as u128 * *stake as u128 / committee.total_stake() as u128).unwrap();
+                let commission = reward * committee.get_commission(*validator) as u64 / 100;
+                *commissions.entry(*validator).or_default() += commission;
+            }
+        }
         for ((staker, (validator, stake)), (next_staker, (next_validator, next_stake))) in
             stakers.into_iter().zip(next_stakers.into_iter())
         {
             assert_eq!(staker, next_staker);
             assert_eq!(validator, next_validator);
-            let reward = block_reward as u128 * stake as u128 / committee.total_stake() as u128;
-            assert_eq!(stake + u64::try_from(reward).unwrap(), next_stake, "stake: {stake}, reward: {reward}");
+            let reward = u64::try_from(block_reward as u128 * stake as u128 / committee.total_stake() as u128).unwrap();
+            let expected = match staker == validator {
+                true => stake + reward + commissions.get(&validator).copied().unwrap_or_default(),
+                false => stake + reward - reward * committee.get_commission(validator) as u64 / 100,
+            };
+            assert_eq!(expected, next_stake, "stake: {stake}, reward: {reward}");
+        }
+    }
+
+    #[test]
+    fn test_staking_rewards_with_commission() {
+        let rng = &mut TestRng::default();
+        // Sample a random committee.
+        let committee = ledger_committee::test_helpers::sample_committee(rng);
+        // Retrieve a validator and its commission.
+        let validator = *committee.members().iter().next().unwrap().0;
+        let commission = committee.get_commission(validator) as u64;
+        // Sample a delegator.
+        let delegator = Address::rand(rng);
+
+        for _ in 0..ITERATIONS {
+            // Sample a random block reward.
+            let block_reward = rng.gen_range(0..MAX_COINBASE_REWARD);
+            // Sample a random stake for the delegator.
+            let stake = rng.gen_range(MIN_DELEGATOR_STAKE..committee.get_stake(validator) / 2);
+            // Construct the stakers.
+            let stakers = indexmap! {
+                validator => (validator, MIN_VALIDATOR_STAKE),
+                delegator => (validator, stake),
+            };
+            let next_stakers = staking_rewards::<CurrentNetwork>(&stakers, &committee, block_reward);
+            assert_eq!(next_stakers.len(), 2);
+
+            // Compute the expected rewards.
+            let total_stake = committee.total_stake() as u128;
+            let validator_reward =
+                u64::try_from(block_reward as u128 * MIN_VALIDATOR_STAKE as u128 / total_stake).unwrap();
+            let delegator_reward = u64::try_from(block_reward as u128 * stake as u128 / total_stake).unwrap();
+            let expected_commission = delegator_reward * commission / 100;
+
+            // Ensure the commission is deducted from the delegator, and credited to the validator.
+            assert_eq!(next_stakers[&delegator], (validator, stake + delegator_reward - expected_commission));
+            assert_eq!(
+                next_stakers[&validator],
+                (validator, MIN_VALIDATOR_STAKE + validator_reward + expected_commission)
+            );
+            // Ensure the total rewards are unchanged by the commission.
+            let total = next_stakers.values().map(|(_, stake)| *stake).sum::<u64>();
+            assert_eq!(total, MIN_VALIDATOR_STAKE + stake + validator_reward + delegator_reward);
         }
     }
 
@@ -0,0 +1,556 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{proving_rewards, staking_rewards, to_next_committee};
+use console::{
+    account::{Address, PrivateKey},
+    network::prelude::*,
+};
+use ledger_block::{block_reward, coinbase_reward, coinbase_target, proof_target, puzzle_reward};
+use ledger_committee::Committee;
+
+use indexmap::IndexMap;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// The configuration of an economic simulation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulationConfig {
+    /// The number of blocks to simulate.
+    pub num_blocks: u32,
+    /// The mean number of seconds between consecutive blocks.
+    pub block_interval: u16,
+    /// The maximum number of seconds a block interval deviates from the mean.
+    pub block_interval_jitter: u16,
+    /// The hashrate of each prover, as the proof target it accumulates per second.
+    pub prover_hashrates: Vec<u64>,
+    /// The self-bonded stake (in microcredits) and commission (in percent) of each validator.
+    pub validators: Vec<(u64, u8)>,
+    /// The validator index and the stake (in microcredits) of each delegator.
+    pub delegators: Vec<(usize, u64)>,
+    /// The priority fees (in microcredits) paid in each block.
+    pub transaction_fees: u64,
+    /// The number of blocks between recorded samples.
+    pub sample_interval: u32,
+    /// The seed of the simulation RNG.
+    pub seed: u64,
+}
+
+/// A sample of the network economics, recorded after a simulated block.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SimulationSample {
+    /// The block height.
+    height: u32,
+    /// The block timestamp.
+    timestamp: i64,
+    /// The total supply of credits (in microcredits).
+    total_supply: u64,
+    /// The total stake of the committee (in microcredits).
+    total_stake: u64,
+    /// The number of solutions in the block.
+    num_solutions: u32,
+    /// The combined proof target of the solutions in the block.
+    combined_proof_target: u128,
+    /// The cumulative proof target towards the coinbase target.
+    cumulative_proof_target: u128,
+    /// The coinbase target.
+    coinbase_target: u64,
+    /// The proof target.
+    proof_target: u64,
+    /// The coinbase reward.
+    coinbase_reward: u64,
+    /// The block reward.
+    block_reward: u64,
+    /// The puzzle reward.
+    puzzle_reward: u64,
+    /// The staking rewards credited to stakers.
+    staking_rewards: u64,
+    /// The proving rewards credited to provers.
+    proving_rewards: u64,
+}
+
+impl SimulationSample {
+    /// The header of the CSV representation of the samples.
+    pub const CSV_HEADER: &'static str = "height,timestamp,total_supply,total_stake,num_solutions,combined_proof_target,\
+                                          cumulative_proof_target,coinbase_target,proof_target,coinbase_reward,\
+                                          block_reward,puzzle_reward,staking_rewards,proving_rewards";
+
+    /// Returns the block height.
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the block timestamp.
+    pub const fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Returns the total supply of credits (in microcredits).
+    pub const fn total_supply(&self) -> u64 {
+        self.total_supply
+    }
+
+    /// Returns the total stake of the committee (in microcredits).
+    pub const fn total_stake(&self) -> u64 {
+        self.total_stake
+    }
+
+    /// Returns the number of solutions in the block.
+    pub const fn num_solutions(&self) -> u32 {
+        self.num_solutions
+    }
+
+    /// Returns the combined proof target of the solutions in the block.
+    pub const fn combined_proof_target(&self) -> u128 {
+        self.combined_proof_target
+    }
+
+    /// Returns the cumulative proof target towards the coinbase target.
+    pub const fn cumulative_proof_target(&self) -> u128 {
+        self.cumulative_proof_target
+    }
+
+    /// Returns the coinbase target.
+    pub const fn coinbase_target(&self) -> u64 {
+        self.coinbase_target
+    }
+
+    /// Returns the proof target.
+    pub const fn proof_target(&self) -> u64 {
+        self.proof_target
+    }
+
+    /// Returns the coinbase reward.
+    pub const fn coinbase_reward(&self) -> u64 {
+        self.coinbase_reward
+    }
+
+    /// Returns the block reward.
+    pub const fn block_reward(&self) -> u64 {
+        self.block_reward
+    }
+
+    /// Returns the puzzle reward.
+    pub const fn puzzle_reward(&self) -> u64 {
+        self.puzzle_reward
+    }
+
+    /// Returns the staking rewards credited to stakers.
+    pub const fn staking_rewards(&self) -> u64 {
+        self.staking_rewards
+    }
+
+    /// Returns the proving rewards credited to provers.
+    pub const fn proving_rewards(&self) -> u64 {
+        self.proving_rewards
+    }
+
+    /// Returns the CSV record of the sample, in the column order of `SimulationSample::CSV_HEADER`.
+    pub fn to_csv_record(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.height,
+            self.timestamp,
+            self.total_supply,
+            self.total_stake,
+            self.num_solutions,
+            self.combined_proof_target,
+            self.cumulative_proof_target,
+            self.coinbase_target,
+            self.proof_target,
+            self.coinbase_reward,
+            self.block_reward,
+            self.puzzle_reward,
+            self.staking_rewards,
+            self.proving_rewards
+        )
+    }
+}
+
+impl Serialize for SimulationSample {
+    /// Serializes the sample into a JSON object.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut sample = serializer.serialize_struct("SimulationSample", 14)?;
+        sample.serialize_field("height", &self.height)?;
+        sample.serialize_field("timestamp", &self.timestamp)?;
+        sample.serialize_field("total_supply", &self.total_supply)?;
+        sample.serialize_field("total_stake", &self.total_stake)?;
+        sample.serialize_field("num_solutions", &self.num_solutions)?;
+        sample.serialize_field("combined_proof_target", &self.combined_proof_target)?;
+        sample.serialize_field("cumulative_proof_target", &self.cumulative_proof_target)?;
+        sample.serialize_field("coinbase_target", &self.coinbase_target)?;
+        sample.serialize_field("proof_target", &self.proof_target)?;
+        sample.serialize_field("coinbase_reward", &self.coinbase_reward)?;
+        sample.serialize_field("block_reward", &self.block_reward)?;
+        sample.serialize_field("puzzle_reward", &self.puzzle_reward)?;
+        sample.serialize_field("staking_rewards", &self.staking_rewards)?;
+        sample.serialize_field("proving_rewards", &self.proving_rewards)?;
+        sample.end()
+    }
+}
+
+/// Simulates the network economics over synthetic blocks, and returns the recorded samples,
+/// starting with the genesis state.
+///
+/// Each block mirrors the target and reward computations of the ledger when constructing a block,
+/// and the reward ratifications of the VM. A prover with hashrate `h` accumulates `h * elapsed` proof target
+/// over a block interval, and submits one solution at the proof target for every multiple of the proof target
+/// (with the remainder submitted at proportional odds). The simulation is deterministic for a given configuration.
+pub fn simulate_economics<N: Network>(config: &SimulationConfig) -> Result<Vec<SimulationSample>> {
+    // Ensure the configuration is well-formed.
+    ensure!(config.block_interval > 0, "The block interval must be greater than zero");
+    ensure!(
+        config.block_interval_jitter < config.block_interval,
+        "The block interval jitter must be less than the interval"
+    );
+    ensure!(config.sample_interval > 0, "The sample interval must be greater than zero");
+    ensure!(
+        config.delegators.iter().all(|(index, _)| *index < config.validators.len()),
+        "Each delegator must be bonded to a configured validator"
+    );
+
+    // Initialize the RNG.
+    let rng = &mut StdRng::seed_from_u64(config.seed);
+
+    // Sample the validator addresses, along with their commissions.
+    let validators = config
+        .validators
+        .iter()
+        .map(|(_, commission)| Ok((Address::try_from(PrivateKey::<N>::new(rng)?)?, *commission)))
+        .collect::<Result<Vec<_>>>()?;
+    // Initialize the stakers, with each validator bonded to itself.
+    let mut stakers = IndexMap::with_capacity(config.validators.len() + config.delegators.len());
+    for ((validator, _), (stake, _)) in validators.iter().zip(&config.validators) {
+        stakers.insert(*validator, (*validator, *stake));
+    }
+    // Bond the delegators to their validators.
+    for (index, stake) in &config.delegators {
+        let delegator = Address::try_from(PrivateKey::<N>::new(rng)?)?;
+        stakers.insert(delegator, (validators[*index].0, *stake));
+    }
+    // Initialize the committee.
+    let mut committee = {
+        let mut members = IndexMap::with_capacity(validators.len());
+        for (validator, commission) in &validators {
+            members.insert(*validator, (0u64, true, *commission));
+        }
+        for (validator, stake) in stakers.values() {
+            if let Some((microcredits, _, _)) = members.get_mut(validator) {
+                *microcredits = microcredits.saturating_add(*stake);
+            }
+        }
+        Committee::new_genesis(members)?
+    };
+    // Sample the prover addresses.
+    let provers = config
+        .prover_hashrates
+        .iter()
+        .map(|_| Address::try_from(PrivateKey::<N>::new(rng)?))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Initialize the genesis state.
+    let mut timestamp = N::GENESIS_TIMESTAMP;
+    let mut total_supply = N::STARTING_SUPPLY;
+    let mut cumulative_proof_target = 0u128;
+    let mut current_coinbase_target = N::GENESIS_COINBASE_TARGET;
+    let mut current_proof_target = N::GENESIS_PROOF_TARGET;
+    let mut last_coinbase_target = N::GENESIS_COINBASE_TARGET;
+    let mut last_coinbase_timestamp = N::GENESIS_TIMESTAMP;
+
+    // Initialize the samples, starting with the genesis state.
+    let mut samples = Vec::with_capacity((config.num_blocks / config.sample_interval) as usize + 1);
+    samples.push(SimulationSample {
+        timestamp,
+        total_supply,
+        total_stake: committee.total_stake(),
+        coinbase_target: current_coinbase_target,
+        proof_target: current_proof_target,
+        ..Default::default()
+    });
+
+    for height in 1..=config.num_blocks {
+        // Sample the block interval.
+        let jitter = config.block_interval_jitter as i64;
+        let elapsed = config.block_interval as i64 + rng.gen_range(-jitter..=jitter);
+        let next_timestamp = timestamp.saturating_add(elapsed);
+
+        // Sample the number of solutions from each prover.
+        let mut num_solutions = config
+            .prover_hashrates
+            .iter()
+            .map(|hashrate| {
+                // Compute the proof target accumulated by the prover over the block interval.
+                let work = (*hashrate as u128).saturating_mul(elapsed as u128);
+                // Submit one solution per multiple of the proof target, and the remainder at proportional odds.
+                let remainder = (work % current_proof_target as u128) as u64;
+                let lucky = rng.gen_range(0..current_proof_target) < remainder;
+                (work / current_proof_target as u128).saturating_add(lucky as u128).min(N::MAX_SOLUTIONS as u128)
+            })
+            .collect::<Vec<_>>();
+        // If there are too many solutions, keep the maximum number of solutions, proportionally for each prover.
+        let total_solutions = num_solutions.iter().sum::<u128>();
+        if total_solutions > N::MAX_SOLUTIONS as u128 {
+            num_solutions.iter_mut().for_each(|n| *n = *n * N::MAX_SOLUTIONS as u128 / total_solutions);
+        }
+        // Compute the proof targets, with the corresponding addresses.
+        let proof_targets = provers
+            .iter()
+            .zip(&num_solutions)
+            .filter(|(_, n)| **n > 0)
+            .map(|(prover, n)| (*prover, (*n as u64).saturating_mul(current_proof_target)))
+            .collect::<Vec<_>>();
+        // Compute the total number of solutions.
+        let total_solutions = u32::try_from(num_solutions.iter().sum::<u128>())?;
+        // Compute the combined proof target.
+        let combined_proof_target = proof_targets.iter().map(|(_, target)| *target as u128).sum::<u128>();
+
+        // Compute the next cumulative proof target.
+        let next_cumulative_proof_target = cumulative_proof_target.saturating_add(combined_proof_target);
+        // Determine if the coinbase target is reached.
+        let is_coinbase_target_reached = next_cumulative_proof_target >= current_coinbase_target as u128;
+        // Update the next cumulative proof target, if necessary.
+        let next_cumulative_proof_target = match is_coinbase_target_reached {
+            true => 0,
+            false => next_cumulative_proof_target,
+        };
+        // Construct the next coinbase target.
+        let next_coinbase_target = coinbase_target(
+            last_coinbase_target,
+            last_coinbase_timestamp,
+            next_timestamp,
+            N::ANCHOR_TIME,
+            N::NUM_BLOCKS_PER_EPOCH,
+            N::GENESIS_COINBASE_TARGET,
+        )?;
+        // Construct the next proof target.
+        let next_proof_target = proof_target(next_coinbase_target, N::GENESIS_PROOF_TARGET);
+        // Update the last coinbase target and last coinbase timestamp, if necessary.
+        if is_coinbase_target_reached {
+            last_coinbase_target = next_coinbase_target;
+            last_coinbase_timestamp = next_timestamp;
+        }
+
+        // Calculate the coinbase reward.
+        let coinbase_reward = coinbase_reward(
+            height,
+            N::STARTING_SUPPLY,
+            N::ANCHOR_HEIGHT,
+            N::BLOCK_TIME,
+            combined_proof_target,
+            u64::try_from(cumulative_proof_target)?,
+            current_coinbase_target,
+        )?;
+        // Compute the block reward.
+        let block_reward = block_reward(N::STARTING_SUPPLY, N::BLOCK_TIME, coinbase_reward, config.transaction_fees);
+        // Compute the puzzle reward.
+        let puzzle_reward = puzzle_reward(coinbase_reward);
+
+        // Compute the updated stakers, using the committee and block reward.
+        let next_stakers = staking_rewards(&stakers, &committee, block_reward);
+        // Compute the staking rewards that were credited.
+        let staking_rewards = next_stakers
+            .values()
+            .map(|(_, stake)| *stake)
+            .sum::<u64>()
+            .saturating_sub(stakers.values().map(|(_, stake)| *stake).sum::<u64>());
+        // Compute the updated committee, using the stakers.
+        committee = to_next_committee(&committee, height as u64, &next_stakers)?;
+        stakers = next_stakers;
+
+        // Compute the proving rewards that were credited.
+        let proving_rewards = match puzzle_reward == 0 || proof_targets.is_empty() {
+            true => 0,
+            false => proving_rewards(proof_targets, puzzle_reward).values().sum::<u64>(),
+        };
+
+        // Update the total supply, as the transaction fees are paid out of existing balances.
+        total_supply = total_supply
+            .saturating_add(staking_rewards)
+            .saturating_add(proving_rewards)
+            .saturating_sub(config.transaction_fees);
+
+        // Advance the state to the next block.
+        timestamp = next_timestamp;
+        cumulative_proof_target = next_cumulative_proof_target;
+        current_coinbase_target = next_coinbase_target;
+        current_proof_target = next_proof_target;
+
+        // Record the sample, if necessary.
+        if height % config.sample_interval == 0 {
+            samples.push(SimulationSample {
+                height,
+                timestamp,
+                total_supply,
+                total_stake: committee.total_stake(),
+                num_solutions: total_solutions,
+                combined_proof_target,
+                cumulative_proof_target,
+                coinbase_target: current_coinbase_target,
+                proof_target: current_proof_target,
+                coinbase_reward,
+                block_reward,
+                puzzle_reward,
+                staking_rewards,
+                proving_rewards,
+            });
+        }
+    }
+
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ledger_committee::MIN_VALIDATOR_STAKE;
+
+    type CurrentNetwork = console::network::Testnet3;
+
+    /// Returns a sample configuration, with provers that outpace the genesis coinbase target.
+    fn sample_config() -> SimulationConfig {
+        SimulationConfig {
+            num_blocks: 1000,
+            block_interval: CurrentNetwork::BLOCK_TIME,
+            block_interval_jitter: 3,
+            prover_hashrates: vec![1 << 27, 1 << 26, 1 << 20],
+            validators: vec![
+                (10 * MIN_VALIDATOR_STAKE, 0),
+                (10 * MIN_VALIDATOR_STAKE, 5),
+                (10 * MIN_VALIDATOR_STAKE, 10),
+                (10 * MIN_VALIDATOR_STAKE, 50),
+                (10 * MIN_VALIDATOR_STAKE, 100),
+            ],
+            delegators: vec![(1, 100_000_000), (2, 100_000_000), (3, 100_000_000), (4, 100_000_000)],
+            transaction_fees: 1_000,
+            sample_interval: 1,
+            seed: 0,
+        }
+    }
+
+    #[test]
+    fn test_simulate_economics_is_deterministic() {
+        let config = sample_config();
+
+        // Ensure the same configuration produces the same samples.
+        let samples = simulate_economics::<CurrentNetwork>(&config).unwrap();
+        assert_eq!(samples.len(), config.num_blocks as usize + 1);
+        assert_eq!(samples, simulate_economics::<CurrentNetwork>(&config).unwrap());
+
+        // Ensure a different seed produces different samples.
+        let other = simulate_economics::<CurrentNetwork>(&SimulationConfig { seed: 1, ..config }).unwrap();
+        assert_ne!(samples, other);
+    }
+
+    #[test]
+    fn test_simulate_economics_accounting() {
+        let config = sample_config();
+        let samples = simulate_economics::<CurrentNetwork>(&config).unwrap();
+
+        // Ensure the genesis sample reflects the genesis state.
+        let genesis = samples[0];
+        assert_eq!(genesis.height(), 0);
+        assert_eq!(genesis.timestamp(), CurrentNetwork::GENESIS_TIMESTAMP);
+        assert_eq!(genesis.total_supply(), CurrentNetwork::STARTING_SUPPLY);
+        assert_eq!(genesis.total_stake(), 50 * MIN_VALIDATOR_STAKE + 400_000_000);
+        assert_eq!(genesis.coinbase_target(), CurrentNetwork::GENESIS_COINBASE_TARGET);
+        assert_eq!(genesis.proof_target(), CurrentNetwork::GENESIS_PROOF_TARGET);
+
+        for (previous, sample) in samples.iter().zip(samples.iter().skip(1)) {
+            assert_eq!(sample.height(), previous.height() + 1);
+            // Ensure the block interval is within the jitter.
+            let elapsed = sample.timestamp() - previous.timestamp();
+            assert!((7..=13).contains(&elapsed));
+            // Ensure the rewards are derived from the coinbase reward.
+            assert_eq!(sample.puzzle_reward(), sample.coinbase_reward() / 2);
+            assert!(sample.block_reward() >= sample.coinbase_reward() / 2 + config.transaction_fees);
+            // Ensure the credited rewards do not exceed the rewards.
+            assert!(sample.staking_rewards() <= sample.block_reward());
+            assert!(sample.proving_rewards() <= sample.puzzle_reward());
+            // Ensure the total supply and total stake reflect the credited rewards.
+            assert_eq!(
+                sample.total_supply(),
+                previous.total_supply() + sample.staking_rewards() + sample.proving_rewards() - config.transaction_fees
+            );
+            assert_eq!(sample.total_stake(), previous.total_stake() + sample.staking_rewards());
+            // Ensure the proof target is derived from the coinbase target.
+            assert_eq!(
+                sample.proof_target(),
+                proof_target(sample.coinbase_target(), CurrentNetwork::GENESIS_PROOF_TARGET)
+            );
+            assert!(sample.num_solutions() as usize <= CurrentNetwork::MAX_SOLUTIONS);
+        }
+
+        // Ensure the provers outpaced the genesis coinbase target.
+        let last = samples.last().unwrap();
+        assert!(last.coinbase_target() > CurrentNetwork::GENESIS_COINBASE_TARGET);
+        assert!(last.total_supply() > CurrentNetwork::STARTING_SUPPLY);
+    }
+
+    #[test]
+    fn test_simulate_economics_without_provers() {
+        let config = SimulationConfig { prover_hashrates: vec![], sample_interval: 100, ..sample_config() };
+        let samples = simulate_economics::<CurrentNetwork>(&config).unwrap();
+
+        // Ensure only every 100th block is sampled.
+        assert_eq!(
+            samples.iter().map(|sample| sample.height()).collect::<Vec<_>>(),
+            (0..=10).map(|i| i * 100).collect::<Vec<_>>()
+        );
+        for sample in &samples[1..] {
+            // Ensure there is no coinbase reward, while the staking rewards continue.
+            assert_eq!(sample.num_solutions(), 0);
+            assert_eq!(sample.coinbase_reward(), 0);
+            assert_eq!(sample.proving_rewards(), 0);
+            assert!(sample.staking_rewards() > 0);
+            // Ensure the coinbase target does not fall below the genesis coinbase target.
+            assert_eq!(sample.coinbase_target(), CurrentNetwork::GENESIS_COINBASE_TARGET);
+        }
+    }
+
+    #[test]
+    fn test_simulate_economics_with_invalid_config() {
+        let config = sample_config();
+
+        // Ensure the block interval jitter must be less than the block interval.
+        let invalid = SimulationConfig { block_interval_jitter: config.block_interval, ..config.clone() };
+        assert!(simulate_economics::<CurrentNetwork>(&invalid).is_err());
+        // Ensure the sample interval must be greater than zero.
+        let invalid = SimulationConfig { sample_interval: 0, ..config.clone() };
+        assert!(simulate_economics::<CurrentNetwork>(&invalid).is_err());
+        // Ensure the delegators must be bonded to a configured validator.
+        let invalid = SimulationConfig { delegators: vec![(5, 100_000_000)], ..config.clone() };
+        assert!(simulate_economics::<CurrentNetwork>(&invalid).is_err());
+        // Ensure the committee must have at least 3 validators.
+        let invalid = SimulationConfig { validators: config.validators[..2].to_vec(), delegators: vec![], ..config };
+        assert!(simulate_economics::<CurrentNetwork>(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_simulation_sample_formats() {
+        let config = SimulationConfig { num_blocks: 10, ..sample_config() };
+        let samples = simulate_economics::<CurrentNetwork>(&config).unwrap();
+
+        for sample in &samples {
+            // Ensure the CSV record matches the CSV header.
+            let record = sample.to_csv_record();
+            assert_eq!(record.split(',').count(), SimulationSample::CSV_HEADER.split(',').count());
+            assert!(record.starts_with(&format!("{},{},", sample.height(), sample.timestamp())));
+
+            // Ensure the JSON object contains the same fields as the CSV header.
+            let json = serde_json::to_value(sample).unwrap();
+            let object = json.as_object().unwrap();
+            assert_eq!(object.keys().cloned().collect::<Vec<_>>().join(","), SimulationSample::CSV_HEADER);
+            assert_eq!(object["total_supply"].as_u64(), Some(sample.total_supply()));
+        }
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{account::Address, network::prelude::*};
+use ledger_committee::{Committee, MIN_VALIDATOR_STAKE};
+
+use indexmap::IndexMap;
+
+/// The percentage of a validator's own bonded stake that is slashed for equivocation.
+pub const SLASH_PERCENTAGE: u64 = 5;
+/// The maximum number of rounds that equivocation evidence may trail the current round by, to be slashed.
+/// Note: As a block spans at least one round, this is within the unbonding period,
+/// so a validator cannot escape a slash by unbonding.
+pub const MAX_EVIDENCE_AGE_IN_ROUNDS: u64 = 360;
+/// The number of blocks after which unbonded microcredits can be claimed, as defined in `credits.aleo`.
+pub const UNBONDING_PERIOD_IN_BLOCKS: u32 = 360;
+
+/// Returns the updated stakers after slashing the given validator, along with the slashed amount in microcredits,
+/// and the microcredits of each staker that is unbonded as a result.
+/// The slashed amount is defined as: `validator_bond * SLASH_PERCENTAGE / 100`.
+///
+/// This method only slashes the validator's own bond, so delegators are not penalized for the
+/// validator's misbehavior. The slashed microcredits are burned. If the remaining bond of the validator
+/// falls below the minimum stake, the validator is removed from the committee, in which case the remaining
+/// bonds of the validator and its delegators are unbonded.
+pub fn slash_stakers<N: Network>(
+    stakers: &IndexMap<Address<N>, (Address<N>, u64)>,
+    committee: &Committee<N>,
+    validator: Address<N>,
+) -> Result<(IndexMap<Address<N>, (Address<N>, u64)>, u64, IndexMap<Address<N>, u64>)> {
+    // Ensure the validator is in the committee.
+    ensure!(committee.is_committee_member(validator), "Cannot slash '{validator}', as it is not in the committee");
+    // Retrieve the bond of the validator.
+    let bond = match stakers.get(&validator) {
+        Some((bonded_validator, bond)) if *bonded_validator == validator => *bond,
+        _ => bail!("Cannot slash '{validator}', as it is not bonded to itself"),
+    };
+
+    // Compute the slashed amount.
+    let amount = bond.saturating_mul(SLASH_PERCENTAGE).saturating_div(100);
+    // Compute the remaining bond of the validator.
+    let remaining_bond = bond.saturating_sub(amount);
+
+    // If the remaining bond is at least the minimum stake, the validator remains in the committee.
+    if remaining_bond >= MIN_VALIDATOR_STAKE {
+        // Compute the updated stakers.
+        let mut next_stakers = stakers.clone();
+        next_stakers.insert(validator, (validator, remaining_bond));
+        // Return the updated stakers and the slashed amount.
+        return Ok((next_stakers, amount, IndexMap::new()));
+    }
+
+    // Otherwise, remove the validator and its delegators from the stakers, and unbond their remaining bonds.
+    let (unbonded, next_stakers): (IndexMap<_, _>, IndexMap<_, _>) =
+        stakers.iter().partition(|(_, (bonded_validator, _))| *bonded_validator == validator);
+    let unbonded = unbonded
+        .into_iter()
+        .map(|(staker, (_, microcredits))| match *staker == validator {
+            true => (*staker, remaining_bond),
+            false => (*staker, *microcredits),
+        })
+        .filter(|(_, microcredits)| *microcredits > 0)
+        .collect();
+    let next_stakers = next_stakers.into_iter().map(|(staker, state)| (*staker, *state)).collect();
+    // Return the updated stakers, the slashed amount, and the unbonded stakers.
+    Ok((next_stakers, amount, unbonded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::prelude::TestRng;
+
+    use indexmap::indexmap;
+
+    type CurrentNetwork = console::network::Testnet3;
+
+    #[test]
+    fn test_slash_stakers() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = ledger_committee::test_helpers::sample_committee_for_round_and_size(1, 10, rng);
+        // Convert the committee into stakers.
+        let mut stakers = crate::committee::test_helpers::to_stakers(committee.members(), rng);
+        // Retrieve a validator, and double its own bond, so it remains above the minimum stake when slashed.
+        let validator = *committee.members().keys().next().unwrap();
+        let bond = 2 * MIN_VALIDATOR_STAKE;
+        stakers.insert(validator, (validator, bond));
+        let committee = crate::committee::to_next_committee(&committee, 1, &stakers).unwrap();
+
+        // Slash the validator.
+        let (next_stakers, amount, unbonded) = slash_stakers(&stakers, &committee, validator).unwrap();
+        assert_eq!(amount, bond * SLASH_PERCENTAGE / 100);
+        assert_eq!(next_stakers[&validator], (validator, bond - amount));
+        assert!(unbonded.is_empty());
+        // Ensure the other stakers are unchanged.
+        for (staker, state) in &stakers {
+            if *staker != validator {
+                assert_eq!(next_stakers[staker], *state);
+            }
+        }
+
+        // Ensure the next committee remains valid.
+        let next_committee = crate::committee::to_next_committee(&committee, 2, &next_stakers).unwrap();
+        assert_eq!(next_committee.get_stake(validator), committee.get_stake(validator) - amount);
+        assert_eq!(next_committee.total_stake(), committee.total_stake() - amount);
+    }
+
+    #[test]
+    fn test_slash_stakers_removes_validator_below_minimum_stake() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = ledger_committee::test_helpers::sample_committee_for_round_and_size(1, 10, rng);
+        // Convert the committee into stakers, where each validator is bonded with the minimum stake.
+        let stakers = crate::committee::test_helpers::to_stakers(committee.members(), rng);
+        // Retrieve a validator.
+        let validator = *committee.members().keys().next().unwrap();
+        assert_eq!(stakers[&validator], (validator, MIN_VALIDATOR_STAKE));
+
+        // Slash the validator, which is not capped at the minimum stake.
+        let (next_stakers, amount, unbonded) = slash_stakers(&stakers, &committee, validator).unwrap();
+        assert_eq!(amount, MIN_VALIDATOR_STAKE * SLASH_PERCENTAGE / 100);
+
+        // Ensure the validator and its delegators are removed from the stakers, and their remaining bonds are unbonded.
+        for (staker, (bonded_validator, microcredits)) in &stakers {
+            match *bonded_validator == validator {
+                true => {
+                    assert!(!next_stakers.contains_key(staker));
+                    let expected = if *staker == validator { microcredits - amount } else { *microcredits };
+                    assert_eq!(unbonded[staker], expected);
+                }
+                false => assert_eq!(next_stakers[staker], (*bonded_validator, *microcredits)),
+            }
+        }
+        // Ensure the slashed amount is the only stake that is not unbonded.
+        assert_eq!(unbonded.values().sum::<u64>(), committee.get_stake(validator) - amount);
+
+        // Ensure the validator is removed from the next committee.
+        let next_committee = crate::committee::to_next_committee(&committee, 2, &next_stakers).unwrap();
+        assert!(!next_committee.is_committee_member(validator));
+        assert_eq!(next_committee.total_stake(), committee.total_stake() - committee.get_stake(validator));
+    }
+
+    #[test]
+    fn test_slash_stakers_fails_for_non_member() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = ledger_committee::test_helpers::sample_committee(rng);
+        // Sample an address that is not in the committee.
+        let address = Address::rand(rng);
+
+        // Ensure slashing a non-member fails.
+        let stakers = indexmap! { address => (address, MIN_VALIDATOR_STAKE) };
+        assert!(slash_stakers::<CurrentNetwork>(&stakers, &committee, address).is_err());
+    }
+}
@@ -26,7 +26,7 @@ use console::{
     account::{Address, PrivateKey},
     network::prelude::*,
     program::{Identifier, Literal, Locator, Plaintext, ProgramID, ProgramOwner, Record, Value},
-    types::{Field, U64},
+    types::{Field, U32, U64, U8},
 };
 use ledger_block::{
     Block,
@@ -46,7 +46,9 @@ use ledger_committee::Committee;
 use ledger_query::Query;
 use ledger_store::{
     atomic_finalize,
+    helpers::BackendStats,
     BlockStore,
+    CommitStep,
     ConsensusStorage,
     ConsensusStore,
     FinalizeMode,
@@ -55,8 +57,9 @@ use ledger_store::{
     TransactionStore,
     TransitionStore,
 };
-use synthesizer_process::{Authorization, Process, Trace};
+use synthesizer_process::{Authorization, FinalizeTraceEvent, Process, Trace};
 use synthesizer_program::{FinalizeGlobalState, FinalizeOperation, FinalizeStoreTrait, Program};
+use synthesizer_snark::ProverMemoryMode;
 
 use aleo_std::prelude::{finish, lap, timer};
 use indexmap::IndexMap;
@@ -71,6 +74,51 @@ pub struct VM<N: Network, C: ConsensusStorage<N>> {
     store: ConsensusStore<N, C>,
 }
 
+/// Loads the program of the given deployment transaction into the process, and recursively loads all of its imports.
+fn load_deployment_and_imports<N: Network, T: TransactionStorage<N>>(
+    process: &mut Process<N>,
+    transaction_store: &TransactionStore<N, T>,
+    transaction_id: N::TransactionID,
+) -> Result<()> {
+    // Retrieve the deployment from the transaction id.
+    let deployment = match transaction_store.get_deployment(&transaction_id)? {
+        Some(deployment) => deployment,
+        None => bail!("Deployment transaction '{transaction_id}' is not found in storage."),
+    };
+
+    // Fetch the program from the deployment.
+    let program = deployment.program();
+    let program_id = program.id();
+
+    // Return early if the program is already loaded.
+    if process.contains_program(program_id) {
+        return Ok(());
+    }
+
+    // Iterate through the program imports.
+    for import_program_id in program.imports().keys() {
+        // Add the imports to the process if does not exist yet.
+        if !process.contains_program(import_program_id) {
+            // Fetch the deployment transaction id.
+            let Some(transaction_id) =
+                transaction_store.deployment_store().find_transaction_id_from_program_id(import_program_id)?
+            else {
+                bail!("Transaction id for '{program_id}' is not found in storage.");
+            };
+
+            // Recursively load the deployment and its imports.
+            load_deployment_and_imports(process, transaction_store, transaction_id)?
+        }
+    }
+
+    // Load the deployment if it does not exist in the process yet.
+    if !process.contains_program(program_id) {
+        process.load_deployment(&deployment)?;
+    }
+
+    Ok(())
+}
+
 impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     /// Initializes the VM from storage.
     #[inline]
@@ -81,58 +129,15 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         // Initialize the store for 'credits.aleo'.
         let credits = Program::<N>::credits()?;
         for mapping in credits.mappings().values() {
-            // Ensure that all mappings are initialized.
-            if !store.finalize_store().contains_mapping_confirmed(credits.id(), mapping.name())? {
+            // Ensure that all mappings are initialized, unless the store is read-only and they are left to the primary.
+            if !store.is_read_only()
+                && !store.finalize_store().contains_mapping_confirmed(credits.id(), mapping.name())?
+            {
                 // Initialize the mappings for 'credits.aleo'.
                 store.finalize_store().initialize_mapping(*credits.id(), *mapping.name())?;
             }
         }
 
-        // A helper function to load the program into the process, and recursively load all imports.
-        fn load_deployment_and_imports<N: Network, T: TransactionStorage<N>>(
-            process: &mut Process<N>,
-            transaction_store: &TransactionStore<N, T>,
-            transaction_id: N::TransactionID,
-        ) -> Result<()> {
-            // Retrieve the deployment from the transaction id.
-            let deployment = match transaction_store.get_deployment(&transaction_id)? {
-                Some(deployment) => deployment,
-                None => bail!("Deployment transaction '{transaction_id}' is not found in storage."),
-            };
-
-            // Fetch the program from the deployment.
-            let program = deployment.program();
-            let program_id = program.id();
-
-            // Return early if the program is already loaded.
-            if process.contains_program(program_id) {
-                return Ok(());
-            }
-
-            // Iterate through the program imports.
-            for import_program_id in program.imports().keys() {
-                // Add the imports to the process if does not exist yet.
-                if !process.contains_program(import_program_id) {
-                    // Fetch the deployment transaction id.
-                    let Some(transaction_id) =
-                        transaction_store.deployment_store().find_transaction_id_from_program_id(import_program_id)?
-                    else {
-                        bail!("Transaction id for '{program_id}' is not found in storage.");
-                    };
-
-                    // Recursively load the deployment and its imports.
-                    load_deployment_and_imports(process, transaction_store, transaction_id)?
-                }
-            }
-
-            // Load the deployment if it does not exist in the process yet.
-            if !process.contains_program(program_id) {
-                process.load_deployment(&deployment)?;
-            }
-
-            Ok(())
-        }
-
         // Retrieve the transaction store.
         let transaction_store = store.transaction_store();
         // Load the deployments from the store.
@@ -145,6 +150,38 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         Ok(Self { process: Arc::new(RwLock::new(process)), store })
     }
 
+    /// Returns `true` if the VM is backed by a read-only secondary store.
+    #[inline]
+    pub fn is_read_only(&self) -> bool {
+        self.store.is_read_only()
+    }
+
+    /// Returns the statistics that the storage backend estimates for the whole store, if it provides them.
+    pub fn backend_stats(&self) -> Result<Option<BackendStats>> {
+        self.store.backend_stats()
+    }
+
+    /// Catches up with the writes of the primary store, and loads the programs that were deployed since.
+    /// This method is only supported by a VM that is backed by a read-only secondary store.
+    pub fn catch_up_with_primary(&self) -> Result<()> {
+        // Catch up with the primary store.
+        self.store.catch_up_with_primary()?;
+        // Load the new deployments.
+        let transaction_store = self.store.transaction_store();
+        let mut process = self.process.write();
+        for transaction_id in transaction_store.deployment_transaction_ids() {
+            // Skip the deployments that are already loaded.
+            if let Some(program_id) = transaction_store.deployment_store().get_program_id(&transaction_id)? {
+                if process.contains_program(&program_id) {
+                    continue;
+                }
+            }
+            // Load the deployment and its imports.
+            load_deployment_and_imports(&mut process, transaction_store, *transaction_id)?;
+        }
+        Ok(())
+    }
+
     /// Returns `true` if a program with the given program ID exists.
     #[inline]
     pub fn contains_program(&self, program_id: &ProgramID<N>) -> bool {
@@ -191,10 +228,10 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
 
         // Construct the committee members.
         let members = indexmap::indexmap! {
-            Address::try_from(private_keys[0])? => (ledger_committee::MIN_VALIDATOR_STAKE, true),
-            Address::try_from(private_keys[1])? => (ledger_committee::MIN_VALIDATOR_STAKE, true),
-            Address::try_from(private_keys[2])? => (ledger_committee::MIN_VALIDATOR_STAKE, true),
-            Address::try_from(private_keys[3])? => (ledger_committee::MIN_VALIDATOR_STAKE, true),
+            Address::try_from(private_keys[0])? => (ledger_committee::MIN_VALIDATOR_STAKE, true, 0u8),
+            Address::try_from(private_keys[1])? => (ledger_committee::MIN_VALIDATOR_STAKE, true, 0u8),
+            Address::try_from(private_keys[2])? => (ledger_committee::MIN_VALIDATOR_STAKE, true, 0u8),
+            Address::try_from(private_keys[3])? => (ledger_committee::MIN_VALIDATOR_STAKE, true, 0u8),
         };
         // Construct the committee.
         let committee = Committee::<N>::new_genesis(members)?;
@@ -283,6 +320,9 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     /// Adds the given block into the VM.
     #[inline]
     pub fn add_next_block(&self, block: &Block<N>) -> Result<()> {
+        // Ensure the VM is writable.
+        ensure!(!self.is_read_only(), "Cannot add block {} to a read-only VM", block.height());
+
         // Construct the finalize state.
         let state = FinalizeGlobalState::new::<N>(
             block.round(),
@@ -294,15 +334,28 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
 
         // Attention: The following order is crucial because if 'finalize' fails, we can rollback the block.
         // If one first calls 'finalize', then calls 'insert(block)' and it fails, there is no way to rollback 'finalize'.
-
-        // First, insert the block.
-        self.block_store().insert(block)?;
-        // Next, finalize the transactions.
+        // The commit marker is cleared in the same atomic batch as 'finalize', so that if the process is interrupted
+        // before 'finalize' is committed, the block is rolled back when the storage is reopened.
+
+        // First, mark the block as being committed.
+        self.finalize_store().begin_commit(block.height())?;
+        self.store.commit_step(CommitStep::Marked)?;
+        // Next, insert the block.
+        if let Err(error) = self.block_store().insert(block) {
+            // Clear the commit marker.
+            self.finalize_store().end_commit()?;
+            // Return the error.
+            return Err(error);
+        }
+        self.store.commit_step(CommitStep::Inserted)?;
+        // Lastly, finalize the transactions, which clears the commit marker.
         match self.finalize(state, block.ratifications(), block.solutions(), block.transactions()) {
-            Ok(_ratified_finalize_operations) => Ok(()),
+            Ok(_ratified_finalize_operations) => self.store.commit_step(CommitStep::Finalized),
             Err(error) => {
                 // Rollback the block.
                 self.block_store().remove_last_n(1)?;
+                // Clear the commit marker.
+                self.finalize_store().end_commit()?;
                 // Return the error.
                 Err(error)
             }
@@ -1074,4 +1127,200 @@ finalize do:
         // Verify.
         vm.check_transaction(&transaction, None, rng).unwrap();
     }
+
+    /// Returns a program whose deployment updates the block, transaction, and finalize stores.
+    fn sample_crash_recovery_program() -> Program<CurrentNetwork> {
+        Program::from_str(
+            r"
+program crash_recovery.aleo;
+
+mapping m:
+    key as u8.public;
+    value as u32.public;
+
+function do:
+    input r0 as u32.public;
+    async do r0 into r1;
+    output r1 as crash_recovery.aleo/do.future;
+
+finalize do:
+    input r0 as u32.public;
+    set r0 into m[0u8];",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_add_next_block_recovers_from_crash() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a private key.
+        let private_key = sample_genesis_private_key(rng);
+
+        // Initialize a program.
+        let program = sample_crash_recovery_program();
+
+        // Commit the next block without interruption, to obtain the expected state.
+        let reference = sample_vm_with_genesis_block(rng);
+        let genesis_checksum = reference.finalize_store().get_checksum_confirmed().unwrap();
+        let deployment = reference.deploy(&private_key, &program, None, 0, None, rng).unwrap();
+        let block = sample_next_block(&reference, &private_key, &[deployment], rng).unwrap();
+        reference.add_next_block(&block).unwrap();
+        let expected_checksum = reference.finalize_store().get_checksum_confirmed().unwrap();
+
+        // Note: A crash while finishing the atomic batch is only recoverable from durable storage,
+        // as the in-memory maps apply their batches one at a time (see `test_add_next_block_recovers_from_crash_on_disk`).
+        for step in CommitStep::ALL.into_iter().filter(|step| *step != CommitStep::Finishing) {
+            // Interrupt the commit of the next block after the given step.
+            let vm = sample_vm_with_genesis_block(rng);
+            vm.store.crash_after(Some(step));
+            assert!(vm.add_next_block(&block).is_err());
+            vm.store.crash_after(None);
+
+            // Recover the storage, and reload the VM from it.
+            let recovered = vm.store.recover().unwrap();
+            let vm = VM::from(vm.store.clone()).unwrap();
+
+            // Ensure the storage is consistent, and the commit marker is cleared.
+            let report = vm.store.check_consistency(false).unwrap();
+            assert!(report.is_consistent(), "{report}");
+            assert_eq!(vm.finalize_store().pending_commit().unwrap(), None);
+
+            match step {
+                // If the finalize operations were committed, ensure the block is kept.
+                CommitStep::Finalized => {
+                    assert_eq!(recovered, None);
+                    assert!(vm.block_store().contains_block_height(1).unwrap());
+                    assert!(vm.contains_program(program.id()));
+                }
+                // Otherwise, ensure the block is rolled back, and can be committed again.
+                CommitStep::Marked | CommitStep::Inserted | CommitStep::Finishing => {
+                    assert_eq!(recovered, Some(1));
+                    assert!(!vm.block_store().contains_block_height(1).unwrap());
+                    assert!(!vm.contains_program(program.id()));
+                    assert_eq!(vm.finalize_store().get_checksum_confirmed().unwrap(), genesis_checksum);
+                    vm.add_next_block(&block).unwrap();
+                }
+            }
+            // Ensure the state matches the uninterrupted commit.
+            assert_eq!(vm.block_store().current_state_root(), reference.block_store().current_state_root());
+            assert_eq!(vm.finalize_store().get_checksum_confirmed().unwrap(), expected_checksum);
+        }
+    }
+
+    #[cfg(feature = "rocks")]
+    #[test]
+    fn test_add_next_block_recovers_from_crash_on_disk() {
+        use ledger_store::helpers::{rocksdb::ConsensusDB, StorageMode};
+
+        type CurrentConsensusStore = ConsensusStore<CurrentNetwork, ConsensusDB<CurrentNetwork>>;
+
+        let rng = &mut TestRng::default();
+
+        // Initialize a private key, the genesis block, and a program.
+        let private_key = sample_genesis_private_key(rng);
+        let genesis = sample_genesis_block(rng);
+        let program = sample_crash_recovery_program();
+
+        // Commit the next block without interruption, to obtain the expected state.
+        let reference = sample_vm_with_genesis_block(rng);
+        let genesis_checksum = reference.finalize_store().get_checksum_confirmed().unwrap();
+        let deployment = reference.deploy(&private_key, &program, None, 0, None, rng).unwrap();
+        let block = sample_next_block(&reference, &private_key, &[deployment], rng).unwrap();
+        reference.add_next_block(&block).unwrap();
+        let expected_checksum = reference.finalize_store().get_checksum_confirmed().unwrap();
+
+        let directory = std::env::temp_dir().join(format!("snarkvm-crash-recovery-{}", rng.gen::<u64>()));
+        for step in CommitStep::ALL {
+            let storage = StorageMode::Custom(directory.join(format!("{step:?}")));
+
+            // Initialize a VM on disk with the genesis block.
+            let vm = VM::from(CurrentConsensusStore::open(storage.clone()).unwrap()).unwrap();
+            vm.add_next_block(&genesis).unwrap();
+
+            // Interrupt the commit of the next block at the given step.
+            vm.store.crash_after(Some(step));
+            match step {
+                // A crash while finishing the atomic batch panics, as the process is killed mid-write.
+                CommitStep::Finishing => {
+                    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| vm.add_next_block(&block)));
+                    assert!(result.is_err());
+                }
+                _ => assert!(vm.add_next_block(&block).is_err()),
+            }
+            // Close the storage, discarding any atomic batch that was not written.
+            drop(vm);
+
+            // Reopen the storage, which rolls back the interrupted commit, and reload the VM from it.
+            let store = CurrentConsensusStore::open(storage.clone()).unwrap();
+            let vm = VM::from(store).unwrap();
+
+            // Ensure the storage is consistent, and the commit marker is cleared.
+            let report = vm.store.check_consistency(false).unwrap();
+            assert!(report.is_consistent(), "{report}");
+            assert_eq!(vm.finalize_store().pending_commit().unwrap(), None);
+
+            match step {
+                // If the finalize operations were committed, ensure the block is kept.
+                CommitStep::Finalized => {
+                    assert!(vm.block_store().contains_block_height(1).unwrap());
+                    assert!(vm.contains_program(program.id()));
+                }
+                // Otherwise, ensure the block is rolled back, and can be committed again.
+                CommitStep::Marked | CommitStep::Inserted | CommitStep::Finishing => {
+                    assert!(!vm.block_store().contains_block_height(1).unwrap());
+                    assert!(!vm.contains_program(program.id()));
+                    assert_eq!(vm.finalize_store().get_checksum_confirmed().unwrap(), genesis_checksum);
+                    vm.add_next_block(&block).unwrap();
+                }
+            }
+            // Ensure the state matches the uninterrupted commit.
+            assert_eq!(vm.block_store().current_state_root(), reference.block_store().current_state_root());
+            assert_eq!(vm.finalize_store().get_checksum_confirmed().unwrap(), expected_checksum);
+        }
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_check_consistency_against_finalize_root() {
+        use console::program::{Identifier, Literal, Plaintext, ProgramID};
+        use synthesizer_program::FinalizeStoreTrait;
+
+        let rng = &mut TestRng::default();
+
+        // Initialize a private key.
+        let private_key = sample_genesis_private_key(rng);
+        let address = Address::try_from(&private_key).unwrap();
+
+        // Add a block with a deployment, whose public fee updates the balance of the deployer.
+        let vm = sample_vm_with_genesis_block(rng);
+        let program = Program::from_str(
+            r"
+program consistency_check.aleo;
+
+function do:
+    input r0 as u32.public;
+    output r0 as u32.public;",
+        )
+        .unwrap();
+        let deployment = vm.deploy(&private_key, &program, None, 0, None, rng).unwrap();
+        let block = sample_next_block(&vm, &private_key, &[deployment], rng).unwrap();
+        vm.add_next_block(&block).unwrap();
+
+        // Ensure the finalize state matches the finalize root of the block.
+        let report = vm.store.check_consistency(false).unwrap();
+        assert!(report.is_consistent(), "{report}");
+
+        // Tamper with the balance of the deployer.
+        let credits = ProgramID::from_str("credits.aleo").unwrap();
+        let account = Identifier::from_str("account").unwrap();
+        let key = Plaintext::from(Literal::Address(address));
+        vm.finalize_store().update_key_value(credits, account, key, Value::from_str("1u64").unwrap()).unwrap();
+
+        // Ensure the finalize state no longer matches the finalize root of the block.
+        let report = vm.store.check_consistency(false).unwrap();
+        assert!(!report.is_consistent());
+        assert!(report.inconsistencies().iter().any(|inconsistency| inconsistency.map() == "checksum"), "{report}");
+    }
 }
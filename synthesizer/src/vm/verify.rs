@@ -258,9 +258,11 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     fn check_fee_internal(&self, fee: &Fee<N>, deployment_or_execution_id: Field<N>) -> Result<()> {
         let timer = timer!("VM::check_fee");
 
-        // Ensure the fee does not exceed the limit.
-        let fee_amount = fee.amount()?;
-        ensure!(*fee_amount <= N::MAX_FEE, "Fee verification failed: fee exceeds the maximum limit");
+        // Retrieve the base fee and the priority fee, and ensure they do not exceed the limit.
+        let (base_fee_amount, priority_fee_amount) = match fee_amounts(fee) {
+            Ok(amounts) => amounts,
+            Err(error) => bail!("Fee verification failed: {error}"),
+        };
 
         // Verify the fee.
         let verification = self.process.read().verify_fee(fee, deployment_or_execution_id);
@@ -284,8 +286,13 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             else {
                 bail!("Fee verification failed: fee is public, but the payer account balance is missing");
             };
-            // Ensure the balance is sufficient.
-            ensure!(balance >= fee_amount, "Fee verification failed: insufficient balance");
+            // Ensure the balance is sufficient for the base fee.
+            ensure!(*balance >= base_fee_amount, "Fee verification failed: insufficient balance for the base fee");
+            // Ensure the remaining balance is sufficient for the priority fee.
+            ensure!(
+                *balance - base_fee_amount >= priority_fee_amount,
+                "Fee verification failed: insufficient balance for the priority fee"
+            );
         }
 
         // Ensure the global state root exists in the block store.
@@ -417,6 +424,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_check_fee_with_priority_fee() {
+        let rng = &mut TestRng::default();
+        let vm = crate::vm::test_helpers::sample_vm_with_genesis_block(rng);
+
+        // Initialize the caller.
+        let caller_private_key = crate::vm::test_helpers::sample_genesis_private_key(rng);
+
+        // Fetch an execution.
+        let transaction = crate::vm::test_helpers::sample_execution_transaction_with_public_fee(rng);
+        let execution_id = transaction.execution().unwrap().to_execution_id().unwrap();
+
+        // Ensure the priority fee is accounted for separately from the base fee.
+        let fee = transaction.fee_transition().unwrap();
+        let (base_fee_amount, priority_fee_amount) = fee_amounts(&fee).unwrap();
+        assert_eq!(base_fee_amount, *fee.base_amount().unwrap());
+        assert_eq!(priority_fee_amount, *fee.priority_amount().unwrap());
+        assert_eq!(*fee.amount().unwrap(), base_fee_amount + priority_fee_amount);
+        assert!(priority_fee_amount > 0);
+
+        // Authorize a fee, where the priority fee pushes the total fee over the limit.
+        let authorization = vm
+            .authorize_fee_public(&caller_private_key, 10_000_000, CurrentNetwork::MAX_FEE, execution_id, rng)
+            .unwrap();
+        let fee = vm.execute_fee_authorization(authorization, None, rng).unwrap();
+
+        // Ensure the fee is rejected, as the priority fee is within the limit, but the total fee is not.
+        let error = vm.check_fee_internal(&fee, execution_id).unwrap_err();
+        assert!(error.to_string().contains("base fee and priority fee exceeds the maximum limit"));
+
+        // Authorize a fee, where the priority fee exceeds the balance remaining after the base fee.
+        let authorization = vm
+            .authorize_fee_public(&caller_private_key, 10_000_000, CurrentNetwork::MAX_FEE / 2, execution_id, rng)
+            .unwrap();
+        let fee = vm.execute_fee_authorization(authorization, None, rng).unwrap();
+
+        // Ensure the fee is rejected for the priority fee.
+        let error = vm.check_fee_internal(&fee, execution_id).unwrap_err();
+        assert!(error.to_string().contains("insufficient balance for the priority fee"));
+    }
+
     #[test]
     fn test_check_transaction_execution() {
         let rng = &mut TestRng::default();
@@ -45,6 +45,15 @@ impl<'a, T> ExecutionPool<'a, T> {
             self.jobs.into_iter().map(|f| f()).collect()
         }
     }
+
+    /// Lazily executes the jobs one at a time, in the order they were added.
+    /// Each job may still use all available threads, but at most one job is running at any time.
+    pub fn execute_serially(self) -> impl Iterator<Item = T> + 'a
+    where
+        T: 'a,
+    {
+        self.jobs.into_iter().map(|f| f())
+    }
 }
 
 impl<'a, T> Default for ExecutionPool<'a, T> {
@@ -12,11 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cli::commands::{Build, Clean, Execute, New, Run, Update};
+use crate::{
+    cli::commands::{Build, Ceremony, Clean, Estimate, Execute, Inspect, New, Parameters, Run, Simulate, Update},
+    parameters::ParameterFile,
+};
 
 use anstyle::{AnsiColor, Color, Style};
 use anyhow::Result;
 use clap::{builder::Styles, Parser};
+use std::path::PathBuf;
 
 const HEADER_COLOR: Option<Color> = Some(Color::Ansi(AnsiColor::Yellow));
 const LITERAL_COLOR: Option<Color> = Some(Color::Ansi(AnsiColor::Green));
@@ -31,6 +35,15 @@ pub struct CLI {
     /// Specify the verbosity [options: 0, 1, 2, 3]
     #[clap(default_value = "2", short, long)]
     pub verbosity: u8,
+    /// Specify the directory in which parameter files are stored
+    #[clap(long, global = true)]
+    pub parameters_dir: Option<PathBuf>,
+    /// Specify the path to the powers of a universal SRS exported from a ceremony, to use in place of the hard-coded powers
+    #[clap(long, global = true)]
+    pub srs: Option<PathBuf>,
+    /// Never download missing parameter files, and fail with the list of missing files instead
+    #[clap(long, global = true)]
+    pub offline: bool,
     /// Specify a subcommand.
     #[clap(subcommand)]
     pub command: Command,
@@ -40,27 +53,90 @@ pub struct CLI {
 pub enum Command {
     #[clap(name = "build")]
     Build(Build),
+    #[clap(name = "ceremony")]
+    Ceremony(Ceremony),
     #[clap(name = "clean")]
     Clean(Clean),
+    #[clap(name = "estimate")]
+    Estimate(Estimate),
     #[clap(name = "execute")]
     Execute(Execute),
+    #[clap(name = "inspect")]
+    Inspect(Inspect),
     #[clap(name = "new")]
     New(New),
+    #[clap(name = "parameters")]
+    Parameters(Parameters),
     #[clap(name = "run")]
     Run(Run),
+    #[clap(name = "simulate")]
+    Simulate(Simulate),
     #[clap(name = "update")]
     Update(Update),
 }
 
+impl CLI {
+    /// Configures the parameters directory, the universal SRS, and the offline mode.
+    pub fn configure_parameters(&self) -> Result<()> {
+        if let Some(parameters_dir) = &self.parameters_dir {
+            crate::parameters::set_parameters_dir(parameters_dir)?;
+        }
+        if let Some(srs) = &self.srs {
+            crate::parameters::testnet3::set_custom_powers(std::fs::read(srs)?)?;
+        }
+        if self.offline {
+            crate::parameters::set_offline(true);
+        }
+        // In offline mode, fail fast if any parameter files required by the command are missing.
+        if crate::parameters::is_offline() {
+            crate::parameters::ensure_available(&self.command.required_parameter_files())?;
+        }
+        Ok(())
+    }
+}
+
 impl Command {
+    /// Returns the remote parameter files that the command may load.
+    pub fn required_parameter_files(&self) -> Vec<ParameterFile> {
+        use crate::parameters::testnet3::{has_custom_powers, remote_key_files, remote_powers_files};
+
+        // The powers of a custom universal SRS are loaded from the given file instead.
+        let powers_files = || match has_custom_powers() {
+            true => Vec::new(),
+            false => remote_powers_files(),
+        };
+
+        match self {
+            // Building synthesizes the keys of the program.
+            Self::Build(..) => powers_files(),
+            // Executing synthesizes the keys of the program, and proves the calls to `credits.aleo` and the inclusion of records.
+            Self::Execute(..) => powers_files().into_iter().chain(remote_key_files()).collect(),
+            // The remaining commands do not load any remote parameter files, or (in the case of `parameters`) manage them directly.
+            Self::Ceremony(..)
+            | Self::Clean(..)
+            | Self::Estimate(..)
+            | Self::Inspect(..)
+            | Self::New(..)
+            | Self::Parameters(..)
+            | Self::Run(..)
+            | Self::Simulate(..)
+            | Self::Update(..) => Vec::new(),
+        }
+    }
+
     /// Parse the command.
     pub fn parse(self) -> Result<String> {
         match self {
             Self::Build(command) => command.parse(),
+            Self::Ceremony(command) => command.parse(),
             Self::Clean(command) => command.parse(),
+            Self::Estimate(command) => command.parse(),
             Self::Execute(command) => command.parse(),
+            Self::Inspect(command) => command.parse(),
             Self::New(command) => command.parse(),
+            Self::Parameters(command) => command.parse(),
             Self::Run(command) => command.parse(),
+            Self::Simulate(command) => command.parse(),
             Self::Update(command) => command.parse(),
         }
     }
@@ -76,4 +152,30 @@ mod tests {
         use clap::CommandFactory;
         CLI::command().debug_assert()
     }
+
+    #[test]
+    fn test_offline_flag() {
+        // Ensure the offline flag is accepted before and after the subcommand.
+        let cli = CLI::try_parse_from(["snarkvm", "--offline", "build"]).unwrap();
+        assert!(cli.offline);
+        let cli = CLI::try_parse_from(["snarkvm", "build", "--offline"]).unwrap();
+        assert!(cli.offline);
+        let cli = CLI::try_parse_from(["snarkvm", "execute", "main", "--offline"]).unwrap();
+        assert!(cli.offline);
+    }
+
+    #[test]
+    fn test_required_parameter_files() {
+        // Ensure commands that do not load remote parameter files do not require any.
+        let cli = CLI::try_parse_from(["snarkvm", "new", "hello"]).unwrap();
+        assert!(cli.command.required_parameter_files().is_empty());
+        let cli = CLI::try_parse_from(["snarkvm", "run", "main"]).unwrap();
+        assert!(cli.command.required_parameter_files().is_empty());
+
+        // Ensure executing requires more parameter files than building.
+        let build = CLI::try_parse_from(["snarkvm", "build"]).unwrap().command.required_parameter_files();
+        let execute = CLI::try_parse_from(["snarkvm", "execute", "main"]).unwrap().command.required_parameter_files();
+        assert!(!build.is_empty());
+        assert!(execute.len() > build.len());
+    }
 }
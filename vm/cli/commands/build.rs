@@ -20,9 +20,6 @@ pub struct Build {
     /// Uses the specified endpoint.
     #[clap(long)]
     endpoint: Option<String>,
-    /// Toggles offline mode.
-    #[clap(long)]
-    offline: bool,
 }
 
 impl Build {
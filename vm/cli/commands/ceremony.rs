@@ -0,0 +1,150 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    console::prelude::{Environment, FromBytes, ToBytes},
+    parameters::ceremony::Transcript,
+};
+
+use clap::Subcommand;
+use std::path::{Path, PathBuf};
+
+type PairingCurve = <CurrentNetwork as Environment>::PairingCurve;
+
+/// Runs a powers-of-tau ceremony for the universal SRS.
+#[derive(Debug, Parser)]
+pub struct Ceremony {
+    /// Specify a ceremony subcommand.
+    #[clap(subcommand)]
+    command: CeremonyCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum CeremonyCommand {
+    /// Initializes a new ceremony transcript
+    New {
+        /// The number of powers in the universal SRS (must be a power of two)
+        #[clap(long)]
+        num_powers: usize,
+        /// The path to write the transcript to
+        #[clap(short, long)]
+        output: PathBuf,
+    },
+    /// Contributes fresh randomness to a ceremony transcript
+    Contribute {
+        /// The path to read the transcript from
+        #[clap(short, long)]
+        input: PathBuf,
+        /// The path to write the updated transcript to
+        #[clap(short, long)]
+        output: PathBuf,
+    },
+    /// Verifies every contribution in a ceremony transcript
+    Verify {
+        /// The path to read the transcript from
+        #[clap(short, long)]
+        input: PathBuf,
+    },
+    /// Starts a new ceremony transcript for a larger universal SRS, which commits to an existing transcript
+    Extend {
+        /// The path to read the transcript from
+        #[clap(short, long)]
+        input: PathBuf,
+        /// The number of powers in the extended universal SRS (must be a power of two)
+        #[clap(long)]
+        num_powers: usize,
+        /// The path to write the extended transcript to
+        #[clap(short, long)]
+        output: PathBuf,
+    },
+    /// Verifies a ceremony transcript and exports the powers of the universal SRS
+    Export {
+        /// The path to read the transcript from
+        #[clap(short, long)]
+        input: PathBuf,
+        /// The path to write the powers to
+        #[clap(short, long)]
+        output: PathBuf,
+    },
+}
+
+impl Ceremony {
+    /// Runs the ceremony subcommand.
+    pub fn parse(self) -> Result<String> {
+        match self.command {
+            CeremonyCommand::New { num_powers, output } => {
+                let transcript = Transcript::<PairingCurve>::new(num_powers)?;
+                std::fs::write(&output, transcript.to_bytes_le()?)?;
+
+                Ok(format!("✅ Initialized a ceremony for {num_powers} powers {}", path_string(&output).dimmed()))
+            }
+            CeremonyCommand::Contribute { input, output } => {
+                let mut transcript = read_transcript(&input)?;
+                let digest = transcript.contribute(&mut rand::thread_rng())?;
+                std::fs::write(&output, transcript.to_bytes_le()?)?;
+
+                Ok(format!(
+                    "✅ Contributed to the ceremony (digest: {}) {}",
+                    to_hex(&digest).bold(),
+                    path_string(&output).dimmed()
+                ))
+            }
+            CeremonyCommand::Verify { input } => {
+                let transcript = read_transcript(&input)?;
+                transcript.verify(&mut rand::thread_rng())?;
+
+                Ok(format!(
+                    "✅ Verified {} contributions to the ceremony (digest: {})",
+                    transcript.contributions().len(),
+                    to_hex(&transcript.digest()?).bold()
+                ))
+            }
+            CeremonyCommand::Extend { input, num_powers, output } => {
+                let transcript = read_transcript(&input)?;
+                transcript.verify(&mut rand::thread_rng())?;
+                let extended = transcript.extend(num_powers)?;
+                std::fs::write(&output, extended.to_bytes_le()?)?;
+
+                Ok(format!("✅ Extended the ceremony to {num_powers} powers {}", path_string(&output).dimmed()))
+            }
+            CeremonyCommand::Export { input, output } => {
+                let transcript = read_transcript(&input)?;
+                transcript.verify(&mut rand::thread_rng())?;
+                std::fs::write(&output, transcript.to_powers()?.to_bytes_le()?)?;
+
+                Ok(format!(
+                    "✅ Exported {} powers of the universal SRS (use with '--srs') {}",
+                    transcript.num_powers(),
+                    path_string(&output).dimmed()
+                ))
+            }
+        }
+    }
+}
+
+/// Reads a ceremony transcript from the given path.
+fn read_transcript(path: &Path) -> Result<Transcript<PairingCurve>> {
+    Ok(Transcript::read_le(std::fs::read(path)?.as_slice())?)
+}
+
+/// Returns the given path as a string for display.
+fn path_string(path: &Path) -> String {
+    format!("(in \"{}\")", path.display())
+}
+
+/// Returns the given bytes as a hex string.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -0,0 +1,104 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    ledger::store::{helpers::memory::ConsensusMemory, ConsensusStore},
+    synthesizer::VM,
+};
+
+/// Estimates the fee to execute an Aleo program function, without proving it
+#[derive(Debug, Parser)]
+pub struct Estimate {
+    /// The function name.
+    function: Identifier<CurrentNetwork>,
+    /// The function inputs.
+    inputs: Vec<Value<CurrentNetwork>>,
+}
+
+impl Estimate {
+    /// Estimates the execution fee of the Aleo program function with the specified name.
+    #[allow(clippy::format_in_format_args)]
+    pub fn parse(self) -> Result<String> {
+        // Derive the program directory path.
+        let path = std::env::current_dir()?;
+
+        // Load the package.
+        let package = Package::open(&path)?;
+        // Load the package process.
+        let process = package.get_process()?;
+
+        // Initialize a VM with an in-memory store.
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?)?;
+        // Add the imports of the package to the VM.
+        for program_id in package.program().imports().keys() {
+            if !vm.contains_program(program_id) {
+                vm.process().write().add_program(process.get_program(program_id)?)?;
+            }
+        }
+        // Add the program of the package to the VM.
+        vm.process().write().add_program(package.program())?;
+
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Estimate the execution fee.
+        let estimate = vm.estimate_execution_fee(*package.program_id(), self.function, self.inputs, rng)?;
+
+        // Log the estimate.
+        use num_format::ToFormattedString;
+
+        println!("💰 Estimated fee\n");
+        println!(" •  {} transition(s)", estimate.num_transitions());
+        println!(
+            " •  Storage cost: {} microcredits {}",
+            estimate.storage_cost().to_formatted_string(LOCALE),
+            format!("(including a {} byte proof)", estimate.proof_size_in_bytes().to_formatted_string(LOCALE)).dimmed()
+        );
+        println!(" •  Finalize cost: {} microcredits", estimate.finalize_cost().to_formatted_string(LOCALE));
+        println!(" •  Total cost: {} microcredits\n", estimate.total_cost().to_formatted_string(LOCALE).bold());
+
+        // Prepare the locator.
+        let locator = Locator::<CurrentNetwork>::from_str(&format!("{}/{}", package.program_id(), self.function))?;
+        // Prepare the path string.
+        let path_string = format!("(in \"{}\")", path.display());
+
+        Ok(format!("✅ Estimated the fee for '{}' {}", locator.to_string().bold(), path_string.dimmed()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cli::{Command, CLI},
+        prelude::{Identifier, Value},
+    };
+
+    #[test]
+    fn clap_snarkvm_estimate() {
+        let arg_vec = vec!["snarkvm", "estimate", "hello", "1u32", "2u32"];
+        let cli = CLI::parse_from(&arg_vec);
+
+        if let Command::Estimate(estimate) = cli.command {
+            assert_eq!(estimate.function, Identifier::try_from(arg_vec[2]).unwrap());
+            assert_eq!(estimate.inputs, vec![
+                Value::try_from(arg_vec[3]).unwrap(),
+                Value::try_from(arg_vec[4]).unwrap()
+            ]);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
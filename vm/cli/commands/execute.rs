@@ -24,9 +24,6 @@ pub struct Execute {
     /// Uses the specified endpoint.
     #[clap(default_value = "https://api.explorer.aleo.org/v1", long)]
     endpoint: String,
-    /// Toggles offline mode.
-    #[clap(long)]
-    offline: bool,
 }
 
 impl Execute {
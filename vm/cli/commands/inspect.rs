@@ -0,0 +1,263 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    console::{
+        prelude::{Environment, ToBytes},
+        types::Field,
+    },
+    synthesizer::snark::{Certificate, Proof, ProofCheck, VerifyingKey},
+};
+
+use anyhow::{anyhow, bail};
+use clap::Subcommand;
+use std::path::Path;
+
+type PublicInputs = Vec<Vec<<CurrentNetwork as Environment>::Field>>;
+
+/// Decodes and checks proofs, verifying keys, and certificates.
+#[derive(Debug, Parser)]
+pub struct Inspect {
+    /// Specify an inspect subcommand.
+    #[clap(subcommand)]
+    command: InspectCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum InspectCommand {
+    /// Decodes a proof, and prints its batch sizes
+    Proof {
+        /// The proof, or the path to a file containing it
+        proof: String,
+    },
+    /// Decodes a verifying key, and prints its circuit info
+    #[clap(name = "verifying-key")]
+    VerifyingKey {
+        /// The verifying key, or the path to a file containing it
+        verifying_key: String,
+    },
+    /// Decodes a certificate, and checks it for a function of the program in the current directory
+    Certificate {
+        /// The certificate, or the path to a file containing it
+        certificate: String,
+        /// The function of the program in the current directory to check the certificate for
+        #[clap(long, requires = "verifying_key")]
+        function: Option<Identifier<CurrentNetwork>>,
+        /// The verifying key to check the certificate on, or the path to a file containing it
+        #[clap(long, requires = "function")]
+        verifying_key: Option<String>,
+    },
+    /// Checks a proof against a verifying key and public inputs, and reports the check that failed
+    Verify {
+        /// The verifying key, or the path to a file containing it
+        #[clap(long)]
+        verifying_key: String,
+        /// The proof, or the path to a file containing it
+        #[clap(long)]
+        proof: String,
+        /// The public inputs as JSON (e.g. '[["1field", "2field"]]', with one array per instance), or the path to a file containing them
+        #[clap(long)]
+        inputs: String,
+    },
+}
+
+impl Inspect {
+    /// Runs the inspect subcommand.
+    pub fn parse(self) -> Result<String> {
+        match self.command {
+            InspectCommand::Proof { proof } => {
+                let proof = Proof::<CurrentNetwork>::from_str(&read_argument(&proof)?)?;
+                let batch_sizes = proof.batch_sizes();
+
+                Ok(format!(
+                    "✅ Decoded a proof {}\n\n  • Circuits: {}\n  • Batch sizes: {:?}\n  • Total instances: {}\n  • Hiding: {}",
+                    format!("({} bytes)", proof.to_bytes_le()?.len()).dimmed(),
+                    batch_sizes.len(),
+                    batch_sizes,
+                    batch_sizes.iter().sum::<usize>(),
+                    proof.pc_proof.is_hiding()
+                ))
+            }
+            InspectCommand::VerifyingKey { verifying_key } => {
+                let verifying_key = VerifyingKey::<CurrentNetwork>::from_str(&read_argument(&verifying_key)?)?;
+                let info = &verifying_key.circuit_info;
+
+                Ok(format!(
+                    "✅ Decoded a verifying key {}\n\n  • Circuit ID: {}\n  • Public inputs (padded): {}\n  • Variables: {}\n  • Constraints: {}\n  • Non-zero entries: A = {}, B = {}, C = {}\n  • Commitments: {}\n  • Well-formed: {}",
+                    format!("({} bytes)", verifying_key.to_bytes_le()?.len()).dimmed(),
+                    verifying_key.id.to_string().bold(),
+                    info.num_public_inputs,
+                    info.num_variables,
+                    info.num_constraints,
+                    info.num_non_zero_a,
+                    info.num_non_zero_b,
+                    info.num_non_zero_c,
+                    verifying_key.circuit_commitments.len(),
+                    format_check(verifying_key.check_well_formed())
+                ))
+            }
+            InspectCommand::Certificate { certificate, function, verifying_key } => {
+                let certificate = Certificate::<CurrentNetwork>::from_str(&read_argument(&certificate)?)?;
+
+                let mut output = format!(
+                    "✅ Decoded a certificate {}\n\n  • Hiding: {}",
+                    format!("({} bytes)", certificate.to_bytes_le()?.len()).dimmed(),
+                    certificate.pc_proof.is_hiding()
+                );
+
+                if let (Some(function), Some(verifying_key)) = (function, verifying_key) {
+                    let verifying_key = VerifyingKey::<CurrentNetwork>::from_str(&read_argument(&verifying_key)?)
+                        .map_err(|error| anyhow!("Failed to decode the verifying key - {error}"))?;
+
+                    // Load the package in the current directory.
+                    let package = Package::open(&std::env::current_dir()?)?;
+                    let process = package.get_process()?;
+                    let stack = process.get_stack(*package.program_id())?;
+
+                    // Check the verifying key, and the certificate on the circuit of the function.
+                    let locator = Locator::<CurrentNetwork>::new(*package.program_id(), function);
+                    output += &format!(
+                        "\n  • Verifying key is well-formed: {}\n  • Certificate is valid for '{}': {}",
+                        format_check(verifying_key.check_well_formed()),
+                        locator.to_string().bold(),
+                        format_check(stack.verify_certificate::<Aleo, _>(
+                            &function,
+                            &verifying_key,
+                            &certificate,
+                            &mut rand::thread_rng()
+                        ))
+                    );
+                }
+                Ok(output)
+            }
+            InspectCommand::Verify { verifying_key, proof, inputs } => {
+                let verifying_key = VerifyingKey::<CurrentNetwork>::from_str(&read_argument(&verifying_key)?)
+                    .map_err(|error| anyhow!("Failed to decode the verifying key - {error}"))?;
+                let proof = Proof::<CurrentNetwork>::from_str(&read_argument(&proof)?)
+                    .map_err(|error| anyhow!("Failed to decode the proof - {error}"))?;
+                let inputs = parse_inputs(&read_argument(&inputs)?)
+                    .map_err(|error| anyhow!("Failed to parse the public inputs - {error}"))?;
+
+                // Report each check that was performed, up to the first check that failed.
+                if let Err((failed_check, error)) = verifying_key.check_proof_stages(&inputs, &proof) {
+                    let mut report = format!("The {failed_check} check failed - {error}\n");
+                    for check in ProofCheck::ALL {
+                        match check == failed_check {
+                            true => {
+                                report += &format!("\n  • {check}: ❌");
+                                break;
+                            }
+                            false => report += &format!("\n  • {check}: ✅"),
+                        }
+                    }
+                    bail!(report)
+                }
+
+                Ok(format!(
+                    "✅ The proof is valid for {} instances of circuit {}",
+                    inputs.len(),
+                    verifying_key.id.to_string().bold()
+                ))
+            }
+        }
+    }
+}
+
+/// Returns the outcome of a check, for display.
+fn format_check(check: Result<()>) -> String {
+    match check {
+        Ok(()) => "✅".to_string(),
+        Err(error) => format!("❌ {error}"),
+    }
+}
+
+/// Returns the contents of the file at the given path if it exists, and otherwise the argument itself.
+fn read_argument(argument: &str) -> Result<String> {
+    match Path::new(argument).is_file() {
+        true => Ok(std::fs::read_to_string(argument)?.trim().to_string()),
+        false => Ok(argument.trim().to_string()),
+    }
+}
+
+/// Parses the public inputs from JSON, as either an array of public inputs for a single instance,
+/// or an array of such arrays for multiple instances.
+fn parse_inputs(inputs: &str) -> Result<PublicInputs> {
+    let parse_instance = |instance: &serde_json::Value| -> Result<Vec<_>> {
+        let Some(values) = instance.as_array() else { bail!("Expected an array of public inputs, found '{instance}'") };
+        values
+            .iter()
+            .map(|value| match value.as_str() {
+                Some(string) => Ok(*Field::<CurrentNetwork>::from_str(string)?),
+                None => bail!("Expected a public input as a string, found '{value}'"),
+            })
+            .collect()
+    };
+
+    match serde_json::from_str::<serde_json::Value>(inputs)? {
+        serde_json::Value::Array(instances) if !instances.is_empty() && instances.iter().all(|i| i.is_array()) => {
+            instances.iter().map(parse_instance).collect()
+        }
+        instance => Ok(vec![parse_instance(&instance)?]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Command, CLI};
+
+    #[test]
+    fn clap_snarkvm_inspect_certificate() {
+        let arg_vec = vec![
+            "snarkvm",
+            "inspect",
+            "certificate",
+            "certificate.txt",
+            "--function",
+            "hello",
+            "--verifying-key",
+            "vk.txt",
+        ];
+        let cli = CLI::parse_from(&arg_vec);
+
+        if let Command::Inspect(Inspect {
+            command: InspectCommand::Certificate { certificate, function, verifying_key },
+        }) = cli.command
+        {
+            assert_eq!(certificate, arg_vec[3]);
+            assert_eq!(function, Some(Identifier::try_from(arg_vec[5]).unwrap()));
+            assert_eq!(verifying_key, Some(arg_vec[7].to_string()));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+
+        // Ensure the function requires a verifying key.
+        assert!(
+            CLI::try_parse_from(["snarkvm", "inspect", "certificate", "certificate.txt", "--function", "hello"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_inputs() {
+        let one = *Field::<CurrentNetwork>::from_str("1field").unwrap();
+        let two = *Field::<CurrentNetwork>::from_str("2field").unwrap();
+
+        assert_eq!(parse_inputs(r#"["1field", "2field"]"#).unwrap(), vec![vec![one, two]]);
+        assert_eq!(parse_inputs(r#"[["1field"], ["2field"]]"#).unwrap(), vec![vec![one], vec![two]]);
+        assert!(parse_inputs(r#"[1, 2]"#).is_err());
+        assert!(parse_inputs(r#"{"inputs": []}"#).is_err());
+    }
+}
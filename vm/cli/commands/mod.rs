@@ -15,18 +15,33 @@
 pub mod build;
 pub use build::*;
 
+pub mod ceremony;
+pub use ceremony::*;
+
 pub mod clean;
 pub use clean::*;
 
+pub mod estimate;
+pub use estimate::*;
+
 pub mod execute;
 pub use execute::*;
 
+pub mod inspect;
+pub use inspect::*;
+
 pub mod new;
 pub use new::*;
 
+pub mod parameters;
+pub use parameters::*;
+
 pub mod run;
 pub use run::*;
 
+pub mod simulate;
+pub use simulate::*;
+
 pub mod update;
 pub use update::*;
 
@@ -0,0 +1,70 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::parameters::{bundle, testnet3::remote_parameter_files, verify_bundle, verify_files};
+
+use clap::Subcommand;
+use std::path::PathBuf;
+
+/// Manages the parameter files of the network.
+#[derive(Debug, Parser)]
+pub struct Parameters {
+    /// Specify a parameters subcommand.
+    #[clap(subcommand)]
+    command: ParametersCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum ParametersCommand {
+    /// Writes a checksum-verified bundle of all parameter files into a directory
+    Bundle {
+        /// The directory to write the bundle to
+        #[clap(short, long)]
+        output: PathBuf,
+    },
+    /// Checks that all parameter files are available and intact, and lists any missing files
+    Check {
+        /// The bundle directory to check, instead of the parameters directory
+        #[clap(short, long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+impl Parameters {
+    /// Runs the parameters subcommand.
+    pub fn parse(self) -> Result<String> {
+        let files = remote_parameter_files();
+
+        match self.command {
+            ParametersCommand::Bundle { output } => {
+                let manifest = bundle(&files, &output)?;
+
+                Ok(format!(
+                    "✅ Bundled {} parameter files {}",
+                    files.len(),
+                    format!("(with checksums in \"{}\")", manifest.display()).dimmed()
+                ))
+            }
+            ParametersCommand::Check { dir } => {
+                match &dir {
+                    Some(dir) => verify_bundle(&files, dir)?,
+                    None => verify_files(&files)?,
+                }
+
+                Ok(format!("✅ All {} parameter files are available", files.len()))
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    console::network::Network,
+    synthesizer::{simulate_economics, SimulationConfig, SimulationSample},
+};
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+/// Simulates the network economics over synthetic blocks, and writes a time series of supply, rewards and targets
+#[derive(Debug, Parser)]
+pub struct Simulate {
+    /// The number of blocks to simulate
+    #[clap(default_value = "1000000", long)]
+    blocks: u32,
+    /// The mean number of seconds between blocks [default: the network block time]
+    #[clap(long)]
+    block_interval: Option<u16>,
+    /// The maximum number of seconds a block interval deviates from the mean
+    #[clap(default_value = "0", long)]
+    jitter: u16,
+    /// The hashrate of each prover, as the proof target it accumulates per second (comma-separated)
+    #[clap(long, value_delimiter = ',')]
+    hashrates: Vec<u64>,
+    /// The self-bonded stake of each validator, in microcredits (comma-separated)
+    #[clap(long, value_delimiter = ',', required = true)]
+    validators: Vec<u64>,
+    /// The commission of each validator, in percent
+    #[clap(default_value = "0", long)]
+    commission: u8,
+    /// The stake of each delegator, in microcredits, bonded to the validators in turn (comma-separated)
+    #[clap(long, value_delimiter = ',')]
+    delegators: Vec<u64>,
+    /// The priority fees paid in each block, in microcredits
+    #[clap(default_value = "0", long)]
+    fees: u64,
+    /// The number of blocks between recorded samples
+    #[clap(default_value = "100", long)]
+    sample_interval: u32,
+    /// The seed of the simulation
+    #[clap(default_value = "0", long)]
+    seed: u64,
+    /// Writes the time series as JSON, instead of CSV
+    #[clap(long)]
+    json: bool,
+    /// The file to write the time series to
+    #[clap(short, long)]
+    output: PathBuf,
+}
+
+impl Simulate {
+    /// Simulates the network economics, and writes the time series to the output file.
+    pub fn parse(self) -> Result<String> {
+        // Prepare the simulation configuration.
+        let config = SimulationConfig {
+            num_blocks: self.blocks,
+            block_interval: self.block_interval.unwrap_or(CurrentNetwork::BLOCK_TIME),
+            block_interval_jitter: self.jitter,
+            prover_hashrates: self.hashrates,
+            validators: self.validators.iter().map(|stake| (*stake, self.commission)).collect(),
+            delegators: self
+                .delegators
+                .iter()
+                .enumerate()
+                .map(|(i, stake)| (i % self.validators.len(), *stake))
+                .collect(),
+            transaction_fees: self.fees,
+            sample_interval: self.sample_interval,
+            seed: self.seed,
+        };
+
+        // Run the simulation.
+        let samples = simulate_economics::<CurrentNetwork>(&config)?;
+
+        // Write the time series.
+        let mut writer = BufWriter::new(File::create(&self.output)?);
+        match self.json {
+            true => serde_json::to_writer_pretty(&mut writer, &samples)?,
+            false => {
+                writeln!(writer, "{}", SimulationSample::CSV_HEADER)?;
+                for sample in &samples {
+                    writeln!(writer, "{}", sample.to_csv_record())?;
+                }
+            }
+        }
+        writer.flush()?;
+
+        // Retrieve the final sample.
+        let last = samples.last().copied().unwrap_or_default();
+
+        // Log the summary.
+        use num_format::ToFormattedString;
+
+        println!("📈 Simulated {} blocks\n", self.blocks.to_formatted_string(LOCALE));
+        println!(" •  Total supply: {} microcredits", last.total_supply().to_formatted_string(LOCALE));
+        println!(" •  Total stake: {} microcredits", last.total_stake().to_formatted_string(LOCALE));
+        println!(" •  Coinbase target: {}", last.coinbase_target().to_formatted_string(LOCALE));
+        println!(" •  Proof target: {}\n", last.proof_target().to_formatted_string(LOCALE));
+
+        Ok(format!("✅ Wrote {} samples {}", samples.len(), format!("(to \"{}\")", self.output.display()).dimmed()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Command, CLI};
+
+    #[test]
+    fn clap_snarkvm_simulate() {
+        let arg_vec = vec![
+            "snarkvm",
+            "simulate",
+            "--blocks",
+            "1000",
+            "--hashrates",
+            "100,200",
+            "--validators",
+            "1000000000000,2000000000000,3000000000000",
+            "--json",
+            "-o",
+            "series.json",
+        ];
+        let cli = CLI::parse_from(&arg_vec);
+
+        if let Command::Simulate(simulate) = cli.command {
+            assert_eq!(simulate.blocks, 1000);
+            assert_eq!(simulate.block_interval, None);
+            assert_eq!(simulate.hashrates, vec![100, 200]);
+            assert_eq!(simulate.validators, vec![1_000_000_000_000, 2_000_000_000_000, 3_000_000_000_000]);
+            assert!(simulate.delegators.is_empty());
+            assert_eq!(simulate.sample_interval, 100);
+            assert!(simulate.json);
+            assert_eq!(simulate.output, PathBuf::from("series.json"));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
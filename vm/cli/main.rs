@@ -21,6 +21,8 @@ fn main() -> anyhow::Result<()> {
     let cli = CLI::parse();
     // Run the updater.
     println!("{}", Updater::print_cli());
+    // Configure the parameters.
+    cli.configure_parameters()?;
     // Run the CLI.
     match cli.command.parse() {
         Ok(output) => println!("{output}\n"),